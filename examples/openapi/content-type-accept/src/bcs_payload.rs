@@ -90,6 +90,7 @@ impl<T: Serialize + Type> ApiResponse for Bcs<T> {
                     schema: Self::schema_ref(),
                 }],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+};
+
+use crate::{document::Document, generator::Generator};
+
+/// Configuration options for OpenAPI code generation.
+#[derive(Debug)]
+pub struct Config {
+    pub(crate) out_dir: Option<PathBuf>,
+    pub(crate) api_name: String,
+    pub(crate) formats: HashMap<String, String>,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    /// Creates a new code generator configuration with default options.
+    pub fn new() -> Self {
+        Self {
+            out_dir: None,
+            api_name: "Api".to_string(),
+            formats: HashMap::new(),
+        }
+    }
+
+    /// Configures the output directory where generated Rust files will be
+    /// written.
+    ///
+    /// If unset, defaults to the OUT_DIR environment variable. OUT_DIR is set
+    /// by Cargo when executing build scripts, so out_dir typically does not
+    /// need to be configured.
+    pub fn out_dir<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.out_dir = Some(path.into());
+        self
+    }
+
+    /// Sets the name of the generated trait that declares the operations.
+    ///
+    /// The generated server type is named `<name>Server` and the generated
+    /// tags type is named `<name>Tags`.
+    ///
+    /// Default is `Api`.
+    pub fn api_name(mut self, name: impl Into<String>) -> Self {
+        self.api_name = name.into();
+        self
+    }
+
+    /// Maps the schemas with the specified `format` to a Rust type.
+    ///
+    /// The types of the formats that `poem-openapi` supports without any
+    /// feature, such as `int32`, `double`, `binary` and `password`, are
+    /// mapped by default. Other formats are mapped to the Rust type of their
+    /// `type`, so a string with an unknown format is mapped to `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let config = poem_openapi_build::Config::new()
+    ///     .format("uuid", "::uuid::Uuid")
+    ///     .format("date-time", "::chrono::DateTime<::chrono::Utc>");
+    /// ```
+    pub fn format(mut self, format: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.formats.insert(format.into(), rust_type.into());
+        self
+    }
+
+    /// Generates the Rust code for the OpenAPI document and returns it.
+    pub fn generate(&self, path: impl AsRef<Path>) -> Result<String> {
+        let doc = Document::load(path.as_ref())?;
+        Generator::generate(self, &doc)
+    }
+
+    /// Compile an OpenAPI document into a Rust file during a Cargo build with
+    /// additional code generator configuration options.
+    ///
+    /// The file is named after the document, so `petstore.yaml` is compiled
+    /// into `petstore.rs`, which can be included with
+    /// `poem_openapi::include_openapi!("petstore")`.
+    pub fn compile(self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let code = self.generate(path)?;
+
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or_else(|| Error::new(ErrorKind::NotFound, "OUT_DIR is not set"))?,
+        };
+        let file_name = path
+            .file_stem()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid document path"))?;
+        std::fs::write(
+            out_dir.join(format!("{}.rs", file_name.to_string_lossy())),
+            code,
+        )?;
+
+        println!("cargo:rerun-if-changed={}", path.display());
+        Ok(())
+    }
+}
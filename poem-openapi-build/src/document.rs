@@ -0,0 +1,531 @@
+//! The parts of the OpenAPI document that are used to generate the code.
+
+use std::{
+    io::{Error, ErrorKind, Result},
+    path::Path,
+};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Document {
+    pub(crate) openapi: String,
+    pub(crate) info: Info,
+    #[serde(default)]
+    pub(crate) servers: Vec<Server>,
+    #[serde(default)]
+    pub(crate) tags: Vec<Tag>,
+    #[serde(default)]
+    pub(crate) paths: IndexMap<String, PathItem>,
+    #[serde(default)]
+    pub(crate) components: Components,
+    #[serde(default)]
+    pub(crate) security: Vec<IndexMap<String, Vec<String>>>,
+}
+
+impl Document {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)?;
+        let invalid = |err: &dyn std::fmt::Display| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("failed to parse `{}`: {err}", path.display()),
+            )
+        };
+
+        let mut doc: Document = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&data).map_err(|err| invalid(&err))?,
+            _ => serde_yaml::from_str(&data).map_err(|err| invalid(&err))?,
+        };
+        doc.normalize();
+        Ok(doc)
+    }
+
+    pub(crate) fn is_v3_1(&self) -> bool {
+        self.openapi.starts_with("3.1")
+    }
+
+    /// Converts the OpenAPI 3.1 keywords to their OpenAPI 3.0 equivalents, so
+    /// that the generator only deals with one form.
+    fn normalize(&mut self) {
+        self.components
+            .schemas
+            .values_mut()
+            .for_each(Schema::normalize);
+
+        for item in self.paths.values_mut() {
+            for param in &mut item.parameters {
+                param.schema.iter_mut().for_each(Schema::normalize);
+            }
+            for operation in item.operations_mut() {
+                for param in &mut operation.parameters {
+                    param.schema.iter_mut().for_each(Schema::normalize);
+                }
+                let request = operation.request_body.iter_mut();
+                for media in request.flat_map(|request| request.content.values_mut()) {
+                    media.schema.iter_mut().for_each(Schema::normalize);
+                }
+                for resp in operation.responses.values_mut() {
+                    for media in resp.content.values_mut() {
+                        media.schema.iter_mut().for_each(Schema::normalize);
+                    }
+                    for header in resp.headers.values_mut() {
+                        header.schema.iter_mut().for_each(Schema::normalize);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Info {
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) summary: Option<String>,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    pub(crate) version: String,
+    #[serde(default)]
+    pub(crate) terms_of_service: Option<String>,
+    #[serde(default)]
+    pub(crate) contact: Option<Contact>,
+    #[serde(default)]
+    pub(crate) license: Option<License>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Contact {
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+    #[serde(default)]
+    pub(crate) email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct License {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) identifier: Option<String>,
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Server {
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Tag {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct PathItem {
+    #[serde(default)]
+    pub(crate) parameters: Vec<Parameter>,
+    #[serde(default)]
+    pub(crate) get: Option<Operation>,
+    #[serde(default)]
+    pub(crate) put: Option<Operation>,
+    #[serde(default)]
+    pub(crate) post: Option<Operation>,
+    #[serde(default)]
+    pub(crate) delete: Option<Operation>,
+    #[serde(default)]
+    pub(crate) options: Option<Operation>,
+    #[serde(default)]
+    pub(crate) head: Option<Operation>,
+    #[serde(default)]
+    pub(crate) patch: Option<Operation>,
+    #[serde(default)]
+    pub(crate) trace: Option<Operation>,
+}
+
+impl PathItem {
+    pub(crate) fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("put", &self.put),
+            ("post", &self.post),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+            ("trace", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| Some((method, operation.as_ref()?)))
+    }
+
+    fn operations_mut(&mut self) -> impl Iterator<Item = &mut Operation> {
+        [
+            &mut self.get,
+            &mut self.put,
+            &mut self.post,
+            &mut self.delete,
+            &mut self.options,
+            &mut self.head,
+            &mut self.patch,
+            &mut self.trace,
+        ]
+        .into_iter()
+        .filter_map(Option::as_mut)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Operation {
+    #[serde(default)]
+    pub(crate) tags: Vec<String>,
+    #[serde(default)]
+    pub(crate) summary: Option<String>,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) operation_id: Option<String>,
+    #[serde(default)]
+    pub(crate) parameters: Vec<Parameter>,
+    #[serde(default)]
+    pub(crate) request_body: Option<RequestBody>,
+    #[serde(default)]
+    pub(crate) responses: IndexMap<String, Response>,
+    #[serde(default)]
+    pub(crate) deprecated: bool,
+    #[serde(default)]
+    pub(crate) security: Option<Vec<IndexMap<String, Vec<String>>>>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ParameterIn {
+    Query,
+    Header,
+    Path,
+    Cookie,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Parameter {
+    #[serde(rename = "$ref", default)]
+    pub(crate) reference: Option<String>,
+    #[serde(default)]
+    pub(crate) name: String,
+    #[serde(rename = "in", default)]
+    pub(crate) param_in: Option<ParameterIn>,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) required: bool,
+    #[serde(default)]
+    pub(crate) deprecated: bool,
+    #[serde(default)]
+    pub(crate) explode: Option<bool>,
+    #[serde(default)]
+    pub(crate) schema: Option<Schema>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RequestBody {
+    #[serde(rename = "$ref", default)]
+    pub(crate) reference: Option<String>,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) content: IndexMap<String, MediaType>,
+    #[serde(default)]
+    pub(crate) required: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Response {
+    #[serde(rename = "$ref", default)]
+    pub(crate) reference: Option<String>,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) headers: IndexMap<String, Header>,
+    #[serde(default)]
+    pub(crate) content: IndexMap<String, MediaType>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Header {
+    #[serde(rename = "$ref", default)]
+    pub(crate) reference: Option<String>,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) required: bool,
+    #[serde(default)]
+    pub(crate) deprecated: bool,
+    #[serde(default)]
+    pub(crate) schema: Option<Schema>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct MediaType {
+    #[serde(default)]
+    pub(crate) schema: Option<Schema>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Components {
+    #[serde(default)]
+    pub(crate) schemas: IndexMap<String, Schema>,
+    #[serde(default)]
+    pub(crate) security_schemes: IndexMap<String, SecurityScheme>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SecurityScheme {
+    #[serde(rename = "type")]
+    pub(crate) ty: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    #[serde(rename = "in", default)]
+    pub(crate) key_in: Option<String>,
+    #[serde(default)]
+    pub(crate) scheme: Option<String>,
+    #[serde(default)]
+    pub(crate) bearer_format: Option<String>,
+    #[serde(default)]
+    pub(crate) flows: Option<OAuthFlows>,
+    #[serde(default)]
+    pub(crate) open_id_connect_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OAuthFlows {
+    #[serde(default)]
+    pub(crate) implicit: Option<OAuthFlow>,
+    #[serde(default)]
+    pub(crate) password: Option<OAuthFlow>,
+    #[serde(default)]
+    pub(crate) client_credentials: Option<OAuthFlow>,
+    #[serde(default)]
+    pub(crate) authorization_code: Option<OAuthFlow>,
+}
+
+impl OAuthFlows {
+    pub(crate) fn flows(&self) -> impl Iterator<Item = (&'static str, &OAuthFlow)> {
+        [
+            ("implicit", &self.implicit),
+            ("password", &self.password),
+            ("client_credentials", &self.client_credentials),
+            ("authorization_code", &self.authorization_code),
+        ]
+        .into_iter()
+        .filter_map(|(name, flow)| Some((name, flow.as_ref()?)))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OAuthFlow {
+    #[serde(default)]
+    pub(crate) authorization_url: Option<String>,
+    #[serde(default)]
+    pub(crate) token_url: Option<String>,
+    #[serde(default)]
+    pub(crate) refresh_url: Option<String>,
+    #[serde(default)]
+    pub(crate) scopes: IndexMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum SchemaType {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum AdditionalProperties {
+    Bool(bool),
+    Schema(Box<Schema>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ExclusiveBound {
+    Bool(bool),
+    Value(f64),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Discriminator {
+    pub(crate) property_name: String,
+    #[serde(default)]
+    pub(crate) mapping: IndexMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct Schema {
+    #[serde(rename = "$ref")]
+    pub(crate) reference: Option<String>,
+    #[serde(rename = "type")]
+    pub(crate) ty: Option<SchemaType>,
+    pub(crate) format: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) required: Vec<String>,
+    pub(crate) properties: IndexMap<String, Schema>,
+    pub(crate) items: Option<Box<Schema>>,
+    pub(crate) additional_properties: Option<AdditionalProperties>,
+    #[serde(rename = "enum")]
+    pub(crate) enum_items: Vec<Value>,
+    #[serde(rename = "const")]
+    pub(crate) const_value: Option<Value>,
+    pub(crate) deprecated: bool,
+    pub(crate) nullable: bool,
+    pub(crate) read_only: bool,
+    pub(crate) write_only: bool,
+    pub(crate) any_of: Vec<Schema>,
+    pub(crate) one_of: Vec<Schema>,
+    pub(crate) all_of: Vec<Schema>,
+    pub(crate) discriminator: Option<Discriminator>,
+    pub(crate) multiple_of: Option<f64>,
+    pub(crate) maximum: Option<f64>,
+    pub(crate) exclusive_maximum: Option<ExclusiveBound>,
+    pub(crate) minimum: Option<f64>,
+    pub(crate) exclusive_minimum: Option<ExclusiveBound>,
+    pub(crate) max_length: Option<usize>,
+    pub(crate) min_length: Option<usize>,
+    pub(crate) pattern: Option<String>,
+    pub(crate) max_items: Option<usize>,
+    pub(crate) min_items: Option<usize>,
+    pub(crate) unique_items: Option<bool>,
+    pub(crate) max_properties: Option<usize>,
+    pub(crate) min_properties: Option<usize>,
+}
+
+impl Schema {
+    /// Returns the type of the schema, ignoring the `null` type of OpenAPI
+    /// 3.1.
+    pub(crate) fn ty(&self) -> Option<&str> {
+        match &self.ty {
+            Some(SchemaType::Single(ty)) => Some(ty),
+            Some(SchemaType::Multiple(types)) => {
+                types.iter().find(|ty| *ty != "null").map(String::as_str)
+            }
+            None => None,
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(&self.ty, Some(SchemaType::Single(ty)) if ty == "null")
+    }
+
+    /// Returns the name of the referenced schema, including the reference
+    /// with sibling keywords, which is written as `allOf: [{ $ref }, { ... }]`
+    /// in OpenAPI 3.0.
+    pub(crate) fn reference(&self) -> Option<&str> {
+        let reference = match (&self.reference, self.all_of.as_slice()) {
+            (Some(reference), _) => reference,
+            (None, [item, siblings @ ..])
+                if siblings.len() <= 1 && siblings.iter().all(Schema::is_annotation) =>
+            {
+                item.reference.as_deref()?
+            }
+            _ => return None,
+        };
+        reference.strip_prefix("#/components/schemas/")
+    }
+
+    /// Returns `true` if the schema only contains the keywords that annotate
+    /// another schema, such as `description` and `readOnly`.
+    fn is_annotation(&self) -> bool {
+        self.reference.is_none()
+            && self.ty.is_none()
+            && self.properties.is_empty()
+            && self.items.is_none()
+            && self.any_of.is_empty()
+            && self.one_of.is_empty()
+            && self.all_of.is_empty()
+    }
+
+    fn normalize(&mut self) {
+        if let Some(SchemaType::Multiple(types)) = &self.ty {
+            if types.iter().any(|ty| ty == "null") {
+                self.nullable = true;
+            }
+            let types = types
+                .iter()
+                .filter(|ty| *ty != "null")
+                .cloned()
+                .collect::<Vec<_>>();
+            self.ty = match <[String; 1]>::try_from(types) {
+                Ok([ty]) => Some(SchemaType::Single(ty)),
+                Err(types) if types.is_empty() => None,
+                Err(types) => Some(SchemaType::Multiple(types)),
+            };
+        }
+
+        // `anyOf: [{ ... }, { type: null }]`
+        if let [_, _] = self.any_of.as_slice() {
+            if let Some(idx) = self.any_of.iter().position(Schema::is_null) {
+                self.any_of.remove(idx);
+                let inner = self.any_of.remove(0);
+                let description = self.description.take();
+                *self = Schema {
+                    nullable: true,
+                    description: description.or(inner.description.clone()),
+                    ..inner
+                };
+            }
+        }
+
+        if let Some(value) = self.const_value.take() {
+            self.enum_items = vec![value];
+        }
+        self.enum_items.retain(|value| !value.is_null());
+        if self.ty.is_none()
+            && !self.enum_items.is_empty()
+            && self.enum_items.iter().all(Value::is_string)
+        {
+            self.ty = Some(SchemaType::Single("string".to_string()));
+        }
+
+        if let Some(ExclusiveBound::Value(value)) = self.exclusive_maximum {
+            self.maximum = Some(value);
+            self.exclusive_maximum = Some(ExclusiveBound::Bool(true));
+        }
+        if let Some(ExclusiveBound::Value(value)) = self.exclusive_minimum {
+            self.minimum = Some(value);
+            self.exclusive_minimum = Some(ExclusiveBound::Bool(true));
+        }
+
+        if let Some(items) = &mut self.items {
+            items.normalize();
+        }
+        if let Some(AdditionalProperties::Schema(schema)) = &mut self.additional_properties {
+            schema.normalize();
+        }
+        self.properties.values_mut().for_each(Schema::normalize);
+        self.any_of
+            .iter_mut()
+            .chain(&mut self.one_of)
+            .chain(&mut self.all_of)
+            .for_each(Schema::normalize);
+    }
+}
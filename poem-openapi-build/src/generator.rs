@@ -0,0 +1,189 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::Result,
+};
+
+use indexmap::IndexMap;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::{
+    config::Config,
+    document::{Document, SecurityScheme},
+    utils::{doc_attrs, get_crate_name, invalid_document, type_ident, unique_ident},
+};
+
+/// The generated Rust type of a security scheme.
+pub(crate) struct SecuritySchemeType {
+    pub(crate) ident: Ident,
+    /// The `OAuthScopes` type and the variants for the scopes of an OAuth2
+    /// security scheme.
+    pub(crate) scopes: Option<(Ident, IndexMap<String, Ident>)>,
+}
+
+pub(crate) struct Generator<'a> {
+    pub(crate) config: &'a Config,
+    pub(crate) doc: &'a Document,
+    /// The path of the `poem-openapi` crate.
+    pub(crate) crate_name: TokenStream,
+    pub(crate) items: Vec<TokenStream>,
+    pub(crate) schema_types: HashMap<String, Ident>,
+    pub(crate) security_schemes: HashMap<String, SecuritySchemeType>,
+    type_names: HashSet<String>,
+}
+
+impl<'a> Generator<'a> {
+    pub(crate) fn generate(config: &'a Config, doc: &'a Document) -> Result<String> {
+        let mut generator = Generator {
+            config,
+            doc,
+            crate_name: get_crate_name("poem-openapi"),
+            items: Vec::new(),
+            schema_types: HashMap::new(),
+            security_schemes: HashMap::new(),
+            type_names: HashSet::new(),
+        };
+
+        for name in doc.components.schemas.keys() {
+            let ident = type_ident(name);
+            if !generator.type_names.insert(ident.to_string()) {
+                return Err(invalid_document(format!(
+                    "the schema `{name}` has the same Rust name as another schema: `{ident}`"
+                )));
+            }
+            generator.schema_types.insert(name.clone(), ident);
+        }
+
+        for (name, schema) in &doc.components.schemas {
+            let ident = generator.schema_types[name].clone();
+            generator.define_schema(ident, Some(name), schema)?;
+        }
+        for (name, scheme) in &doc.components.security_schemes {
+            generator.define_security_scheme(name, scheme)?;
+        }
+        generator.generate_api()?;
+
+        let items = &generator.items;
+        let file = syn::parse2::<syn::File>(quote!(#(#items)*))
+            .map_err(|err| invalid_document(format!("failed to generate the code: {err}")))?;
+        Ok(prettyplease::unparse(&file))
+    }
+
+    /// Reserves a name for a generated type, appending a number to it if
+    /// another type already uses it.
+    pub(crate) fn new_type_ident(&mut self, name: &str) -> Ident {
+        let ident = unique_ident(type_ident(name), |ident| {
+            self.type_names.contains(&ident.to_string())
+        });
+        self.type_names.insert(ident.to_string());
+        ident
+    }
+
+    fn define_security_scheme(&mut self, name: &str, scheme: &SecurityScheme) -> Result<()> {
+        let crate_name = self.crate_name.clone();
+        let ident = self.new_type_ident(name);
+        let desc = doc_attrs(scheme.description.as_deref());
+        let unsupported = || {
+            invalid_document(format!(
+                "the security scheme `{name}` has an unsupported type: `{}`",
+                scheme.ty
+            ))
+        };
+        let mut scopes = None;
+
+        let (args, inner_ty) = match (scheme.ty.as_str(), scheme.scheme.as_deref()) {
+            ("apiKey", _) => {
+                let key_name = scheme.name.as_deref().ok_or_else(unsupported)?;
+                let key_in = scheme.key_in.as_deref().ok_or_else(unsupported)?;
+                (
+                    quote!(ty = "api_key", key_name = #key_name, key_in = #key_in),
+                    quote!(#crate_name::auth::ApiKey),
+                )
+            }
+            ("http", Some(http_scheme)) if http_scheme.eq_ignore_ascii_case("basic") => {
+                (quote!(ty = "basic"), quote!(#crate_name::auth::Basic))
+            }
+            ("http", Some(http_scheme)) if http_scheme.eq_ignore_ascii_case("bearer") => {
+                let bearer_format = scheme
+                    .bearer_format
+                    .as_deref()
+                    .map(|bearer_format| quote!(, bearer_format = #bearer_format));
+                (
+                    quote!(ty = "bearer" #bearer_format),
+                    quote!(#crate_name::auth::Bearer),
+                )
+            }
+            ("oauth2", _) => {
+                let flows = scheme.flows.as_ref().ok_or_else(unsupported)?;
+                let mut variants = IndexMap::new();
+                let mut scope_items = Vec::new();
+                for (scope, description) in flows.flows().flat_map(|(_, flow)| &flow.scopes) {
+                    if variants.contains_key(scope) {
+                        continue;
+                    }
+                    let variant = type_ident(scope);
+                    let rename = (variant != scope).then(|| quote!(#[oai(rename = #scope)]));
+                    let desc = doc_attrs(Some(description));
+                    scope_items.push(quote!(#desc #rename #variant));
+                    variants.insert(scope.clone(), variant);
+                }
+                let scopes_ident = (!scope_items.is_empty()).then(|| {
+                    let scopes_ident = self.new_type_ident(&format!("{name}Scopes"));
+                    self.items.push(quote! {
+                        #[derive(#crate_name::OAuthScopes, Debug, Clone, Copy, PartialEq, Eq)]
+                        pub enum #scopes_ident {
+                            #(#scope_items),*
+                        }
+                    });
+                    scopes_ident
+                });
+
+                let flows = flows.flows().map(|(flow_name, flow)| {
+                    let flow_name = format_ident!("{}", flow_name);
+                    let urls = [
+                        ("authorization_url", &flow.authorization_url),
+                        ("token_url", &flow.token_url),
+                        ("refresh_url", &flow.refresh_url),
+                    ]
+                    .into_iter()
+                    .filter_map(|(key, url)| {
+                        let key = format_ident!("{}", key);
+                        let url = url.as_ref()?;
+                        Some(quote!(#key = #url))
+                    });
+                    let scopes = scopes_ident
+                        .as_ref()
+                        .map(|scopes_ident| scopes_ident.to_string())
+                        .map(|scopes| quote!(scopes = #scopes));
+                    let args = urls.chain(scopes);
+                    quote!(#flow_name(#(#args),*))
+                });
+                let args = quote!(ty = "oauth2", flows(#(#flows),*));
+                scopes = scopes_ident.map(|scopes_ident| (scopes_ident, variants));
+                (args, quote!(#crate_name::auth::Bearer))
+            }
+            ("openIdConnect", _) => {
+                let url = scheme
+                    .open_id_connect_url
+                    .as_deref()
+                    .ok_or_else(unsupported)?;
+                (
+                    quote!(ty = "openid_connect", openid_connect_url = #url),
+                    quote!(#crate_name::auth::Bearer),
+                )
+            }
+            _ => return Err(unsupported()),
+        };
+
+        let rename = (ident != name).then(|| quote!(rename = #name,));
+        self.items.push(quote! {
+            #desc
+            #[derive(#crate_name::SecurityScheme)]
+            #[oai(#rename #args)]
+            pub struct #ident(pub #inner_ty);
+        });
+        self.security_schemes
+            .insert(name.to_string(), SecuritySchemeType { ident, scopes });
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! Codegen module of `poem-openapi`
+//!
+//! Reads an OpenAPI 3.0 or 3.1 document in JSON or YAML format and generates
+//! the types and the server scaffolding that implement it with
+//! `poem-openapi`.
+//!
+//! # Example
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! fn main() -> std::io::Result<()> {
+//!     poem_openapi_build::Config::new()
+//!         .api_name("PetStore")
+//!         .compile("petstore.yaml")
+//! }
+//! ```
+//!
+//! Then include the generated code and implement the generated trait:
+//!
+//! ```ignore
+//! mod petstore {
+//!     poem_openapi::include_openapi!("petstore");
+//! }
+//!
+//! struct MyPetStore;
+//!
+//! impl petstore::PetStore for MyPetStore {
+//!     async fn find_pet(
+//!         &self,
+//!         id: poem_openapi::param::Path<i64>,
+//!     ) -> poem::Result<petstore::FindPetResponse> {
+//!         todo!()
+//!     }
+//! }
+//!
+//! let service = petstore::PetStoreServer::new(MyPetStore).into_service();
+//! ```
+//!
+//! # Generated items
+//!
+//! | OpenAPI | Rust |
+//! |---------|------|
+//! | Object schema | `#[derive(Object)]` struct |
+//! | String schema with `enum` | `#[derive(Enum)]` enum |
+//! | `oneOf` / `anyOf` schema | `#[derive(Union)]` enum |
+//! | Security scheme | `#[derive(SecurityScheme)]` struct |
+//! | OAuth2 scopes | `#[derive(OAuthScopes)]` enum |
+//! | Tags | `#[derive(Tags)]` enum named `<ApiName>Tags` |
+//! | Responses of an operation | `#[derive(ApiResponse)]` enum named `<Operation>Response` |
+//! | Request body with a custom media type or several media types | `#[derive(ApiRequest)]` enum named `<Operation>Request` |
+//! | Operations | A trait named `<ApiName>` and an `#[OpenApi]` implementation for `<ApiName>Server<T>` |
+//!
+//! The schemas, the parameters and the responses must be defined inline or
+//! reference the schemas in `components/schemas`. When a document uses the
+//! same shapes that `poem-openapi` generates, the document returned by
+//! `OpenApiService::spec` is identical to the input document, except that:
+//!
+//! - `poem-openapi` only writes the schemas and the tags that are used by the
+//!   operations.
+//! - The names of the response headers are written in uppercase.
+//! - The numbers of the validators are written as floats.
+//! - A union with a `discriminator` also defines a schema for each mapping.
+
+#![doc(html_favicon_url = "https://raw.githubusercontent.com/poem-web/poem/master/favicon.ico")]
+#![doc(html_logo_url = "https://raw.githubusercontent.com/poem-web/poem/master/logo.png")]
+#![forbid(unsafe_code)]
+#![deny(unreachable_pub)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![warn(missing_docs)]
+
+mod config;
+mod document;
+mod generator;
+mod operation;
+mod schema;
+mod utils;
+
+use std::path::Path;
+
+pub use config::Config;
+
+/// Compile an OpenAPI document into a Rust file during a Cargo build with
+/// default options.
+pub fn compile_openapi(path: impl AsRef<Path>) -> std::io::Result<()> {
+    Config::new().compile(path)
+}
@@ -0,0 +1,777 @@
+use std::{collections::HashSet, io::Result};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use indexmap::IndexMap;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::{
+    document::{MediaType, Operation, Parameter, ParameterIn, RequestBody, Response, Schema},
+    generator::Generator,
+    utils::{
+        doc_attrs, field_ident, invalid_document, summary_doc_attrs, type_ident, unique_ident,
+        unraw,
+    },
+};
+
+/// The methods generated for an operation.
+struct OperationMethods {
+    trait_method: TokenStream,
+    server_method: TokenStream,
+}
+
+impl Generator<'_> {
+    pub(crate) fn generate_api(&mut self) -> Result<()> {
+        let crate_name = self.crate_name.clone();
+        let api_ident = type_ident(&self.config.api_name);
+        let server_ident = format_ident!("{}Server", api_ident);
+        let tags_ident = format_ident!("{}Tags", api_ident);
+        for ident in [&api_ident, &server_ident, &tags_ident] {
+            if self
+                .schema_types
+                .values()
+                .any(|schema_ident| schema_ident == ident)
+            {
+                return Err(invalid_document(format!(
+                    "the generated type `{ident}` has the same name as a schema"
+                )));
+            }
+        }
+
+        let doc = self.doc;
+        let tags = self.generate_tags(&tags_ident);
+        let mut trait_methods = Vec::new();
+        let mut server_methods = Vec::new();
+        let mut method_names = HashSet::new();
+
+        for (path, item) in &doc.paths {
+            for (method, operation) in item.operations() {
+                let mut params = item.parameters.clone();
+                for param in &operation.parameters {
+                    params.retain(|p| p.name != param.name || p.param_in != param.param_in);
+                    params.push(param.clone());
+                }
+
+                let name = match &operation.operation_id {
+                    Some(operation_id) => operation_id.to_snake_case(),
+                    None => format!("{method}_{path}").to_snake_case(),
+                };
+                if !method_names.insert(name.clone()) {
+                    return Err(invalid_document(format!(
+                        "the operation `{method} {path}` has the same Rust name as another \
+                         operation: `{name}`"
+                    )));
+                }
+
+                let methods = self.generate_operation(
+                    &name,
+                    path,
+                    method,
+                    operation,
+                    &params,
+                    tags.as_ref().map(|(ident, tags)| (ident, tags)),
+                )?;
+                trait_methods.push(methods.trait_method);
+                server_methods.push(methods.server_method);
+            }
+        }
+
+        let into_service = self.generate_into_service();
+        let api_doc = format!(" The operations of `{}`.", doc.info.title);
+        let server_doc = format!(" The API that calls an implementation of [`{api_ident}`].");
+        self.items.push(quote! {
+            #[doc = #api_doc]
+            pub trait #api_ident: Send + Sync + 'static {
+                #(#trait_methods)*
+            }
+
+            #[doc = #server_doc]
+            pub struct #server_ident<T> {
+                inner: T,
+            }
+
+            impl<T: #api_ident> #server_ident<T> {
+                /// Create a server with an implementation of the operations.
+                pub fn new(inner: T) -> Self {
+                    Self { inner }
+                }
+
+                /// Returns a reference to the implementation of the operations.
+                pub fn get_ref(&self) -> &T {
+                    &self.inner
+                }
+
+                /// Create an `OpenApiService` with the information of the
+                /// document.
+                pub fn into_service(self) -> #crate_name::OpenApiService<Self, ()> {
+                    #into_service
+                }
+            }
+
+            #[#crate_name::OpenApi]
+            impl<T: #api_ident> #server_ident<T> {
+                #(#server_methods)*
+            }
+        });
+        Ok(())
+    }
+
+    fn generate_tags(&mut self, tags_ident: &Ident) -> Option<(Ident, IndexMap<String, Ident>)> {
+        let crate_name = self.crate_name.clone();
+        let doc = self.doc;
+        let mut descriptions = IndexMap::new();
+        for tag in &doc.tags {
+            descriptions.insert(tag.name.as_str(), tag.description.as_deref());
+        }
+        for tag in doc
+            .paths
+            .values()
+            .flat_map(|item| item.operations())
+            .flat_map(|(_, operation)| &operation.tags)
+        {
+            descriptions.entry(tag.as_str()).or_insert(None);
+        }
+        if descriptions.is_empty() {
+            return None;
+        }
+
+        let mut variants = IndexMap::new();
+        let mut items = Vec::new();
+        for (name, description) in descriptions {
+            let variant = unique_ident(type_ident(name), |ident| {
+                variants.values().any(|v| v == ident)
+            });
+            let rename = (variant != name).then(|| quote!(#[oai(rename = #name)]));
+            let desc = doc_attrs(description);
+            items.push(quote!(#desc #rename #variant));
+            variants.insert(name.to_string(), variant);
+        }
+
+        self.items.push(quote! {
+            #[derive(#crate_name::Tags)]
+            pub enum #tags_ident {
+                #(#items),*
+            }
+        });
+        Some((tags_ident.clone(), variants))
+    }
+
+    fn generate_into_service(&self) -> TokenStream {
+        let crate_name = &self.crate_name;
+        let info = &self.doc.info;
+        let title = &info.title;
+        let version = &info.version;
+        let mut calls = Vec::new();
+
+        if let Some(summary) = &info.summary {
+            calls.push(quote!(.summary(#summary)));
+        }
+        if let Some(description) = &info.description {
+            calls.push(quote!(.description(#description)));
+        }
+        if let Some(url) = &info.terms_of_service {
+            calls.push(quote!(.terms_of_service(#url)));
+        }
+        if let Some(contact) = &info.contact {
+            let fields = [
+                ("name", &contact.name),
+                ("url", &contact.url),
+                ("email", &contact.email),
+            ]
+            .into_iter()
+            .filter_map(|(method, value)| {
+                let method = format_ident!("{}", method);
+                let value = value.as_ref()?;
+                Some(quote!(.#method(#value)))
+            });
+            calls.push(quote!(.contact(#crate_name::ContactObject::new() #(#fields)*)));
+        }
+        if let Some(license) = &info.license {
+            let name = &license.name;
+            let fields = [("identifier", &license.identifier), ("url", &license.url)]
+                .into_iter()
+                .filter_map(|(method, value)| {
+                    let method = format_ident!("{}", method);
+                    let value = value.as_ref()?;
+                    Some(quote!(.#method(#value)))
+                });
+            calls.push(quote!(.license(#crate_name::LicenseObject::new(#name) #(#fields)*)));
+        }
+        for server in &self.doc.servers {
+            let url = &server.url;
+            let description = server
+                .description
+                .as_ref()
+                .map(|description| quote!(.description(#description)));
+            calls.push(quote!(.server(#crate_name::ServerObject::new(#url) #description)));
+        }
+        if self.doc.is_v3_1() {
+            calls.push(quote!(.openapi_version(#crate_name::OpenApiVersion::V3_1)));
+        }
+
+        quote!(#crate_name::OpenApiService::new(self, #title, #version) #(#calls)*)
+    }
+
+    fn generate_operation(
+        &mut self,
+        name: &str,
+        path: &str,
+        method: &str,
+        operation: &Operation,
+        params: &[Parameter],
+        tags: Option<(&Ident, &IndexMap<String, Ident>)>,
+    ) -> Result<OperationMethods> {
+        let crate_name = self.crate_name.clone();
+        let method_ident = field_ident(name);
+        let type_prefix = name.to_upper_camel_case();
+        let mut args = Vec::new();
+        let mut arg_names = HashSet::new();
+
+        for param in params {
+            let (arg, ty, attrs) = self.generate_param(&type_prefix, param)?;
+            if !arg_names.insert(arg.to_string()) {
+                return Err(invalid_document(format!(
+                    "the operation `{method} {path}` has the parameters with the same Rust \
+                     name: `{arg}`"
+                )));
+            }
+            args.push((arg, ty, attrs));
+        }
+
+        if let Some(request) = &operation.request_body {
+            let (ty, attrs) = self.generate_request(&type_prefix, request)?;
+            let arg = match arg_names.contains("body") {
+                true => format_ident!("request_body"),
+                false => format_ident!("body"),
+            };
+            arg_names.insert(arg.to_string());
+            args.push((arg, ty, attrs));
+        }
+
+        if let Some((ty, attrs)) = self.generate_security(&type_prefix, operation)? {
+            let arg = match arg_names.contains("auth") {
+                true => format_ident!("security_scheme"),
+                false => format_ident!("auth"),
+            };
+            args.push((arg, ty, attrs));
+        }
+
+        let response_ty = self.generate_responses(&type_prefix, method, path, operation)?;
+
+        let mut oai_path = String::new();
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            oai_path.push('/');
+            match segment
+                .strip_prefix('{')
+                .and_then(|segment| segment.strip_suffix('}'))
+            {
+                Some(var) => {
+                    oai_path.push(':');
+                    oai_path.push_str(var);
+                }
+                None if segment.contains('{') => {
+                    return Err(invalid_document(format!(
+                        "unsupported path: `{path}`, a path parameter must be a whole segment"
+                    )));
+                }
+                None => oai_path.push_str(segment),
+            }
+        }
+        if oai_path.is_empty() {
+            oai_path.push('/');
+        }
+
+        let mut operation_args = vec![quote!(path = #oai_path), quote!(method = #method)];
+        if let Some((tags_ident, tag_variants)) = tags {
+            for tag in &operation.tags {
+                let tag = format!("{}::{}", tags_ident, tag_variants[tag]);
+                operation_args.push(quote!(tag = #tag));
+            }
+        }
+        if let Some(operation_id) = &operation.operation_id {
+            operation_args.push(quote!(operation_id = #operation_id));
+        }
+        if operation.deprecated {
+            operation_args.push(quote!(deprecated));
+        }
+
+        let desc = summary_doc_attrs(
+            operation.summary.as_deref(),
+            operation.description.as_deref(),
+        );
+        let trait_args = args.iter().map(|(arg, ty, _)| quote!(#arg: #ty));
+        let server_args = args.iter().map(|(arg, ty, attrs)| quote!(#attrs #arg: #ty));
+        let arg_names = args.iter().map(|(arg, _, _)| arg);
+
+        Ok(OperationMethods {
+            trait_method: quote! {
+                #desc
+                fn #method_ident(&self, #(#trait_args),*) -> impl ::std::future::Future<Output = #crate_name::__private::poem::Result<#response_ty>> + Send;
+            },
+            server_method: quote! {
+                #desc
+                #[oai(#(#operation_args),*)]
+                async fn #method_ident(&self, #(#server_args),*) -> #crate_name::__private::poem::Result<#response_ty> {
+                    self.inner.#method_ident(#(#arg_names),*).await
+                }
+            },
+        })
+    }
+
+    /// Returns the name, the type and the attributes of the argument for a
+    /// parameter.
+    fn generate_param(
+        &mut self,
+        type_prefix: &str,
+        param: &Parameter,
+    ) -> Result<(Ident, TokenStream, TokenStream)> {
+        let crate_name = self.crate_name.clone();
+        if let Some(reference) = &param.reference {
+            return Err(invalid_document(format!(
+                "unsupported parameter reference: `{reference}`"
+            )));
+        }
+        let Some(param_in) = param.param_in else {
+            return Err(invalid_document(format!(
+                "the parameter `{}` has no location",
+                param.name
+            )));
+        };
+
+        let arg = field_ident(&param.name);
+        let schema = param.schema.clone().unwrap_or_default();
+        let ty = self.rust_type(
+            &schema,
+            &format!("{type_prefix}{}", param.name.to_upper_camel_case()),
+        )?;
+        let ty = match param.required || param_in == ParameterIn::Path {
+            true => ty,
+            false => quote!(::std::option::Option<#ty>),
+        };
+        let ty = match param_in {
+            ParameterIn::Path => quote!(#crate_name::param::Path<#ty>),
+            ParameterIn::Query => quote!(#crate_name::param::Query<#ty>),
+            ParameterIn::Header => quote!(#crate_name::param::Header<#ty>),
+            ParameterIn::Cookie => quote!(#crate_name::param::Cookie<#ty>),
+        };
+
+        let mut args = Vec::new();
+        if unraw(&arg) != param.name {
+            let name = &param.name;
+            args.push(quote!(name = #name));
+        }
+        if param.deprecated {
+            args.push(quote!(deprecated));
+        }
+        if param.explode == Some(false) {
+            args.push(quote!(explode = false));
+        }
+        args.extend(self.validator_args(&schema));
+
+        let desc = doc_attrs(param.description.as_deref());
+        let oai = (!args.is_empty()).then(|| quote!(#[oai(#(#args),*)]));
+        Ok((arg, ty, quote!(#desc #oai)))
+    }
+
+    /// Returns the type and the attributes of the argument for a request body.
+    fn generate_request(
+        &mut self,
+        type_prefix: &str,
+        request: &RequestBody,
+    ) -> Result<(TokenStream, TokenStream)> {
+        let crate_name = self.crate_name.clone();
+        if let Some(reference) = &request.reference {
+            return Err(invalid_document(format!(
+                "unsupported request body reference: `{reference}`"
+            )));
+        }
+        if request.content.is_empty() {
+            return Err(invalid_document(format!(
+                "the request body of `{type_prefix}` has no content"
+            )));
+        }
+        let desc = doc_attrs(request.description.as_deref());
+
+        let mut payloads = Vec::new();
+        for (content_type, media) in &request.content {
+            let hint = match request.content.len() {
+                1 => format!("{type_prefix}Body"),
+                _ => format!("{type_prefix}Body{}", payload_variant_name(content_type)),
+            };
+            let optional = request.content.len() == 1 && !request.required;
+            payloads.push(self.payload_type(content_type, media, &hint, optional)?);
+        }
+        if let [payload] = payloads.as_slice() {
+            if payload.content_type.is_none() {
+                return Ok((payload.ty.clone(), desc));
+            }
+        }
+
+        let ident = self.new_type_ident(&format!("{type_prefix}Request"));
+        let mut variants = Vec::new();
+        let mut variant_names = Vec::new();
+        for (content_type, payload) in request.content.keys().zip(payloads) {
+            let variant = unique_variant(&mut variant_names, payload_variant_name(content_type));
+            let ty = payload.ty;
+            let content_type = payload
+                .content_type
+                .map(|content_type| quote!(#[oai(content_type = #content_type)]));
+            variants.push(quote!(#content_type #variant(#ty)));
+        }
+
+        self.items.push(quote! {
+            #[derive(#crate_name::ApiRequest, Debug)]
+            pub enum #ident {
+                #(#variants),*
+            }
+        });
+        Ok((quote!(#ident), desc))
+    }
+
+    /// Returns the type and the attributes of the argument for the security
+    /// requirements of an operation.
+    fn generate_security(
+        &mut self,
+        type_prefix: &str,
+        operation: &Operation,
+    ) -> Result<Option<(TokenStream, TokenStream)>> {
+        let doc = self.doc;
+        let requirements = operation.security.as_ref().unwrap_or(&doc.security);
+        let mut schemes = Vec::new();
+        let mut has_fallback = false;
+
+        for requirement in requirements {
+            match requirement.len() {
+                0 => has_fallback = true,
+                1 => schemes.extend(requirement.iter()),
+                _ => {
+                    return Err(invalid_document(format!(
+                        "the operation `{type_prefix}` requires several security schemes at \
+                         the same time, which is not supported"
+                    )));
+                }
+            }
+        }
+        if schemes.is_empty() {
+            return Ok(None);
+        }
+
+        let mut scope_args = Vec::new();
+        let mut scheme_types = Vec::new();
+        for (name, scopes) in &schemes {
+            let Some(scheme) = self.security_schemes.get(*name) else {
+                return Err(invalid_document(format!(
+                    "the security scheme `{name}` is not found in `components/securitySchemes`"
+                )));
+            };
+            for scope in *scopes {
+                let scope = scheme
+                    .scopes
+                    .as_ref()
+                    .and_then(|(scopes_ident, variants)| {
+                        Some(format!("{}::{}", scopes_ident, variants.get(scope)?))
+                    })
+                    .ok_or_else(|| {
+                        invalid_document(format!(
+                            "the scope `{scope}` is not defined in the security scheme `{name}`"
+                        ))
+                    })?;
+                scope_args.push(quote!(scope = #scope));
+            }
+            scheme_types.push(scheme.ident.clone());
+        }
+        if !scope_args.is_empty() && scheme_types.len() > 1 {
+            return Err(invalid_document(format!(
+                "the operation `{type_prefix}` requires OAuth2 scopes and accepts several \
+                 security schemes, which is not supported"
+            )));
+        }
+        let oai = (!scope_args.is_empty()).then(|| quote!(#[oai(#(#scope_args),*)]));
+
+        if let ([ident], false) = (scheme_types.as_slice(), has_fallback) {
+            return Ok(Some((quote!(#ident), quote!(#oai))));
+        }
+
+        let crate_name = self.crate_name.clone();
+        let fallback = has_fallback.then(|| {
+            quote!(
+                #[oai(fallback)]
+                Anonymous,
+            )
+        });
+        let ident = self.new_type_ident(&format!("{type_prefix}Auth"));
+        self.items.push(quote! {
+            #[derive(#crate_name::SecurityScheme)]
+            pub enum #ident {
+                #(#scheme_types(#scheme_types),)*
+                #fallback
+            }
+        });
+        Ok(Some((quote!(#ident), quote!(#oai))))
+    }
+
+    /// Defines the `ApiResponse` type for the responses of an operation and
+    /// returns it.
+    fn generate_responses(
+        &mut self,
+        type_prefix: &str,
+        method: &str,
+        path: &str,
+        operation: &Operation,
+    ) -> Result<Ident> {
+        let crate_name = self.crate_name.clone();
+        if operation.responses.is_empty() {
+            return Err(invalid_document(format!(
+                "the operation `{method} {path}` has no responses"
+            )));
+        }
+
+        let ident = self.new_type_ident(&format!("{type_prefix}Response"));
+        let mut variants = Vec::new();
+        let mut variant_names = Vec::new();
+
+        for (status, resp) in &operation.responses {
+            let (variant, status) = response_status(status).ok_or_else(|| {
+                invalid_document(format!(
+                    "the operation `{method} {path}` has an invalid response status: `{status}`"
+                ))
+            })?;
+            let variant = unique_variant(&mut variant_names, variant);
+            let mut fields = Vec::new();
+            let mut content_type_attr = None;
+
+            let status_attr = match &status {
+                ResponseStatus::Code(code) => Some(quote!(status = #code)),
+                ResponseStatus::Range(range) => Some(quote!(status_range = #range)),
+                ResponseStatus::Default => None,
+            };
+            if status_attr.is_none() || matches!(status, ResponseStatus::Range(_)) {
+                fields.push(quote!(#crate_name::__private::poem::http::StatusCode));
+            }
+
+            let Response {
+                reference,
+                description,
+                headers,
+                content,
+            } = resp;
+            if let Some(reference) = reference {
+                return Err(invalid_document(format!(
+                    "unsupported response reference: `{reference}`"
+                )));
+            }
+
+            match content.iter().collect::<Vec<_>>().as_slice() {
+                [] => {}
+                [(content_type, media)] => {
+                    let payload = self.payload_type(
+                        content_type,
+                        media,
+                        &format!("{ident}{variant}"),
+                        false,
+                    )?;
+                    let ty = payload.ty;
+                    fields.push(quote!(#ty));
+                    content_type_attr = payload
+                        .content_type
+                        .map(|content_type| quote!(content_type = #content_type));
+                }
+                content => {
+                    let content_ident = self.new_type_ident(&format!("{ident}{variant}Content"));
+                    let mut content_variants = Vec::new();
+                    let mut content_variant_names = Vec::new();
+                    for (content_type, media) in content {
+                        let payload = self.payload_type(
+                            content_type,
+                            media,
+                            &format!("{content_ident}{}", payload_variant_name(content_type)),
+                            false,
+                        )?;
+                        let content_variant = unique_variant(
+                            &mut content_variant_names,
+                            payload_variant_name(content_type),
+                        );
+                        let ty = payload.ty;
+                        let content_type = payload
+                            .content_type
+                            .map(|content_type| quote!(#[oai(content_type = #content_type)]));
+                        content_variants.push(quote!(#content_type #content_variant(#ty)));
+                    }
+                    self.items.push(quote! {
+                        #[derive(#crate_name::ResponseContent, Debug)]
+                        pub enum #content_ident {
+                            #(#content_variants),*
+                        }
+                    });
+                    fields.push(quote!(#content_ident));
+                }
+            }
+
+            for (header_name, header) in headers {
+                if let Some(reference) = &header.reference {
+                    return Err(invalid_document(format!(
+                        "unsupported header reference: `{reference}`"
+                    )));
+                }
+                let schema = header.schema.clone().unwrap_or_default();
+                let ty = self.rust_type(
+                    &schema,
+                    &format!("{ident}{variant}{}", header_name.to_upper_camel_case()),
+                )?;
+                let ty = match header.required {
+                    true => ty,
+                    false => quote!(::std::option::Option<#ty>),
+                };
+                let deprecated = header.deprecated.then(|| quote!(, deprecated));
+                let desc = doc_attrs(header.description.as_deref());
+                fields.push(quote!(#desc #[oai(header = #header_name #deprecated)] #ty));
+            }
+
+            let args = status_attr
+                .into_iter()
+                .chain(content_type_attr)
+                .collect::<Vec<_>>();
+            let oai = (!args.is_empty()).then(|| quote!(#[oai(#(#args),*)]));
+            let desc = doc_attrs(description.as_deref());
+            let fields = (!fields.is_empty()).then(|| quote!((#(#fields),*)));
+            variants.push(quote! {
+                #desc
+                #oai
+                #variant #fields
+            });
+        }
+
+        self.items.push(quote! {
+            #[derive(#crate_name::ApiResponse, Debug)]
+            pub enum #ident {
+                #(#variants),*
+            }
+        });
+        Ok(ident)
+    }
+
+    /// Returns the payload type for a media type, and the content type that
+    /// must be specified with `#[oai(content_type = "...")]` if it is not the
+    /// default content type of the payload.
+    fn payload_type(
+        &mut self,
+        content_type: &str,
+        media: &MediaType,
+        hint: &str,
+        optional: bool,
+    ) -> Result<Payload> {
+        let crate_name = self.crate_name.clone();
+        let schema = media.schema.clone().unwrap_or_default();
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        let (ty, default_content_type) = match essence.as_str() {
+            "application/json" => {
+                let ty = self.payload_inner_type(&schema, hint, optional)?;
+                (
+                    quote!(#crate_name::payload::Json<#ty>),
+                    "application/json; charset=utf-8",
+                )
+            }
+            essence if essence.ends_with("+json") => {
+                let ty = self.payload_inner_type(&schema, hint, optional)?;
+                (quote!(#crate_name::payload::Json<#ty>), "")
+            }
+            "application/xml" | "text/xml" => {
+                let ty = self.rust_type(&schema, hint)?;
+                (
+                    quote!(#crate_name::payload::Xml<#ty>),
+                    "application/xml; charset=utf-8",
+                )
+            }
+            "application/yaml" => {
+                let ty = self.rust_type(&schema, hint)?;
+                (
+                    quote!(#crate_name::payload::Yaml<#ty>),
+                    "application/yaml; charset=utf-8",
+                )
+            }
+            "text/plain" => (
+                quote!(#crate_name::payload::PlainText<::std::string::String>),
+                "text/plain; charset=utf-8",
+            ),
+            "text/html" => (
+                quote!(#crate_name::payload::Html<::std::string::String>),
+                "text/html; charset=utf-8",
+            ),
+            _ => (
+                quote!(#crate_name::payload::Binary<::std::vec::Vec<u8>>),
+                "application/octet-stream",
+            ),
+        };
+
+        Ok(Payload {
+            ty,
+            content_type: (content_type != default_content_type).then(|| content_type.to_string()),
+        })
+    }
+
+    fn payload_inner_type(
+        &mut self,
+        schema: &Schema,
+        hint: &str,
+        optional: bool,
+    ) -> Result<TokenStream> {
+        let ty = self.rust_type(schema, hint)?;
+        Ok(match optional {
+            true => quote!(::std::option::Option<#ty>),
+            false => ty,
+        })
+    }
+}
+
+struct Payload {
+    ty: TokenStream,
+    content_type: Option<String>,
+}
+
+enum ResponseStatus {
+    Code(u16),
+    Range(String),
+    Default,
+}
+
+/// Parses the key of a response, and returns the name of the variant for it.
+fn response_status(status: &str) -> Option<(String, ResponseStatus)> {
+    if status == "default" {
+        return Some(("Default".to_string(), ResponseStatus::Default));
+    }
+    if let Ok(code) = status.parse::<u16>() {
+        let name = http::StatusCode::from_u16(code)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .map(|reason| reason.to_upper_camel_case())
+            .unwrap_or_else(|| format!("Status{code}"));
+        return Some((name, ResponseStatus::Code(code)));
+    }
+    match status.as_bytes() {
+        [b'1'..=b'5', b'X' | b'x', b'X' | b'x'] => {
+            let range = status.to_ascii_uppercase();
+            Some((format!("Status{range}"), ResponseStatus::Range(range)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the name of a variant for the payload with a content type.
+fn payload_variant_name(content_type: &str) -> String {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    let subtype = essence.rsplit('/').next().unwrap_or(essence);
+    let subtype = subtype.rsplit('+').next().unwrap_or(subtype);
+    subtype.to_upper_camel_case()
+}
+
+fn unique_variant(variant_names: &mut Vec<Ident>, name: String) -> Ident {
+    let variant = unique_ident(type_ident(&name), |variant| variant_names.contains(variant));
+    variant_names.push(variant.clone());
+    variant
+}
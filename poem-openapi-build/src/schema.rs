@@ -0,0 +1,372 @@
+use std::io::Result;
+
+use heck::ToUpperCamelCase;
+use indexmap::IndexMap;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::{
+    document::{AdditionalProperties, ExclusiveBound, Schema},
+    generator::Generator,
+    utils::{doc_attrs, field_ident, invalid_document, type_ident, unique_ident, unraw},
+};
+
+impl Generator<'_> {
+    /// Defines a named type for a schema.
+    ///
+    /// `name` is the name of the schema in `components/schemas`, or `None` for
+    /// an inline schema.
+    pub(crate) fn define_schema(
+        &mut self,
+        ident: Ident,
+        name: Option<&str>,
+        schema: &Schema,
+    ) -> Result<()> {
+        let is_union = schema.reference().is_none()
+            && (!schema.one_of.is_empty() || !schema.any_of.is_empty());
+        let is_enum = schema.ty() == Some("string") && !schema.enum_items.is_empty();
+        let is_object = schema.reference().is_none()
+            && (!schema.properties.is_empty()
+                || !schema.all_of.is_empty()
+                || (schema.ty() == Some("object") && schema.additional_properties.is_none()));
+
+        if is_union {
+            self.define_union(ident, name, schema)
+        } else if is_enum {
+            self.define_enum(ident, name, schema)
+        } else if is_object {
+            self.define_object(ident, name, schema)
+        } else {
+            let desc = doc_attrs(schema.description.as_deref());
+            let ty = self.inline_type(schema, &ident.to_string())?;
+            self.items.push(quote! {
+                #desc
+                pub type #ident = #ty;
+            });
+            Ok(())
+        }
+    }
+
+    /// Returns the Rust type of a schema, defining new types for the inline
+    /// objects, enums and unions, which are named after `hint`.
+    pub(crate) fn rust_type(&mut self, schema: &Schema, hint: &str) -> Result<TokenStream> {
+        if let Some(name) = schema.reference() {
+            return match self.schema_types.get(name) {
+                Some(ident) => Ok(quote!(#ident)),
+                None => Err(invalid_document(format!(
+                    "the schema `{name}` is not found in `components/schemas`"
+                ))),
+            };
+        }
+        if let Some(reference) = &schema.reference {
+            return Err(invalid_document(format!(
+                "unsupported reference: `{reference}`"
+            )));
+        }
+
+        let needs_type = !schema.one_of.is_empty()
+            || !schema.any_of.is_empty()
+            || !schema.all_of.is_empty()
+            || !schema.properties.is_empty()
+            || (schema.ty() == Some("string") && !schema.enum_items.is_empty());
+        if needs_type {
+            let ident = self.new_type_ident(hint);
+            self.define_schema(ident.clone(), None, schema)?;
+            return Ok(quote!(#ident));
+        }
+
+        self.inline_type(schema, hint)
+    }
+
+    /// Returns the Rust type of a schema that does not define a new type.
+    fn inline_type(&mut self, schema: &Schema, hint: &str) -> Result<TokenStream> {
+        let crate_name = self.crate_name.clone();
+
+        if let Some(ty) = schema
+            .format
+            .as_ref()
+            .and_then(|format| self.config.formats.get(format))
+        {
+            return syn::parse_str::<syn::Type>(ty)
+                .map(|ty| quote!(#ty))
+                .map_err(|_| invalid_document(format!("invalid Rust type: `{ty}`")));
+        }
+
+        let format = schema.format.as_deref();
+        Ok(match schema.ty() {
+            Some("string") => match format {
+                Some("binary") => quote!(#crate_name::types::Binary<::std::vec::Vec<u8>>),
+                Some("bytes") => quote!(#crate_name::types::Base64<::std::vec::Vec<u8>>),
+                Some("password") => quote!(#crate_name::types::Password),
+                Some("ipv4") => quote!(::std::net::Ipv4Addr),
+                Some("ipv6") => quote!(::std::net::Ipv6Addr),
+                Some("uri") => quote!(#crate_name::__private::poem::http::Uri),
+                Some("path") => quote!(::std::path::PathBuf),
+                _ => quote!(::std::string::String),
+            },
+            Some("integer") => match format {
+                Some("int8") => quote!(i8),
+                Some("int16") => quote!(i16),
+                Some("int32") => quote!(i32),
+                Some("uint8") => quote!(u8),
+                Some("uint16") => quote!(u16),
+                Some("uint32") => quote!(u32),
+                Some("uint64") => quote!(u64),
+                _ => quote!(i64),
+            },
+            Some("number") => match format {
+                Some("float") => quote!(f32),
+                _ => quote!(f64),
+            },
+            Some("boolean") => quote!(bool),
+            Some("array") => {
+                let item = match &schema.items {
+                    Some(items) => self.rust_type(items, &format!("{hint}Item"))?,
+                    None => self.any_type(),
+                };
+                quote!(::std::vec::Vec<#item>)
+            }
+            Some("object") | None => match &schema.additional_properties {
+                Some(AdditionalProperties::Schema(value)) => {
+                    let value = self.rust_type(value, &format!("{hint}Value"))?;
+                    quote!(::std::collections::BTreeMap<::std::string::String, #value>)
+                }
+                Some(AdditionalProperties::Bool(true)) => {
+                    let value = self.any_type();
+                    quote!(::std::collections::BTreeMap<::std::string::String, #value>)
+                }
+                _ => self.any_type(),
+            },
+            Some(ty) => return Err(invalid_document(format!("unsupported type: `{ty}`"))),
+        })
+    }
+
+    fn any_type(&self) -> TokenStream {
+        let crate_name = &self.crate_name;
+        quote!(#crate_name::types::Any<#crate_name::__private::serde_json::Value>)
+    }
+
+    /// Returns the arguments of the `validator` attribute for the keywords of a
+    /// schema.
+    ///
+    /// Like `poem-openapi`, the keywords for the elements of an array or a map
+    /// are read from the schema of its items.
+    pub(crate) fn validator_args(&self, schema: &Schema) -> Option<TokenStream> {
+        let elements = match (&schema.items, &schema.additional_properties) {
+            (Some(items), _) => items,
+            (None, Some(AdditionalProperties::Schema(value))) => value,
+            _ => schema,
+        };
+        let mut args = Vec::new();
+
+        if let Some(value) = elements.multiple_of {
+            let value = value.to_string();
+            args.push(quote!(multiple_of = #value));
+        }
+        for (key, value, exclusive) in [
+            ("maximum", elements.maximum, &elements.exclusive_maximum),
+            ("minimum", elements.minimum, &elements.exclusive_minimum),
+        ] {
+            if let Some(value) = value {
+                let key = format_ident!("{}", key);
+                let value = value.to_string();
+                let exclusive = matches!(exclusive, Some(ExclusiveBound::Bool(true)))
+                    .then(|| quote!(, exclusive));
+                args.push(quote!(#key(value = #value #exclusive)));
+            }
+        }
+        for (key, value) in [
+            ("max_length", elements.max_length),
+            ("min_length", elements.min_length),
+            ("max_items", schema.max_items),
+            ("min_items", schema.min_items),
+            ("max_properties", schema.max_properties),
+            ("min_properties", schema.min_properties),
+        ] {
+            if let Some(value) = value {
+                let key = format_ident!("{}", key);
+                args.push(quote!(#key = #value));
+            }
+        }
+        if let Some(pattern) = &elements.pattern {
+            args.push(quote!(pattern = #pattern));
+        }
+        if schema.unique_items == Some(true) {
+            args.push(quote!(unique_items));
+        }
+
+        (!args.is_empty()).then(|| quote!(validator(#(#args),*)))
+    }
+
+    fn define_object(&mut self, ident: Ident, name: Option<&str>, schema: &Schema) -> Result<()> {
+        let crate_name = self.crate_name.clone();
+        let mut fields = Vec::new();
+        let mut properties = IndexMap::new();
+        let mut required = schema.required.clone();
+
+        for item in &schema.all_of {
+            match item.reference() {
+                Some(reference) => {
+                    let field = field_ident(reference);
+                    let ty = self.rust_type(item, reference)?;
+                    fields.push(quote!(#[oai(flatten)] pub #field: #ty));
+                }
+                None => {
+                    properties.extend(item.properties.iter());
+                    required.extend(item.required.iter().cloned());
+                }
+            }
+        }
+        properties.extend(schema.properties.iter());
+
+        for (prop_name, prop) in properties {
+            let field = field_ident(prop_name);
+            let ty =
+                self.rust_type(prop, &format!("{ident}{}", prop_name.to_upper_camel_case()))?;
+            let ty = if required.contains(prop_name) && !prop.nullable {
+                ty
+            } else {
+                quote!(::std::option::Option<#ty>)
+            };
+
+            let mut args = Vec::new();
+            if unraw(&field) != *prop_name {
+                args.push(quote!(rename = #prop_name));
+            }
+            for (flag, enabled) in [
+                ("deprecated", prop.deprecated),
+                ("read_only", prop.read_only),
+                ("write_only", prop.write_only),
+                ("nullable", prop.nullable),
+            ] {
+                if enabled {
+                    let flag = format_ident!("{}", flag);
+                    args.push(quote!(#flag));
+                }
+            }
+            args.extend(self.validator_args(prop));
+
+            let desc = doc_attrs(prop.description.as_deref());
+            let oai = (!args.is_empty()).then(|| quote!(#[oai(#(#args),*)]));
+            fields.push(quote!(#desc #oai pub #field: #ty));
+        }
+
+        let mut args = Vec::new();
+        if let Some(name) = name.filter(|name| ident != name) {
+            args.push(quote!(rename = #name));
+        }
+        if schema.deprecated {
+            args.push(quote!(deprecated));
+        }
+        let oai = (!args.is_empty()).then(|| quote!(#[oai(#(#args),*)]));
+        let desc = doc_attrs(schema.description.as_deref());
+
+        self.items.push(quote! {
+            #desc
+            #[derive(#crate_name::Object, Debug, Clone, PartialEq)]
+            #oai
+            pub struct #ident {
+                #(#fields),*
+            }
+        });
+        Ok(())
+    }
+
+    fn define_enum(&mut self, ident: Ident, name: Option<&str>, schema: &Schema) -> Result<()> {
+        let crate_name = self.crate_name.clone();
+        let mut variants = Vec::new();
+        let mut variant_names = Vec::new();
+
+        for value in &schema.enum_items {
+            let Some(value) = value.as_str() else {
+                return Err(invalid_document(format!(
+                    "the enum `{ident}` has a value that is not a string: `{value}`"
+                )));
+            };
+            let variant = type_ident(value);
+            if variant_names.contains(&variant) {
+                return Err(invalid_document(format!(
+                    "the enum `{ident}` has the values with the same Rust name: `{variant}`"
+                )));
+            }
+            let rename = (variant != value).then(|| quote!(#[oai(rename = #value)]));
+            variants.push(quote!(#rename #variant));
+            variant_names.push(variant);
+        }
+
+        let rename = name
+            .filter(|name| ident != name)
+            .map(|name| quote!(#[oai(rename = #name)]));
+        let desc = doc_attrs(schema.description.as_deref());
+        self.items.push(quote! {
+            #desc
+            #[derive(#crate_name::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+            #rename
+            pub enum #ident {
+                #(#variants),*
+            }
+        });
+        Ok(())
+    }
+
+    fn define_union(&mut self, ident: Ident, name: Option<&str>, schema: &Schema) -> Result<()> {
+        let crate_name = self.crate_name.clone();
+        let (items, one_of) = match schema.one_of.is_empty() {
+            false => (&schema.one_of, true),
+            true => (&schema.any_of, false),
+        };
+        let mut variants = Vec::new();
+        let mut variant_names = Vec::new();
+
+        for item in items {
+            let (variant, schema_name) = match item.reference() {
+                Some(reference) => (type_ident(reference), Some(reference)),
+                None => (type_ident(item.ty().unwrap_or("object")), None),
+            };
+            let variant = unique_ident(variant, |variant| variant_names.contains(variant));
+            let ty = self.rust_type(item, &format!("{ident}{variant}"))?;
+
+            let mapping = schema.discriminator.as_ref().and_then(|discriminator| {
+                let schema_name = schema_name?;
+                let mapping = discriminator
+                    .mapping
+                    .iter()
+                    .find(|(_, target)| {
+                        target
+                            .strip_prefix("#/components/schemas/")
+                            .unwrap_or(target)
+                            == schema_name
+                    })
+                    .map(|(mapping, _)| mapping.as_str())
+                    .unwrap_or(schema_name);
+                (variant != mapping).then(|| quote!(#[oai(mapping = #mapping)]))
+            });
+            variants.push(quote!(#mapping #variant(#ty)));
+            variant_names.push(variant);
+        }
+
+        let mut args = Vec::new();
+        if let Some(name) = name.filter(|name| ident != name) {
+            args.push(quote!(rename = #name));
+        }
+        if one_of {
+            args.push(quote!(one_of));
+        }
+        if let Some(discriminator) = &schema.discriminator {
+            let property_name = &discriminator.property_name;
+            args.push(quote!(discriminator_name = #property_name));
+        }
+        let oai = (!args.is_empty()).then(|| quote!(#[oai(#(#args),*)]));
+        let desc = doc_attrs(schema.description.as_deref());
+
+        self.items.push(quote! {
+            #desc
+            #[derive(#crate_name::Union, Debug, Clone, PartialEq)]
+            #oai
+            pub enum #ident {
+                #(#variants),*
+            }
+        });
+        Ok(())
+    }
+}
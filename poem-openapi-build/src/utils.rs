@@ -0,0 +1,94 @@
+use std::io::{Error, ErrorKind};
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro_crate::{FoundCrate, crate_name};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+
+pub(crate) fn get_crate_name(name: &str) -> TokenStream {
+    let name = match crate_name(name) {
+        Ok(FoundCrate::Name(name)) => name,
+        Ok(FoundCrate::Itself) | Err(_) => name.replace('-', "_"),
+    };
+    let name = Ident::new(&name, Span::call_site());
+    quote!(::#name)
+}
+
+pub(crate) fn invalid_document(msg: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidData, msg.into())
+}
+
+/// Creates an identifier for a type from a name in the document.
+pub(crate) fn type_ident(name: &str) -> Ident {
+    let name = name.to_upper_camel_case();
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        Ident::new(&format!("V{name}"), Span::call_site())
+    } else if name == "Self" {
+        Ident::new("Self_", Span::call_site())
+    } else {
+        Ident::new(&name, Span::call_site())
+    }
+}
+
+/// Returns `ident`, or the first of `ident2`, `ident3`, ... if it is taken.
+pub(crate) fn unique_ident(ident: Ident, is_taken: impl Fn(&Ident) -> bool) -> Ident {
+    if !is_taken(&ident) {
+        return ident;
+    }
+    (2u32..)
+        .map(|n| format_ident!("{}{}", ident, n))
+        .find(|ident| !is_taken(ident))
+        .unwrap()
+}
+
+/// Creates an identifier for a field, a parameter or a method from a name in
+/// the document.
+pub(crate) fn field_ident(name: &str) -> Ident {
+    let name = name.to_snake_case();
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        Ident::new(&format!("_{name}"), Span::call_site())
+    } else if matches!(name.as_str(), "self" | "super" | "crate") {
+        Ident::new(&format!("{name}_"), Span::call_site())
+    } else if syn::parse_str::<Ident>(&name).is_err() {
+        Ident::new_raw(&name, Span::call_site())
+    } else {
+        Ident::new(&name, Span::call_site())
+    }
+}
+
+/// Returns the name used by `poem-openapi` for an identifier, which ignores the
+/// `r#` prefix of the raw identifiers.
+pub(crate) fn unraw(ident: &Ident) -> String {
+    let name = ident.to_string();
+    match name.strip_prefix("r#") {
+        Some(name) => name.to_string(),
+        None => name,
+    }
+}
+
+/// Creates the `#[doc]` attributes for a description, one for each line, so
+/// that `poem-openapi` restores the same description from them.
+pub(crate) fn doc_attrs(description: Option<&str>) -> TokenStream {
+    let lines = description
+        .into_iter()
+        .flat_map(str::lines)
+        .map(|line| match line.trim() {
+            "" => String::new(),
+            line => format!(" {line}"),
+        });
+    quote!(#(#[doc = #lines])*)
+}
+
+/// Creates the `#[doc]` attributes for the summary and the description of an
+/// operation.
+pub(crate) fn summary_doc_attrs(summary: Option<&str>, description: Option<&str>) -> TokenStream {
+    match (summary, description) {
+        (Some(summary), Some(description)) => doc_attrs(Some(&format!(
+            "{}\n\n{}",
+            summary.trim(),
+            description.trim()
+        ))),
+        (Some(doc), None) | (None, Some(doc)) => doc_attrs(Some(doc)),
+        (None, None) => TokenStream::new(),
+    }
+}
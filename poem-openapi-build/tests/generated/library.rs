@@ -0,0 +1,99 @@
+#[derive(::poem_openapi::Object, Debug, Clone, PartialEq)]
+pub struct Author {
+    pub name: ::std::string::String,
+}
+/// A book in the library.
+#[derive(::poem_openapi::Object, Debug, Clone, PartialEq)]
+pub struct Book {
+    #[oai(read_only)]
+    pub id: i64,
+    pub kind: BookKind,
+    pub title: ::std::string::String,
+    #[oai(nullable)]
+    pub subtitle: ::std::option::Option<::std::string::String>,
+    #[oai(nullable)]
+    pub author: ::std::option::Option<Author>,
+    #[oai(validator(maximum(value = "5", exclusive), minimum(value = "0", exclusive)))]
+    pub rating: ::std::option::Option<f64>,
+}
+#[derive(::poem_openapi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookKind {
+    #[oai(rename = "book")]
+    Book,
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum GetBookResponse {
+    /// The book
+    #[oai(status = 200u16)]
+    Ok(::poem_openapi::payload::Json<Book>),
+    /// The book was not found
+    #[oai(status = 404u16)]
+    NotFound,
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum UpdateBookResponse {
+    /// The book was updated
+    #[oai(status = 200u16)]
+    Ok(::poem_openapi::payload::Json<Book>),
+}
+/// The operations of `Library`.
+pub trait Library: Send + Sync + 'static {
+    /// Find a book by its id
+    fn get_book(
+        &self,
+        book_id: ::poem_openapi::param::Path<i64>,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<GetBookResponse>,
+    > + Send;
+    /// Update a book
+    fn update_book(
+        &self,
+        book_id: ::poem_openapi::param::Path<i64>,
+        body: ::poem_openapi::payload::Json<Book>,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<UpdateBookResponse>,
+    > + Send;
+}
+/// The API that calls an implementation of [`Library`].
+pub struct LibraryServer<T> {
+    inner: T,
+}
+impl<T: Library> LibraryServer<T> {
+    /// Create a server with an implementation of the operations.
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+    /// Returns a reference to the implementation of the operations.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+    /// Create an `OpenApiService` with the information of the
+    /// document.
+    pub fn into_service(self) -> ::poem_openapi::OpenApiService<Self, ()> {
+        ::poem_openapi::OpenApiService::new(self, "Library", "1.0.0")
+            .description("A sample API that uses the OpenAPI 3.1 keywords.")
+            .openapi_version(::poem_openapi::OpenApiVersion::V3_1)
+    }
+}
+#[::poem_openapi::OpenApi]
+impl<T: Library> LibraryServer<T> {
+    /// Find a book by its id
+    #[oai(path = "/books/:bookId", method = "get", operation_id = "getBook")]
+    async fn get_book(
+        &self,
+        #[oai(name = "bookId", validator(minimum(value = "0", exclusive)))]
+        book_id: ::poem_openapi::param::Path<i64>,
+    ) -> ::poem_openapi::__private::poem::Result<GetBookResponse> {
+        self.inner.get_book(book_id).await
+    }
+    /// Update a book
+    #[oai(path = "/books/:bookId", method = "put", operation_id = "updateBook")]
+    async fn update_book(
+        &self,
+        #[oai(name = "bookId", validator(minimum(value = "0", exclusive)))]
+        book_id: ::poem_openapi::param::Path<i64>,
+        body: ::poem_openapi::payload::Json<Book>,
+    ) -> ::poem_openapi::__private::poem::Result<UpdateBookResponse> {
+        self.inner.update_book(book_id, body).await
+    }
+}
@@ -0,0 +1,355 @@
+#[derive(::poem_openapi::Object, Debug, Clone, PartialEq)]
+pub struct Category {
+    pub id: i64,
+    pub name: ::std::string::String,
+}
+/// An error returned by the API.
+#[derive(::poem_openapi::Object, Debug, Clone, PartialEq)]
+pub struct Error {
+    pub code: i32,
+    pub message: ::std::string::String,
+}
+#[derive(::poem_openapi::Object, Debug, Clone, PartialEq)]
+pub struct NewPet {
+    /// The name of the pet
+    #[oai(validator(max_length = 32usize, min_length = 1usize))]
+    pub name: ::std::string::String,
+    pub tag: ::std::option::Option<::std::string::String>,
+    pub category: ::std::option::Option<Category>,
+    #[oai(validator(max_items = 8usize))]
+    pub photo_urls: ::std::option::Option<
+        ::std::vec::Vec<::poem_openapi::__private::poem::http::Uri>,
+    >,
+}
+/// A pet in the store.
+#[derive(::poem_openapi::Object, Debug, Clone, PartialEq)]
+pub struct Pet {
+    #[oai(read_only)]
+    pub id: i64,
+    pub name: ::std::string::String,
+    pub tag: ::std::option::Option<::std::string::String>,
+    pub status: PetStatus,
+    #[oai(deprecated)]
+    pub birthday: ::std::option::Option<chrono::DateTime<chrono::Utc>>,
+}
+/// The status of a pet in the store.
+#[derive(::poem_openapi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PetStatus {
+    #[oai(rename = "available")]
+    Available,
+    #[oai(rename = "pending")]
+    Pending,
+    #[oai(rename = "sold")]
+    Sold,
+}
+#[derive(::poem_openapi::Union, Debug, Clone, PartialEq)]
+#[oai(one_of)]
+pub enum PetOrError {
+    Pet(Pet),
+    Error(Error),
+}
+#[derive(::poem_openapi::SecurityScheme)]
+#[oai(rename = "api_key", ty = "api_key", key_name = "X-API-Key", key_in = "header")]
+pub struct ApiKey(pub ::poem_openapi::auth::ApiKey);
+#[derive(::poem_openapi::OAuthScopes, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PetstoreAuthScopes {
+    /// Modify the pets
+    #[oai(rename = "write:pets")]
+    WritePets,
+    /// Read the pets
+    #[oai(rename = "read:pets")]
+    ReadPets,
+}
+#[derive(::poem_openapi::SecurityScheme)]
+#[oai(
+    rename = "petstore_auth",
+    ty = "oauth2",
+    flows(
+        implicit(
+            authorization_url = "https://petstore.example.com/oauth/authorize",
+            scopes = "PetstoreAuthScopes"
+        )
+    )
+)]
+pub struct PetstoreAuth(pub ::poem_openapi::auth::Bearer);
+#[derive(::poem_openapi::Tags)]
+pub enum PetStoreTags {
+    /// Everything about the pets
+    #[oai(rename = "pet")]
+    Pet,
+    /// Access to the orders
+    #[oai(rename = "store")]
+    Store,
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum ListPetsResponse {
+    /// A paged array of pets
+    #[oai(status = 200u16)]
+    Ok(
+        ::poem_openapi::payload::Json<::std::vec::Vec<Pet>>,
+        /// A link to the next page of responses
+        #[oai(header = "X-NEXT")]
+        ::std::option::Option<::std::string::String>,
+    ),
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum CreatePetResponse {
+    /// The pet was created
+    #[oai(status = 201u16)]
+    Created(::poem_openapi::payload::Json<Pet>),
+    /// The pet is invalid
+    #[oai(status = 400u16)]
+    BadRequest(::poem_openapi::payload::Json<Error>),
+}
+#[derive(::poem_openapi::SecurityScheme)]
+pub enum ShowPetByIdAuth {
+    ApiKey(ApiKey),
+    #[oai(fallback)]
+    Anonymous,
+}
+#[derive(::poem_openapi::ResponseContent, Debug)]
+pub enum ShowPetByIdResponseOkContent {
+    Json(::poem_openapi::payload::Json<Pet>),
+    Xml(::poem_openapi::payload::Xml<Pet>),
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum ShowPetByIdResponse {
+    /// Expected response to a valid request
+    #[oai(status = 200u16)]
+    Ok(ShowPetByIdResponseOkContent),
+    /// The pet was not found
+    #[oai(status = 404u16)]
+    NotFound,
+    /// Unexpected error
+    Default(
+        ::poem_openapi::__private::poem::http::StatusCode,
+        ::poem_openapi::payload::Json<Error>,
+    ),
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum UpdatePetResponse {
+    /// The updated pet, or the reason why it was not updated
+    #[oai(status = 200u16)]
+    Ok(::poem_openapi::payload::Json<PetOrError>),
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum DeletePetResponse {
+    /// The pet was deleted
+    #[oai(status = 204u16)]
+    NoContent,
+}
+#[derive(::poem_openapi::ApiResponse, Debug)]
+pub enum GetInventoryResponse {
+    /// Successful operation
+    #[oai(status = 200u16)]
+    Ok(
+        ::poem_openapi::payload::Json<
+            ::std::collections::BTreeMap<::std::string::String, i32>,
+        >,
+    ),
+    /// A client error
+    #[oai(status_range = "4XX")]
+    Status4Xx(
+        ::poem_openapi::__private::poem::http::StatusCode,
+        ::poem_openapi::payload::PlainText<::std::string::String>,
+    ),
+}
+/// The operations of `Pet Store`.
+pub trait PetStore: Send + Sync + 'static {
+    /// List all pets
+    fn list_pets(
+        &self,
+        limit: ::poem_openapi::param::Query<::std::option::Option<i32>>,
+        status: ::poem_openapi::param::Query<
+            ::std::option::Option<::std::vec::Vec<PetStatus>>,
+        >,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<ListPetsResponse>,
+    > + Send;
+    /// Create a pet
+    fn create_pet(
+        &self,
+        body: ::poem_openapi::payload::Json<NewPet>,
+        auth: PetstoreAuth,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<CreatePetResponse>,
+    > + Send;
+    /// Info for a specific pet
+    fn show_pet_by_id(
+        &self,
+        pet_id: ::poem_openapi::param::Path<i64>,
+        x_request_id: ::poem_openapi::param::Header<
+            ::std::option::Option<::std::string::String>,
+        >,
+        auth: ShowPetByIdAuth,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<ShowPetByIdResponse>,
+    > + Send;
+    /// Updates a pet
+    fn update_pet(
+        &self,
+        pet_id: ::poem_openapi::param::Path<i64>,
+        body: ::poem_openapi::payload::Json<NewPet>,
+        auth: PetstoreAuth,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<UpdatePetResponse>,
+    > + Send;
+    /// Deletes a pet
+    fn delete_pet(
+        &self,
+        pet_id: ::poem_openapi::param::Path<i64>,
+        auth: ApiKey,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<DeletePetResponse>,
+    > + Send;
+    /// Returns the pet inventories by status
+    ///
+    /// Returns a map of status codes to quantities.
+    /// The map only contains the statuses with at least one pet.
+    fn get_inventory(
+        &self,
+    ) -> impl ::std::future::Future<
+        Output = ::poem_openapi::__private::poem::Result<GetInventoryResponse>,
+    > + Send;
+}
+/// The API that calls an implementation of [`PetStore`].
+pub struct PetStoreServer<T> {
+    inner: T,
+}
+impl<T: PetStore> PetStoreServer<T> {
+    /// Create a server with an implementation of the operations.
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+    /// Returns a reference to the implementation of the operations.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+    /// Create an `OpenApiService` with the information of the
+    /// document.
+    pub fn into_service(self) -> ::poem_openapi::OpenApiService<Self, ()> {
+        ::poem_openapi::OpenApiService::new(self, "Pet Store", "1.0.0")
+            .description("A sample API that uses a pet store as an example.")
+            .contact(
+                ::poem_openapi::ContactObject::new()
+                    .name("Pet Store Team")
+                    .email("team@petstore.example.com"),
+            )
+            .license(
+                ::poem_openapi::LicenseObject::new("MIT")
+                    .url("https://opensource.org/licenses/MIT"),
+            )
+            .server(
+                ::poem_openapi::ServerObject::new("https://petstore.example.com/api")
+                    .description("Production"),
+            )
+    }
+}
+#[::poem_openapi::OpenApi]
+impl<T: PetStore> PetStoreServer<T> {
+    /// List all pets
+    #[oai(
+        path = "/pets",
+        method = "get",
+        tag = "PetStoreTags::Pet",
+        operation_id = "listPets"
+    )]
+    async fn list_pets(
+        &self,
+        /// How many items to return at one time
+        #[oai(validator(maximum(value = "100"), minimum(value = "1")))]
+        limit: ::poem_openapi::param::Query<::std::option::Option<i32>>,
+        #[oai(explode = false)]
+        status: ::poem_openapi::param::Query<
+            ::std::option::Option<::std::vec::Vec<PetStatus>>,
+        >,
+    ) -> ::poem_openapi::__private::poem::Result<ListPetsResponse> {
+        self.inner.list_pets(limit, status).await
+    }
+    /// Create a pet
+    #[oai(
+        path = "/pets",
+        method = "post",
+        tag = "PetStoreTags::Pet",
+        operation_id = "createPet"
+    )]
+    async fn create_pet(
+        &self,
+        body: ::poem_openapi::payload::Json<NewPet>,
+        #[oai(scope = "PetstoreAuthScopes::WritePets")]
+        auth: PetstoreAuth,
+    ) -> ::poem_openapi::__private::poem::Result<CreatePetResponse> {
+        self.inner.create_pet(body, auth).await
+    }
+    /// Info for a specific pet
+    #[oai(
+        path = "/pets/:petId",
+        method = "get",
+        tag = "PetStoreTags::Pet",
+        operation_id = "showPetById"
+    )]
+    async fn show_pet_by_id(
+        &self,
+        /// The id of the pet to retrieve
+        #[oai(name = "petId")]
+        pet_id: ::poem_openapi::param::Path<i64>,
+        #[oai(name = "X-Request-Id")]
+        x_request_id: ::poem_openapi::param::Header<
+            ::std::option::Option<::std::string::String>,
+        >,
+        auth: ShowPetByIdAuth,
+    ) -> ::poem_openapi::__private::poem::Result<ShowPetByIdResponse> {
+        self.inner.show_pet_by_id(pet_id, x_request_id, auth).await
+    }
+    /// Updates a pet
+    #[oai(
+        path = "/pets/:petId",
+        method = "put",
+        tag = "PetStoreTags::Pet",
+        operation_id = "updatePet"
+    )]
+    async fn update_pet(
+        &self,
+        #[oai(name = "petId")]
+        pet_id: ::poem_openapi::param::Path<i64>,
+        body: ::poem_openapi::payload::Json<NewPet>,
+        #[oai(
+            scope = "PetstoreAuthScopes::WritePets",
+            scope = "PetstoreAuthScopes::ReadPets"
+        )]
+        auth: PetstoreAuth,
+    ) -> ::poem_openapi::__private::poem::Result<UpdatePetResponse> {
+        self.inner.update_pet(pet_id, body, auth).await
+    }
+    /// Deletes a pet
+    #[oai(
+        path = "/pets/:petId",
+        method = "delete",
+        tag = "PetStoreTags::Pet",
+        operation_id = "deletePet",
+        deprecated
+    )]
+    async fn delete_pet(
+        &self,
+        #[oai(name = "petId")]
+        pet_id: ::poem_openapi::param::Path<i64>,
+        auth: ApiKey,
+    ) -> ::poem_openapi::__private::poem::Result<DeletePetResponse> {
+        self.inner.delete_pet(pet_id, auth).await
+    }
+    /// Returns the pet inventories by status
+    ///
+    /// Returns a map of status codes to quantities.
+    /// The map only contains the statuses with at least one pet.
+    #[oai(
+        path = "/store/inventory",
+        method = "get",
+        tag = "PetStoreTags::Store",
+        operation_id = "getInventory"
+    )]
+    async fn get_inventory(
+        &self,
+    ) -> ::poem_openapi::__private::poem::Result<GetInventoryResponse> {
+        self.inner.get_inventory().await
+    }
+}
@@ -0,0 +1,125 @@
+use poem::test::TestClient;
+use poem_openapi::{param::Path, payload::Json};
+use poem_openapi_build::Config;
+use serde_json::Value;
+
+#[allow(dead_code)]
+mod library {
+    include!("generated/library.rs");
+}
+
+use library::*;
+
+fn config() -> Config {
+    Config::new().api_name("Library")
+}
+
+struct MyLibrary;
+
+impl Library for MyLibrary {
+    async fn get_book(&self, book_id: Path<i64>) -> poem::Result<GetBookResponse> {
+        Ok(match book_id.0 {
+            1 => GetBookResponse::Ok(Json(Book {
+                id: 1,
+                kind: BookKind::Book,
+                title: "Dune".to_string(),
+                subtitle: None,
+                author: Some(Author {
+                    name: "Frank Herbert".to_string(),
+                }),
+                rating: Some(4.5),
+            })),
+            _ => GetBookResponse::NotFound,
+        })
+    }
+
+    async fn update_book(
+        &self,
+        book_id: Path<i64>,
+        body: Json<Book>,
+    ) -> poem::Result<UpdateBookResponse> {
+        Ok(UpdateBookResponse::Ok(Json(Book {
+            id: book_id.0,
+            ..body.0
+        })))
+    }
+}
+
+#[test]
+fn generated_code_is_up_to_date() {
+    let code = config().generate("tests/library.json").unwrap();
+    if std::env::var_os("UPDATE_GENERATED").is_some() {
+        std::fs::write("tests/generated/library.rs", &code).unwrap();
+    }
+    assert_eq!(
+        code,
+        include_str!("generated/library.rs"),
+        "run the tests with `UPDATE_GENERATED=1` to update the generated code"
+    );
+}
+
+#[test]
+fn spec_matches_document() {
+    let expected: Value = serde_json::from_str(include_str!("library.json")).unwrap();
+    let service = LibraryServer::new(MyLibrary).into_service();
+    let spec: Value = serde_json::from_str(&service.spec()).unwrap();
+    assert_eq!(spec, expected);
+}
+
+#[tokio::test]
+async fn call_operations() {
+    let service = LibraryServer::new(MyLibrary).into_service();
+    let cli = TestClient::new(service);
+
+    let resp = cli.get("/books/1").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_json(serde_json::json!({
+        "id": 1,
+        "kind": "book",
+        "title": "Dune",
+        "subtitle": null,
+        "author": { "name": "Frank Herbert" },
+        "rating": 4.5,
+    }))
+    .await;
+
+    cli.get("/books/2")
+        .send()
+        .await
+        .assert_status(poem::http::StatusCode::NOT_FOUND);
+    cli.get("/books/0")
+        .send()
+        .await
+        .assert_status(poem::http::StatusCode::BAD_REQUEST);
+
+    let resp = cli
+        .put("/books/2")
+        .body_json(&serde_json::json!({
+            "kind": "book",
+            "title": "Emma",
+            "subtitle": null,
+            "author": null,
+        }))
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_json(serde_json::json!({
+        "id": 2,
+        "kind": "book",
+        "title": "Emma",
+        "subtitle": null,
+        "author": null,
+        "rating": null,
+    }))
+    .await;
+
+    cli.put("/books/2")
+        .body_json(&serde_json::json!({
+            "kind": "book",
+            "title": "Emma",
+            "rating": 5.0,
+        }))
+        .send()
+        .await
+        .assert_status(poem::http::StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,206 @@
+use poem::test::TestClient;
+use poem_openapi::{
+    auth::ApiKey as ApiKeyValue,
+    param::{Header, Path, Query},
+    payload::Json,
+};
+use poem_openapi_build::Config;
+use serde_json::Value;
+
+#[allow(dead_code)]
+mod petstore {
+    include!("generated/petstore.rs");
+}
+
+use petstore::*;
+
+fn config() -> Config {
+    Config::new()
+        .api_name("PetStore")
+        .format("date-time", "chrono::DateTime<chrono::Utc>")
+}
+
+struct MyPetStore;
+
+impl PetStore for MyPetStore {
+    async fn list_pets(
+        &self,
+        _limit: Query<Option<i32>>,
+        _status: Query<Option<Vec<PetStatus>>>,
+    ) -> poem::Result<ListPetsResponse> {
+        Ok(ListPetsResponse::Ok(Json(vec![]), None))
+    }
+
+    async fn create_pet(
+        &self,
+        body: Json<NewPet>,
+        _auth: PetstoreAuth,
+    ) -> poem::Result<CreatePetResponse> {
+        Ok(CreatePetResponse::Created(Json(Pet {
+            id: 1,
+            name: body.0.name,
+            tag: body.0.tag,
+            status: PetStatus::Available,
+            birthday: None,
+        })))
+    }
+
+    async fn show_pet_by_id(
+        &self,
+        pet_id: Path<i64>,
+        _x_request_id: Header<Option<String>>,
+        _auth: ShowPetByIdAuth,
+    ) -> poem::Result<ShowPetByIdResponse> {
+        Ok(match pet_id.0 {
+            1 => ShowPetByIdResponse::Ok(ShowPetByIdResponseOkContent::Json(Json(Pet {
+                id: 1,
+                name: "Tom".to_string(),
+                tag: None,
+                status: PetStatus::Sold,
+                birthday: None,
+            }))),
+            _ => ShowPetByIdResponse::NotFound,
+        })
+    }
+
+    async fn update_pet(
+        &self,
+        pet_id: Path<i64>,
+        body: Json<NewPet>,
+        _auth: PetstoreAuth,
+    ) -> poem::Result<UpdatePetResponse> {
+        Ok(UpdatePetResponse::Ok(Json(PetOrError::Pet(Pet {
+            id: pet_id.0,
+            name: body.0.name,
+            tag: body.0.tag,
+            status: PetStatus::Pending,
+            birthday: None,
+        }))))
+    }
+
+    async fn delete_pet(
+        &self,
+        _pet_id: Path<i64>,
+        auth: ApiKey,
+    ) -> poem::Result<DeletePetResponse> {
+        let ApiKey(ApiKeyValue { key }) = auth;
+        assert_eq!(key, "secret");
+        Ok(DeletePetResponse::NoContent)
+    }
+
+    async fn get_inventory(&self) -> poem::Result<GetInventoryResponse> {
+        Ok(GetInventoryResponse::Ok(Json(
+            [("sold".to_string(), 3)].into_iter().collect(),
+        )))
+    }
+}
+
+/// Converts the numbers to floats, because `poem-openapi` writes the
+/// validators as floats.
+fn normalize_numbers(value: &mut Value) {
+    match value {
+        Value::Number(n) => *value = Value::from(n.as_f64().unwrap()),
+        Value::Array(values) => values.iter_mut().for_each(normalize_numbers),
+        Value::Object(map) => map.values_mut().for_each(normalize_numbers),
+        _ => {}
+    }
+}
+
+#[test]
+fn generated_code_is_up_to_date() {
+    let code = config().generate("tests/petstore.yaml").unwrap();
+    if std::env::var_os("UPDATE_GENERATED").is_some() {
+        std::fs::write("tests/generated/petstore.rs", &code).unwrap();
+    }
+    assert_eq!(
+        code,
+        include_str!("generated/petstore.rs"),
+        "run the tests with `UPDATE_GENERATED=1` to update the generated code"
+    );
+}
+
+#[test]
+fn spec_matches_document() {
+    let mut expected: Value = serde_yaml::from_str(include_str!("petstore.yaml")).unwrap();
+    let service = PetStoreServer::new(MyPetStore).into_service();
+    let mut spec: Value = serde_json::from_str(&service.spec()).unwrap();
+    normalize_numbers(&mut expected);
+    normalize_numbers(&mut spec);
+    assert_eq!(spec, expected);
+}
+
+#[tokio::test]
+async fn call_operations() {
+    let service = PetStoreServer::new(MyPetStore).into_service();
+    let cli = TestClient::new(service);
+
+    let resp = cli.get("/pets/1").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_json(serde_json::json!({
+        "id": 1,
+        "name": "Tom",
+        "tag": null,
+        "status": "sold",
+        "birthday": null,
+    }))
+    .await;
+
+    cli.get("/pets/2")
+        .send()
+        .await
+        .assert_status(poem::http::StatusCode::NOT_FOUND);
+
+    cli.delete("/pets/1")
+        .header("X-API-Key", "secret")
+        .send()
+        .await
+        .assert_status(poem::http::StatusCode::NO_CONTENT);
+    cli.delete("/pets/1")
+        .send()
+        .await
+        .assert_status(poem::http::StatusCode::UNAUTHORIZED);
+
+    let resp = cli
+        .post("/pets")
+        .header("Authorization", "Bearer token")
+        .body_json(&serde_json::json!({ "name": "" }))
+        .send()
+        .await;
+    resp.assert_status(poem::http::StatusCode::BAD_REQUEST);
+
+    let resp = cli
+        .post("/pets")
+        .header("Authorization", "Bearer token")
+        .body_json(&serde_json::json!({ "name": "Jerry", "tag": "mouse" }))
+        .send()
+        .await;
+    resp.assert_status(poem::http::StatusCode::CREATED);
+    resp.assert_json(serde_json::json!({
+        "id": 1,
+        "name": "Jerry",
+        "tag": "mouse",
+        "status": "available",
+        "birthday": null,
+    }))
+    .await;
+
+    let resp = cli
+        .put("/pets/2")
+        .header("Authorization", "Bearer token")
+        .body_json(&serde_json::json!({ "name": "Spike" }))
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_json(serde_json::json!({
+        "id": 2,
+        "name": "Spike",
+        "tag": null,
+        "status": "pending",
+        "birthday": null,
+    }))
+    .await;
+
+    let resp = cli.get("/store/inventory").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_json(serde_json::json!({ "sold": 3 })).await;
+}
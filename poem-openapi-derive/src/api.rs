@@ -2,13 +2,13 @@ use darling::{FromMeta, util::SpannedValue};
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
-    Error, Expr, FnArg, ImplItem, ImplItemFn, ItemImpl, Pat, Path, ReturnType, Type, ext::IdentExt,
-    visit_mut::VisitMut,
+    Error, Expr, FnArg, GenericArgument, ImplItem, ImplItemFn, ItemImpl, Pat, Path, PathArguments,
+    ReturnType, Type, ext::IdentExt, visit_mut::VisitMut,
 };
 
 use crate::{
     common_args::{
-        APIMethod, CodeSample, DefaultValue, ExampleValue, ExternalDocument, ExtraHeader,
+        APIMethod, Callback, CodeSample, DefaultValue, ExampleValue, ExternalDocument, ExtraHeader,
     },
     error::GeneratorResult,
     parameter_style::ParameterStyle,
@@ -34,6 +34,8 @@ pub(crate) struct APIArgs {
     request_headers: Vec<ExtraHeader>,
     #[darling(default)]
     ignore_case: Option<bool>,
+    #[darling(default)]
+    client: Option<Ident>,
 }
 
 #[derive(FromMeta)]
@@ -59,10 +61,14 @@ struct APIOperation {
     actual_type: Option<Type>,
     #[darling(default, multiple, rename = "code_sample")]
     code_samples: Vec<CodeSample>,
+    #[darling(default, multiple, rename = "callback")]
+    callbacks: Vec<Callback>,
     #[darling(default)]
     hidden: bool,
     #[darling(default)]
     ignore_case: Option<bool>,
+    #[darling(default)]
+    skip_client: bool,
 }
 
 #[derive(FromMeta, Default)]
@@ -84,6 +90,8 @@ struct APIOperationParam {
     explode: Option<bool>,
     #[darling(default)]
     style: Option<ParameterStyle>,
+    #[darling(default)]
+    skip_client: bool,
     // for oauth
     #[darling(multiple, default, rename = "scope")]
     scopes: Vec<Path>,
@@ -93,6 +101,7 @@ struct Context {
     add_routes: Vec<TokenStream>,
     operations: Vec<(TokenStream, TokenStream)>,
     register_items: Vec<TokenStream>,
+    client_methods: Vec<TokenStream>,
 }
 
 pub(crate) fn generate(args: APIArgs, mut item_impl: ItemImpl) -> GeneratorResult<TokenStream> {
@@ -103,6 +112,7 @@ pub(crate) fn generate(args: APIArgs, mut item_impl: ItemImpl) -> GeneratorResul
         add_routes: Default::default(),
         operations: Default::default(),
         register_items: Default::default(),
+        client_methods: Default::default(),
     };
 
     for item in &mut item_impl.items {
@@ -124,6 +134,7 @@ pub(crate) fn generate(args: APIArgs, mut item_impl: ItemImpl) -> GeneratorResul
         add_routes,
         operations,
         register_items,
+        client_methods,
     } = ctx;
 
     let paths = {
@@ -137,9 +148,33 @@ pub(crate) fn generate(args: APIArgs, mut item_impl: ItemImpl) -> GeneratorResul
         paths
     };
 
+    let client = args.client.as_ref().map(|client| {
+        let doc = format!(
+            "A client that calls the operations of `{}`.",
+            quote!(#ident).to_string().replace(' ', "")
+        );
+        quote! {
+            #[doc = #doc]
+            pub struct #client<T> {
+                transport: T,
+            }
+
+            impl<T: #crate_name::client::ClientTransport> #client<T> {
+                /// Create a client that sends the requests with the specified transport.
+                pub fn new(transport: T) -> Self {
+                    Self { transport }
+                }
+
+                #(#client_methods)*
+            }
+        }
+    });
+
     let expanded = quote! {
         #item_impl
 
+        #client
+
         impl #impl_generics #crate_name::OpenApi for #ident #where_clause {
             fn meta() -> ::std::vec::Vec<#crate_name::registry::MetaApi> {
                 ::std::vec![#crate_name::registry::MetaApi {
@@ -188,8 +223,10 @@ fn generate_operation(
         request_headers,
         actual_type,
         code_samples,
+        callbacks,
         hidden,
         ignore_case,
+        skip_client,
     } = args;
     if methods.is_empty() {
         return Err(Error::new_spanned(
@@ -204,7 +241,7 @@ fn generate_operation(
     let description = optional_literal(&description);
     let tags = api_args.common_tags.iter().chain(&tags);
     let prefix_path = &api_args.prefix_path;
-    let (oai_path, new_path) = convert_oai_path(&path)?;
+    let (oai_path, new_path, path_vars) = convert_oai_path(&path)?;
     let oai_path = prefix_path
         .as_ref()
         .map(|prefix| quote! { #crate_name::__private::join_path(#prefix, #oai_path) })
@@ -249,8 +286,8 @@ fn generate_operation(
     let mut request_meta = Vec::new();
     let mut params_meta = Vec::new();
     let mut security = Vec::new();
-
-    let mut path_param_count = 0;
+    let mut client_params = Vec::new();
+    let mut client_args = Vec::new();
 
     for i in 1..item_method.sig.inputs.len() {
         let arg = &mut item_method.sig.inputs[i];
@@ -290,6 +327,8 @@ fn generate_operation(
             _ => false,
         };
 
+        let is_client_arg = !operation_param.skip_client && !is_poem_extractor(&arg_ty);
+
         RemoveLifetime.visit_type_mut(&mut arg_ty);
 
         let pname = format_ident!("p{}", i);
@@ -303,9 +342,19 @@ fn generate_operation(
             .or(api_args.ignore_case)
             .unwrap_or(false);
         let extract_param_name = if is_path {
-            let n = format!("param{path_param_count}");
-            path_param_count += 1;
-            n
+            // the path parameters are matched by name, the variables of the prefix
+            // path are kept as is in the route
+            match path_vars.iter().position(|var| *var == param_name) {
+                Some(index) => format!("param{index}"),
+                None if prefix_path.is_some() => param_name.clone(),
+                None => {
+                    return Err(Error::new_spanned(
+                        &arg_ident,
+                        format!("`{param_name}` is not a variable of the path."),
+                    )
+                    .into());
+                }
+            }
         } else {
             param_name.clone()
         };
@@ -405,6 +454,13 @@ fn generate_operation(
             #param_checker
         });
 
+        if is_client_arg {
+            client_params.push(quote!(#arg_ident: #arg_ty));
+            client_args.push(quote! {
+                #crate_name::client::ToClientRequest::to_client_request(#arg_ident, &mut __request, #param_name, #explode);
+            });
+        }
+
         // param meta
         let param_desc = optional_literal_string(&param_description);
         let deprecated = operation_param.deprecated;
@@ -464,6 +520,11 @@ fn generate_operation(
     }
 
     if !hidden {
+        for callback in &callbacks {
+            let webhook = &callback.webhook;
+            ctx.register_items
+                .push(quote!(<#webhook as #crate_name::Webhook>::register(registry);));
+        }
         if let Some(actual_type) = &actual_type {
             ctx.register_items
                 .push(quote!(<#actual_type as #crate_name::ApiResponse>::register(registry);));
@@ -530,6 +591,27 @@ fn generate_operation(
         });
     }
 
+    if api_args.client.is_some() && !skip_client {
+        let docs = item_method
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"));
+        let deprecated = deprecated.then(|| quote!(#[deprecated]));
+        let http_method = methods[0].to_http_method();
+        let client_res_ty = client_response_type(&res_ty);
+        ctx.client_methods.push(quote! {
+            #(#docs)*
+            #deprecated
+            pub async fn #fn_ident(&self, #(#client_params),*) -> #crate_name::__private::poem::Result<#client_res_ty> {
+                #[allow(unused_mut)]
+                let mut __request = #crate_name::client::ClientRequest::new(#crate_name::__private::poem::http::Method::#http_method, #oai_path);
+                #(#client_args)*
+                let __resp = #crate_name::client::ClientTransport::send(&self.transport, __request.into_request()?).await?;
+                <#client_res_ty as #crate_name::client::FromClientResponse>::from_client_response(__resp).await
+            }
+        });
+    }
+
     let mut tag_names = Vec::new();
     for tag in tags {
         ctx.register_items
@@ -623,6 +705,23 @@ fn generate_operation(
         })
         .collect::<Vec<_>>();
 
+    let callbacks = callbacks
+        .iter()
+        .map(|callback| {
+            let Callback { name, url, webhook } = callback;
+            quote! {
+                #crate_name::registry::MetaCallback {
+                    name: #name,
+                    url: #url,
+                    operations: <#webhook as #crate_name::Webhook>::meta()
+                        .into_iter()
+                        .map(|webhook| webhook.operation)
+                        .collect(),
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
     if !hidden {
         for method in &methods {
             let http_method = method.to_http_method();
@@ -655,6 +754,7 @@ fn generate_operation(
                         #(#security)*
                         security
                     },
+                    callbacks: ::std::vec![#(#callbacks),*],
                     operation_id: #operation_id,
                     code_samples: ::std::vec![#(#code_samples),*],
                 }
@@ -665,3 +765,35 @@ fn generate_operation(
 
     Ok(())
 }
+
+/// Returns `true` if the argument is a reference or `Data`, which cannot be
+/// provided by the client.
+fn is_poem_extractor(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(_) => true,
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Data"),
+        _ => false,
+    }
+}
+
+/// Returns `T` for `Result<T>`, because the client methods return
+/// `poem::Result`.
+fn client_response_type(ty: &Type) -> &Type {
+    let Type::Path(path) = ty else {
+        return ty;
+    };
+    match path.path.segments.last() {
+        Some(segment) if segment.ident == "Result" => match &segment.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                GenericArgument::Type(ty) => ty,
+                _ => ty,
+            },
+            _ => ty,
+        },
+        _ => ty,
+    }
+}
@@ -286,3 +286,28 @@ pub(crate) struct CodeSample {
     pub(crate) label: Option<String>,
     pub(crate) source: syn::Expr,
 }
+
+#[derive(FromMeta)]
+pub(crate) struct LinkParameter {
+    pub(crate) name: String,
+    pub(crate) value: String,
+}
+
+#[derive(FromMeta)]
+pub(crate) struct Link {
+    pub(crate) name: String,
+    pub(crate) operation_id: String,
+    #[darling(default, multiple, rename = "parameter")]
+    pub(crate) parameters: Vec<LinkParameter>,
+    #[darling(default)]
+    pub(crate) request_body: Option<String>,
+    #[darling(default)]
+    pub(crate) description: Option<String>,
+}
+
+#[derive(FromMeta)]
+pub(crate) struct Callback {
+    pub(crate) name: String,
+    pub(crate) url: String,
+    pub(crate) webhook: syn::Type,
+}
@@ -34,6 +34,8 @@ struct RequestArgs {
 
     #[darling(default)]
     internal: bool,
+    #[darling(default)]
+    client: bool,
 }
 
 pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
@@ -55,7 +57,9 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let mut from_requests = Vec::new();
     let mut content = Vec::new();
     let mut schemas = Vec::new();
+    let mut to_client_requests = Vec::new();
 
+    let client_impl_generics = quote!(#impl_generics);
     let impl_generics = {
         let mut s = quote!(#impl_generics).to_string();
         match s.find('<') {
@@ -106,6 +110,17 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     }
                 });
                 schemas.push(payload_ty);
+
+                let update_content_type = variant.content_type.as_ref().map(|content_type| {
+                    let content_type = &**content_type;
+                    quote!(request.content_type(#content_type);)
+                });
+                to_client_requests.push(quote! {
+                    #ident::#item_ident(payload) => {
+                        #crate_name::client::ToClientRequest::to_client_request(payload, request, name, explode);
+                        #update_content_type
+                    }
+                });
             }
             _ => {
                 return Err(
@@ -115,6 +130,20 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
     }
 
+    let to_client_request = if args.client {
+        Some(quote! {
+            impl #client_impl_generics #crate_name::client::ToClientRequest for #ident #ty_generics #where_clause {
+                fn to_client_request(self, request: &mut #crate_name::client::ClientRequest, name: &str, explode: bool) {
+                    match self {
+                        #(#to_client_requests)*
+                    }
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     let expanded = {
         quote! {
             impl #impl_generics #crate_name::ApiExtractor<'__request> for #ident #ty_generics #where_clause {
@@ -156,6 +185,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     }
                 }
             }
+
+            #to_client_request
         }
     };
 
@@ -8,7 +8,7 @@ use quote::quote;
 use syn::{Attribute, DeriveInput, Error, Generics, Path, Type};
 
 use crate::{
-    common_args::{ExtraHeader, LitOrPath},
+    common_args::{ExtraHeader, Link, LitOrPath},
     error::GeneratorResult,
     utils::{get_crate_name, get_description, optional_literal, optional_literal_string},
 };
@@ -42,6 +42,8 @@ struct ResponseItem {
     headers: Vec<ExtraHeader>,
     #[darling(default)]
     actual_type: Option<Type>,
+    #[darling(default, multiple, rename = "link")]
+    links: Vec<Link>,
 }
 
 #[derive(FromDeriveInput)]
@@ -59,6 +61,8 @@ struct ResponseArgs {
     headers: Vec<ExtraHeader>,
     #[darling(default)]
     display: bool,
+    #[darling(default)]
+    client: bool,
 }
 
 pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
@@ -79,6 +83,9 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let mut error_messages = Vec::new();
     let mut responses_meta = Vec::new();
     let mut schemas = Vec::new();
+    let mut client_statuses = Vec::new();
+    let mut client_ranges = Vec::new();
+    let mut client_defaults = Vec::new();
 
     for variant in e {
         if matches!((&variant.status, &variant.status_range), (Some(_), Some(_))) {
@@ -97,6 +104,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         let mut match_headers = Vec::new();
         let mut with_headers = Vec::new();
         let mut meta_headers = Vec::new();
+        let mut parse_headers = Vec::new();
 
         // headers
         for (idx, header) in headers.iter().enumerate() {
@@ -111,6 +119,9 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     resp.headers_mut().insert(#header_name, header);
                 }
             }});
+            parse_headers.push(quote! {
+                let #ident = #crate_name::client::parse_header::<#header_ty>(resp.headers(), #header_name)?;
+            });
             match_headers.push(ident);
             meta_headers.push(quote! {
                 #crate_name::registry::MetaHeader {
@@ -144,6 +155,36 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             });
         }
 
+        // links
+        let meta_links = variant
+            .links
+            .iter()
+            .map(|link| {
+                let Link {
+                    name,
+                    operation_id,
+                    parameters,
+                    request_body,
+                    description,
+                } = link;
+                let parameters = parameters.iter().map(|param| {
+                    let (name, value) = (&param.name, &param.value);
+                    quote!((#name, #value))
+                });
+                let request_body = optional_literal(request_body);
+                let description = optional_literal(description);
+                quote! {
+                    #crate_name::registry::MetaLink {
+                        name: #name,
+                        operation_id: #operation_id,
+                        parameters: ::std::vec![#(#parameters),*],
+                        request_body: #request_body,
+                        description: #description,
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
         fn update_content_type(
             crate_name: &TokenStream,
             content_type: Option<&str>,
@@ -219,6 +260,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                             content
                         },
                         headers: ::std::vec![#(#meta_headers),*],
+                        links: ::std::vec![#(#meta_links),*],
                     }
                 });
                 if let Some(actual_type) = variant.actual_type.as_ref() {
@@ -226,6 +268,14 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 } else {
                     schemas.push(media_ty);
                 }
+                client_ranges.push((
+                    status_class(&variant.status_range),
+                    quote! {
+                        #(#parse_headers)*
+                        let media = #crate_name::client::parse_payload::<#media_ty>(resp).await?;
+                        ::std::result::Result::Ok(#ident::#item_ident(status, media, #(#match_headers),*))
+                    },
+                ));
             }
             1 if variant.status_range.is_some() => {
                 // #[oai(status_range = "2XX")]
@@ -248,8 +298,16 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         status_range: ::std::option::Option::Some(#status_range),
                         content: ::std::vec![],
                         headers: ::std::vec![#(#meta_headers),*],
+                        links: ::std::vec![#(#meta_links),*],
                     }
                 });
+                client_ranges.push((
+                    status_class(&variant.status_range),
+                    quote! {
+                        #(#parse_headers)*
+                        ::std::result::Result::Ok(#ident::#item_ident(status, #(#match_headers),*))
+                    },
+                ));
             }
             2 => {
                 // Item(StatusCode, media)
@@ -282,6 +340,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                             content
                         },
                         headers: ::std::vec![#(#meta_headers),*],
+                        links: ::std::vec![#(#meta_links),*],
                     }
                 });
                 if let Some(actual_type) = variant.actual_type.as_ref() {
@@ -289,6 +348,11 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 } else {
                     schemas.push(media_ty);
                 }
+                client_defaults.push(quote! {
+                    #(#parse_headers)*
+                    let media = #crate_name::client::parse_payload::<#media_ty>(resp).await?;
+                    ::std::result::Result::Ok(#ident::#item_ident(status, media, #(#match_headers),*))
+                });
             }
             1 => {
                 // #[oai(status = 200)]
@@ -323,6 +387,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                             content
                         },
                         headers: ::std::vec![#(#meta_headers),*],
+                        links: ::std::vec![#(#meta_links),*],
                     }
                 });
                 if let Some(actual_type) = variant.actual_type.as_ref() {
@@ -330,6 +395,14 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 } else {
                     schemas.push(media_ty);
                 }
+                client_statuses.push((
+                    status,
+                    quote! {
+                        #(#parse_headers)*
+                        let media = #crate_name::client::parse_payload::<#media_ty>(resp).await?;
+                        ::std::result::Result::Ok(#ident::#item_ident(media, #(#match_headers),*))
+                    },
+                ));
             }
             0 => {
                 // #[oai(status = 200)]
@@ -359,8 +432,16 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         status_range: ::std::option::Option::None,
                         content: ::std::vec![],
                         headers: ::std::vec![#(#meta_headers),*],
+                        links: ::std::vec![#(#meta_links),*],
                     }
                 });
+                client_statuses.push((
+                    status,
+                    quote! {
+                        #(#parse_headers)*
+                        ::std::result::Result::Ok(#item)
+                    },
+                ));
             }
             _ => {
                 return Err(
@@ -397,6 +478,42 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
     };
 
+    let from_client_response = if args.client {
+        let mut conditions = Vec::new();
+        let mut items = Vec::new();
+        for (status, item) in &client_statuses {
+            conditions.push(quote!(status.as_u16() == #status));
+            items.push(quote!(if status.as_u16() == #status { return { #item }; }));
+        }
+        for (class, item) in &client_ranges {
+            conditions.push(quote!(status.as_u16() / 100 == #class));
+            items.push(quote!(if status.as_u16() / 100 == #class { return { #item }; }));
+        }
+        if let Some(item) = client_defaults.first() {
+            conditions.push(quote!(true));
+            items.push(quote!(return { #item };));
+        } else {
+            items.push(quote! {
+                ::std::result::Result::Err(#crate_name::__private::poem::Error::from_response(resp))
+            });
+        }
+
+        Some(quote! {
+            impl #impl_generics #crate_name::client::FromClientResponse for #ident #ty_generics #where_clause {
+                fn has_status(status: #crate_name::__private::poem::http::StatusCode) -> bool {
+                    false #(|| #conditions)*
+                }
+
+                async fn from_client_response(resp: #crate_name::__private::poem::Response) -> #crate_name::__private::poem::Result<Self> {
+                    let status = resp.status();
+                    #(#items)*
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     let expanded = {
         quote! {
             impl #impl_generics #crate_name::__private::poem::IntoResponse for #ident #ty_generics #where_clause {
@@ -434,6 +551,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     err
                 }
             }
+
+            #from_client_response
         }
     };
 
@@ -476,6 +595,13 @@ fn get_status_range(span: Span, status_range: &Option<String>) -> GeneratorResul
     }
 }
 
+fn status_class(status_range: &Option<String>) -> u16 {
+    status_range
+        .as_ref()
+        .and_then(|status_range| status_range[..1].parse().ok())
+        .unwrap_or_default()
+}
+
 fn parse_fields(
     fields: &Fields<ResponseField>,
 ) -> syn::Result<(Vec<&ResponseField>, Vec<&ResponseField>)> {
@@ -30,6 +30,8 @@ struct ResponseContentArgs {
 
     #[darling(default)]
     internal: bool,
+    #[darling(default)]
+    client: bool,
 }
 
 pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
@@ -51,6 +53,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
     let mut into_responses = Vec::new();
     let mut schemas = Vec::new();
     let mut content = Vec::new();
+    let mut from_requests = Vec::new();
 
     for variant in e.iter() {
         let item_ident = &variant.ident;
@@ -93,6 +96,21 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 } else {
                     quote!()
                 };
+                let check_content_type = if let Some(content_type) = &variant.content_type {
+                    let content_type = content_type.as_str();
+                    quote!(content_type == #content_type)
+                } else if let Some(actual_type) = &variant.actual_type {
+                    quote!(<#actual_type as #crate_name::payload::Payload>::check_content_type(content_type))
+                } else {
+                    quote!(<#item_ty as #crate_name::payload::Payload>::check_content_type(content_type))
+                };
+                from_requests.push(quote! {
+                    if #check_content_type {
+                        return ::std::result::Result::Ok(#ident::#item_ident(
+                            <#item_ty as #crate_name::payload::ParsePayload>::from_request(request, body).await?
+                        ));
+                    }
+                });
                 into_responses.push(quote! {
                     #ident::#item_ident(resp) => {
                         let mut resp = #crate_name::__private::poem::IntoResponse::into_response(resp);
@@ -120,6 +138,34 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
         }
     }
 
+    let parse_payload = if args.client {
+        Some(quote! {
+            impl #impl_generics #crate_name::payload::ParsePayload for #ident #ty_generics #where_clause {
+                const IS_REQUIRED: bool = true;
+
+                async fn from_request(
+                    request: &#crate_name::__private::poem::Request,
+                    body: &mut #crate_name::__private::poem::RequestBody,
+                ) -> #crate_name::__private::poem::Result<Self> {
+                    match request.content_type() {
+                        ::std::option::Option::Some(content_type) => {
+                            #(#from_requests)*
+                            ::std::result::Result::Err(
+                                ::std::convert::Into::into(#crate_name::error::ContentTypeError::NotSupported {
+                                    content_type: ::std::string::ToString::to_string(content_type),
+                            }))
+                        }
+                        ::std::option::Option::None => {
+                            ::std::result::Result::Err(::std::convert::Into::into(#crate_name::error::ContentTypeError::ExpectContentType))
+                        }
+                    }
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     let expanded = {
         quote! {
             impl #impl_generics #crate_name::ResponseContent for #ident #ty_generics #where_clause {
@@ -139,6 +185,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                     }
                 }
             }
+
+            #parse_payload
         }
     };
 
@@ -219,6 +219,8 @@ struct SecuritySchemeArgs {
     openid_connect_url: Option<String>,
     #[darling(default)]
     checker: Option<Path>,
+    #[darling(default)]
+    client: bool,
 }
 
 impl SecuritySchemeArgs {
@@ -401,6 +403,32 @@ impl SecuritySchemeArgs {
         Ok(ts)
     }
 
+    fn generate_to_client_request(&self, crate_name: &TokenStream) -> GeneratorResult<TokenStream> {
+        let ident = &self.ident;
+        let set_credentials = match self.auth_type()? {
+            AuthType::ApiKey => {
+                let key_name = self.key_name.as_ref().unwrap().as_str();
+                match self.key_in.as_ref().unwrap() {
+                    ApiKeyInType::Query => quote!(request.query(#key_name, &self.0.key);),
+                    ApiKeyInType::Header => quote!(request.header(#key_name, &self.0.key);),
+                    ApiKeyInType::Cookie => quote!(request.cookie(#key_name, &self.0.key);),
+                }
+            }
+            AuthType::Basic => quote!(request.basic_auth(&self.0.username, &self.0.password);),
+            AuthType::Bearer | AuthType::OAuth2 | AuthType::OpenIdConnect => {
+                quote!(request.bearer_auth(&self.0.token);)
+            }
+        };
+
+        Ok(quote! {
+            impl #crate_name::client::ToClientRequest for #ident {
+                fn to_client_request(self, request: &mut #crate_name::client::ClientRequest, _name: &str, _explode: bool) {
+                    #set_credentials
+                }
+            }
+        })
+    }
+
     fn generate_from_request(&self, crate_name: &TokenStream) -> GeneratorResult<TokenStream> {
         match self.auth_type()? {
             AuthType::ApiKey => {
@@ -449,6 +477,10 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 args.generate_register_security_scheme(&crate_name, &oai_typename)?;
             let from_request = args.generate_from_request(&crate_name)?;
             let path = args.checker.as_ref();
+            let to_client_request = match path {
+                Some(_) => None,
+                None => Some(args.generate_to_client_request(&crate_name)?),
+            };
 
             let output = match path {
                 Some(_) => quote! {
@@ -484,6 +516,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         ::std::result::Result::Ok(Self(output))
                     }
                 }
+
+                #to_client_request
             };
 
             Ok(expanded)
@@ -496,6 +530,7 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             let mut registers = Vec::new();
             let mut security_schemes = Vec::new();
             let mut from_requests = Vec::new();
+            let mut to_client_requests = Vec::new();
             let mut has_fallback = false;
 
             if items.is_empty() {
@@ -505,6 +540,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
             for item in items {
                 if item.fallback {
                     has_fallback = true;
+                    let item_ident = &item.ident;
+                    to_client_requests.push(quote! { #ident::#item_ident => {} });
                     continue;
                 }
 
@@ -528,7 +565,10 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         ::std::result::Result::Ok(item) => return Ok(#ident::#item_ident(item)),
                         ::std::result::Result::Err(err) => last_err = ::std::option::Option::Some(err),
                     }
-                })
+                });
+                to_client_requests.push(quote! {
+                    #ident::#item_ident(item) => #crate_name::client::ToClientRequest::to_client_request(item, request, name, explode),
+                });
             }
 
             let fallback = items.iter().find(|item| item.fallback);
@@ -545,6 +585,20 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                 None => quote! { ::std::result::Result::Err(last_err.unwrap()) },
             };
 
+            let to_client_request = if args.client {
+                Some(quote! {
+                    impl #crate_name::client::ToClientRequest for #ident {
+                        fn to_client_request(self, request: &mut #crate_name::client::ClientRequest, name: &str, explode: bool) {
+                            match self {
+                                #(#to_client_requests)*
+                            }
+                        }
+                    }
+                })
+            } else {
+                None
+            };
+
             let expanded = quote! {
                 impl<'a> #crate_name::ApiExtractor<'a> for #ident {
                     const TYPES: &'static [#crate_name::ApiExtractorType] = &[#crate_name::ApiExtractorType::SecurityScheme];
@@ -576,6 +630,8 @@ pub(crate) fn generate(args: DeriveInput) -> GeneratorResult<TokenStream> {
                         #fallback
                     }
                 }
+
+                #to_client_request
             };
 
             Ok(expanded)
@@ -1,5 +1,3 @@
-use std::collections::HashSet;
-
 use darling::{FromMeta, util::SpannedValue};
 use proc_macro_crate::{FoundCrate, crate_name};
 use proc_macro2::{Ident, Span, TokenStream};
@@ -109,15 +107,19 @@ pub(crate) fn parse_oai_attrs<T: FromMeta>(attrs: &[Attribute]) -> GeneratorResu
     Ok(None)
 }
 
-pub(crate) fn convert_oai_path(path: &SpannedValue<String>) -> Result<(String, String)> {
+/// Returns the path in the OpenAPI format, the path for the route in which the
+/// variables are renamed to `param0`, `param1`..., and the names of the
+/// variables.
+pub(crate) fn convert_oai_path(
+    path: &SpannedValue<String>,
+) -> Result<(String, String, Vec<String>)> {
     if !path.starts_with('/') {
         return Err(Error::new(path.span(), "The path must start with '/'."));
     }
 
     let mut oai_path = String::new();
     let mut new_path = String::new();
-    let mut vars = HashSet::new();
-    let mut param_count = 0;
+    let mut vars = Vec::new();
 
     for s in path.split('/') {
         if s.is_empty() {
@@ -130,15 +132,15 @@ pub(crate) fn convert_oai_path(path: &SpannedValue<String>) -> Result<(String, S
             oai_path.push('}');
 
             new_path.push_str("/:");
-            new_path.push_str(&format!("param{param_count}"));
-            param_count += 1;
+            new_path.push_str(&format!("param{}", vars.len()));
 
-            if !vars.insert(var) {
+            if vars.iter().any(|name| name == var) {
                 return Err(Error::new(
                     path.span(),
                     format!("Repeated path variable `{}`.", &s[1..]),
                 ));
             }
+            vars.push(var.to_string());
         } else {
             oai_path.push('/');
             oai_path.push_str(s);
@@ -156,7 +158,7 @@ pub(crate) fn convert_oai_path(path: &SpannedValue<String>) -> Result<(String, S
         new_path += "/";
     }
 
-    Ok((oai_path, new_path))
+    Ok((oai_path, new_path, vars))
 }
 
 pub(crate) struct RemoveLifetime;
@@ -289,6 +289,7 @@ fn generate_operation(
                         responses: <#res_ty as #crate_name::ApiResponse>::meta(),
                         deprecated: #deprecated,
                         security: ::std::vec![],
+                        callbacks: ::std::vec![],
                         operation_id: #operation_id,
                         code_samples: ::std::vec![],
                     }
@@ -26,3 +26,40 @@ impl BearerAuthorization for Bearer {
         Err(AuthorizationError.into())
     }
 }
+
+#[cfg(feature = "jwt")]
+impl Bearer {
+    /// Validates the token as a JSON Web Token with the specified validator,
+    /// and returns its claims.
+    ///
+    /// This can be used in the checker of a security scheme, to share the
+    /// validator with the [`JwtAuth`](poem::middleware::JwtAuth) middleware.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{Request, Result, middleware::JwtValidator};
+    /// use poem_openapi::{SecurityScheme, auth::Bearer};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     sub: String,
+    /// }
+    ///
+    /// #[derive(SecurityScheme)]
+    /// #[oai(ty = "bearer", bearer_format = "JWT", checker = "jwt_checker")]
+    /// struct JwtAuthorization(User);
+    ///
+    /// async fn jwt_checker(req: &Request, bearer: Bearer) -> Result<User> {
+    ///     let validator = req.data::<JwtValidator>().expect("missing validator");
+    ///     bearer.validate_jwt(validator).await
+    /// }
+    /// ```
+    pub async fn validate_jwt<T: serde::de::DeserializeOwned>(
+        &self,
+        validator: &poem::middleware::JwtValidator,
+    ) -> Result<T> {
+        validator.validate(&self.token).await
+    }
+}
@@ -354,6 +354,7 @@ impl ApiResponse for () {
                 status_range: None,
                 content: vec![],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
@@ -408,6 +409,7 @@ where
                 status_range: None,
                 content: vec![],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
@@ -0,0 +1,452 @@
+//! Typed clients generated from the API definitions.
+//!
+//! When the `client` parameter is specified, the
+//! [`OpenApi`](macro@crate::OpenApi) macro generates a client struct with an
+//! async method for each operation. The methods take the same parameter and
+//! payload types as the operations, and return their response types. Requests
+//! are sent with a [`ClientTransport`], such as [`EndpointTransport`] which
+//! calls an endpoint in the same process.
+//!
+//! The response types and the request objects derived with the
+//! [`ApiResponse`](macro@crate::ApiResponse),
+//! [`ApiRequest`](macro@crate::ApiRequest) and
+//! [`ResponseContent`](macro@crate::ResponseContent) macros must also specify
+//! the `client` parameter.
+//!
+//! # Example
+//!
+//! ```
+//! use poem_openapi::{
+//!     ApiResponse, Object, OpenApi,
+//!     client::EndpointTransport,
+//!     param::Path,
+//!     payload::{Json, PlainText},
+//! };
+//!
+//! #[derive(Object)]
+//! struct Pet {
+//!     id: u64,
+//!     name: String,
+//! }
+//!
+//! #[derive(ApiResponse)]
+//! #[oai(client)]
+//! enum FindPetResponse {
+//!     #[oai(status = 200)]
+//!     Ok(Json<Pet>),
+//!     #[oai(status = 404)]
+//!     NotFound(PlainText<String>),
+//! }
+//!
+//! struct PetApi;
+//!
+//! #[OpenApi(prefix_path = "/pets", client = "PetClient")]
+//! impl PetApi {
+//!     #[oai(path = "/:id", method = "get")]
+//!     async fn find_pet(&self, id: Path<u64>) -> FindPetResponse {
+//!         match id.0 {
+//!             1 => FindPetResponse::Ok(Json(Pet {
+//!                 id: 1,
+//!                 name: "Kitty".to_string(),
+//!             })),
+//!             _ => FindPetResponse::NotFound(PlainText("not found".to_string())),
+//!         }
+//!     }
+//! }
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let client = PetClient::new(EndpointTransport::new(poem_openapi::OpenApiService::new(
+//!     PetApi, "Pets", "1.0",
+//! )));
+//!
+//! match client.find_pet(Path(1)).await.unwrap() {
+//!     FindPetResponse::Ok(pet) => assert_eq!(pet.name, "Kitty"),
+//!     FindPetResponse::NotFound(_) => unreachable!(),
+//! }
+//! assert!(matches!(
+//!     client.find_pet(Path(2)).await.unwrap(),
+//!     FindPetResponse::NotFound(_)
+//! ));
+//! # });
+//! ```
+
+mod transport;
+
+use std::future::Future;
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use poem::{
+    Body, Error, IntoResponse, Request, Response, Result,
+    http::{
+        HeaderMap, HeaderValue, Method, StatusCode, Uri,
+        header::{self, HeaderName},
+    },
+    web::headers::{Authorization, HeaderMapExt},
+};
+use serde::Serialize;
+use serde_json::Value;
+
+#[cfg(feature = "reqwest")]
+pub use self::transport::ReqwestTransport;
+pub use self::transport::{ClientTransport, EndpointTransport};
+#[cfg(feature = "cookie")]
+use crate::param::Cookie;
+use crate::{
+    error::ClientError,
+    param::{Header, Path, Query},
+    payload::{Form, ParsePayload, Payload},
+    types::{ParseFromParameter, ToJSON, Type},
+};
+
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// A request that is built by the generated client methods.
+pub struct ClientRequest {
+    method: Method,
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+    headers: HeaderMap,
+    cookies: Vec<String>,
+    body: Body,
+    error: Option<ClientError>,
+}
+
+impl ClientRequest {
+    /// Create a request with the specified method and path.
+    ///
+    /// The path parameters are specified as `{name}` or `:name` segments.
+    pub fn new(method: Method, path: impl AsRef<str>) -> Self {
+        Self {
+            method,
+            segments: path.as_ref().split('/').map(ToString::to_string).collect(),
+            query: Vec::new(),
+            headers: HeaderMap::new(),
+            cookies: Vec::new(),
+            body: Body::empty(),
+            error: None,
+        }
+    }
+
+    /// Replaces the `{name}` or `:name` segment of the path with the
+    /// specified value.
+    pub fn path_param(&mut self, name: &str, value: &str) {
+        let segment = self.segments.iter_mut().find(|segment| {
+            segment
+                .strip_prefix('{')
+                .and_then(|segment| segment.strip_suffix('}'))
+                .or_else(|| segment.strip_prefix(':'))
+                == Some(name)
+        });
+        match segment {
+            Some(segment) => *segment = utf8_percent_encode(value, COMPONENT).to_string(),
+            None => self.set_error(ClientError::InvalidRequest {
+                reason: format!("the path parameter `{name}` is not in the path"),
+            }),
+        }
+    }
+
+    /// Appends a query parameter.
+    pub fn query(&mut self, name: &str, value: impl Into<String>) {
+        self.query.push((name.to_string(), value.into()));
+    }
+
+    /// Appends a header.
+    pub fn header(&mut self, name: &str, value: &str) {
+        match (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            (Ok(name), Ok(value)) => {
+                self.headers.append(name, value);
+            }
+            _ => self.set_error(ClientError::InvalidHeader {
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Appends a cookie.
+    pub fn cookie(&mut self, name: &str, value: &str) {
+        self.cookies.push(format!(
+            "{}={}",
+            name,
+            utf8_percent_encode(value, COMPONENT)
+        ));
+    }
+
+    /// Sets the `Authorization` header with the basic authentication
+    /// credentials.
+    pub fn basic_auth(&mut self, username: &str, password: &str) {
+        self.headers
+            .typed_insert(Authorization::basic(username, password));
+    }
+
+    /// Sets the `Authorization` header with the bearer token.
+    pub fn bearer_auth(&mut self, token: &str) {
+        match Authorization::bearer(token) {
+            Ok(authorization) => self.headers.typed_insert(authorization),
+            Err(_) => self.set_error(ClientError::InvalidHeader {
+                name: header::AUTHORIZATION.to_string(),
+            }),
+        }
+    }
+
+    /// Sets the `Content-Type` header.
+    pub fn content_type(&mut self, content_type: &str) {
+        match HeaderValue::try_from(content_type) {
+            Ok(content_type) => {
+                self.headers.insert(header::CONTENT_TYPE, content_type);
+            }
+            Err(_) => self.set_error(ClientError::InvalidHeader {
+                name: header::CONTENT_TYPE.to_string(),
+            }),
+        }
+    }
+
+    /// Sets the body and its content type.
+    pub fn body(&mut self, content_type: &str, body: impl Into<Body>) {
+        self.content_type(content_type);
+        self.body = body.into();
+    }
+
+    fn set_error(&mut self, err: ClientError) {
+        self.error.get_or_insert(err);
+    }
+
+    /// Consumes this object to return the HTTP request.
+    ///
+    /// # Errors
+    ///
+    /// - [`ClientError`] if a header value is invalid, or a path parameter is
+    ///   not in the path.
+    pub fn into_request(self) -> Result<Request> {
+        if let Some(err) = self.error {
+            return Err(err.into());
+        }
+
+        let mut uri = self.segments.join("/");
+        if !self.query.is_empty() {
+            uri.push('?');
+            uri.push_str(&serde_urlencoded::to_string(&self.query).map_err(|err| {
+                ClientError::InvalidRequest {
+                    reason: err.to_string(),
+                }
+            })?);
+        }
+        let uri: Uri = uri.parse().map_err(|_| ClientError::InvalidRequest {
+            reason: format!("invalid uri `{uri}`"),
+        })?;
+
+        let mut headers = self.headers;
+        if !self.cookies.is_empty() {
+            let cookies = HeaderValue::try_from(self.cookies.join("; ")).map_err(|_| {
+                ClientError::InvalidHeader {
+                    name: header::COOKIE.to_string(),
+                }
+            })?;
+            headers.insert(header::COOKIE, cookies);
+        }
+
+        let mut request = Request::builder()
+            .method(self.method)
+            .uri(uri)
+            .body(self.body);
+        *request.headers_mut() = headers;
+        Ok(request)
+    }
+}
+
+/// Represents an operation argument that can be added to a [`ClientRequest`].
+///
+/// # Provided Implementations
+///
+/// - **Path&lt;T: ToJSON>**, **Query&lt;T: ToJSON>** and **Header&lt;T:
+///   ToJSON>**
+/// - **Cookie&lt;T: ToJSON>**, requires the `cookie` feature.
+/// - **Form&lt;T: Serialize>**
+/// - Any payload type that implements [`IntoResponse`], encoded in the same
+///   way as the responses.
+/// - Any type derived from the [`SecurityScheme`](macro@crate::SecurityScheme)
+///   macro without a checker.
+pub trait ToClientRequest {
+    /// Adds this argument to the request.
+    ///
+    /// `name` and `explode` are the name and the `explode` option of the
+    /// parameter.
+    fn to_client_request(self, request: &mut ClientRequest, name: &str, explode: bool);
+}
+
+fn param_value(value: Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) => Some(value),
+        value => Some(value.to_string()),
+    }
+}
+
+fn param_values(value: Option<Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(values)) => values.into_iter().filter_map(param_value).collect(),
+        Some(value) => param_value(value).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+impl<T: ToJSON> ToClientRequest for Path<T> {
+    fn to_client_request(self, request: &mut ClientRequest, name: &str, _explode: bool) {
+        request.path_param(name, &param_values(self.0.to_json()).join(","));
+    }
+}
+
+impl<T: ToJSON> ToClientRequest for Query<T> {
+    fn to_client_request(self, request: &mut ClientRequest, name: &str, explode: bool) {
+        let values = param_values(self.0.to_json());
+        if explode {
+            for value in values {
+                request.query(name, value);
+            }
+        } else if !values.is_empty() {
+            request.query(name, values.join(","));
+        }
+    }
+}
+
+impl<T: ToJSON> ToClientRequest for Header<T> {
+    fn to_client_request(self, request: &mut ClientRequest, name: &str, _explode: bool) {
+        for value in param_values(self.0.to_json()) {
+            request.header(name, &value);
+        }
+    }
+}
+
+#[cfg(feature = "cookie")]
+impl<T: ToJSON> ToClientRequest for Cookie<T> {
+    fn to_client_request(self, request: &mut ClientRequest, name: &str, _explode: bool) {
+        let values = param_values(self.0.to_json());
+        if !values.is_empty() {
+            request.cookie(name, &values.join(","));
+        }
+    }
+}
+
+impl<T: Serialize + Type> ToClientRequest for Form<T> {
+    fn to_client_request(self, request: &mut ClientRequest, _name: &str, _explode: bool) {
+        match serde_urlencoded::to_string(&self.0) {
+            Ok(body) => request.body(Self::CONTENT_TYPE, body),
+            Err(err) => request.set_error(ClientError::InvalidRequest {
+                reason: err.to_string(),
+            }),
+        }
+    }
+}
+
+impl<T: Payload + IntoResponse> ToClientRequest for T {
+    fn to_client_request(self, request: &mut ClientRequest, _name: &str, _explode: bool) {
+        request.body(T::CONTENT_TYPE, self.into_response().into_body());
+    }
+}
+
+/// Represents a response type that can be parsed from the responses of the
+/// server.
+///
+/// # Provided Implementations
+///
+/// - **()**
+/// - **poem::Error**, which is created from any response.
+/// - **Result&lt;T: FromClientResponse, E: FromClientResponse>**
+/// - Any type that implements [`ParsePayload`].
+/// - Any type derived from the [`ApiResponse`](macro@crate::ApiResponse)
+///   macro with the `client` parameter.
+pub trait FromClientResponse: Sized {
+    /// Returns `true` if the responses with the specified status are described
+    /// by this type.
+    fn has_status(status: StatusCode) -> bool;
+
+    /// Parse this object from the response.
+    fn from_client_response(resp: Response) -> impl Future<Output = Result<Self>> + Send;
+}
+
+impl FromClientResponse for () {
+    fn has_status(status: StatusCode) -> bool {
+        status.is_success()
+    }
+
+    async fn from_client_response(resp: Response) -> Result<Self> {
+        match resp.status().is_success() {
+            true => Ok(()),
+            false => Err(Error::from_response(resp)),
+        }
+    }
+}
+
+impl FromClientResponse for Error {
+    fn has_status(_status: StatusCode) -> bool {
+        true
+    }
+
+    async fn from_client_response(resp: Response) -> Result<Self> {
+        Ok(Error::from_response(resp))
+    }
+}
+
+impl<T, E> FromClientResponse for Result<T, E>
+where
+    T: FromClientResponse + Send,
+    E: FromClientResponse + Send,
+{
+    fn has_status(status: StatusCode) -> bool {
+        T::has_status(status) || E::has_status(status)
+    }
+
+    async fn from_client_response(resp: Response) -> Result<Self> {
+        if T::has_status(resp.status()) {
+            T::from_client_response(resp).await.map(Ok)
+        } else {
+            E::from_client_response(resp).await.map(Err)
+        }
+    }
+}
+
+impl<T: ParsePayload + Send> FromClientResponse for T {
+    fn has_status(status: StatusCode) -> bool {
+        status.is_success()
+    }
+
+    async fn from_client_response(resp: Response) -> Result<Self> {
+        match resp.status().is_success() {
+            true => parse_payload(resp).await,
+            false => Err(Error::from_response(resp)),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub async fn parse_payload<T: ParsePayload>(resp: Response) -> Result<T> {
+    let (parts, body) = resp.into_parts();
+    let mut request = Request::builder().body(body);
+    *request.headers_mut() = parts.headers;
+    let (request, mut body) = request.split();
+    T::from_request(&request, &mut body).await.map_err(|err| {
+        ClientError::InvalidResponse {
+            reason: err.to_string(),
+        }
+        .into()
+    })
+}
+
+#[doc(hidden)]
+pub fn parse_header<T: ParseFromParameter>(headers: &HeaderMap, name: &str) -> Result<T> {
+    T::parse_from_parameters(
+        headers
+            .get_all(name)
+            .iter()
+            .filter_map(|value| value.to_str().ok()),
+    )
+    .map_err(|err| {
+        ClientError::InvalidResponse {
+            reason: format!("failed to parse header `{name}`: {}", err.into_message()),
+        }
+        .into()
+    })
+}
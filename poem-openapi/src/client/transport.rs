@@ -0,0 +1,116 @@
+use std::future::Future;
+
+use poem::{Endpoint, IntoEndpoint, Request, Response, Result};
+
+/// Represents a transport that sends the requests of the generated clients.
+///
+/// # Provided Implementations
+///
+/// - [`EndpointTransport`], which calls an endpoint in the same process.
+/// - **poem::test::TestClient&lt;E: Endpoint>**, requires the `test` feature.
+/// - [`ReqwestTransport`](crate::client::ReqwestTransport), requires the
+///   `reqwest` feature.
+pub trait ClientTransport: Send + Sync {
+    /// Sends the request and returns the response of the server.
+    fn send(&self, req: Request) -> impl Future<Output = Result<Response>> + Send;
+}
+
+/// A transport that calls an endpoint in the same process.
+///
+/// The errors returned by the endpoint are converted to the responses.
+pub struct EndpointTransport<E> {
+    ep: E,
+}
+
+impl<E: Endpoint> EndpointTransport<E> {
+    /// Create a transport for the specified endpoint.
+    pub fn new<T>(ep: T) -> EndpointTransport<T::Endpoint>
+    where
+        T: IntoEndpoint<Endpoint = E>,
+    {
+        EndpointTransport {
+            ep: ep.into_endpoint(),
+        }
+    }
+}
+
+impl<E: Endpoint> ClientTransport for EndpointTransport<E> {
+    async fn send(&self, req: Request) -> Result<Response> {
+        Ok(self.ep.get_response(req).await)
+    }
+}
+
+#[cfg(feature = "test")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test")))]
+impl<E: Endpoint> ClientTransport for poem::test::TestClient<E> {
+    async fn send(&self, req: Request) -> Result<Response> {
+        let (parts, body) = req.into_parts();
+        let mut builder = self.request(parts.method, parts.uri.to_string());
+        for (name, value) in &parts.headers {
+            builder = builder.header(name.clone(), value.clone());
+        }
+        Ok(builder.body(body).send().await.0)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+mod reqwest_transport {
+    use poem::{Request, Response, Result};
+
+    use crate::{client::ClientTransport, error::ClientError};
+
+    /// A transport that sends the requests to a server with
+    /// [`reqwest`](https://crates.io/crates/reqwest).
+    #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+    pub struct ReqwestTransport {
+        client: reqwest::Client,
+        base_url: String,
+    }
+
+    impl ReqwestTransport {
+        /// Create a transport that sends the requests to the server with the
+        /// specified base url, such as `http://localhost:3000`.
+        pub fn new(base_url: impl Into<String>) -> Self {
+            Self {
+                client: reqwest::Client::new(),
+                base_url: base_url.into(),
+            }
+        }
+
+        /// Sets the `reqwest` client used to send the requests.
+        #[must_use]
+        pub fn client(self, client: reqwest::Client) -> Self {
+            Self { client, ..self }
+        }
+    }
+
+    fn transport_error(err: impl ToString) -> ClientError {
+        ClientError::Transport {
+            reason: err.to_string(),
+        }
+    }
+
+    impl ClientTransport for ReqwestTransport {
+        async fn send(&self, req: Request) -> Result<Response> {
+            let (parts, body) = req.into_parts();
+            let url = format!("{}{}", self.base_url.trim_end_matches('/'), parts.uri);
+            let resp = self
+                .client
+                .request(parts.method, url)
+                .headers(parts.headers)
+                .body(body.into_bytes().await?)
+                .send()
+                .await
+                .map_err(transport_error)?;
+
+            let mut builder = Response::builder().status(resp.status());
+            for (name, value) in resp.headers() {
+                builder = builder.header(name.clone(), value.clone());
+            }
+            Ok(builder.body(resp.bytes().await.map_err(transport_error)?))
+        }
+    }
+}
+
+#[cfg(feature = "reqwest")]
+pub use reqwest_transport::ReqwestTransport;
@@ -96,3 +96,64 @@ impl ResponseError for AuthorizationError {
         StatusCode::UNAUTHORIZED
     }
 }
+
+/// Client error.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Invalid header value.
+    #[error("invalid value of the header `{name}`")]
+    InvalidHeader {
+        /// The header name.
+        name: String,
+    },
+
+    /// Invalid request.
+    #[error("invalid request: {reason}")]
+    InvalidRequest {
+        /// The reason for the error.
+        reason: String,
+    },
+
+    /// Failed to send the request.
+    #[error("failed to send the request: {reason}")]
+    Transport {
+        /// The reason for the error.
+        reason: String,
+    },
+
+    /// Failed to parse the response of the server.
+    #[error("failed to parse the response: {reason}")]
+    InvalidResponse {
+        /// The reason for the error.
+        reason: String,
+    },
+}
+
+impl ResponseError for ClientError {
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_GATEWAY
+    }
+}
+
+/// A response does not match the specification.
+///
+/// Only returned by an [`OpenApiService`](crate::OpenApiService) with
+/// [`ResponseValidation::Fail`](crate::ResponseValidation::Fail).
+#[derive(Debug, Error)]
+#[error("the response of `{method} {path}` does not match the specification: {reason}")]
+pub struct ResponseValidationError {
+    /// The method of the operation.
+    pub method: poem::http::Method,
+
+    /// The path of the operation.
+    pub path: String,
+
+    /// The reason for the error.
+    pub reason: String,
+}
+
+impl ResponseError for ResponseValidationError {
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
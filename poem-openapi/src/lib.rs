@@ -116,6 +116,9 @@
 //! | prost-wkt-types    | Integrate with the [`prost-wkt-types` crate](https://crates.io/crates/prost-wkt-types) |
 //! | static-files       | Support for static file response                                                       |
 //! | websocket          | Support for websocket                                                                  |
+//! | jwt                | Support for validating bearer tokens as JSON Web Tokens                                |
+//! | test               | Use `poem::test::TestClient` as the transport of the generated clients                 |
+//! | reqwest            | Send the requests of the generated clients with `reqwest`                              |
 //! | sonic-rs           | Uses [`sonic-rs`](https://github.com/cloudwego/sonic-rs) instead of `serde_json`. Pls, checkout `sonic-rs` requirements to properly enable `sonic-rs` capabilities |
 
 #![doc(html_favicon_url = "https://raw.githubusercontent.com/poem-web/poem/master/favicon.ico")]
@@ -131,6 +134,7 @@
 pub mod macros;
 
 pub mod auth;
+pub mod client;
 pub mod error;
 pub mod param;
 pub mod payload;
@@ -144,6 +148,7 @@ pub mod validation;
 mod base;
 mod openapi;
 mod path_util;
+mod response_validation;
 #[cfg(any(
     feature = "openapi-explorer",
     feature = "rapidoc",
@@ -159,7 +164,8 @@ pub use base::{
     OperationId, ParameterStyle, ResponseContent, Tags, Webhook,
 };
 pub use openapi::{
-    ContactObject, ExternalDocumentObject, ExtraHeader, LicenseObject, OpenApiService, ServerObject,
+    ContactObject, ExternalDocumentObject, ExtraHeader, LicenseObject, OpenApiService,
+    OpenApiVersion, ResponseValidation, ServerObject,
 };
 #[doc = include_str!("docs/request.md")]
 pub use poem_openapi_derive::ApiRequest;
@@ -50,3 +50,12 @@ macro_rules! impl_apirequest_for_payload {
         }
     };
 }
+
+/// Include the types and the API generated by `poem-openapi-build` from an
+/// OpenAPI document.
+#[macro_export]
+macro_rules! include_openapi {
+    ($name: tt) => {
+        include!(concat!(env!("OUT_DIR"), concat!("/", $name, ".rs")));
+    };
+}
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     marker::PhantomData,
+    sync::Arc,
 };
 
 use poem::{
@@ -13,10 +14,12 @@ use poem::{middleware::CookieJarManager, web::cookie::CookieKey};
 use crate::{
     OpenApi, Webhook,
     base::UrlQuery,
+    path_util::route_path,
     registry::{
         Document, MetaContact, MetaExternalDocument, MetaHeader, MetaInfo, MetaLicense,
         MetaOperationParam, MetaParamIn, MetaSchemaRef, MetaServer, MetaServerVariable, Registry,
     },
+    response_validation::ValidateResponseEndpoint,
     types::Type,
 };
 
@@ -257,6 +260,48 @@ impl ExtraHeader {
     }
 }
 
+/// The version of the OpenAPI specification generated by the
+/// [`OpenApiService`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum OpenApiVersion {
+    /// OpenAPI 3.0.0
+    #[default]
+    V3_0,
+    /// OpenAPI 3.1.0, whose schemas use the JSON Schema 2020-12 semantics.
+    ///
+    /// The nullable types are represented with type arrays, the single-value
+    /// enums with `const`, and the examples with `examples` arrays. The
+    /// references with sibling keywords, such as `description`, are not
+    /// wrapped in `allOf`.
+    V3_1,
+}
+
+/// How the [`OpenApiService`] reports the responses that do not match the
+/// specification.
+///
+/// The status, the declared headers and the content type of each response
+/// returned by an operation are checked against the responses of the
+/// operation, and the JSON bodies are checked against their schemas. This
+/// includes the error responses returned by the operations, such as the `Err`
+/// variant of `Result<T, E: ApiResponse>`, but not the errors of the
+/// extractors, such as a missing parameter.
+///
+/// The JSON bodies are buffered to be checked, so the validation is intended
+/// to be enabled in debug builds and tests.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum ResponseValidation {
+    /// The responses are not checked.
+    #[default]
+    Disabled,
+    /// The mismatches are logged with `tracing`, and the responses are
+    /// returned unchanged.
+    Log,
+    /// The responses that do not match are replaced with a
+    /// [`ResponseValidationError`](crate::error::ResponseValidationError),
+    /// which is a `500 Internal Server Error`.
+    Fail,
+}
+
 /// An OpenAPI service for Poem.
 #[derive(Clone)]
 pub struct OpenApiService<T, W> {
@@ -270,6 +315,8 @@ pub struct OpenApiService<T, W> {
     extra_response_headers: Vec<(ExtraHeader, MetaSchemaRef, bool)>,
     extra_request_headers: Vec<(ExtraHeader, MetaSchemaRef, bool)>,
     url_prefix: Option<String>,
+    version: OpenApiVersion,
+    response_validation: ResponseValidation,
 }
 
 impl<T> OpenApiService<T, ()> {
@@ -291,6 +338,8 @@ impl<T> OpenApiService<T, ()> {
             extra_response_headers: vec![],
             extra_request_headers: vec![],
             url_prefix: None,
+            version: OpenApiVersion::V3_0,
+            response_validation: ResponseValidation::Disabled,
         }
     }
 }
@@ -309,6 +358,8 @@ impl<T, W> OpenApiService<T, W> {
             extra_response_headers: self.extra_response_headers,
             extra_request_headers: self.extra_request_headers,
             url_prefix: None,
+            version: self.version,
+            response_validation: self.response_validation,
         }
     }
 
@@ -447,6 +498,79 @@ impl<T, W> OpenApiService<T, W> {
         }
     }
 
+    /// Sets the version of the generated OpenAPI specification.
+    ///
+    /// Default is [`OpenApiVersion::V3_0`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem_openapi::{OpenApi, OpenApiService, OpenApiVersion};
+    ///
+    /// struct Api;
+    ///
+    /// #[OpenApi]
+    /// impl Api {
+    ///     #[oai(path = "/", method = "get")]
+    ///     async fn index(&self) {}
+    /// }
+    ///
+    /// let api_service =
+    ///     OpenApiService::new(Api, "Demo", "1.0").openapi_version(OpenApiVersion::V3_1);
+    /// let spec: serde_json::Value = serde_json::from_str(&api_service.spec()).unwrap();
+    /// assert_eq!(spec["openapi"], "3.1.0");
+    /// ```
+    #[must_use]
+    pub fn openapi_version(self, version: OpenApiVersion) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Checks the responses of the operations against the specification.
+    ///
+    /// Default is [`ResponseValidation::Disabled`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{http::StatusCode, test::TestClient};
+    /// use poem_openapi::{Object, OpenApi, OpenApiService, ResponseValidation, payload::Json};
+    ///
+    /// #[derive(Object)]
+    /// struct User {
+    ///     #[oai(validator(max_length = 8))]
+    ///     name: String,
+    /// }
+    ///
+    /// struct Api;
+    ///
+    /// #[OpenApi]
+    /// impl Api {
+    ///     #[oai(path = "/", method = "get")]
+    ///     async fn index(&self) -> Json<User> {
+    ///         Json(User {
+    ///             name: "a name that is too long".to_string(),
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let api_service =
+    ///     OpenApiService::new(Api, "Demo", "1.0").response_validation(ResponseValidation::Fail);
+    /// let cli = TestClient::new(api_service);
+    /// cli.get("/")
+    ///     .send()
+    ///     .await
+    ///     .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn response_validation(self, response_validation: ResponseValidation) -> Self {
+        Self {
+            response_validation,
+            ..self
+        }
+    }
+
     /// Create the OpenAPI Explorer endpoint.
     #[must_use]
     #[cfg(feature = "openapi-explorer")]
@@ -674,6 +798,7 @@ impl<T, W> OpenApiService<T, W> {
             registry,
             external_document: self.external_document.as_ref(),
             url_prefix: self.url_prefix.as_deref(),
+            version: self.version,
         };
         doc.remove_unused_schemas();
 
@@ -718,12 +843,13 @@ impl<T: OpenApi, W: Webhook> IntoEndpoint for OpenApiService<T, W> {
         };
 
         // check duplicate operation id
-        let mut operation_ids = HashSet::new();
-        for operation in T::meta()
+        let operations = T::meta()
             .into_iter()
             .flat_map(|api| api.paths.into_iter())
             .flat_map(|path| path.operations.into_iter())
-        {
+            .collect::<Vec<_>>();
+        let mut operation_ids = HashSet::new();
+        for operation in &operations {
             if let Some(operation_id) = operation.operation_id {
                 if !operation_ids.insert(operation_id) {
                     panic!("duplicate operation id: {operation_id}");
@@ -731,9 +857,62 @@ impl<T: OpenApi, W: Webhook> IntoEndpoint for OpenApiService<T, W> {
             }
         }
 
+        // check the operations referenced by the links
+        for link in operations
+            .iter()
+            .flat_map(|operation| &operation.responses.responses)
+            .flat_map(|response| &response.links)
+        {
+            if !operation_ids.contains(link.operation_id) {
+                panic!(
+                    "the link `{}` references an unknown operation id: {}",
+                    link.name, link.operation_id
+                );
+            }
+        }
+
+        // check duplicate methods in the callbacks, they share a path item
+        for callback in operations.iter().flat_map(|operation| &operation.callbacks) {
+            let mut methods = HashSet::new();
+            for operation in &callback.operations {
+                if !methods.insert(&operation.method) {
+                    panic!(
+                        "the callback `{}` has more than one `{}` operation",
+                        callback.name, operation.method
+                    );
+                }
+            }
+        }
+
         let mut items = HashMap::new();
         self.api.add_routes(&mut items);
 
+        if self.response_validation != ResponseValidation::Disabled {
+            let mut registry = Registry::new();
+            T::register(&mut registry);
+            let registry = Arc::new(registry);
+
+            for path in T::meta().into_iter().flat_map(|api| api.paths) {
+                let Some(route_method) = items.get_mut(&route_path(&path.path)) else {
+                    continue;
+                };
+                for operation in path.operations {
+                    let Some(ep) = route_method.remove(&operation.method) else {
+                        continue;
+                    };
+                    let ep = ValidateResponseEndpoint {
+                        inner: ep,
+                        mode: self.response_validation,
+                        registry: registry.clone(),
+                        method: operation.method.clone(),
+                        path: path.path.clone(),
+                        responses: operation.responses,
+                    };
+                    route_method.insert(operation.method, ep.boxed());
+                }
+            }
+        }
+
         let route = items
             .into_iter()
             .fold(Route::new(), |route, (path, paths)| {
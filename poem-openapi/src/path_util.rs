@@ -24,6 +24,25 @@ pub fn join_path(base: &str, path: &str) -> String {
     }
 }
 
+/// Converts a path in the specification, such as `/users/{id}`, to the path
+/// of the route added by the `OpenApi` macro, such as `/users/:param0`.
+pub(crate) fn route_path(oai_path: &str) -> String {
+    let mut param_count = 0;
+    oai_path
+        .split('/')
+        .map(|s| {
+            if s.starts_with('{') && s.ends_with('}') {
+                let s = format!(":param{param_count}");
+                param_count += 1;
+                s
+            } else {
+                s.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,4 +61,12 @@ mod tests {
         assert_eq!(join_path("/abc/", "/def"), "/abc/def");
         assert_eq!(join_path("/abc/", "/def/"), "/abc/def/");
     }
+
+    #[test]
+    fn test_route_path() {
+        assert_eq!(route_path("/"), "/");
+        assert_eq!(route_path("/abc"), "/abc");
+        assert_eq!(route_path("/abc/{id}"), "/abc/:param0");
+        assert_eq!(route_path("/abc/{a}/def/{b}/"), "/abc/:param0/def/:param1/");
+    }
 }
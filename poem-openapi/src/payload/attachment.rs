@@ -116,6 +116,7 @@ impl<T: Into<Body> + Send> ApiResponse for Attachment<T> {
                     deprecated: false,
                     schema: String::schema_ref(),
                 }],
+                links: vec![],
             }],
         }
     }
@@ -155,6 +155,7 @@ impl<T: AsRef<[u8]> + Send> ApiResponse for Base64<T> {
                     schema: Self::schema_ref(),
                 }],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
@@ -143,6 +143,7 @@ impl<T: Into<Body> + Send> ApiResponse for Binary<T> {
                     schema: Self::schema_ref(),
                 }],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
@@ -69,6 +69,7 @@ impl<T: Into<String> + Send> ApiResponse for Html<T> {
                     schema: Self::schema_ref(),
                 }],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
@@ -88,6 +88,7 @@ impl<T: ToJSON> ApiResponse for Json<T> {
                     schema: Self::schema_ref(),
                 }],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
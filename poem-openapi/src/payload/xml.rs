@@ -90,6 +90,7 @@ impl<T: ToXML> ApiResponse for Xml<T> {
                     schema: Self::schema_ref(),
                 }],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
@@ -88,6 +88,7 @@ impl<T: ToYAML> ApiResponse for Yaml<T> {
                     schema: Self::schema_ref(),
                 }],
                 headers: vec![],
+                links: vec![],
             }],
         }
     }
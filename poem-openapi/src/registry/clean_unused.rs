@@ -63,6 +63,12 @@ impl<'a> Document<'a> {
         for response in &operation.responses.responses {
             self.traverse_media_types(used_types, &response.content);
         }
+
+        for callback in &operation.callbacks {
+            for operation in &callback.operations {
+                self.traverse_operation(used_types, operation);
+            }
+        }
     }
 
     pub(crate) fn remove_unused_schemas(&mut self) {
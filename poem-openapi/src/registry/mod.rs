@@ -1,5 +1,6 @@
 mod clean_unused;
 mod ser;
+mod v3_1;
 
 use std::{
     cmp::Ordering,
@@ -31,8 +32,8 @@ pub struct MetaDiscriminatorObject {
     pub mapping: Vec<(String, String)>,
 }
 
-fn serialize_mapping<S: Serializer>(
-    mapping: &[(String, String)],
+fn serialize_mapping<S: Serializer, K: Serialize, V: Serialize>(
+    mapping: &[(K, V)],
     serializer: S,
 ) -> Result<S::Ok, S::Error> {
     let mut s = serializer.serialize_map(None)?;
@@ -408,7 +409,7 @@ fn serialize_content<S: Serializer>(
     s.end()
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 pub struct MetaResponses {
     pub responses: Vec<MetaResponse>,
 }
@@ -426,7 +427,7 @@ pub struct MetaHeader {
     pub schema: MetaSchemaRef,
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, Default, PartialEq, Serialize)]
 pub struct MetaResponse {
     pub description: &'static str,
     #[serde(skip)]
@@ -443,6 +444,11 @@ pub struct MetaResponse {
         serialize_with = "serialize_headers"
     )]
     pub headers: Vec<MetaHeader>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_links"
+    )]
+    pub links: Vec<MetaLink>,
 }
 
 fn serialize_headers<S: Serializer>(
@@ -456,6 +462,56 @@ fn serialize_headers<S: Serializer>(
     s.end()
 }
 
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaLink {
+    #[serde(skip)]
+    pub name: &'static str,
+    pub operation_id: &'static str,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_mapping"
+    )]
+    pub parameters: Vec<(&'static str, &'static str)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'static str>,
+}
+
+fn serialize_links<S: Serializer>(links: &[MetaLink], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_map(None)?;
+    for link in links {
+        s.serialize_entry(link.name, link)?;
+    }
+    s.end()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MetaCallback {
+    pub name: &'static str,
+    pub url: &'static str,
+    pub operations: Vec<MetaOperation>,
+}
+
+fn serialize_callbacks<S: Serializer>(
+    callbacks: &[MetaCallback],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut map: BTreeMap<&str, BTreeMap<&str, BTreeMap<String, &MetaOperation>>> = BTreeMap::new();
+    for callback in callbacks {
+        let path_item = map
+            .entry(callback.name)
+            .or_default()
+            .entry(callback.url)
+            .or_default();
+        for operation in &callback.operations {
+            path_item.insert(operation.method.to_string().to_lowercase(), operation);
+        }
+    }
+    map.serialize(serializer)
+}
+
 #[derive(Debug, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MetaWebhook {
@@ -472,7 +528,7 @@ pub struct MetaCodeSample {
     pub source: &'static str,
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, Default, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MetaOperation {
     #[serde(skip)]
@@ -494,6 +550,11 @@ pub struct MetaOperation {
     pub deprecated: bool,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub security: Vec<HashMap<&'static str, Vec<&'static str>>>,
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_callbacks"
+    )]
+    pub callbacks: Vec<MetaCallback>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub operation_id: Option<&'static str>,
     #[serde(rename = "x-code-samples", skip_serializing_if = "Vec::is_empty")]
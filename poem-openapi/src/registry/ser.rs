@@ -1,13 +1,17 @@
 use std::collections::BTreeMap;
 
-use serde::{Serialize, Serializer, ser::SerializeMap};
-
-use crate::registry::{
-    MetaApi, MetaExternalDocument, MetaInfo, MetaPath, MetaResponses, MetaSchema, MetaSchemaRef,
-    MetaSecurityScheme, MetaServer, MetaWebhook, Registry,
+use serde::{
+    Serialize, Serializer,
+    ser::{Error, SerializeMap},
 };
 
-const OPENAPI_VERSION: &str = "3.0.0";
+use crate::{
+    OpenApiVersion,
+    registry::{
+        MetaApi, MetaExternalDocument, MetaInfo, MetaPath, MetaResponses, MetaSchema,
+        MetaSchemaRef, MetaSecurityScheme, MetaServer, MetaWebhook, Registry, v3_1,
+    },
+};
 
 impl Serialize for MetaSchemaRef {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -92,10 +96,30 @@ pub(crate) struct Document<'a> {
     pub(crate) registry: Registry,
     pub(crate) external_document: Option<&'a MetaExternalDocument>,
     pub(crate) url_prefix: Option<&'a str>,
+    pub(crate) version: OpenApiVersion,
 }
 
 impl Serialize for Document<'_> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.version {
+            OpenApiVersion::V3_0 => self.serialize_document(serializer, "3.0.0"),
+            OpenApiVersion::V3_1 => {
+                let mut doc = self
+                    .serialize_document(serde_json::value::Serializer, "3.1.0")
+                    .map_err(S::Error::custom)?;
+                v3_1::convert_document(&mut doc);
+                doc.serialize(serializer)
+            }
+        }
+    }
+}
+
+impl Document<'_> {
+    fn serialize_document<S: Serializer>(
+        &self,
+        serializer: S,
+        openapi_version: &str,
+    ) -> Result<S::Ok, S::Error> {
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Components<'a> {
@@ -106,7 +130,7 @@ impl Serialize for Document<'_> {
 
         let mut s = serializer.serialize_map(None)?;
 
-        s.serialize_entry("openapi", OPENAPI_VERSION)?;
+        s.serialize_entry("openapi", openapi_version)?;
         s.serialize_entry("info", &self.info)?;
         s.serialize_entry("servers", self.servers)?;
         s.serialize_entry("tags", &self.registry.tags)?;
@@ -0,0 +1,241 @@
+//! Converts the OpenAPI 3.0 document to OpenAPI 3.1, whose schemas use the
+//! JSON Schema 2020-12 semantics.
+
+use serde_json::{Map, Value, json};
+
+pub(crate) fn convert_document(doc: &mut Value) {
+    if let Some(Value::Object(schemas)) = doc.pointer_mut("/components/schemas") {
+        schemas.values_mut().for_each(convert_schema);
+    }
+    for key in ["paths", "webhooks"] {
+        if let Some(Value::Object(items)) = doc.get_mut(key) {
+            items.values_mut().for_each(convert_path_item);
+        }
+    }
+}
+
+fn convert_path_item(item: &mut Value) {
+    if let Value::Object(operations) = item {
+        operations.values_mut().for_each(convert_operation);
+    }
+}
+
+fn convert_content(content: Option<&mut Value>) {
+    if let Some(Value::Object(content)) = content {
+        for media_type in content.values_mut() {
+            if let Some(schema) = media_type.get_mut("schema") {
+                convert_schema(schema);
+            }
+        }
+    }
+}
+
+fn convert_operation(operation: &mut Value) {
+    if let Some(Value::Array(params)) = operation.get_mut("parameters") {
+        for param in params {
+            if let Some(schema) = param.get_mut("schema") {
+                convert_schema(schema);
+            }
+        }
+    }
+
+    if let Some(request) = operation.get_mut("requestBody") {
+        convert_content(request.get_mut("content"));
+    }
+
+    if let Some(Value::Object(responses)) = operation.get_mut("responses") {
+        for resp in responses.values_mut() {
+            convert_content(resp.get_mut("content"));
+            if let Some(Value::Object(headers)) = resp.get_mut("headers") {
+                for header in headers.values_mut() {
+                    if let Some(schema) = header.get_mut("schema") {
+                        convert_schema(schema);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(Value::Object(callbacks)) = operation.get_mut("callbacks") {
+        for callback in callbacks.values_mut() {
+            if let Value::Object(items) = callback {
+                items.values_mut().for_each(convert_path_item);
+            }
+        }
+    }
+}
+
+/// Replaces `allOf: [{ $ref }, { ... }]`, which is generated for a reference
+/// with sibling keywords, with the `$ref` and its siblings.
+fn collapse_ref(schema: &mut Map<String, Value>) {
+    let Some(Value::Array(all_of)) = schema.get("allOf") else {
+        return;
+    };
+    let Some(reference) = all_of
+        .first()
+        .and_then(Value::as_object)
+        .filter(|item| item.len() == 1)
+        .and_then(|item| item.get("$ref"))
+        .cloned()
+    else {
+        return;
+    };
+
+    let mut siblings = schema.clone();
+    siblings.remove("allOf");
+    let collapsible = match &all_of[1..] {
+        [] => true,
+        [Value::Object(item)] => *item == siblings,
+        _ => false,
+    };
+    if collapsible {
+        schema.remove("allOf");
+        schema.insert("$ref".to_string(), reference);
+    }
+}
+
+fn convert_schema(schema: &mut Value) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+
+    collapse_ref(obj);
+
+    for key in ["items", "additionalProperties"] {
+        if let Some(item) = obj.get_mut(key) {
+            convert_schema(item);
+        }
+    }
+    if let Some(Value::Object(properties)) = obj.get_mut("properties") {
+        properties.values_mut().for_each(convert_schema);
+    }
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Array(items)) = obj.get_mut(key) {
+            items.iter_mut().for_each(convert_schema);
+        }
+    }
+
+    if let Some(example) = obj.remove("example") {
+        obj.insert("examples".to_string(), Value::Array(vec![example]));
+    }
+
+    for (exclusive, bound) in [
+        ("exclusiveMaximum", "maximum"),
+        ("exclusiveMinimum", "minimum"),
+    ] {
+        if obj.remove(exclusive) == Some(Value::Bool(true)) {
+            if let Some(value) = obj.remove(bound) {
+                obj.insert(exclusive.to_string(), value);
+            }
+        }
+    }
+
+    if obj.remove("nullable") == Some(Value::Bool(true)) {
+        if let Some(Value::Array(items)) = obj.get_mut("enum") {
+            items.push(Value::Null);
+        }
+        match obj.get_mut("type") {
+            Some(ty @ Value::String(_)) => {
+                let inner = ty.take();
+                *ty = json!([inner, "null"]);
+            }
+            _ => {
+                let inner = std::mem::take(obj);
+                obj.insert(
+                    "anyOf".to_string(),
+                    json!([Value::Object(inner), { "type": "null" }]),
+                );
+            }
+        }
+    }
+
+    if let Some(Value::Array(items)) = obj.get("enum") {
+        if let [value] = items.as_slice() {
+            let value = value.clone();
+            obj.remove("enum");
+            obj.insert("const".to_string(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(mut schema: Value) -> Value {
+        convert_schema(&mut schema);
+        schema
+    }
+
+    #[test]
+    fn nullable() {
+        assert_eq!(
+            convert(json!({ "type": "string", "nullable": true })),
+            json!({ "type": ["string", "null"] })
+        );
+        assert_eq!(
+            convert(json!({ "type": "string", "enum": ["a", "b"], "nullable": true })),
+            json!({ "type": ["string", "null"], "enum": ["a", "b", null] })
+        );
+        assert_eq!(
+            convert(json!({
+                "allOf": [{ "$ref": "#/components/schemas/A" }, { "nullable": true }],
+                "nullable": true,
+            })),
+            json!({
+                "anyOf": [{ "$ref": "#/components/schemas/A" }, { "type": "null" }],
+            })
+        );
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(
+            convert(json!({
+                "type": "integer",
+                "maximum": 10,
+                "exclusiveMaximum": true,
+                "minimum": 1,
+                "exclusiveMinimum": false,
+                "example": 5,
+            })),
+            json!({
+                "type": "integer",
+                "exclusiveMaximum": 10,
+                "minimum": 1,
+                "examples": [5],
+            })
+        );
+        assert_eq!(
+            convert(json!({ "type": "string", "enum": ["a"] })),
+            json!({ "type": "string", "const": "a" })
+        );
+    }
+
+    #[test]
+    fn ref_siblings() {
+        assert_eq!(
+            convert(json!({
+                "allOf": [
+                    { "$ref": "#/components/schemas/A" },
+                    { "description": "abc", "readOnly": true },
+                ],
+                "description": "abc",
+                "readOnly": true,
+            })),
+            json!({
+                "$ref": "#/components/schemas/A",
+                "description": "abc",
+                "readOnly": true,
+            })
+        );
+
+        let schema = json!({
+            "allOf": [
+                { "$ref": "#/components/schemas/A" },
+                { "$ref": "#/components/schemas/B" },
+            ],
+        });
+        assert_eq!(convert(schema.clone()), schema);
+    }
+}
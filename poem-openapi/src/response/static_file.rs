@@ -46,6 +46,7 @@ impl ApiResponse for StaticFileResponse {
                             schema: String::schema_ref(),
                         },
                     ],
+                    links: vec![],
                 },
                 MetaResponse {
                     description: "Not modified",
@@ -53,6 +54,7 @@ impl ApiResponse for StaticFileResponse {
                     status_range: None,
                     content: vec![],
                     headers: vec![],
+                    links: vec![],
                 },
                 MetaResponse {
                     description: "Bad request",
@@ -60,6 +62,7 @@ impl ApiResponse for StaticFileResponse {
                     status_range: None,
                     content: vec![],
                     headers: vec![],
+                    links: vec![],
                 },
                 MetaResponse {
                     description: "Resource was not found",
@@ -67,6 +70,7 @@ impl ApiResponse for StaticFileResponse {
                     status_range: None,
                     content: vec![],
                     headers: vec![],
+                    links: vec![],
                 },
                 MetaResponse {
                     description: "Precondition failed",
@@ -74,6 +78,7 @@ impl ApiResponse for StaticFileResponse {
                     status_range: None,
                     content: vec![],
                     headers: vec![],
+                    links: vec![],
                 },
                 MetaResponse {
                     description: "The Content-Range response HTTP header indicates where in a full body message a partial message belongs.",
@@ -81,6 +86,7 @@ impl ApiResponse for StaticFileResponse {
                     status_range: None,
                     content: vec![],
                     headers: vec![],
+                    links: vec![],
                 },
                 MetaResponse {
                     description: "Internal server error",
@@ -88,6 +94,7 @@ impl ApiResponse for StaticFileResponse {
                     status_range: None,
                     content: vec![],
                     headers: vec![],
+                    links: vec![],
                 },
             ],
         }
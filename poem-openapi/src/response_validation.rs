@@ -0,0 +1,486 @@
+use std::{collections::HashSet, sync::Arc};
+
+use mime::Mime;
+use poem::{
+    Endpoint, Error, IntoResponse, Request, Response, Result,
+    http::{HeaderMap, Method, StatusCode, header},
+};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::{
+    ResponseValidation,
+    error::ResponseValidationError,
+    registry::{MetaMediaType, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef, Registry},
+};
+
+/// An endpoint that checks the responses of an operation against the
+/// specification.
+pub(crate) struct ValidateResponseEndpoint<E> {
+    pub(crate) inner: E,
+    pub(crate) mode: ResponseValidation,
+    pub(crate) registry: Arc<Registry>,
+    pub(crate) method: Method,
+    pub(crate) path: String,
+    pub(crate) responses: MetaResponses,
+}
+
+impl<E: Endpoint> Endpoint for ValidateResponseEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let (mut resp, is_err) = match self.inner.call(req).await {
+            Ok(resp) => (resp.into_response(), false),
+            // the error responses of the operation, such as the `Err` variant of
+            // `Result<T, E: ApiResponse>`, are validated in the same way, while
+            // the errors of the extractors are not declared by the operation
+            Err(err) if err.is_from_response() => (err.into_response(), true),
+            Err(err) => return Err(err),
+        };
+        // the error responses are returned as errors again, so that the
+        // middlewares wrapping the operation see the same result
+        let output = |resp| match is_err {
+            true => Err(Error::from_response(resp)),
+            false => Ok(resp),
+        };
+        let mut errors = Vec::new();
+
+        match find_response(&self.responses, resp.status()) {
+            Some(meta) => {
+                check_headers(&self.registry, meta, resp.headers(), &mut errors);
+                resp = check_content(&self.registry, meta, resp, &mut errors).await?;
+            }
+            None => errors.push(format!("the status `{}` is not declared", resp.status())),
+        }
+
+        if errors.is_empty() {
+            return output(resp);
+        }
+        let reason = errors.join("; ");
+        match self.mode {
+            ResponseValidation::Disabled => output(resp),
+            ResponseValidation::Log => {
+                tracing::warn!(
+                    method = %self.method,
+                    path = %self.path,
+                    reason = %reason,
+                    "the response does not match the specification"
+                );
+                output(resp)
+            }
+            ResponseValidation::Fail => Err(ResponseValidationError {
+                method: self.method.clone(),
+                path: self.path.clone(),
+                reason,
+            }
+            .into()),
+        }
+    }
+}
+
+/// Finds the declared response for a status, preferring the exact status to
+/// a range such as `4XX` and a range to the default response.
+fn find_response(responses: &MetaResponses, status: StatusCode) -> Option<&MetaResponse> {
+    let range = format!("{}XX", status.as_u16() / 100);
+    let responses = &responses.responses;
+    responses
+        .iter()
+        .find(|resp| resp.status == Some(status.as_u16()))
+        .or_else(|| {
+            responses.iter().find(|resp| {
+                resp.status_range
+                    .as_deref()
+                    .is_some_and(|status_range| status_range.eq_ignore_ascii_case(&range))
+            })
+        })
+        .or_else(|| {
+            responses
+                .iter()
+                .find(|resp| resp.status.is_none() && resp.status_range.is_none())
+        })
+}
+
+fn check_headers(
+    registry: &Registry,
+    meta: &MetaResponse,
+    headers: &HeaderMap,
+    errors: &mut Vec<String>,
+) {
+    for header in &meta.headers {
+        let Some(value) = headers.get(&header.name) else {
+            if header.required {
+                errors.push(format!("the header `{}` is missing", header.name));
+            }
+            continue;
+        };
+        let Ok(value) = value.to_str() else {
+            errors.push(format!(
+                "the header `{}` is not a valid string",
+                header.name
+            ));
+            continue;
+        };
+        let path = format!("header `{}`", header.name);
+        let value = match resolve(registry, &header.schema).map(|schema| schema.ty) {
+            Some("integer" | "number" | "boolean") => match serde_json::from_str(value) {
+                Ok(value) => value,
+                Err(_) => Value::String(value.to_string()),
+            },
+            _ => Value::String(value.to_string()),
+        };
+        check_value(registry, &header.schema, &value, &path, errors);
+    }
+}
+
+async fn check_content(
+    registry: &Registry,
+    meta: &MetaResponse,
+    mut resp: Response,
+    errors: &mut Vec<String>,
+) -> Result<Response> {
+    let content_type = resp.content_type().map(ToString::to_string);
+
+    if meta.content.is_empty() {
+        if let Some(content_type) = content_type {
+            errors.push(format!(
+                "the content type `{content_type}` is not declared, no content is expected"
+            ));
+        }
+        return Ok(resp);
+    }
+
+    let Some(content_type) = content_type else {
+        errors.push("the `Content-Type` header is missing".to_string());
+        return Ok(resp);
+    };
+    let Some(media) = content_type
+        .parse::<Mime>()
+        .ok()
+        .and_then(|mime| find_media_type(&meta.content, &mime))
+    else {
+        errors.push(format!("the content type `{content_type}` is not declared"));
+        return Ok(resp);
+    };
+
+    // Only the JSON bodies that are not encoded are checked against the schema.
+    if !is_json(media) || resp.headers().contains_key(header::CONTENT_ENCODING) {
+        return Ok(resp);
+    }
+    let data = resp.take_body().into_bytes().await?;
+    match serde_json::from_slice::<Value>(&data) {
+        Ok(value) => check_value(registry, &media.schema, &value, "$", errors),
+        Err(err) => errors.push(format!("the body is not valid JSON: {err}")),
+    }
+    resp.set_body(data);
+    Ok(resp)
+}
+
+fn find_media_type<'a>(content: &'a [MetaMediaType], mime: &Mime) -> Option<&'a MetaMediaType> {
+    content.iter().find(|media| {
+        let Ok(expected) = media.content_type.parse::<Mime>() else {
+            return false;
+        };
+        (expected.type_() == mime::STAR || expected.type_() == mime.type_())
+            && (expected.subtype() == mime::STAR || expected.subtype() == mime.subtype())
+    })
+}
+
+fn is_json(media: &MetaMediaType) -> bool {
+    media
+        .content_type
+        .parse::<Mime>()
+        .is_ok_and(|mime| mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON))
+}
+
+fn resolve<'a>(registry: &'a Registry, schema: &'a MetaSchemaRef) -> Option<&'a MetaSchema> {
+    match schema {
+        MetaSchemaRef::Inline(schema) => Some(schema),
+        MetaSchemaRef::Reference(name) => registry.schemas.get(name),
+    }
+}
+
+fn check_value(
+    registry: &Registry,
+    schema: &MetaSchemaRef,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    // The schemas that are not registered are not checked.
+    if let Some(schema) = resolve(registry, schema) {
+        check_schema(registry, schema, value, path, errors);
+    }
+}
+
+fn check_schema(
+    registry: &Registry,
+    schema: &MetaSchema,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if value.is_null() && (schema.nullable || schema.is_empty()) {
+        return;
+    }
+
+    if !schema.enum_items.is_empty() && !schema.enum_items.contains(value) {
+        errors.push(format!("{path}: `{value}` is not one of the enum values"));
+        return;
+    }
+
+    let type_matches = match schema.ty {
+        "" => true,
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    };
+    if !type_matches {
+        errors.push(format!("{path}: expected {}, found `{value}`", schema.ty));
+        return;
+    }
+
+    for item in &schema.all_of {
+        check_value(registry, item, value, path, errors);
+    }
+    if !schema.any_of.is_empty() || !schema.one_of.is_empty() {
+        check_union(registry, schema, value, path, errors);
+    }
+
+    match value {
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                check_number(schema, number, path, errors);
+            }
+        }
+        Value::String(s) => check_string(schema, s, path, errors),
+        Value::Array(items) => check_array(registry, schema, items, path, errors),
+        Value::Object(object) => check_object(registry, schema, object, path, errors),
+        _ => {}
+    }
+}
+
+fn check_union(
+    registry: &Registry,
+    schema: &MetaSchema,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    // A discriminator selects the schema to check from the value.
+    if let Some(discriminator) = &schema.discriminator {
+        let Some(name) = value
+            .get(discriminator.property_name)
+            .and_then(Value::as_str)
+        else {
+            errors.push(format!(
+                "{path}: the discriminator property `{}` is missing",
+                discriminator.property_name
+            ));
+            return;
+        };
+        let reference = discriminator
+            .mapping
+            .iter()
+            .find(|(value, _)| value == name)
+            .map(|(_, reference)| reference.as_str());
+        let reference = match reference {
+            Some(reference) => reference.trim_start_matches("#/components/schemas/"),
+            None => name,
+        };
+        match schema
+            .one_of
+            .iter()
+            .chain(&schema.any_of)
+            .find(|item| matches!(item, MetaSchemaRef::Reference(item) if item == reference))
+        {
+            Some(item) => check_value(registry, item, value, path, errors),
+            None => errors.push(format!(
+                "{path}: `{name}` is not a valid value of the discriminator property `{}`",
+                discriminator.property_name
+            )),
+        }
+        return;
+    }
+
+    let (items, exactly_one) = match schema.one_of.is_empty() {
+        true => (&schema.any_of, false),
+        false => (&schema.one_of, true),
+    };
+    let matches = items
+        .iter()
+        .filter(|item| {
+            let mut item_errors = Vec::new();
+            check_value(registry, item, value, path, &mut item_errors);
+            item_errors.is_empty()
+        })
+        .count();
+    if matches == 0 {
+        errors.push(format!(
+            "{path}: the value does not match any of the schemas"
+        ));
+    } else if exactly_one && matches > 1 {
+        errors.push(format!(
+            "{path}: the value matches {matches} of the `oneOf` schemas"
+        ));
+    }
+}
+
+fn check_number(schema: &MetaSchema, number: f64, path: &str, errors: &mut Vec<String>) {
+    if let Some(multiple_of) = schema.multiple_of {
+        if multiple_of != 0.0 && (number / multiple_of).fract() != 0.0 {
+            errors.push(format!(
+                "{path}: `{number}` is not a multiple of `{multiple_of}`"
+            ));
+        }
+    }
+    if let Some(maximum) = schema.maximum {
+        let valid = match schema.exclusive_maximum.unwrap_or_default() {
+            true => number < maximum,
+            false => number <= maximum,
+        };
+        if !valid {
+            errors.push(format!(
+                "{path}: `{number}` is greater than the maximum `{maximum}`"
+            ));
+        }
+    }
+    if let Some(minimum) = schema.minimum {
+        let valid = match schema.exclusive_minimum.unwrap_or_default() {
+            true => number > minimum,
+            false => number >= minimum,
+        };
+        if !valid {
+            errors.push(format!(
+                "{path}: `{number}` is less than the minimum `{minimum}`"
+            ));
+        }
+    }
+}
+
+fn check_string(schema: &MetaSchema, s: &str, path: &str, errors: &mut Vec<String>) {
+    let len = s.chars().count();
+    if let Some(max_length) = schema.max_length {
+        if len > max_length {
+            errors.push(format!(
+                "{path}: the length {len} is greater than {max_length}"
+            ));
+        }
+    }
+    if let Some(min_length) = schema.min_length {
+        if len < min_length {
+            errors.push(format!(
+                "{path}: the length {len} is less than {min_length}"
+            ));
+        }
+    }
+    if let Some(pattern) = &schema.pattern {
+        if Regex::new(pattern).is_ok_and(|re| !re.is_match(s)) {
+            errors.push(format!(
+                "{path}: `{s}` does not match the pattern `{pattern}`"
+            ));
+        }
+    }
+}
+
+fn check_array(
+    registry: &Registry,
+    schema: &MetaSchema,
+    items: &[Value],
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let len = items.len();
+    if let Some(max_items) = schema.max_items {
+        if len > max_items {
+            errors.push(format!("{path}: {len} items are more than {max_items}"));
+        }
+    }
+    if let Some(min_items) = schema.min_items {
+        if len < min_items {
+            errors.push(format!("{path}: {len} items are less than {min_items}"));
+        }
+    }
+    if schema.unique_items == Some(true) {
+        let mut seen = HashSet::new();
+        if !items.iter().all(|item| seen.insert(item.to_string())) {
+            errors.push(format!("{path}: the items are not unique"));
+        }
+    }
+    if let Some(item_schema) = &schema.items {
+        for (idx, item) in items.iter().enumerate() {
+            check_value(
+                registry,
+                item_schema,
+                item,
+                &format!("{path}[{idx}]"),
+                errors,
+            );
+        }
+    }
+}
+
+fn check_object(
+    registry: &Registry,
+    schema: &MetaSchema,
+    object: &serde_json::Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    let len = object.len();
+    if let Some(max_properties) = schema.max_properties {
+        if len > max_properties {
+            errors.push(format!(
+                "{path}: {len} properties are more than {max_properties}"
+            ));
+        }
+    }
+    if let Some(min_properties) = schema.min_properties {
+        if len < min_properties {
+            errors.push(format!(
+                "{path}: {len} properties are less than {min_properties}"
+            ));
+        }
+    }
+
+    for name in &schema.required {
+        if !object.contains_key(*name) {
+            errors.push(format!("{path}: the property `{name}` is missing"));
+        }
+    }
+
+    for (name, value) in object {
+        let property_path = format!("{path}.{name}");
+        match schema
+            .properties
+            .iter()
+            .find(|(property, _)| property == name)
+        {
+            Some((_, property_schema)) => {
+                // The optional properties are written as `null` when they have no value.
+                if value.is_null() && !schema.required.contains(&name.as_str()) {
+                    continue;
+                }
+                if resolve(registry, property_schema).is_some_and(|schema| schema.write_only) {
+                    errors.push(format!("{property_path}: the property is write-only"));
+                    continue;
+                }
+                check_value(registry, property_schema, value, &property_path, errors);
+            }
+            None => {
+                if let Some(additional_properties) = &schema.additional_properties {
+                    check_value(
+                        registry,
+                        additional_properties,
+                        value,
+                        &property_path,
+                        errors,
+                    );
+                }
+            }
+        }
+    }
+}
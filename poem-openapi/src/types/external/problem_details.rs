@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+
+use poem::error::ProblemDetails;
+use serde_json::Value;
+
+use crate::{
+    registry::{MetaSchema, MetaSchemaRef, Registry},
+    types::{ParseError, ParseFromJSON, ParseResult, ToJSON, Type},
+};
+
+impl Type for ProblemDetails {
+    const IS_REQUIRED: bool = true;
+
+    type RawValueType = Self;
+
+    type RawElementValueType = Self;
+
+    fn name() -> Cow<'static, str> {
+        "ProblemDetails".into()
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        MetaSchemaRef::Reference(Self::name().into_owned())
+    }
+
+    fn register(registry: &mut Registry) {
+        registry.create_schema::<Self, _>(Self::name().into_owned(), |_| {
+            let string = |format, description| {
+                MetaSchemaRef::Inline(Box::new(MetaSchema {
+                    description: Some(description),
+                    ..match format {
+                        Some(format) => MetaSchema::new_with_format("string", format),
+                        None => MetaSchema::new("string"),
+                    }
+                }))
+            };
+
+            MetaSchema {
+                description: Some("A problem details object as defined by RFC 9457."),
+                required: vec!["status"],
+                properties: vec![
+                    (
+                        "type",
+                        MetaSchemaRef::Inline(Box::new(MetaSchema {
+                            description: Some("A URI reference that identifies the problem type."),
+                            default: Some(Value::String("about:blank".to_string())),
+                            ..MetaSchema::new_with_format("string", "uri-reference")
+                        })),
+                    ),
+                    (
+                        "title",
+                        string(None, "A short, human-readable summary of the problem type."),
+                    ),
+                    (
+                        "status",
+                        MetaSchemaRef::Inline(Box::new(MetaSchema {
+                            description: Some("The HTTP status code."),
+                            minimum: Some(100.0),
+                            maximum: Some(599.0),
+                            ..MetaSchema::new_with_format("integer", "int32")
+                        })),
+                    ),
+                    (
+                        "detail",
+                        string(
+                            None,
+                            "A human-readable explanation specific to this occurrence of the problem.",
+                        ),
+                    ),
+                    (
+                        "instance",
+                        string(
+                            Some("uri-reference"),
+                            "A URI reference that identifies the specific occurrence of the problem.",
+                        ),
+                    ),
+                ],
+                additional_properties: Some(Box::new(Value::schema_ref())),
+                ..MetaSchema::new("object")
+            }
+        })
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+
+    fn raw_element_iter<'a>(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = &'a Self::RawElementValueType> + 'a> {
+        Box::new(self.as_raw_value().into_iter())
+    }
+}
+
+impl ParseFromJSON for ProblemDetails {
+    fn parse_from_json(value: Option<Value>) -> ParseResult<Self> {
+        let value = value.unwrap_or_default();
+        serde_json::from_value(value).map_err(ParseError::custom)
+    }
+}
+
+impl ToJSON for ProblemDetails {
+    fn to_json(&self) -> Option<Value> {
+        serde_json::to_value(self).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use poem::http::StatusCode;
+
+    use super::*;
+
+    #[test]
+    fn problem_details() {
+        let mut registry = Registry::new();
+        ProblemDetails::register(&mut registry);
+        let schema = registry.schemas.get("ProblemDetails").unwrap();
+        assert_eq!(schema.ty, "object");
+        assert_eq!(
+            schema
+                .properties
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>(),
+            vec!["type", "title", "status", "detail", "instance"]
+        );
+
+        let problem = ProblemDetails::new(StatusCode::NOT_FOUND).extension("id", 1);
+        assert_eq!(
+            ProblemDetails::parse_from_json(problem.to_json()).unwrap(),
+            problem
+        );
+    }
+}
@@ -218,6 +218,24 @@ async fn prefix_path() {
         .send()
         .await
         .assert_status_is_ok();
+
+    struct Api5;
+
+    #[OpenApi(prefix_path = "/v1/:customer_id")]
+    impl Api5 {
+        #[oai(path = "/hello/:name", method = "get")]
+        async fn test(&self, name: Path<String>, customer_id: Path<String>) -> PlainText<String> {
+            PlainText(format!("{}:{}", customer_id.0, name.0))
+        }
+    }
+
+    let ep = OpenApiService::new(Api5, "test", "1.0");
+    TestClient::new(ep)
+        .get("/v1/1234/hello/sunli")
+        .send()
+        .await
+        .assert_text("1234:sunli")
+        .await;
 }
 
 #[tokio::test]
@@ -1146,3 +1164,291 @@ async fn info_extensions() {
         panic!("Spec root isn't a YAML mapping");
     }
 }
+
+#[tokio::test]
+async fn openapi_3_1() {
+    use poem_openapi::{OpenApiVersion, Webhook};
+    use serde_json::json;
+
+    #[derive(Object)]
+    struct Inner {
+        value: i32,
+    }
+
+    #[derive(Object)]
+    struct Obj {
+        #[oai(nullable)]
+        name: Option<String>,
+        #[oai(validator(maximum(value = "10", exclusive)))]
+        count: i32,
+        /// The inner object
+        #[oai(nullable)]
+        inner: Option<Inner>,
+    }
+
+    #[Webhook]
+    trait MyWebhooks {
+        #[oai(method = "post")]
+        #[allow(dead_code)]
+        fn created(&self, obj: Json<Obj>);
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/", method = "post")]
+        async fn create(&self, _obj: Json<Obj>) {}
+    }
+
+    let service = OpenApiService::new(Api, "test", "1.0").webhooks::<&dyn MyWebhooks>();
+    let spec = serde_json::from_str::<serde_json::Value>(&service.spec()).unwrap();
+    assert_eq!(spec["openapi"], "3.0.0");
+    assert_eq!(
+        spec["components"]["schemas"]["Obj"]["properties"]["name"]["nullable"],
+        true
+    );
+
+    let service = service.openapi_version(OpenApiVersion::V3_1);
+    let spec = serde_json::from_str::<serde_json::Value>(&service.spec()).unwrap();
+    assert_eq!(spec["openapi"], "3.1.0");
+    assert_eq!(
+        spec["webhooks"]["created"]["post"]["requestBody"]["content"]["application/json; charset=utf-8"]
+            ["schema"],
+        json!({ "$ref": "#/components/schemas/Obj" })
+    );
+
+    let properties = &spec["components"]["schemas"]["Obj"]["properties"];
+    assert_eq!(properties["name"], json!({ "type": ["string", "null"] }));
+    assert_eq!(
+        properties["count"],
+        json!({ "type": "integer", "format": "int32", "exclusiveMaximum": 10.0 })
+    );
+    assert_eq!(
+        properties["inner"],
+        json!({
+            "anyOf": [
+                {
+                    "$ref": "#/components/schemas/Inner",
+                    "description": "The inner object",
+                },
+                { "type": "null" },
+            ],
+        })
+    );
+
+    let spec = serde_yaml::from_str::<serde_json::Value>(&service.spec_yaml()).unwrap();
+    assert_eq!(spec["openapi"], "3.1.0");
+}
+
+#[tokio::test]
+async fn links() {
+    use serde_json::json;
+
+    #[derive(Object)]
+    struct User {
+        id: i64,
+    }
+
+    #[derive(ApiResponse)]
+    enum CreateUserResponse {
+        #[oai(
+            status = 201,
+            link(
+                name = "GetUser",
+                operation_id = "getUser",
+                parameter(name = "id", value = "$response.body#/id"),
+                description = "Get the created user"
+            ),
+            link(
+                name = "UpdateUser",
+                operation_id = "updateUser",
+                parameter(name = "id", value = "$response.body#/id"),
+                request_body = "$request.body"
+            )
+        )]
+        Created(Json<User>),
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/users", method = "post")]
+        async fn create(&self) -> CreateUserResponse {
+            CreateUserResponse::Created(Json(User { id: 1 }))
+        }
+
+        #[oai(path = "/users/:id", method = "get", operation_id = "getUser")]
+        async fn get(&self, id: Path<i64>) -> Json<User> {
+            Json(User { id: id.0 })
+        }
+
+        #[oai(path = "/users/:id", method = "put", operation_id = "updateUser")]
+        async fn update(&self, id: Path<i64>) -> Json<User> {
+            Json(User { id: id.0 })
+        }
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    let links = &meta.paths[0].operations[0].responses.responses[0].links;
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].name, "GetUser");
+    assert_eq!(links[0].operation_id, "getUser");
+    assert_eq!(links[0].parameters, vec![("id", "$response.body#/id")]);
+    assert_eq!(links[0].request_body, None);
+    assert_eq!(links[0].description, Some("Get the created user"));
+    assert_eq!(links[1].request_body, Some("$request.body"));
+
+    let spec = OpenApiService::new(Api, "test", "1.0").spec();
+    let spec = serde_json::from_str::<serde_json::Value>(&spec).unwrap();
+    assert_eq!(
+        spec["paths"]["/users"]["post"]["responses"]["201"]["links"],
+        json!({
+            "GetUser": {
+                "operationId": "getUser",
+                "parameters": { "id": "$response.body#/id" },
+                "description": "Get the created user",
+            },
+            "UpdateUser": {
+                "operationId": "updateUser",
+                "parameters": { "id": "$response.body#/id" },
+                "requestBody": "$request.body",
+            },
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "the link `GetUser` references an unknown operation id: getUser")]
+fn link_to_unknown_operation() {
+    #[derive(ApiResponse)]
+    enum CreateUserResponse {
+        #[oai(status = 201, link(name = "GetUser", operation_id = "getUser"))]
+        Created,
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(path = "/users", method = "post")]
+        async fn create(&self) -> CreateUserResponse {
+            CreateUserResponse::Created
+        }
+    }
+
+    let _ = TestClient::new(OpenApiService::new(Api, "test", "1.0"));
+}
+
+#[tokio::test]
+async fn callbacks() {
+    use poem_openapi::Webhook;
+    use serde_json::json;
+
+    #[derive(Object)]
+    struct Subscription {
+        callback_url: String,
+    }
+
+    #[derive(Object)]
+    struct Event {
+        id: i64,
+    }
+
+    #[Webhook]
+    #[allow(dead_code)]
+    trait EventWebhooks {
+        #[oai(method = "post")]
+        fn on_event(&self, event: Json<Event>);
+
+        #[oai(method = "delete")]
+        fn on_unsubscribe(&self);
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(
+            path = "/subscribe",
+            method = "post",
+            callback(
+                name = "onEvent",
+                url = "{$request.body#/callback_url}",
+                webhook = "&dyn EventWebhooks"
+            )
+        )]
+        async fn subscribe(&self, _subscription: Json<Subscription>) {}
+    }
+
+    let meta: MetaApi = Api::meta().remove(0);
+    let callbacks = &meta.paths[0].operations[0].callbacks;
+    assert_eq!(callbacks.len(), 1);
+    assert_eq!(callbacks[0].name, "onEvent");
+    assert_eq!(callbacks[0].url, "{$request.body#/callback_url}");
+    assert_eq!(callbacks[0].operations.len(), 2);
+    assert_eq!(callbacks[0].operations[0].method, Method::POST);
+    assert_eq!(callbacks[0].operations[1].method, Method::DELETE);
+
+    let mut registry = Registry::new();
+    Api::register(&mut registry);
+    assert!(registry.schemas.contains_key("Event"));
+
+    let spec = OpenApiService::new(Api, "test", "1.0").spec();
+    let spec = serde_json::from_str::<serde_json::Value>(&spec).unwrap();
+    let callback = &spec["paths"]["/subscribe"]["post"]["callbacks"]["onEvent"]["{$request.body#/callback_url}"];
+    assert_eq!(
+        callback["post"]["requestBody"]["content"]["application/json; charset=utf-8"]["schema"],
+        json!({ "$ref": "#/components/schemas/Event" })
+    );
+    assert!(callback["delete"].is_object());
+    assert!(spec["components"]["schemas"]["Event"].is_object());
+}
+
+#[test]
+#[should_panic(expected = "the callback `onEvent` has more than one `POST` operation")]
+fn callback_duplicate_method() {
+    use poem_openapi::{
+        Webhook,
+        registry::{MetaOperation, MetaWebhook},
+    };
+
+    // the derived webhooks reject the duplicate methods at compile time
+    struct EventWebhooks;
+
+    impl Webhook for EventWebhooks {
+        fn meta() -> Vec<MetaWebhook> {
+            ["onCreated", "onDeleted"]
+                .into_iter()
+                .map(|name| MetaWebhook {
+                    name,
+                    operation: MetaOperation {
+                        method: Method::POST,
+                        ..Default::default()
+                    },
+                })
+                .collect()
+        }
+
+        fn register(_registry: &mut Registry) {}
+    }
+
+    struct Api;
+
+    #[OpenApi]
+    impl Api {
+        #[oai(
+            path = "/subscribe",
+            method = "post",
+            callback(
+                name = "onEvent",
+                url = "{$request.body#/callback_url}",
+                webhook = "EventWebhooks"
+            )
+        )]
+        async fn subscribe(&self) {}
+    }
+
+    let _ = TestClient::new(OpenApiService::new(Api, "test", "1.0"));
+}
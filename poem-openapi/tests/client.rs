@@ -0,0 +1,299 @@
+use poem::{
+    Error,
+    http::{Method, StatusCode},
+};
+use poem_openapi::{
+    ApiRequest, ApiResponse, Object, OpenApi, OpenApiService, ResponseContent, SecurityScheme,
+    auth::{ApiKey, Bearer},
+    client::{ClientRequest, EndpointTransport},
+    param::{Header, Path, Query},
+    payload::{Json, PlainText},
+};
+
+#[derive(Object, Debug, Clone, PartialEq, Eq)]
+struct Pet {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+}
+
+#[derive(SecurityScheme)]
+#[oai(ty = "api_key", key_name = "X-API-Key", key_in = "header")]
+struct MyApiKey(ApiKey);
+
+#[derive(SecurityScheme)]
+#[oai(ty = "bearer")]
+struct MyBearer(Bearer);
+
+#[derive(SecurityScheme)]
+#[oai(client)]
+enum MyAuth {
+    ApiKey(MyApiKey),
+    Bearer(MyBearer),
+}
+
+#[derive(ApiResponse, Debug, PartialEq, Eq)]
+#[oai(client)]
+enum FindPetResponse {
+    #[oai(status = 200)]
+    Ok(Json<Pet>, #[oai(header = "X-Request-Id")] String),
+    #[oai(status = 404)]
+    NotFound(PlainText<String>),
+    #[oai(status_range = "5XX")]
+    ServerError(StatusCode, PlainText<String>),
+}
+
+#[derive(ApiRequest)]
+#[oai(client)]
+enum CreatePetRequest {
+    Json(Json<Pet>),
+    #[oai(content_type = "text/x-pet")]
+    Text(PlainText<String>),
+}
+
+#[derive(ResponseContent, Debug, PartialEq, Eq)]
+#[oai(client)]
+enum PetContent {
+    Json(Json<Pet>),
+    Text(PlainText<String>),
+}
+
+#[derive(ApiResponse, Debug, PartialEq, Eq)]
+#[oai(client)]
+enum CreatePetResponse {
+    #[oai(status = 201)]
+    Created(PetContent),
+}
+
+struct Api;
+
+#[OpenApi(prefix_path = "/pets", client = "PetClient")]
+impl Api {
+    #[oai(path = "/:id", method = "get")]
+    async fn find_pet(
+        &self,
+        id: Path<u64>,
+        #[oai(name = "X-Request-Id")] request_id: Header<String>,
+        auth: MyAuth,
+    ) -> FindPetResponse {
+        if let MyAuth::Bearer(_) = auth {
+            return FindPetResponse::ServerError(
+                StatusCode::SERVICE_UNAVAILABLE,
+                PlainText("unavailable".to_string()),
+            );
+        }
+        match id.0 {
+            1 => FindPetResponse::Ok(
+                Json(Pet {
+                    id: 1,
+                    name: "cat".to_string(),
+                    tags: vec![],
+                }),
+                request_id.0,
+            ),
+            _ => FindPetResponse::NotFound(PlainText("not found".to_string())),
+        }
+    }
+
+    #[oai(path = "/", method = "get")]
+    async fn list_pets(
+        &self,
+        name: Query<String>,
+        tags: Query<Vec<String>>,
+        #[oai(explode = false)] ids: Query<Vec<u64>>,
+    ) -> Json<Vec<Pet>> {
+        Json(
+            ids.0
+                .into_iter()
+                .map(|id| Pet {
+                    id,
+                    name: name.0.clone(),
+                    tags: tags.0.clone(),
+                })
+                .collect(),
+        )
+    }
+
+    #[oai(path = "/", method = "post")]
+    async fn create_pet(&self, req: CreatePetRequest) -> CreatePetResponse {
+        CreatePetResponse::Created(match req {
+            CreatePetRequest::Json(pet) => PetContent::Json(pet),
+            CreatePetRequest::Text(text) => PetContent::Text(text),
+        })
+    }
+
+    #[oai(path = "/:id", method = "delete")]
+    async fn delete_pet(&self, id: Path<u64>) -> poem::Result<()> {
+        match id.0 {
+            1 => Ok(()),
+            _ => Err(Error::from_status(StatusCode::NOT_FOUND)),
+        }
+    }
+
+    #[oai(path = "/:id/name", method = "get")]
+    async fn pet_name(&self, id: Path<String>) -> PlainText<String> {
+        PlainText(id.0)
+    }
+
+    #[oai(path = "/:b/compare/:a", method = "get")]
+    async fn compare(&self, a: Path<String>, b: Path<String>) -> PlainText<String> {
+        PlainText(format!("{} {}", a.0, b.0))
+    }
+
+    #[oai(path = "/internal", method = "get", skip_client)]
+    async fn internal(&self) {}
+}
+
+fn client() -> PetClient<impl poem_openapi::client::ClientTransport> {
+    PetClient::new(EndpointTransport::new(OpenApiService::new(
+        Api, "test", "1.0",
+    )))
+}
+
+fn api_key(key: &str) -> MyAuth {
+    MyAuth::ApiKey(MyApiKey(ApiKey {
+        key: key.to_string(),
+    }))
+}
+
+#[tokio::test]
+async fn response_variants() {
+    let client = client();
+
+    assert_eq!(
+        client
+            .find_pet(Path(1), Header("abc".to_string()), api_key("key"))
+            .await
+            .unwrap(),
+        FindPetResponse::Ok(
+            Json(Pet {
+                id: 1,
+                name: "cat".to_string(),
+                tags: vec![],
+            }),
+            "abc".to_string()
+        )
+    );
+    assert_eq!(
+        client
+            .find_pet(Path(2), Header("abc".to_string()), api_key("key"))
+            .await
+            .unwrap(),
+        FindPetResponse::NotFound(PlainText("not found".to_string()))
+    );
+    assert_eq!(
+        client
+            .find_pet(
+                Path(1),
+                Header("abc".to_string()),
+                MyAuth::Bearer(MyBearer(Bearer {
+                    token: "token".to_string()
+                }))
+            )
+            .await
+            .unwrap(),
+        FindPetResponse::ServerError(
+            StatusCode::SERVICE_UNAVAILABLE,
+            PlainText("unavailable".to_string())
+        )
+    );
+}
+
+#[tokio::test]
+async fn query_params() {
+    let pets = client()
+        .list_pets(
+            Query("a b&c".to_string()),
+            Query(vec!["x".to_string(), "y".to_string()]),
+            Query(vec![1, 2]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        pets.0,
+        vec![
+            Pet {
+                id: 1,
+                name: "a b&c".to_string(),
+                tags: vec!["x".to_string(), "y".to_string()],
+            },
+            Pet {
+                id: 2,
+                name: "a b&c".to_string(),
+                tags: vec!["x".to_string(), "y".to_string()],
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn request_objects() {
+    let client = client();
+    let pet = Pet {
+        id: 3,
+        name: "dog".to_string(),
+        tags: vec!["a".to_string()],
+    };
+
+    assert_eq!(
+        client
+            .create_pet(CreatePetRequest::Json(Json(pet.clone())))
+            .await
+            .unwrap(),
+        CreatePetResponse::Created(PetContent::Json(Json(pet)))
+    );
+    assert_eq!(
+        client
+            .create_pet(CreatePetRequest::Text(PlainText("dog".to_string())))
+            .await
+            .unwrap(),
+        CreatePetResponse::Created(PetContent::Text(PlainText("dog".to_string())))
+    );
+}
+
+#[tokio::test]
+async fn result_and_path_encoding() {
+    let client = client();
+
+    client.delete_pet(Path(1)).await.unwrap();
+    let err = client.delete_pet(Path(2)).await.unwrap_err();
+    assert_eq!(err.status(), StatusCode::NOT_FOUND);
+
+    assert_eq!(
+        client.pet_name(Path("a/b c".to_string())).await.unwrap().0,
+        "a/b c"
+    );
+}
+
+#[tokio::test]
+async fn path_params_by_name() {
+    let client = client();
+    assert_eq!(
+        client
+            .compare(Path("a".to_string()), Path("b".to_string()))
+            .await
+            .unwrap()
+            .0,
+        "a b"
+    );
+
+    let mut request = ClientRequest::new(Method::GET, "/pets/{id}");
+    request.path_param("name", "a");
+    let err = request.into_request().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid request: the path parameter `name` is not in the path"
+    );
+}
+
+#[cfg(feature = "test")]
+#[tokio::test]
+async fn test_client_transport() {
+    let cli = poem::test::TestClient::new(OpenApiService::new(Api, "test", "1.0"));
+    let client = PetClient::new(cli);
+
+    assert_eq!(
+        client.pet_name(Path("abc".to_string())).await.unwrap().0,
+        "abc"
+    );
+}
@@ -53,7 +53,8 @@ fn meta() {
                     status: Some(200),
                     status_range: None,
                     content: vec![],
-                    headers: vec![]
+                    headers: vec![],
+                    links: vec![]
                 },
                 MetaResponse {
                     description: "A\nB\n\nC",
@@ -63,7 +64,8 @@ fn meta() {
                         content_type: "application/json; charset=utf-8",
                         schema: MetaSchemaRef::Reference("BadRequestResult".to_string())
                     }],
-                    headers: vec![]
+                    headers: vec![],
+                    links: vec![]
                 },
                 MetaResponse {
                     description: "yaml response",
@@ -73,7 +75,8 @@ fn meta() {
                         content_type: "application/yaml; charset=utf-8",
                         schema: MetaSchemaRef::Reference("BadRequestResult".to_string())
                     }],
-                    headers: vec![]
+                    headers: vec![],
+                    links: vec![]
                 },
                 MetaResponse {
                     description: "",
@@ -83,7 +86,8 @@ fn meta() {
                         content_type: "text/plain; charset=utf-8",
                         schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new("string"))),
                     }],
-                    headers: vec![]
+                    headers: vec![],
+                    links: vec![]
                 }
             ],
         },
@@ -281,7 +285,8 @@ async fn generic() {
                     content_type: "application/json; charset=utf-8",
                     schema: MetaSchemaRef::Inline(Box::new(MetaSchema::new("string")))
                 }],
-                headers: vec![]
+                headers: vec![],
+                links: vec![]
             },],
         },
     );
@@ -318,7 +323,8 @@ async fn item_content_type() {
                             "integer", "int32"
                         )))
                     }],
-                    headers: vec![]
+                    headers: vec![],
+                    links: vec![]
                 },
                 MetaResponse {
                     description: "",
@@ -330,7 +336,8 @@ async fn item_content_type() {
                             "integer", "int32"
                         )))
                     }],
-                    headers: vec![]
+                    headers: vec![],
+                    links: vec![]
                 }
             ],
         },
@@ -613,3 +620,47 @@ async fn status_range() {
     let resp = cli.get("/?error=server").send().await;
     resp.assert_status(StatusCode::INSUFFICIENT_STORAGE);
 }
+
+#[tokio::test]
+async fn problem_details() {
+    use poem::error::ProblemDetails;
+
+    #[derive(ApiResponse)]
+    #[allow(dead_code)]
+    enum Resp {
+        #[oai(status = 200)]
+        Ok(Json<i32>),
+        #[oai(status = 404, content_type = "application/problem+json")]
+        NotFound(Json<ProblemDetails>),
+    }
+
+    let meta = Resp::meta();
+    assert_eq!(
+        meta.responses[1].content[0].content_type,
+        "application/problem+json"
+    );
+    assert_eq!(
+        meta.responses[1].content[0].schema,
+        MetaSchemaRef::Reference("ProblemDetails".to_string())
+    );
+
+    let mut registry = Registry::new();
+    Resp::register(&mut registry);
+    assert!(registry.schemas.contains_key("ProblemDetails"));
+
+    let mut resp = Resp::NotFound(Json(
+        ProblemDetails::new(StatusCode::NOT_FOUND).detail("pet not found"),
+    ))
+    .into_response();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(resp.content_type(), Some("application/problem+json"));
+    assert_eq!(
+        serde_json::from_slice::<Value>(&resp.take_body().into_bytes().await.unwrap()).unwrap(),
+        json!({
+            "type": "about:blank",
+            "title": "Not Found",
+            "status": 404,
+            "detail": "pet not found",
+        })
+    );
+}
@@ -65,7 +65,8 @@ async fn use_in_api_response() {
                 status: Some(200),
                 status_range: None,
                 content: MyResponseContent::media_types(),
-                headers: vec![]
+                headers: vec![],
+                links: vec![]
             }]
         }
     );
@@ -0,0 +1,329 @@
+use poem::{
+    Endpoint, EndpointExt,
+    http::{HeaderValue, StatusCode, header},
+    test::TestClient,
+};
+use poem_openapi::{
+    ApiResponse, Enum, Object, OpenApi, OpenApiService, ResponseValidation, Union,
+    param::{Path, Query},
+    payload::{Json, PlainText},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Enum)]
+enum Status {
+    Available,
+    Sold,
+}
+
+#[derive(Debug, Object)]
+struct Pet {
+    id: i64,
+    #[oai(validator(max_length = 8))]
+    name: String,
+    tag: Option<String>,
+    status: Status,
+    #[oai(validator(maximum(value = "10")))]
+    rank: u32,
+}
+
+#[derive(Debug, Object)]
+struct Error {
+    code: i32,
+    message: String,
+}
+
+#[derive(Debug, Union)]
+#[oai(one_of)]
+enum PetOrError {
+    Pet(Pet),
+    Error(Error),
+}
+
+#[derive(Debug, ApiResponse)]
+enum DeletePetError {
+    #[oai(status = 409)]
+    Conflict(Json<Pet>),
+}
+
+#[derive(Debug, ApiResponse)]
+enum GetPetResponse {
+    #[oai(status = 200)]
+    Ok(Json<Pet>, #[oai(header = "X-Rank")] u32),
+    #[oai(status = 404)]
+    NotFound,
+}
+
+fn pet(name: &str, rank: u32) -> Pet {
+    Pet {
+        id: 1,
+        name: name.to_string(),
+        tag: None,
+        status: Status::Available,
+        rank,
+    }
+}
+
+struct Api;
+
+#[OpenApi]
+impl Api {
+    #[oai(path = "/pets/:name", method = "get")]
+    async fn get_pet(&self, name: Path<String>, rank: Query<u32>) -> GetPetResponse {
+        match name.0.as_str() {
+            "missing" => GetPetResponse::NotFound,
+            _ => GetPetResponse::Ok(Json(pet(&name.0, rank.0)), rank.0),
+        }
+    }
+
+    #[oai(path = "/pets/:name", method = "delete")]
+    async fn delete_pet(&self, name: Path<String>) -> Result<(), DeletePetError> {
+        Err(DeletePetError::Conflict(Json(pet(&name.0, 1))))
+    }
+
+    #[oai(path = "/union/:name", method = "get")]
+    async fn union(&self, name: Path<String>) -> Json<PetOrError> {
+        match name.0.as_str() {
+            "error" => Json(PetOrError::Error(Error {
+                code: 1,
+                message: "error".to_string(),
+            })),
+            _ => Json(PetOrError::Pet(pet(&name.0, 1))),
+        }
+    }
+
+    #[oai(path = "/text", method = "get", transform = "json_content_type")]
+    async fn text(&self) -> PlainText<String> {
+        PlainText("hello".to_string())
+    }
+
+    #[oai(path = "/ranks/invalid", method = "get", transform = "invalid_rank")]
+    async fn invalid_rank(&self) -> GetPetResponse {
+        GetPetResponse::Ok(Json(pet("tom", 3)), 3)
+    }
+
+    #[oai(path = "/ranks/missing", method = "get", transform = "missing_rank")]
+    async fn missing_rank(&self) -> GetPetResponse {
+        GetPetResponse::Ok(Json(pet("tom", 3)), 3)
+    }
+
+    #[oai(path = "/created", method = "post", transform = "created")]
+    async fn create(&self) -> Json<i32> {
+        Json(1)
+    }
+}
+
+fn json_content_type(ep: impl Endpoint) -> impl Endpoint {
+    ep.map_to_response().map(|mut resp| async move {
+        resp.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        resp
+    })
+}
+
+fn invalid_rank(ep: impl Endpoint) -> impl Endpoint {
+    ep.map_to_response().map(|mut resp| async move {
+        resp.headers_mut()
+            .insert("X-Rank", HeaderValue::from_static("abc"));
+        resp
+    })
+}
+
+fn missing_rank(ep: impl Endpoint) -> impl Endpoint {
+    ep.map_to_response().map(|mut resp| async move {
+        resp.headers_mut().remove("X-Rank");
+        resp
+    })
+}
+
+fn created(ep: impl Endpoint) -> impl Endpoint {
+    ep.map_to_response().map(|mut resp| async move {
+        resp.set_status(StatusCode::CREATED);
+        resp
+    })
+}
+
+fn client(mode: ResponseValidation) -> TestClient<impl Endpoint> {
+    TestClient::new(OpenApiService::new(Api, "test", "1.0").response_validation(mode))
+}
+
+#[tokio::test]
+async fn valid_responses() {
+    let cli = client(ResponseValidation::Fail);
+
+    let resp = cli.get("/pets/tom").query("rank", &3).send().await;
+    resp.assert_status_is_ok();
+    resp.assert_header("X-Rank", "3");
+    resp.assert_json(serde_json::json!({
+        "id": 1,
+        "name": "tom",
+        "tag": null,
+        "status": "Available",
+        "rank": 3,
+    }))
+    .await;
+
+    cli.get("/pets/missing")
+        .query("rank", &3)
+        .send()
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    cli.get("/union/tom").send().await.assert_status_is_ok();
+    cli.get("/union/error").send().await.assert_status_is_ok();
+    cli.delete("/pets/tom")
+        .send()
+        .await
+        .assert_status(StatusCode::CONFLICT);
+    // the errors of the extractors are not validated
+    cli.get("/pets/tom")
+        .send()
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn invalid_body() {
+    let cli = client(ResponseValidation::Fail);
+
+    let resp = cli.get("/pets/long_name").query("rank", &3).send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    resp.assert_text(
+        "the response of `GET /pets/{name}` does not match the specification: $.name: the \
+         length 9 is greater than 8",
+    )
+    .await;
+
+    let resp = cli.get("/pets/tom").query("rank", &11).send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    resp.assert_text(
+        "the response of `GET /pets/{name}` does not match the specification: $.rank: `11` is \
+         greater than the maximum `10`",
+    )
+    .await;
+
+    cli.get("/union/long_name")
+        .send()
+        .await
+        .assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+
+    let resp = cli.delete("/pets/long_name").send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    resp.assert_text(
+        "the response of `DELETE /pets/{name}` does not match the specification: $.name: the \
+         length 9 is greater than 8",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn invalid_content_type() {
+    let cli = client(ResponseValidation::Fail);
+
+    let resp = cli.get("/text").send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    resp.assert_text(
+        "the response of `GET /text` does not match the specification: the content type \
+         `application/json` is not declared",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn invalid_header() {
+    let cli = client(ResponseValidation::Fail);
+
+    let resp = cli.get("/ranks/invalid").send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    resp.assert_text(
+        "the response of `GET /ranks/invalid` does not match the specification: header `X-RANK`: \
+         expected integer, found `\"abc\"`",
+    )
+    .await;
+
+    let resp = cli.get("/ranks/missing").send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    resp.assert_text(
+        "the response of `GET /ranks/missing` does not match the specification: the header \
+         `X-RANK` is missing",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn invalid_status() {
+    let cli = client(ResponseValidation::Fail);
+
+    let resp = cli.post("/created").send().await;
+    resp.assert_status(StatusCode::INTERNAL_SERVER_ERROR);
+    resp.assert_text(
+        "the response of `POST /created` does not match the specification: the status `201 \
+         Created` is not declared",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn log_mismatches() {
+    let cli = client(ResponseValidation::Log);
+
+    let resp = cli.get("/pets/long_name").query("rank", &3).send().await;
+    resp.assert_status_is_ok();
+    resp.assert_header("X-Rank", "3");
+
+    let resp = cli.post("/created").send().await;
+    resp.assert_status(StatusCode::CREATED);
+    resp.assert_json(1).await;
+
+    let resp = cli.get("/ranks/invalid").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_header("X-Rank", "abc");
+
+    let resp = cli.get("/ranks/missing").send().await;
+    resp.assert_status_is_ok();
+    resp.assert_header_is_not_exist("X-Rank");
+}
+
+#[tokio::test]
+async fn disabled_by_default() {
+    let cli = TestClient::new(OpenApiService::new(Api, "test", "1.0"));
+    cli.get("/pets/long_name")
+        .query("rank", &3)
+        .send()
+        .await
+        .assert_status_is_ok();
+}
+
+#[tokio::test]
+async fn error_responses_are_errors() {
+    for mode in [
+        ResponseValidation::Disabled,
+        ResponseValidation::Log,
+        ResponseValidation::Fail,
+    ] {
+        let ep = OpenApiService::new(Api, "test", "1.0")
+            .response_validation(mode)
+            .around(|ep, req| async move {
+                let is_err = ep.call(req).await.is_err();
+                Ok(is_err.to_string())
+            });
+        let cli = TestClient::new(ep);
+
+        cli.delete("/pets/tom")
+            .send()
+            .await
+            .assert_text("true")
+            .await;
+        cli.delete("/pets/long_name")
+            .send()
+            .await
+            .assert_text("true")
+            .await;
+        cli.get("/pets/tom")
+            .query("rank", &3)
+            .send()
+            .await
+            .assert_text("false")
+            .await;
+    }
+}
@@ -681,3 +681,62 @@ async fn fallback() {
         ])
     )
 }
+
+#[cfg(feature = "jwt")]
+#[tokio::test]
+async fn jwt_checker() {
+    use jsonwebtoken::{EncodingKey, Header};
+    use poem::{
+        EndpointExt,
+        middleware::{JwtAlgorithm, JwtKey, JwtValidator},
+    };
+
+    #[derive(serde::Deserialize)]
+    struct User {
+        sub: String,
+    }
+
+    #[derive(SecurityScheme)]
+    #[oai(ty = "bearer", bearer_format = "JWT", checker = "jwt_checker")]
+    struct JwtAuthorization(User);
+
+    async fn jwt_checker(req: &Request, bearer: Bearer) -> poem::Result<User> {
+        let validator = req.data::<JwtValidator>().unwrap();
+        bearer.validate_jwt(validator).await
+    }
+
+    struct MyApi;
+
+    #[OpenApi]
+    impl MyApi {
+        #[oai(path = "/test", method = "get")]
+        async fn test(&self, auth: JwtAuthorization) -> PlainText<String> {
+            PlainText(format!("Authed: {}", auth.0.sub))
+        }
+    }
+
+    let validator = JwtValidator::new([JwtKey::hmac(JwtAlgorithm::HS256, "secret")]);
+    let service = OpenApiService::new(MyApi, "test", "1.0");
+    let client = TestClient::new(service.data(validator));
+
+    let token = jsonwebtoken::encode(
+        &Header::new(JwtAlgorithm::HS256),
+        &json!({ "sub": "sunli", "exp": 4102444800u64 }),
+        &EncodingKey::from_secret(b"secret"),
+    )
+    .unwrap();
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::bearer(&token).unwrap())
+        .send()
+        .await;
+    resp.assert_status_is_ok();
+    resp.assert_text("Authed: sunli").await;
+
+    let resp = client
+        .get("/test")
+        .typed_header(headers::Authorization::bearer("invalid").unwrap())
+        .send()
+        .await;
+    resp.assert_status(StatusCode::UNAUTHORIZED);
+}
@@ -184,7 +184,8 @@ async fn response() {
                     content_type: "application/json; charset=utf-8",
                     schema: i32::schema_ref(),
                 }],
-                headers: vec![]
+                headers: vec![],
+                links: vec![]
             }]
         }
     );
@@ -169,13 +169,19 @@ impl Body {
         size_hint.lower() == 0 && size_hint.upper() == Some(0)
     }
 
+    /// Returns the size of this body if it is known in advance.
+    #[cfg(feature = "compression")]
+    pub(crate) fn exact_size(&self) -> Option<u64> {
+        hyper::body::Body::size_hint(&self.0).exact()
+    }
+
     /// Consumes this body object to return a [`Bytes`] that contains all data.
     pub async fn into_bytes(self) -> Result<Bytes, ReadBodyError> {
         Ok(self
             .0
             .collect()
             .await
-            .map_err(|err| ReadBodyError::Io(IoError::other(err)))?
+            .map_err(ReadBodyError::Io)?
             .to_bytes())
     }
 
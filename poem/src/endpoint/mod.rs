@@ -16,6 +16,8 @@ mod map;
 mod map_to_response;
 #[cfg(feature = "prometheus")]
 mod prometheus_exporter;
+#[cfg(feature = "proxy")]
+mod proxy;
 #[cfg(feature = "static-files")]
 mod static_files;
 mod to_response;
@@ -40,6 +42,8 @@ pub use map::Map;
 pub use map_to_response::MapToResponse;
 #[cfg(feature = "prometheus")]
 pub use prometheus_exporter::PrometheusExporter;
+#[cfg(feature = "proxy")]
+pub use proxy::{LoadBalance, Proxy};
 #[cfg(feature = "static-files")]
 pub use static_files::{StaticFileEndpoint, StaticFilesEndpoint};
 pub use to_response::ToResponse;
@@ -0,0 +1,615 @@
+use std::{
+    fmt::Write,
+    net::IpAddr,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use http::{
+    HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri, Version, header,
+    uri::{PathAndQuery, Scheme},
+};
+use http_body_util::BodyExt;
+use hyper_util::{
+    client::legacy::{
+        Client,
+        connect::{Connect, HttpConnector},
+    },
+    rt::{TokioExecutor, TokioIo},
+};
+use parking_lot::Mutex;
+
+use crate::{Body, Endpoint, Request, Response, Result, body::BoxBody, error::ProxyError};
+
+/// Headers that are only meaningful for a single connection, and must not be
+/// forwarded by proxies.
+static HOP_BY_HOP_HEADERS: [HeaderName; 9] = [
+    header::CONNECTION,
+    HeaderName::from_static("keep-alive"),
+    header::PROXY_AUTHENTICATE,
+    header::PROXY_AUTHORIZATION,
+    HeaderName::from_static("proxy-connection"),
+    header::TE,
+    header::TRAILER,
+    header::TRANSFER_ENCODING,
+    header::UPGRADE,
+];
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+
+/// The strategy used to select an upstream server.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum LoadBalance {
+    /// Select the upstream servers in turn.
+    #[default]
+    RoundRobin,
+    /// Select a random upstream server.
+    Random,
+}
+
+#[derive(Default)]
+struct Health {
+    fails: u32,
+    unavailable_until: Option<Instant>,
+}
+
+struct Upstream {
+    scheme: Scheme,
+    authority: http::uri::Authority,
+    path: String,
+    health: Mutex<Health>,
+}
+
+impl Upstream {
+    fn is_available(&self, now: Instant) -> bool {
+        self.health
+            .lock()
+            .unavailable_until
+            .is_none_or(|until| until <= now)
+    }
+
+    fn succeeded(&self) {
+        let mut health = self.health.lock();
+        health.fails = 0;
+        health.unavailable_until = None;
+    }
+
+    fn failed(&self, max_fails: u32, fail_timeout: Duration) {
+        let mut health = self.health.lock();
+        health.fails += 1;
+        if max_fails > 0 && health.fails >= max_fails {
+            health.fails = 0;
+            health.unavailable_until = Some(Instant::now() + fail_timeout);
+        }
+    }
+
+    fn target_uri(&self, uri: &Uri) -> Result<Uri, ProxyError> {
+        let mut path_and_query = self.path.trim_end_matches('/').to_string();
+        path_and_query.push_str(uri.path());
+        if let Some(query) = uri.query() {
+            path_and_query.push('?');
+            path_and_query.push_str(query);
+        }
+
+        Uri::builder()
+            .scheme(self.scheme.clone())
+            .authority(self.authority.clone())
+            .path_and_query(
+                PathAndQuery::try_from(path_and_query)
+                    .map_err(|err| ProxyError::InvalidRequest(err.to_string()))?,
+            )
+            .build()
+            .map_err(|err| ProxyError::InvalidRequest(err.to_string()))
+    }
+}
+
+/// An endpoint that forwards requests to one or more upstream servers.
+///
+/// The path of the request is appended to the path of the upstream URL, so
+/// when the endpoint is nested in a [`Route`](crate::Route), the prefix is
+/// removed before the request is forwarded.
+///
+/// The endpoint:
+///
+/// - Removes the hop-by-hop headers from requests and responses.
+/// - Adds the `Forwarded`, `X-Forwarded-For`, `X-Forwarded-Host` and
+///   `X-Forwarded-Proto` headers.
+/// - Streams request and response bodies.
+/// - Forwards connection upgrades, such as WebSockets.
+///
+/// Upstream servers are marked as unavailable for
+/// [`fail_timeout`](Proxy::fail_timeout) after
+/// [`max_fails`](Proxy::max_fails) consecutive failures. A failure is an error
+/// connecting to the server, or a `502`, `503` or `504` response. If all
+/// upstream servers are unavailable, they are used anyway.
+///
+/// Only `http` upstream servers are supported by the default connector, use
+/// [`Proxy::with_connector`] to use other connectors.
+///
+/// # Errors
+///
+/// - [`ProxyError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     Route,
+///     endpoint::{LoadBalance, Proxy},
+/// };
+///
+/// let app = Route::new().nest(
+///     "/api",
+///     Proxy::new(["http://10.0.0.1:8080/api", "http://10.0.0.2:8080/api"])
+///         .load_balance(LoadBalance::Random),
+/// );
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "proxy")))]
+pub struct Proxy<C = HttpConnector> {
+    client: Client<C, BoxBody>,
+    upstreams: Vec<Upstream>,
+    load_balance: LoadBalance,
+    next: AtomicUsize,
+    max_fails: u32,
+    fail_timeout: Duration,
+    preserve_host: bool,
+}
+
+impl Proxy {
+    /// Create a `Proxy` endpoint that forwards requests to the specified
+    /// upstream URLs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no upstream URL is specified, or if an URL is not an absolute
+    /// URL.
+    pub fn new<I, T>(upstreams: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let upstreams = upstreams
+            .into_iter()
+            .map(|url| {
+                let url = url.as_ref();
+                let uri = url
+                    .parse::<Uri>()
+                    .unwrap_or_else(|err| panic!("invalid upstream url `{url}`: {err}"));
+                let (Some(scheme), Some(authority)) = (uri.scheme(), uri.authority()) else {
+                    panic!("upstream url `{url}` must be an absolute url");
+                };
+                Upstream {
+                    scheme: scheme.clone(),
+                    authority: authority.clone(),
+                    path: uri.path().to_string(),
+                    health: Default::default(),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert!(!upstreams.is_empty(), "at least one upstream is required");
+
+        Self {
+            client: Client::builder(TokioExecutor::new()).build(HttpConnector::new()),
+            upstreams,
+            load_balance: LoadBalance::default(),
+            next: AtomicUsize::new(0),
+            max_fails: 1,
+            fail_timeout: Duration::from_secs(10),
+            preserve_host: false,
+        }
+    }
+}
+
+impl<C> Proxy<C> {
+    /// Uses the specified connector to connect to the upstream servers, for
+    /// example to connect to `https` upstream servers.
+    pub fn with_connector<C2>(self, connector: C2) -> Proxy<C2>
+    where
+        C2: Connect + Clone,
+    {
+        Proxy {
+            client: Client::builder(TokioExecutor::new()).build(connector),
+            upstreams: self.upstreams,
+            load_balance: self.load_balance,
+            next: self.next,
+            max_fails: self.max_fails,
+            fail_timeout: self.fail_timeout,
+            preserve_host: self.preserve_host,
+        }
+    }
+
+    /// Sets the strategy used to select an upstream server. (defaults to
+    /// [`LoadBalance::RoundRobin`])
+    #[must_use]
+    pub fn load_balance(self, load_balance: LoadBalance) -> Self {
+        Self {
+            load_balance,
+            ..self
+        }
+    }
+
+    /// Sets the number of consecutive failures after which an upstream server
+    /// is considered unavailable, `0` disables the health checks. (defaults to
+    /// `1`)
+    #[must_use]
+    pub fn max_fails(self, max_fails: u32) -> Self {
+        Self { max_fails, ..self }
+    }
+
+    /// Sets the duration for which an upstream server is considered
+    /// unavailable. (defaults to 10 seconds)
+    #[must_use]
+    pub fn fail_timeout(self, fail_timeout: Duration) -> Self {
+        Self {
+            fail_timeout,
+            ..self
+        }
+    }
+
+    /// Specifies whether to forward the `Host` header of the request, instead
+    /// of using the host of the upstream server. (defaults to `false`)
+    #[must_use]
+    pub fn preserve_host(self, preserve_host: bool) -> Self {
+        Self {
+            preserve_host,
+            ..self
+        }
+    }
+
+    fn select_upstream(&self) -> &Upstream {
+        let count = self.upstreams.len();
+        let start = match self.load_balance {
+            LoadBalance::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % count,
+            LoadBalance::Random => rand::random_range(0..count),
+        };
+        let now = Instant::now();
+
+        (0..count)
+            .map(|idx| &self.upstreams[(start + idx) % count])
+            .find(|upstream| upstream.is_available(now))
+            .unwrap_or(&self.upstreams[start])
+    }
+}
+
+impl<C> Endpoint for Proxy<C>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        let upstream = self.select_upstream();
+        let is_upgrade = is_upgrade_request(&req);
+        let on_upgrade = if is_upgrade {
+            req.take_upgrade().ok()
+        } else {
+            None
+        };
+
+        let host = req
+            .headers()
+            .get(header::HOST)
+            .and_then(|value| value.to_str().ok())
+            .map(ToString::to_string)
+            .or_else(|| req.uri().authority().map(ToString::to_string));
+        let client_ip = req.remote_addr().as_socket_addr().map(|addr| addr.ip());
+        let scheme = req.scheme().clone();
+
+        let mut headers = std::mem::take(req.headers_mut());
+        remove_hop_by_hop_headers(&mut headers, is_upgrade);
+        add_forwarded_headers(&mut headers, client_ip, host.as_deref(), &scheme);
+        if !self.preserve_host {
+            headers.remove(header::HOST);
+        }
+
+        let mut upstream_req = http::Request::new(BoxBody::from(req.take_body()));
+        *upstream_req.method_mut() = req.method().clone();
+        *upstream_req.uri_mut() = upstream.target_uri(req.uri())?;
+        *upstream_req.version_mut() = Version::HTTP_11;
+        *upstream_req.headers_mut() = headers;
+
+        let mut resp = match self.client.request(upstream_req).await {
+            Ok(resp) => resp,
+            Err(err) => {
+                upstream.failed(self.max_fails, self.fail_timeout);
+                return Err(ProxyError::Upstream(err.to_string()).into());
+            }
+        };
+
+        if matches!(
+            resp.status(),
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+        ) {
+            upstream.failed(self.max_fails, self.fail_timeout);
+        } else {
+            upstream.succeeded();
+        }
+
+        let is_upgraded = resp.status() == StatusCode::SWITCHING_PROTOCOLS;
+        match on_upgrade {
+            Some(on_upgrade) if is_upgraded => {
+                let upstream_upgrade = hyper::upgrade::on(&mut resp);
+                tokio::spawn(async move {
+                    match (on_upgrade.await, upstream_upgrade.await) {
+                        (Ok(mut downstream), Ok(upstream)) => {
+                            let mut upstream = TokioIo::new(upstream);
+                            let _ =
+                                tokio::io::copy_bidirectional(&mut downstream, &mut upstream).await;
+                        }
+                        (Err(err), _) => {
+                            tracing::debug!(error = %err, "failed to upgrade the connection");
+                        }
+                        (_, Err(err)) => {
+                            tracing::debug!(error = %err, "failed to upgrade the upstream connection");
+                        }
+                    }
+                });
+            }
+            _ => remove_hop_by_hop_headers(resp.headers_mut(), false),
+        }
+
+        let (parts, body) = resp.into_parts();
+        let mut resp = Response::builder()
+            .status(parts.status)
+            .version(parts.version)
+            .body(Body(body.map_err(std::io::Error::other).boxed()));
+        *resp.headers_mut() = parts.headers;
+        Ok(resp)
+    }
+}
+
+fn is_upgrade_request(req: &Request) -> bool {
+    req.method() == Method::GET
+        && req.headers().contains_key(header::UPGRADE)
+        && req
+            .headers()
+            .get_all(header::CONNECTION)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|value| value.trim().eq_ignore_ascii_case("upgrade"))
+}
+
+fn remove_hop_by_hop_headers(headers: &mut HeaderMap, keep_upgrade: bool) {
+    let upgrade = keep_upgrade
+        .then(|| headers.get(header::UPGRADE).cloned())
+        .flatten();
+
+    // the headers listed in the `Connection` header are hop-by-hop headers too
+    let connection_headers = headers
+        .get_all(header::CONNECTION)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|name| HeaderName::try_from(name.trim()).ok())
+        .collect::<Vec<_>>();
+    for name in connection_headers {
+        headers.remove(name);
+    }
+    for name in &HOP_BY_HOP_HEADERS {
+        headers.remove(name);
+    }
+
+    if let Some(upgrade) = upgrade {
+        headers.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+        headers.insert(header::UPGRADE, upgrade);
+    }
+}
+
+fn add_forwarded_headers(
+    headers: &mut HeaderMap,
+    client_ip: Option<IpAddr>,
+    host: Option<&str>,
+    scheme: &Scheme,
+) {
+    let mut forwarded = String::new();
+    if let Some(ip) = client_ip {
+        match ip {
+            IpAddr::V4(ip) => write!(forwarded, "for={ip}").unwrap(),
+            IpAddr::V6(ip) => write!(forwarded, "for=\"[{ip}]\"").unwrap(),
+        }
+
+        let value = match headers
+            .get(&X_FORWARDED_FOR)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(value) => format!("{value}, {ip}"),
+            None => ip.to_string(),
+        };
+        if let Ok(value) = HeaderValue::try_from(value) {
+            headers.insert(X_FORWARDED_FOR, value);
+        }
+    }
+
+    if let Some(host) = host {
+        if !forwarded.is_empty() {
+            forwarded.push(';');
+        }
+        if host.bytes().all(is_token_char) {
+            write!(forwarded, "host={host}").unwrap();
+        } else {
+            write!(forwarded, "host=\"{host}\"").unwrap();
+        }
+
+        if let Ok(value) = HeaderValue::try_from(host) {
+            headers.insert(X_FORWARDED_HOST, value);
+        }
+    }
+
+    if !forwarded.is_empty() {
+        forwarded.push(';');
+    }
+    write!(forwarded, "proto={}", scheme.as_str()).unwrap();
+    if let Ok(value) = HeaderValue::try_from(scheme.as_str()) {
+        headers.insert(X_FORWARDED_PROTO, value);
+    }
+
+    if let Ok(value) = HeaderValue::try_from(forwarded) {
+        headers.append(header::FORWARDED, value);
+    }
+}
+
+fn is_token_char(ch: u8) -> bool {
+    ch.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+    use crate::{
+        Route, Server,
+        endpoint::make_sync,
+        handler,
+        listener::{Acceptor, Listener, TcpListener},
+        test::TestClient,
+    };
+
+    async fn start_upstream<E>(ep: E) -> SocketAddr
+    where
+        E: crate::IntoEndpoint + Send + 'static,
+        E::Endpoint: 'static,
+    {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        tokio::spawn(Server::new_with_acceptor(acceptor).run(ep));
+        addr
+    }
+
+    #[handler(internal)]
+    fn echo(req: &Request, body: String) -> String {
+        let mut s = format!("{} {} {}\n", req.method(), req.uri(), body);
+        for name in [
+            "host",
+            "forwarded",
+            "x-forwarded-for",
+            "x-forwarded-host",
+            "x-forwarded-proto",
+            "x-custom",
+            "proxy-authorization",
+        ] {
+            if let Some(value) = req.header(name) {
+                writeln!(s, "{name}: {value}").unwrap();
+            }
+        }
+        s
+    }
+
+    #[tokio::test]
+    async fn forward_request() {
+        let addr = start_upstream(Route::new().at("/v1/*path", echo)).await;
+        let app = Route::new().nest("/api", Proxy::new([format!("http://{addr}/v1")]));
+
+        let resp = TestClient::new(app)
+            .post("/api/users?page=1")
+            .header(header::HOST, "example.com")
+            .header(header::CONNECTION, "x-custom")
+            .header("x-custom", "a")
+            .header(header::PROXY_AUTHORIZATION, "secret")
+            .body("hello")
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_text(format!(
+            "POST /v1/users?page=1 hello\nhost: {addr}\nforwarded: host=example.com;proto=http\nx-forwarded-host: example.com\nx-forwarded-proto: http\n"
+        ))
+        .await;
+    }
+
+    #[tokio::test]
+    async fn forwarded_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(X_FORWARDED_FOR, HeaderValue::from_static("10.0.0.1"));
+        add_forwarded_headers(
+            &mut headers,
+            Some("2001:db8::1".parse().unwrap()),
+            Some("example.com:8080"),
+            &Scheme::HTTPS,
+        );
+        assert_eq!(
+            headers.get(header::FORWARDED).unwrap(),
+            "for=\"[2001:db8::1]\";host=\"example.com:8080\";proto=https"
+        );
+        assert_eq!(
+            headers.get(X_FORWARDED_FOR).unwrap(),
+            "10.0.0.1, 2001:db8::1"
+        );
+        assert_eq!(headers.get(X_FORWARDED_HOST).unwrap(), "example.com:8080");
+        assert_eq!(headers.get(X_FORWARDED_PROTO).unwrap(), "https");
+    }
+
+    #[tokio::test]
+    async fn load_balance() {
+        let a = start_upstream(make_sync(|_| "a")).await;
+        let b = start_upstream(make_sync(|_| "b")).await;
+        let cli = TestClient::new(Proxy::new([format!("http://{a}"), format!("http://{b}")]));
+
+        for expected in ["a", "b", "a", "b"] {
+            cli.get("/").send().await.assert_text(expected).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn passive_health_check() {
+        // reserve a port that refuses connections
+        let unavailable = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let available = start_upstream(echo).await;
+
+        let cli = TestClient::new(
+            Proxy::new([
+                format!("http://{unavailable}"),
+                format!("http://{available}"),
+            ])
+            .fail_timeout(Duration::from_secs(60)),
+        );
+
+        cli.get("/")
+            .send()
+            .await
+            .assert_status(StatusCode::BAD_GATEWAY);
+        for _ in 0..4 {
+            cli.get("/").send().await.assert_status_is_ok();
+        }
+    }
+
+    #[cfg(feature = "websocket")]
+    #[tokio::test]
+    async fn websocket() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        use crate::{IntoResponse, web::websocket::WebSocket};
+
+        #[handler(internal)]
+        async fn ws_echo(ws: WebSocket) -> impl IntoResponse {
+            ws.on_upgrade(|mut stream| async move {
+                while let Some(Ok(msg)) = stream.next().await {
+                    if stream.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+            })
+        }
+
+        let upstream = start_upstream(ws_echo).await;
+        let addr = start_upstream(Proxy::new([format!("http://{upstream}")])).await;
+
+        let (mut stream, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+            .await
+            .unwrap();
+        stream.send(Message::Text("hello".into())).await.unwrap();
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            Message::Text("hello".into())
+        );
+    }
+}
@@ -78,6 +78,7 @@ pub struct StaticFilesEndpoint {
     no_cache_index: bool,
     prefer_utf8: bool,
     redirect_to_slash: bool,
+    precompressed: bool,
 }
 
 impl StaticFilesEndpoint {
@@ -104,6 +105,7 @@ impl StaticFilesEndpoint {
             no_cache_index: false,
             prefer_utf8: true,
             redirect_to_slash: false,
+            precompressed: false,
         }
     }
 
@@ -170,6 +172,37 @@ impl StaticFilesEndpoint {
             ..self
         }
     }
+
+    /// Specifies whether to serve the precompressed versions of the files.
+    ///
+    /// If enabled, the `.zst`, `.br` or `.gz` version of the requested file,
+    /// such as `app.js.br` for `app.js`, is served instead when it exists and
+    /// the client accepts its encoding.
+    ///
+    /// Default is `false`.
+    #[must_use]
+    pub fn precompressed(self, value: bool) -> Self {
+        Self {
+            precompressed: value,
+            ..self
+        }
+    }
+
+    async fn create_response(
+        &self,
+        req: &Request,
+        path: &Path,
+        no_cache: bool,
+    ) -> Result<Response> {
+        let static_file = StaticFileRequest::from_request_without_body(req).await?;
+        if self.precompressed {
+            Ok(static_file.create_precompressed_response(path, self.prefer_utf8, no_cache)?)
+        } else {
+            Ok(static_file
+                .create_response(path, self.prefer_utf8, no_cache)?
+                .into_response())
+        }
+    }
 }
 
 impl Endpoint for StaticFilesEndpoint {
@@ -210,10 +243,9 @@ impl Endpoint for StaticFilesEndpoint {
                 if let Some(index_file) = &self.index_file {
                     let index_path = self.path.join(index_file);
                     if index_path.is_file() {
-                        return Ok(StaticFileRequest::from_request_without_body(&req)
-                            .await?
-                            .create_response(&index_path, self.prefer_utf8, self.no_cache_index)?
-                            .into_response());
+                        return self
+                            .create_response(&req, &index_path, self.no_cache_index)
+                            .await;
                     }
                 }
             }
@@ -221,10 +253,7 @@ impl Endpoint for StaticFilesEndpoint {
         }
 
         if file_path.is_file() {
-            Ok(StaticFileRequest::from_request_without_body(&req)
-                .await?
-                .create_response(&file_path, self.prefer_utf8, false)?
-                .into_response())
+            self.create_response(&req, &file_path, false).await
         } else {
             if self.redirect_to_slash
                 && !req.original_uri().path().ends_with('/')
@@ -240,10 +269,9 @@ impl Endpoint for StaticFilesEndpoint {
             if let Some(index_file) = &self.index_file {
                 let index_path = file_path.join(index_file);
                 if index_path.is_file() {
-                    return Ok(StaticFileRequest::from_request_without_body(&req)
-                        .await?
-                        .create_response(&index_path, self.prefer_utf8, self.no_cache_index)?
-                        .into_response());
+                    return self
+                        .create_response(&req, &index_path, self.no_cache_index)
+                        .await;
                 }
             }
 
@@ -4,6 +4,7 @@ use std::{
     convert::Infallible,
     error::Error as StdError,
     fmt::{self, Debug, Display, Formatter},
+    io::ErrorKind,
     string::FromUtf8Error,
 };
 
@@ -504,6 +505,36 @@ impl Error {
     pub fn set_error_message(&mut self, msg: impl Into<String>) {
         self.msg = Some(msg.into());
     }
+
+    /// Returns the [`ProblemDetails`] describing this error.
+    ///
+    /// If the error is a [`ProblemDetails`], it is returned as is. Otherwise,
+    /// the problem details are created from the status code and the message
+    /// of the error, and the members of the [`ProblemDetails`] attached with
+    /// [`Error::set_data`] take precedence.
+    pub fn problem_details(&self) -> ProblemDetails {
+        if let Some(problem) = self.downcast_ref::<ProblemDetails>() {
+            return problem.clone();
+        }
+
+        let status = self.status();
+        let mut problem = self
+            .data::<ProblemDetails>()
+            .cloned()
+            .unwrap_or_else(|| ProblemDetails::new(status));
+        problem.status = status.as_u16();
+        if problem.title.is_none() {
+            problem.title = status.canonical_reason().map(ToString::to_string);
+        }
+        if problem.detail.is_none() {
+            // errors created from a status code have no other message
+            let detail = self.to_string();
+            if detail != status.to_string() {
+                problem.detail = Some(detail);
+            }
+        }
+        problem
+    }
 }
 
 define_http_error!(
@@ -628,14 +659,184 @@ impl<T: IntoResponse> IntoResult<T> for T {
     }
 }
 
+/// A problem details object as defined by
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457), which is rendered as
+/// `application/problem+json`.
+///
+/// It can be returned as an error, or attached to any error with
+/// [`Error::set_data`] to add members to the problem details rendered by the
+/// [`ProblemJson`](crate::middleware::ProblemJson) middleware.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     Result, error::ProblemDetails, handler, http::StatusCode, test::TestClient,
+/// };
+///
+/// #[handler]
+/// fn index() -> Result<()> {
+///     Err(ProblemDetails::new(StatusCode::FORBIDDEN)
+///         .ty("https://example.com/probs/out-of-credit")
+///         .detail("Your current balance is 30, but that costs 50.")
+///         .extension("balance", 30)
+///         .into())
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = TestClient::new(index).get("/").send().await;
+/// resp.assert_status(StatusCode::FORBIDDEN);
+/// resp.assert_content_type("application/problem+json");
+/// resp.assert_json(serde_json::json!({
+///     "type": "https://example.com/probs/out-of-credit",
+///     "title": "Forbidden",
+///     "status": 403,
+///     "detail": "Your current balance is 30, but that costs 50.",
+///     "balance": 30,
+/// }))
+/// .await;
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProblemDetails {
+    /// A URI reference that identifies the problem type. (defaults to
+    /// `about:blank`)
+    #[serde(rename = "type", default = "ProblemDetails::default_type")]
+    pub ty: String,
+    /// A short, human-readable summary of the problem type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The HTTP status code.
+    pub status: u16,
+    /// A human-readable explanation specific to this occurrence of the
+    /// problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// A URI reference that identifies the specific occurrence of the
+    /// problem.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// The extension members.
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ProblemDetails {
+    /// The content type of problem details.
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    fn default_type() -> String {
+        "about:blank".to_string()
+    }
+
+    /// Create a `ProblemDetails` with the specified status code, whose title
+    /// is the canonical reason of the status code.
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            ty: Self::default_type(),
+            title: status.canonical_reason().map(ToString::to_string),
+            status: status.as_u16(),
+            detail: None,
+            instance: None,
+            extensions: Default::default(),
+        }
+    }
+
+    /// Sets the problem type.
+    #[must_use]
+    pub fn ty(self, ty: impl Into<String>) -> Self {
+        Self {
+            ty: ty.into(),
+            ..self
+        }
+    }
+
+    /// Sets the title.
+    #[must_use]
+    pub fn title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Sets the detail.
+    #[must_use]
+    pub fn detail(self, detail: impl Into<String>) -> Self {
+        Self {
+            detail: Some(detail.into()),
+            ..self
+        }
+    }
+
+    /// Sets the instance.
+    #[must_use]
+    pub fn instance(self, instance: impl Into<String>) -> Self {
+        Self {
+            instance: Some(instance.into()),
+            ..self
+        }
+    }
+
+    /// Adds an extension member.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value cannot be serialized to JSON.
+    #[must_use]
+    pub fn extension(mut self, name: impl Into<String>, value: impl serde::Serialize) -> Self {
+        let value = serde_json::to_value(value).expect("valid json value");
+        self.extensions.insert(name.into(), value);
+        self
+    }
+
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+impl Display for ProblemDetails {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (&self.detail, &self.title) {
+            (Some(detail), _) => f.write_str(detail),
+            (None, Some(title)) => f.write_str(title),
+            (None, None) => Display::fmt(&self.status_code(), f),
+        }
+    }
+}
+
+impl StdError for ProblemDetails {}
+
+impl ResponseError for ProblemDetails {
+    fn status(&self) -> StatusCode {
+        self.status_code()
+    }
+
+    fn as_response(&self) -> Response {
+        self.clone().into_response()
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let body = serde_json::to_vec(&self).expect("valid json value");
+        Response::builder()
+            .status(self.status_code())
+            .content_type(Self::CONTENT_TYPE)
+            .body(body)
+    }
+}
+
 macro_rules! define_simple_errors {
-    ($($(#[$docs:meta])* ($name:ident, $status:ident, $err_msg:literal);)*) => {
+    ($($(#[doc = $doc:literal])* $(#[cfg($cfg:meta)])? ($name:ident, $status:ident, $err_msg:literal);)*) => {
         $(
-        $(#[$docs])*
+        $(#[doc = $doc])*
+        $(#[cfg($cfg)])?
         #[derive(Debug, thiserror::Error, Copy, Clone, Eq, PartialEq)]
         #[error($err_msg)]
         pub struct $name;
 
+        $(#[cfg($cfg)])?
         impl ResponseError for $name {
             fn status(&self) -> StatusCode {
                 StatusCode::$status
@@ -654,6 +855,15 @@ define_simple_errors!(
 
     /// Error occurred in the router.
     (MethodNotAllowedError, METHOD_NOT_ALLOWED, "method not allowed");
+
+    /// The `If-Match` header of the request does not match the current entity
+    /// tag of the resource.
+    (PreconditionFailedError, PRECONDITION_FAILED, "precondition failed");
+
+    /// The request was not received from a TLS connection, so the
+    /// [`TlsInfo`](crate::web::TlsInfo) extractor fails.
+    #[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl-tls"))]
+    (MissingTlsInfoError, FORBIDDEN, "the request was not received from a tls connection");
 );
 
 /// A possible error value when reading the body.
@@ -681,6 +891,9 @@ impl ResponseError for ReadBodyError {
         match self {
             ReadBodyError::BodyHasBeenTaken => StatusCode::INTERNAL_SERVER_ERROR,
             ReadBodyError::Utf8(_) => StatusCode::BAD_REQUEST,
+            ReadBodyError::Io(err) if err.kind() == ErrorKind::TimedOut => {
+                StatusCode::REQUEST_TIMEOUT
+            }
             ReadBodyError::Io(_) => StatusCode::BAD_REQUEST,
             ReadBodyError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
         }
@@ -867,6 +1080,10 @@ pub enum ParseMultipartError {
     #[error("expect content type `multipart/form-data`")]
     ContentTypeRequired,
 
+    /// Too many fields.
+    #[error("too many fields, the limit is {0}")]
+    TooManyFields(usize),
+
     /// Parse error.
     #[error("parse: {0}")]
     Multipart(#[from] multer::Error),
@@ -883,11 +1100,31 @@ pub enum ParseMultipartError {
 #[cfg(feature = "multipart")]
 impl ResponseError for ParseMultipartError {
     fn status(&self) -> StatusCode {
+        fn is_size_exceeded(err: &multer::Error) -> bool {
+            matches!(
+                err,
+                multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. }
+            )
+        }
+
         match self {
             ParseMultipartError::InvalidContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             ParseMultipartError::ContentTypeRequired => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ParseMultipartError::TooManyFields(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ParseMultipartError::Multipart(err) if is_size_exceeded(err) => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
             ParseMultipartError::Multipart(_) => StatusCode::BAD_REQUEST,
             ParseMultipartError::Utf8(_) => StatusCode::BAD_REQUEST,
+            // the errors of the fields are wrapped by `Field::into_async_read`
+            ParseMultipartError::Io(err)
+                if err
+                    .get_ref()
+                    .and_then(|err| err.downcast_ref::<multer::Error>())
+                    .is_some_and(is_size_exceeded) =>
+            {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
             ParseMultipartError::Io(_) => StatusCode::BAD_REQUEST,
         }
     }
@@ -1036,6 +1273,32 @@ impl ResponseError for SizedLimitError {
     }
 }
 
+/// A possible error value occurred in the `Timeout` middleware.
+#[derive(Debug, thiserror::Error, Copy, Clone, Eq, PartialEq)]
+pub enum TimeoutError {
+    /// The endpoint did not respond in time.
+    #[error("request timed out")]
+    Endpoint(StatusCode),
+
+    /// Reading the request body timed out.
+    #[error("reading the request body timed out")]
+    ReadBody,
+
+    /// The response body did not produce the next chunk in time.
+    #[error("response body timed out")]
+    ResponseBody,
+}
+
+impl ResponseError for TimeoutError {
+    fn status(&self) -> StatusCode {
+        match self {
+            TimeoutError::Endpoint(status) => *status,
+            TimeoutError::ReadBody => StatusCode::REQUEST_TIMEOUT,
+            TimeoutError::ResponseBody => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
 /// A possible error value occurred when adding a route.
 #[derive(Debug, thiserror::Error, Eq, PartialEq)]
 pub enum RouteError {
@@ -1141,6 +1404,161 @@ impl ResponseError for RedisSessionError {
     }
 }
 
+/// A possible error value occurred when deal with SQL session storages.
+#[cfg(any(feature = "sqlite-session", feature = "postgres-session"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "sqlite-session", feature = "postgres-session")))
+)]
+#[derive(Debug, thiserror::Error)]
+pub enum SqlxSessionError {
+    /// Invalid table name.
+    #[error("invalid table name: {0}")]
+    InvalidTableName(String),
+
+    /// Sqlx error.
+    #[error("sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+}
+
+#[cfg(any(feature = "sqlite-session", feature = "postgres-session"))]
+impl ResponseError for SqlxSessionError {
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// A possible error value occurred in the `RateLimit` middleware.
+#[cfg(feature = "rate-limit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitError {
+    /// Too many requests
+    #[error("too many requests")]
+    TooManyRequests(crate::middleware::RateLimitDecision),
+
+    /// Redis error.
+    #[cfg(feature = "redis-rate-limit")]
+    #[error("redis: {0}")]
+    Redis(redis::RedisError),
+}
+
+#[cfg(feature = "rate-limit")]
+impl ResponseError for RateLimitError {
+    fn status(&self) -> StatusCode {
+        match self {
+            RateLimitError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            #[cfg(feature = "redis-rate-limit")]
+            RateLimitError::Redis(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn as_response(&self) -> Response {
+        let mut resp = self.to_string().into_response();
+        resp.set_status(self.status());
+        match self {
+            RateLimitError::TooManyRequests(decision) => {
+                crate::middleware::set_rate_limit_headers(resp.headers_mut(), decision);
+            }
+            #[cfg(feature = "redis-rate-limit")]
+            RateLimitError::Redis(_) => {}
+        }
+        resp
+    }
+}
+
+/// A possible error value occurred in the `Proxy` endpoint.
+#[cfg(feature = "proxy")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proxy")))]
+#[derive(Debug, thiserror::Error)]
+pub enum ProxyError {
+    /// Invalid request
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// Failed to send the request to the upstream server
+    #[error("upstream: {0}")]
+    Upstream(String),
+}
+
+#[cfg(feature = "proxy")]
+impl ResponseError for ProxyError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ProxyError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            ProxyError::Upstream(_) => StatusCode::BAD_GATEWAY,
+        }
+    }
+}
+
+/// A possible error value occurred in the `JwtAuth` middleware.
+#[cfg(feature = "jwt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+#[derive(Debug, thiserror::Error)]
+pub enum JwtError {
+    /// The request does not contain a bearer token.
+    #[error("missing bearer token")]
+    MissingToken,
+
+    /// The token is invalid.
+    #[error("invalid token: {0}")]
+    InvalidToken(jsonwebtoken::errors::Error),
+
+    /// The token is signed with an unknown key.
+    #[error("unknown key")]
+    UnknownKey,
+
+    /// Invalid key
+    #[error("invalid key: {0}")]
+    InvalidKey(jsonwebtoken::errors::Error),
+
+    /// Failed to fetch the JSON Web Key Set.
+    #[error("failed to fetch the json web key set: {0}")]
+    Jwks(String),
+
+    /// The request was not authenticated by the `JwtAuth` middleware.
+    #[error("missing claims")]
+    MissingClaims,
+
+    /// The claims cannot be deserialized.
+    #[error("invalid claims: {0}")]
+    InvalidClaims(serde_json::Error),
+}
+
+#[cfg(feature = "jwt")]
+impl ResponseError for JwtError {
+    fn status(&self) -> StatusCode {
+        match self {
+            JwtError::MissingToken
+            | JwtError::InvalidToken(_)
+            | JwtError::UnknownKey
+            | JwtError::InvalidClaims(_) => StatusCode::UNAUTHORIZED,
+            JwtError::InvalidKey(_) | JwtError::Jwks(_) | JwtError::MissingClaims => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    fn as_response(&self) -> Response {
+        let mut resp = self.to_string().into_response();
+        resp.set_status(self.status());
+        let challenge = match self {
+            JwtError::MissingToken => Some("Bearer"),
+            JwtError::InvalidToken(_) | JwtError::UnknownKey | JwtError::InvalidClaims(_) => {
+                Some("Bearer error=\"invalid_token\"")
+            }
+            _ => None,
+        };
+        if let Some(challenge) = challenge {
+            resp.headers_mut().insert(
+                http::header::WWW_AUTHENTICATE,
+                http::HeaderValue::from_static(challenge),
+            );
+        }
+        resp
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::{Error as IoError, ErrorKind};
@@ -1243,4 +1661,57 @@ mod tests {
             "my error message"
         );
     }
+
+    #[test]
+    fn test_problem_details() {
+        let err = Error::from_status(StatusCode::NOT_FOUND);
+        assert_eq!(
+            err.problem_details(),
+            ProblemDetails::new(StatusCode::NOT_FOUND)
+        );
+
+        let err = Error::from_string("missing name", StatusCode::BAD_REQUEST);
+        assert_eq!(
+            err.problem_details(),
+            ProblemDetails::new(StatusCode::BAD_REQUEST).detail("missing name")
+        );
+
+        let mut err = Error::from_string("missing name", StatusCode::BAD_REQUEST);
+        err.set_data(
+            ProblemDetails::new(StatusCode::BAD_REQUEST)
+                .ty("https://example.com/probs/validation")
+                .extension("field", "name"),
+        );
+        assert_eq!(
+            err.problem_details(),
+            ProblemDetails::new(StatusCode::BAD_REQUEST)
+                .ty("https://example.com/probs/validation")
+                .detail("missing name")
+                .extension("field", "name")
+        );
+
+        let problem = ProblemDetails::new(StatusCode::CONFLICT).detail("conflict");
+        let err = Error::from(problem.clone());
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(err.problem_details(), problem);
+    }
+
+    #[test]
+    fn test_problem_details_serde() {
+        let problem = ProblemDetails::new(StatusCode::FORBIDDEN)
+            .instance("/account/12345")
+            .extension("balance", 30);
+        let value = serde_json::json!({
+            "type": "about:blank",
+            "title": "Forbidden",
+            "status": 403,
+            "instance": "/account/12345",
+            "balance": 30,
+        });
+        assert_eq!(serde_json::to_value(&problem).unwrap(), value);
+        assert_eq!(
+            serde_json::from_value::<ProblemDetails>(value).unwrap(),
+            problem
+        );
+    }
 }
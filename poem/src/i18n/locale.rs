@@ -7,13 +7,17 @@ use unic_langid::LanguageIdentifier;
 use crate::{
     FromRequest, Request, RequestBody, Result,
     error::I18NError,
-    i18n::{I18NArgs, I18NBundle, I18NResources},
+    i18n::{I18NArgs, I18NBundle, I18NResources, LocaleSource},
 };
 
 type LanguageArray = SmallVec<[LanguageIdentifier; 8]>;
 
-/// An extractor that parses the `Accept-Language` header and negotiates
-/// language bundles.
+/// An extractor that negotiates language bundles according to the
+/// [`LocaleSource`]s of the [`I18NResources`], which is the `Accept-Language`
+/// header by default.
+///
+/// If the [`Localization`](crate::i18n::Localization) middleware is used, the
+/// locale resolved by it is returned.
 ///
 /// # Example
 ///
@@ -59,9 +63,11 @@ type LanguageArray = SmallVec<[LanguageIdentifier; 8]>;
 /// resp.assert_text("你好世界！").await;
 /// # });
 /// ```
+#[derive(Clone)]
 pub struct Locale {
     bundle: I18NBundle,
     accept_languages: LanguageArray,
+    source: Option<LocaleSource>,
 }
 
 impl Locale {
@@ -108,14 +114,30 @@ impl Locale {
     pub fn accept_languages(&self) -> &[unic_langid::LanguageIdentifier] {
         &self.accept_languages
     }
-}
 
-impl<'a> FromRequest<'a> for Locale {
-    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
-        let resources = req
-            .extensions()
-            .get::<I18NResources>()
-            .expect("To use the `Locale` extractor, the `I18NResources` data is required.");
+    /// Returns the resolved language, which is the default language if none
+    /// of the requested languages is available.
+    pub fn language(&self) -> Option<&LanguageIdentifier> {
+        self.bundle.language()
+    }
+
+    /// Returns the source that provided the most preferred language, or
+    /// `None` if no source provided any language.
+    pub fn source(&self) -> Option<&LocaleSource> {
+        self.source.as_ref()
+    }
+
+    pub(crate) fn resolve(resources: &I18NResources, req: &Request) -> Self {
+        let mut languages = Vec::new();
+        let mut source = None;
+
+        for locale_source in resources.locale_sources() {
+            let res = locale_source.languages(resources, req);
+            if source.is_none() && !res.is_empty() {
+                source = Some(locale_source.clone());
+            }
+            languages.extend(res);
+        }
 
         let accept_languages = req
             .headers()
@@ -124,14 +146,29 @@ impl<'a> FromRequest<'a> for Locale {
             .map(parse_accept_languages)
             .unwrap_or_default();
 
-        Ok(Self {
-            bundle: resources.negotiate_languages(&accept_languages),
+        Self {
+            bundle: resources.negotiate_languages(&languages),
             accept_languages,
-        })
+            source,
+        }
+    }
+}
+
+impl<'a> FromRequest<'a> for Locale {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        if let Some(locale) = req.extensions().get::<Locale>() {
+            return Ok(locale.clone());
+        }
+
+        let resources = req
+            .extensions()
+            .get::<I18NResources>()
+            .expect("To use the `Locale` extractor, the `I18NResources` data is required.");
+        Ok(Self::resolve(resources, req))
     }
 }
 
-fn parse_accept_languages(value: &str) -> LanguageArray {
+pub(crate) fn parse_accept_languages(value: &str) -> LanguageArray {
     let mut languages = SmallVec::<[_; 8]>::new();
 
     for s in value.split(',').map(str::trim) {
@@ -0,0 +1,344 @@
+#[cfg(feature = "cookie")]
+use std::time::Duration;
+
+use http::{HeaderValue, header};
+
+#[cfg(feature = "cookie")]
+use crate::web::cookie::{Cookie, CookieJar, SameSite};
+use crate::{
+    Endpoint, Middleware, Request, Response, Result,
+    i18n::{I18NResources, Locale, LocaleSource},
+};
+
+/// Middleware that resolves the [`Locale`] of the requests.
+///
+/// The resolved locale is stored in the request extensions, so the [`Locale`]
+/// extractor returns it without negotiating the languages again, and the
+/// [`I18NResources`] data is not required. The response gets the
+/// `Content-Language` header with the resolved language unless the endpoint
+/// sets it, and the `Vary` header with the request headers that the resolution
+/// depends on. These headers are also added to the error responses.
+///
+/// If a cookie name is specified with `Localization::persist_cookie`, the
+/// language provided by the query parameter or the path prefix is stored in
+/// that cookie, so that the user's choice is kept for the next requests when
+/// the resources use the same `LocaleSource::Cookie`. Both require the
+/// `cookie` feature.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Route, handler,
+///     http::header,
+///     i18n::{I18NResources, Locale, LocaleSource, Localization},
+///     test::TestClient,
+/// };
+///
+/// let resources = I18NResources::builder()
+///     .add_ftl("en-US", "hello-world = hello world!")
+///     .add_ftl("fr", "hello-world = bonjour le monde !")
+///     .locale_sources([
+///         LocaleSource::Query("lang".to_string()),
+///         LocaleSource::AcceptLanguage,
+///     ])
+///     .build()
+///     .unwrap();
+///
+/// #[handler]
+/// async fn index(locale: Locale) -> String {
+///     locale
+///         .text("hello-world")
+///         .unwrap_or_else(|_| "error".to_string())
+/// }
+///
+/// let app = Route::new()
+///     .at("/", index)
+///     .with(Localization::new(resources));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").query("lang", &"fr").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_header(header::CONTENT_LANGUAGE, "fr");
+/// resp.assert_text("bonjour le monde !").await;
+///
+/// let resp = cli
+///     .get("/")
+///     .header(header::ACCEPT_LANGUAGE, "fr")
+///     .send()
+///     .await;
+/// resp.assert_header(header::VARY, "accept-language");
+/// resp.assert_text("bonjour le monde !").await;
+/// # });
+/// ```
+pub struct Localization {
+    resources: I18NResources,
+    #[cfg(feature = "cookie")]
+    cookie_name: Option<String>,
+    #[cfg(feature = "cookie")]
+    cookie_max_age: Duration,
+}
+
+impl Localization {
+    /// Create a `Localization` middleware.
+    pub fn new(resources: I18NResources) -> Self {
+        Self {
+            resources,
+            #[cfg(feature = "cookie")]
+            cookie_name: None,
+            #[cfg(feature = "cookie")]
+            cookie_max_age: Duration::from_secs(60 * 60 * 24 * 365),
+        }
+    }
+
+    /// Stores the language chosen by the query parameter or the path prefix
+    /// in the cookie with the specified name.
+    #[cfg(feature = "cookie")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+    #[must_use]
+    pub fn persist_cookie(self, name: impl Into<String>) -> Self {
+        Self {
+            cookie_name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Sets the `Max-Age` of the cookie that stores the language.
+    ///
+    /// Default is one year.
+    #[cfg(feature = "cookie")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+    #[must_use]
+    pub fn cookie_max_age(self, max_age: Duration) -> Self {
+        Self {
+            cookie_max_age: max_age,
+            ..self
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for Localization {
+    type Output = LocalizationEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        let mut vary = Vec::new();
+        for source in self.resources.locale_sources() {
+            let name = match source {
+                #[cfg(feature = "cookie")]
+                LocaleSource::Cookie(_) => "cookie",
+                LocaleSource::AcceptLanguage => "accept-language",
+                LocaleSource::Query(_) | LocaleSource::PathPrefix => continue,
+            };
+            if !vary.contains(&name) {
+                vary.push(name);
+            }
+        }
+
+        LocalizationEndpoint {
+            inner: ep,
+            resources: self.resources.clone(),
+            #[cfg(feature = "cookie")]
+            cookie_name: self.cookie_name.clone(),
+            #[cfg(feature = "cookie")]
+            cookie_max_age: self.cookie_max_age,
+            vary: (!vary.is_empty()).then(|| HeaderValue::from_str(&vary.join(", ")).unwrap()),
+        }
+    }
+}
+
+/// Endpoint for the `Localization` middleware.
+pub struct LocalizationEndpoint<E> {
+    inner: E,
+    resources: I18NResources,
+    #[cfg(feature = "cookie")]
+    cookie_name: Option<String>,
+    #[cfg(feature = "cookie")]
+    cookie_max_age: Duration,
+    vary: Option<HeaderValue>,
+}
+
+#[cfg(feature = "cookie")]
+impl<E> LocalizationEndpoint<E> {
+    fn persisted_cookie(&self, req: &Request, locale: &Locale) -> Option<Cookie> {
+        let cookie_name = self.cookie_name.as_ref()?;
+        if !matches!(
+            locale.source(),
+            Some(LocaleSource::Query(_) | LocaleSource::PathPrefix)
+        ) {
+            return None;
+        }
+
+        let language = locale.language()?.to_string();
+        let current = CookieJar::extract_from_headers(req.headers()).get(cookie_name);
+        if current.is_some_and(|cookie| cookie.value_str() == language) {
+            return None;
+        }
+
+        let mut cookie = Cookie::new_with_str(cookie_name, language);
+        cookie.set_path("/");
+        cookie.set_max_age(self.cookie_max_age);
+        cookie.set_same_site(SameSite::Lax);
+        Some(cookie)
+    }
+}
+
+impl<E: Endpoint> Endpoint for LocalizationEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        let locale = Locale::resolve(&self.resources, &req);
+        #[cfg(feature = "cookie")]
+        let cookie = self.persisted_cookie(&req, &locale);
+        let language = locale.language().map(ToString::to_string);
+
+        req.extensions_mut().insert(self.resources.clone());
+        req.extensions_mut().insert(locale);
+        let mut resp = self.inner.get_response(req).await;
+
+        let headers = resp.headers_mut();
+        if !headers.contains_key(header::CONTENT_LANGUAGE) {
+            if let Some(language) =
+                language.and_then(|language| HeaderValue::from_str(&language).ok())
+            {
+                headers.insert(header::CONTENT_LANGUAGE, language);
+            }
+        }
+        if let Some(vary) = &self.vary {
+            headers.append(header::VARY, vary.clone());
+        }
+        #[cfg(feature = "cookie")]
+        if let Some(cookie) = cookie {
+            if let Ok(value) = HeaderValue::from_str(&cookie.to_string()) {
+                headers.append(header::SET_COOKIE, value);
+            }
+        }
+
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EndpointExt, Route, handler, test::TestClient};
+
+    #[handler(internal)]
+    fn index(locale: Locale) -> String {
+        locale.text("hello").unwrap()
+    }
+
+    fn resources() -> I18NResources {
+        I18NResources::builder()
+            .add_ftl("en-US", "hello = hello")
+            .add_ftl("fr", "hello = bonjour")
+            .add_ftl("zh-CN", "hello = 你好")
+            .locale_sources([
+                LocaleSource::Query("lang".to_string()),
+                #[cfg(feature = "cookie")]
+                LocaleSource::Cookie("lang".to_string()),
+                LocaleSource::PathPrefix,
+                LocaleSource::AcceptLanguage,
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[cfg(feature = "cookie")]
+    #[tokio::test]
+    async fn resolve() {
+        let app = Route::new()
+            .at("/", index)
+            .at("/:lang", index)
+            .with(Localization::new(resources()));
+        let cli = TestClient::new(app);
+
+        let resp = cli.get("/").send().await;
+        resp.assert_header(header::CONTENT_LANGUAGE, "en-US");
+        resp.assert_header(header::VARY, "cookie, accept-language");
+        resp.assert_text("hello").await;
+
+        for (path, query, cookie, accept_language, expected) in [
+            ("/", None, None, Some("zh-CN"), "你好"),
+            ("/fr", None, None, Some("zh-CN"), "bonjour"),
+            ("/fr", None, Some("lang=zh-CN"), Some("en-US"), "你好"),
+            ("/", Some("fr"), Some("lang=zh-CN"), None, "bonjour"),
+            ("/", Some("de"), Some("lang=zh-CN"), None, "你好"),
+            ("/", Some("de"), None, Some("de, fr;q=0.5"), "bonjour"),
+        ] {
+            let mut req = cli.get(path);
+            if let Some(query) = query {
+                req = req.query("lang", &query);
+            }
+            #[cfg(feature = "cookie")]
+            if let Some(cookie) = cookie {
+                req = req.header(header::COOKIE, cookie);
+            }
+            if let Some(accept_language) = accept_language {
+                req = req.header(header::ACCEPT_LANGUAGE, accept_language);
+            }
+            req.send().await.assert_text(expected).await;
+        }
+    }
+
+    #[cfg(feature = "cookie")]
+    #[tokio::test]
+    async fn persist_cookie() {
+        let app = Route::new()
+            .at("/", index)
+            .at("/:lang", index)
+            .with(Localization::new(resources()).persist_cookie("lang"));
+        let cli = TestClient::new(app).with_cookie_jar();
+
+        let resp = cli
+            .get("/")
+            .header(header::ACCEPT_LANGUAGE, "zh-CN")
+            .send()
+            .await;
+        resp.assert_text("你好").await;
+        assert!(cli.cookie_jar().unwrap().get("lang").is_none());
+
+        cli.get("/")
+            .query("lang", &"fr")
+            .send()
+            .await
+            .assert_text("bonjour")
+            .await;
+        assert_eq!(
+            cli.cookie_jar().unwrap().get("lang").unwrap().value_str(),
+            "fr"
+        );
+        cli.get("/").send().await.assert_text("bonjour").await;
+
+        // the cookie takes precedence over the path prefix
+        cli.get("/zh-CN").send().await.assert_text("bonjour").await;
+
+        let resp = cli.get("/").query("lang", &"zh-CN").send().await;
+        resp.assert_header(header::CONTENT_LANGUAGE, "zh-CN");
+        resp.assert_text("你好").await;
+        assert_eq!(
+            cli.cookie_jar().unwrap().get("lang").unwrap().value_str(),
+            "zh-CN"
+        );
+        cli.get("/").send().await.assert_text("你好").await;
+    }
+
+    #[tokio::test]
+    async fn error_response() {
+        let localization = Localization::new(resources());
+        #[cfg(feature = "cookie")]
+        let localization = localization.persist_cookie("lang");
+        let cli = TestClient::new(Route::new().at("/", index).with(localization));
+
+        let resp = cli.get("/missing").query("lang", &"fr").send().await;
+        resp.assert_status(http::StatusCode::NOT_FOUND);
+        resp.assert_header(header::CONTENT_LANGUAGE, "fr");
+        #[cfg(feature = "cookie")]
+        {
+            resp.assert_header(header::VARY, "cookie, accept-language");
+            resp.cookie("lang").assert_value("fr");
+        }
+        #[cfg(not(feature = "cookie"))]
+        resp.assert_header(header::VARY, "accept-language");
+    }
+}
@@ -58,10 +58,21 @@
 //! # Use extractor
 //!
 //! See also: [`crate::i18n::Locale`]
+//!
+//! # Locale resolution
+//!
+//! By default, the languages are negotiated according to the
+//! `Accept-Language` header. Use
+//! [`I18NResourcesBuilder::locale_sources`] to also read them from a query
+//! parameter, a cookie or the path prefix, and the
+//! [`Localization`](crate::i18n::Localization) middleware to set the
+//! `Content-Language` header and persist the user's choice in a cookie.
 
 mod args;
 mod locale;
+mod middleware;
 mod resources;
+mod source;
 
 pub use fluent_langneg::NegotiationStrategy;
 pub use unic_langid;
@@ -69,5 +80,7 @@ pub use unic_langid;
 pub use self::{
     args::I18NArgs,
     locale::Locale,
+    middleware::{Localization, LocalizationEndpoint},
     resources::{I18NBundle, I18NResources, I18NResourcesBuilder},
+    source::LocaleSource,
 };
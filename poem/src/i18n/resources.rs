@@ -16,13 +16,14 @@ type FluentBundle = fluent::bundle::FluentBundle<FluentResource, IntlLangMemoize
 
 use fluent_langneg::NegotiationStrategy;
 
-use crate::i18n::I18NArgs;
+use crate::i18n::{I18NArgs, LocaleSource};
 
 struct InnerResources {
     available_languages: Vec<LanguageIdentifier>,
     bundles: HashMap<LanguageIdentifier, Arc<FluentBundle>>,
     default_language: LanguageIdentifier,
     strategy: NegotiationStrategy,
+    sources: Vec<LocaleSource>,
 }
 
 /// I18N resources builder.
@@ -31,6 +32,7 @@ pub struct I18NResourcesBuilder {
     resources: Vec<(String, String)>,
     default_language: LanguageIdentifier,
     strategy: NegotiationStrategy,
+    sources: Vec<LocaleSource>,
 }
 
 impl I18NResourcesBuilder {
@@ -106,6 +108,36 @@ impl I18NResourcesBuilder {
         self
     }
 
+    /// Sets the sources of the languages requested by the client, in the
+    /// order of precedence.
+    ///
+    /// The languages from all the sources are negotiated together, so the
+    /// later sources are used as fallbacks.
+    ///
+    /// Default is `[LocaleSource::AcceptLanguage]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::i18n::{I18NResources, LocaleSource};
+    ///
+    /// let resources = I18NResources::builder()
+    ///     .add_ftl("en-US", "hello-world = Hello world!")
+    ///     .add_ftl("fr", "hello-world = Bonjour le monde !")
+    ///     .locale_sources([
+    ///         LocaleSource::Query("lang".to_string()),
+    ///         LocaleSource::PathPrefix,
+    ///         LocaleSource::AcceptLanguage,
+    ///     ])
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[must_use]
+    pub fn locale_sources(mut self, sources: impl IntoIterator<Item = LocaleSource>) -> Self {
+        self.sources = sources.into_iter().collect();
+        self
+    }
+
     /// Consumes this builder and returns a [`I18NResources`] object.
     pub fn build(self) -> Result<I18NResources, I18NError> {
         let mut bundles = HashMap::new();
@@ -135,6 +167,7 @@ impl I18NResourcesBuilder {
                     .collect(),
                 default_language: self.default_language,
                 strategy: self.strategy,
+                sources: self.sources,
             }),
         })
     }
@@ -195,9 +228,27 @@ impl I18NResources {
             resources: vec![],
             default_language: langid!("en-US"),
             strategy: NegotiationStrategy::Filtering,
+            sources: vec![LocaleSource::AcceptLanguage],
         }
     }
 
+    /// Returns the sources of the languages requested by the client.
+    pub fn locale_sources(&self) -> &[LocaleSource] {
+        &self.inner.sources
+    }
+
+    /// Returns `true` if the resources contain a language that matches the
+    /// specified language.
+    pub(crate) fn is_available(&self, language: &LanguageIdentifier) -> bool {
+        !fluent_langneg::negotiate_languages(
+            &[language],
+            &self.inner.available_languages,
+            None,
+            NegotiationStrategy::Filtering,
+        )
+        .is_empty()
+    }
+
     /// Negotiate the language according to the input language id list and
     /// return the [`I18NBundle`].
     pub fn negotiate_languages(&self, languages: &[impl AsRef<LanguageIdentifier>]) -> I18NBundle {
@@ -219,6 +270,7 @@ impl I18NResources {
 }
 
 /// A collection of localization messages.
+#[derive(Clone)]
 pub struct I18NBundle(SmallVec<[Arc<FluentBundle>; 8]>);
 
 impl I18NBundle {
@@ -235,6 +287,11 @@ impl I18NBundle {
         Err(I18NError::FluentMessageNotFound { id: id.to_string() })
     }
 
+    /// Returns the most preferred language of this bundle.
+    pub fn language(&self) -> Option<&LanguageIdentifier> {
+        self.0.first().and_then(|bundle| bundle.locales.first())
+    }
+
     /// Gets the text with arguments.
     ///
     /// # Example
@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use http::header;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    Request,
+    i18n::{I18NResources, locale::parse_accept_languages},
+};
+
+/// A source of the languages requested by the client.
+///
+/// The sources are tried in the order specified by
+/// [`I18NResourcesBuilder::locale_sources`](crate::i18n::I18NResourcesBuilder::locale_sources).
+/// The query parameter, the cookie and the path prefix only provide a language
+/// if it is available in the resources.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LocaleSource {
+    /// The query parameter with the specified name, such as `lang` for
+    /// `/about?lang=fr`.
+    Query(String),
+    /// The cookie with the specified name.
+    #[cfg(feature = "cookie")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+    Cookie(String),
+    /// The first segment of the path, such as `fr` for `/fr/about`.
+    PathPrefix,
+    /// The `Accept-Language` header.
+    AcceptLanguage,
+}
+
+impl LocaleSource {
+    /// Returns the languages provided by this source, with the most
+    /// preferred language first.
+    pub(crate) fn languages(
+        &self,
+        resources: &I18NResources,
+        req: &Request,
+    ) -> Vec<LanguageIdentifier> {
+        let value = match self {
+            LocaleSource::Query(name) => req.uri().query().and_then(|query| {
+                serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+                    .ok()?
+                    .into_iter()
+                    .find_map(|(key, value)| (key == *name).then_some(value))
+            }),
+            #[cfg(feature = "cookie")]
+            LocaleSource::Cookie(name) => {
+                crate::web::cookie::CookieJar::extract_from_headers(req.headers())
+                    .get(name)
+                    .map(|cookie| cookie.value_str().to_string())
+            }
+            LocaleSource::PathPrefix => req
+                .uri()
+                .path()
+                .trim_start_matches('/')
+                .split('/')
+                .next()
+                .map(ToString::to_string),
+            LocaleSource::AcceptLanguage => {
+                return req
+                    .headers()
+                    .get(header::ACCEPT_LANGUAGE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| parse_accept_languages(value).into_vec())
+                    .unwrap_or_default();
+            }
+        };
+
+        value
+            .filter(|value| !value.is_empty())
+            .and_then(|value| LanguageIdentifier::from_str(&value).ok())
+            .filter(|language| resources.is_available(language))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unic_langid::langids;
+
+    use super::*;
+
+    #[test]
+    fn languages() {
+        let resources = I18NResources::builder()
+            .add_ftl("en-US", "hello = hello")
+            .add_ftl("fr", "hello = bonjour")
+            .build()
+            .unwrap();
+        let req = Request::builder()
+            .uri_str("/fr/about?lang=en-US&lang=fr")
+            .header(header::COOKIE, "lang=de; locale=fr")
+            .header(header::ACCEPT_LANGUAGE, "de;q=0.5, en")
+            .finish();
+
+        for (source, languages) in [
+            (LocaleSource::Query("lang".to_string()), langids!("en-US")),
+            (LocaleSource::Query("locale".to_string()), vec![]),
+            (LocaleSource::PathPrefix, langids!("fr")),
+            (LocaleSource::AcceptLanguage, langids!("en", "de")),
+        ] {
+            assert_eq!(source.languages(&resources, &req), languages);
+        }
+
+        #[cfg(feature = "cookie")]
+        for (source, languages) in [
+            (LocaleSource::Cookie("lang".to_string()), vec![]),
+            (LocaleSource::Cookie("locale".to_string()), langids!("fr")),
+        ] {
+            assert_eq!(source.languages(&resources, &req), languages);
+        }
+
+        let req = Request::builder().uri_str("/about").finish();
+        assert!(
+            LocaleSource::PathPrefix
+                .languages(&resources, &req)
+                .is_empty()
+        );
+    }
+}
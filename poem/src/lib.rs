@@ -231,6 +231,7 @@
 //! |Feature           |Description                     |
 //! |------------------|--------------------------------|
 //! | server | Server and listener APIs(enable by default) |
+//! |cache        | Support for caching responses |
 //! |compression  | Support decompress request body and compress response body |
 //! |cookie            | Support for Cookie             |
 //! |csrf | Support for Cross-Site Request Forgery (CSRF) protection |
@@ -239,9 +240,16 @@
 //! |openssl-tls        | Support for HTTP server over TLS with [`openssl-tls`](https://crates.io/crates/openssl)  |
 //! |opentelemetry     | Support for opentelemetry    |
 //! |prometheus        | Support for Prometheus       |
+//! |proxy             | Support for reverse proxy endpoint |
 //! |redis-session     | Support for RedisSession     |
+//! |sqlite-session    | Support for session storage with SQLite |
+//! |postgres-session  | Support for session storage with PostgreSQL |
+//! |rate-limit        | Support for rate limiting    |
+//! |redis-rate-limit  | Support for rate limiting with Redis |
 //! |rustls            | Support for HTTP server over TLS with [`rustls`](https://crates.io/crates/rustls)  |
+//! |http3             | Support for HTTP/3 server over QUIC with [`quinn`](https://crates.io/crates/quinn) |
 //! |session           | Support for session    |
+//! |socket-activation | Support for systemd socket activation and handing off listeners to a new process |
 //! |sse               | Support Server-Sent Events (SSE)       |
 //! |tempfile          | Support for [`tempfile`](https://crates.io/crates/tempfile) |
 //! |test              | Test utilities to test your endpoints. |
@@ -250,6 +258,7 @@
 //! | anyhow        | Integrate with the [`anyhow`](https://crates.io/crates/anyhow) crate. |
 //! | eyre06        | Integrate with version 0.6.x of the [`eyre`](https://crates.io/crates/eyre) crate. |
 //! | i18n          | Support for internationalization |
+//! | jwt           | Support for JSON Web Token authentication |
 //! | acme-native-roots | Support for ACME(Automatic Certificate Management Environment) |
 //! | acme-webpki-roots | Support for ACME using webpki TLS roots rather than native TLS roots |
 //! | tokio-metrics | Integrate with the [`tokio-metrics`](https://crates.io/crates/tokio-metrics) crate. |
@@ -22,7 +22,7 @@ use x509_parser::prelude::{FromDer, X509Certificate};
 
 use crate::{
     listener::{
-        Acceptor, HandshakeStream, Listener,
+        Acceptor, ConnectionExtensions, HandshakeStream, Listener,
         acme::{
             AutoCert, ChallengeType, Http01TokensMap,
             client::AcmeClient,
@@ -30,7 +30,7 @@ use crate::{
             resolver::{ACME_TLS_ALPN_NAME, ResolveServerCert},
         },
     },
-    web::{LocalAddr, RemoteAddr},
+    web::{LocalAddr, RemoteAddr, TlsInfo},
 };
 
 pub(crate) async fn auto_cert_acceptor<T: Listener>(
@@ -218,9 +218,28 @@ impl<T: Acceptor> Acceptor for AutoCertAcceptor<T> {
         self.inner.local_addr()
     }
 
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> IoResult<Vec<crate::listener::ListenerFd>> {
+        self.inner.listener_fds()
+    }
+
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        io.extensions().clone()
+    }
+
     async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
         let (stream, local_addr, remote_addr, _) = self.inner.accept().await?;
-        let stream = HandshakeStream::new(self.acceptor.accept(stream));
+        let extensions = T::connection_extensions(&stream);
+        let handshake = self.acceptor.accept(stream);
+        let fut = {
+            let extensions = extensions.clone();
+            async move {
+                let stream = handshake.await?;
+                extensions.insert(TlsInfo::from_rustls(stream.get_ref().1));
+                Ok(stream)
+            }
+        };
+        let stream = HandshakeStream::new(fut, extensions);
         Ok((stream, local_addr, remote_addr, Scheme::HTTPS))
     }
 }
@@ -7,7 +7,7 @@ use http::uri::Scheme;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf, Result as IoResult};
 
 use crate::{
-    listener::{Acceptor, Listener},
+    listener::{Acceptor, ConnectionExtensions, Listener},
     web::{LocalAddr, RemoteAddr},
 };
 
@@ -58,6 +58,27 @@ impl<A: Acceptor, B: Acceptor> Acceptor for Combined<A, B> {
             }
         }
     }
+
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        match io {
+            CombinedStream::A(a) => A::connection_extensions(a),
+            CombinedStream::B(b) => B::connection_extensions(b),
+        }
+    }
+
+    #[cfg(feature = "http3")]
+    fn take_http3_acceptors(&mut self) -> Vec<crate::listener::Http3Acceptor> {
+        let mut acceptors = self.a.take_http3_acceptors();
+        acceptors.extend(self.b.take_http3_acceptors());
+        acceptors
+    }
+
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> IoResult<Vec<crate::listener::ListenerFd>> {
+        let mut fds = self.a.listener_fds()?;
+        fds.extend(self.b.listener_fds()?);
+        Ok(fds)
+    }
 }
 
 /// A IO stream for CombinedAcceptor.
@@ -8,6 +8,8 @@ use std::{
 use futures_util::{FutureExt, future::BoxFuture};
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf, Result};
 
+use crate::listener::ConnectionExtensions;
+
 enum State<S> {
     Handshaking(BoxFuture<'static, Result<S>>),
     Ready(S),
@@ -17,17 +19,24 @@ enum State<S> {
 /// A handshake stream for tls.
 pub struct HandshakeStream<S> {
     state: State<S>,
+    extensions: ConnectionExtensions,
 }
 
 impl<S> HandshakeStream<S> {
-    pub(crate) fn new<F>(handshake: F) -> Self
+    pub(crate) fn new<F>(handshake: F, extensions: ConnectionExtensions) -> Self
     where
         F: Future<Output = Result<S>> + Send + 'static,
     {
         Self {
             state: State::Handshaking(handshake.boxed()),
+            extensions,
         }
     }
+
+    /// Returns the extensions of the connection.
+    pub(crate) fn extensions(&self) -> &ConnectionExtensions {
+        &self.extensions
+    }
 }
 
 impl<S> AsyncRead for HandshakeStream<S>
@@ -0,0 +1,459 @@
+use std::{io::Error as IoError, sync::Arc};
+
+use bytes::{Buf, Bytes};
+use futures_util::{StreamExt, stream::BoxStream};
+use h3::server::RequestResolver;
+use http::{HeaderValue, uri::Scheme};
+use quinn::crypto::rustls::QuicServerConfig;
+use tokio::{
+    io::Result as IoResult,
+    net::{ToSocketAddrs, lookup_host},
+    task::JoinSet,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    Body, Endpoint, Request, RequestParts, Response,
+    endpoint::DynEndpoint,
+    listener::{Acceptor, BoxIo, IntoTlsConfigStream, Listener, RustlsConfig},
+    web::{LocalAddr, RemoteAddr},
+};
+
+/// A listener that serves HTTP/3 over [`QUIC`](https://www.rfc-editor.org/rfc/rfc9000).
+///
+/// HTTP/3 requests are not exchanged over a byte stream, so the acceptor of
+/// this listener never yields connections through [`Acceptor::accept`].
+/// Instead, the [`Server`](crate::Server) serves it separately, which also
+/// means it can be combined with other listeners.
+///
+/// When it is combined with a TLS listener, the HTTPS responses served by the
+/// TLS listener advertise the HTTP/3 endpoint with the `Alt-Svc` header.
+///
+/// # Example
+///
+/// ```no_run
+/// use poem::listener::{Http3Listener, Listener, RustlsCertificate, RustlsConfig, TcpListener};
+///
+/// # let cert_bytes: Vec<u8> = todo!();
+/// # let key_bytes: Vec<u8> = todo!();
+/// let config = || {
+///     RustlsConfig::new().fallback(
+///         RustlsCertificate::new()
+///             .cert(cert_bytes.clone())
+///             .key(key_bytes.clone()),
+///     )
+/// };
+/// let listener = TcpListener::bind("0.0.0.0:443")
+///     .rustls(config())
+///     .combine(Http3Listener::bind("0.0.0.0:443", config()));
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "http3")))]
+pub struct Http3Listener<T, S> {
+    addr: T,
+    config_stream: S,
+}
+
+impl<T, S> Http3Listener<T, S> {
+    /// Binds to the provided UDP address, and returns a [`Http3Listener`].
+    pub fn bind(addr: T, config_stream: S) -> Self {
+        Self {
+            addr,
+            config_stream,
+        }
+    }
+}
+
+impl<T, S> Listener for Http3Listener<T, S>
+where
+    T: ToSocketAddrs + Send,
+    S: IntoTlsConfigStream<RustlsConfig>,
+{
+    type Acceptor = Http3Acceptor;
+
+    async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
+        let addr = lookup_host(self.addr)
+            .await?
+            .next()
+            .ok_or_else(|| IoError::other("could not resolve to any address"))?;
+        let mut config_stream = self.config_stream.into_stream()?.boxed();
+        let config = config_stream
+            .next()
+            .await
+            .ok_or_else(|| IoError::other("no valid tls config."))?;
+        let endpoint = quinn::Endpoint::server(create_quic_server_config(&config)?, addr)?;
+        let local_addr = LocalAddr(endpoint.local_addr()?.into());
+
+        Ok(Http3Acceptor {
+            endpoint: Some(endpoint),
+            local_addr,
+            config_stream: Some(config_stream),
+        })
+    }
+}
+
+fn create_quic_server_config(config: &RustlsConfig) -> IoResult<quinn::ServerConfig> {
+    let mut server_config = config.create_server_config()?;
+    server_config.alpn_protocols = vec![b"h3".to_vec()];
+    let crypto = QuicServerConfig::try_from(server_config).map_err(IoError::other)?;
+    Ok(quinn::ServerConfig::with_crypto(Arc::new(crypto)))
+}
+
+/// A acceptor that accepts HTTP/3 connections.
+#[cfg_attr(docsrs, doc(cfg(feature = "http3")))]
+pub struct Http3Acceptor {
+    /// `None` once the endpoint has been taken by
+    /// [`Acceptor::take_http3_acceptors`].
+    endpoint: Option<quinn::Endpoint>,
+    local_addr: LocalAddr,
+    config_stream: Option<BoxStream<'static, RustlsConfig>>,
+}
+
+impl Http3Acceptor {
+    /// Returns the value of the `Alt-Svc` header that advertises the
+    /// specified acceptors.
+    pub(crate) fn alt_svc(acceptors: &[Http3Acceptor]) -> Option<HeaderValue> {
+        let value = acceptors
+            .iter()
+            .filter_map(|acceptor| acceptor.local_addr.as_socket_addr())
+            .map(|addr| format!("h3=\":{}\"; ma=86400", addr.port()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        HeaderValue::try_from(value)
+            .ok()
+            .filter(|value| !value.is_empty())
+    }
+
+    pub(crate) async fn serve(
+        mut self,
+        ep: Arc<dyn DynEndpoint<Output = Response>>,
+        shutdown_token: CancellationToken,
+    ) {
+        let Some(endpoint) = self.endpoint.take() else {
+            return;
+        };
+
+        if let Some(mut config_stream) = self.config_stream.take() {
+            let endpoint = endpoint.clone();
+            let shutdown_token = shutdown_token.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::select! {
+                        res = config_stream.next() => {
+                            let Some(config) = res else { break };
+                            match create_quic_server_config(&config) {
+                                Ok(server_config) => {
+                                    endpoint.set_server_config(Some(server_config));
+                                    tracing::info!("tls config changed.");
+                                }
+                                Err(err) => tracing::error!(error = %err, "invalid tls config."),
+                            }
+                        }
+                        _ = shutdown_token.cancelled() => break,
+                    }
+                }
+            });
+        }
+
+        let mut connections = JoinSet::new();
+
+        loop {
+            tokio::select! {
+                incoming = endpoint.accept() => {
+                    let Some(incoming) = incoming else { break };
+                    connections.spawn(serve_connection(
+                        incoming,
+                        self.local_addr.clone(),
+                        ep.clone(),
+                        shutdown_token.clone(),
+                    ));
+                }
+                _ = shutdown_token.cancelled() => break,
+            }
+        }
+
+        // refuse new connections, and wait for the existing ones to finish
+        endpoint.set_server_config(None);
+        while connections.join_next().await.is_some() {}
+        endpoint.wait_idle().await;
+    }
+}
+
+impl Acceptor for Http3Acceptor {
+    type Io = BoxIo;
+
+    fn local_addr(&self) -> Vec<LocalAddr> {
+        vec![self.local_addr.clone()]
+    }
+
+    async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
+        futures_util::future::pending().await
+    }
+
+    fn take_http3_acceptors(&mut self) -> Vec<Http3Acceptor> {
+        let Some(endpoint) = std::mem::take(&mut self.endpoint) else {
+            return Vec::new();
+        };
+        vec![Http3Acceptor {
+            endpoint: Some(endpoint),
+            local_addr: self.local_addr.clone(),
+            config_stream: self.config_stream.take(),
+        }]
+    }
+}
+
+async fn serve_connection(
+    incoming: quinn::Incoming,
+    local_addr: LocalAddr,
+    ep: Arc<dyn DynEndpoint<Output = Response>>,
+    shutdown_token: CancellationToken,
+) {
+    let conn = match incoming.await {
+        Ok(conn) => conn,
+        Err(err) => {
+            tracing::debug!(error = %err, "failed to establish quic connection");
+            return;
+        }
+    };
+    let remote_addr = RemoteAddr(conn.remote_address().into());
+    let mut conn =
+        match h3::server::Connection::<_, Bytes>::new(h3_quinn::Connection::new(conn)).await {
+            Ok(conn) => conn,
+            Err(err) => {
+                tracing::debug!(error = %err, "failed to establish http3 connection");
+                return;
+            }
+        };
+
+    let mut requests = JoinSet::new();
+    let mut shutting_down = false;
+
+    loop {
+        tokio::select! {
+            res = conn.accept() => match res {
+                Ok(Some(resolver)) => {
+                    requests.spawn(serve_request(
+                        resolver,
+                        local_addr.clone(),
+                        remote_addr.clone(),
+                        ep.clone(),
+                    ));
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    tracing::debug!(error = %err, "http3 connection error");
+                    break;
+                }
+            },
+            _ = shutdown_token.cancelled(), if !shutting_down => {
+                shutting_down = true;
+                if conn.shutdown(0).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    while requests.join_next().await.is_some() {}
+}
+
+async fn serve_request(
+    resolver: RequestResolver<h3_quinn::Connection, Bytes>,
+    local_addr: LocalAddr,
+    remote_addr: RemoteAddr,
+    ep: Arc<dyn DynEndpoint<Output = Response>>,
+) {
+    let (req, stream) = match resolver.resolve_request().await {
+        Ok(res) => res,
+        Err(err) => {
+            tracing::debug!(error = %err, "failed to receive http3 request");
+            return;
+        }
+    };
+    let (mut send_stream, recv_stream) = stream.split();
+
+    let body = Body::from_bytes_stream(futures_util::stream::unfold(
+        Some(recv_stream),
+        |recv_stream| async move {
+            let mut recv_stream = recv_stream?;
+            match recv_stream.recv_data().await {
+                Ok(Some(mut data)) => {
+                    let data = data.copy_to_bytes(data.remaining());
+                    Some((Ok(data), Some(recv_stream)))
+                }
+                Ok(None) => None,
+                Err(err) => Some((Err(IoError::other(err)), None)),
+            }
+        },
+    ));
+    let (parts, ()) = req.into_parts();
+    let req = Request::from_parts(
+        RequestParts::from((parts, local_addr, remote_addr, Scheme::HTTPS)),
+        body,
+    );
+
+    let (parts, body) = ep.get_response(req).await.into_parts();
+    let mut resp = http::Response::new(());
+    *resp.status_mut() = parts.status;
+    *resp.headers_mut() = parts.headers;
+
+    if let Err(err) = send_stream.send_response(resp).await {
+        tracing::debug!(error = %err, "failed to send http3 response");
+        return;
+    }
+
+    let mut body = body.into_bytes_stream();
+    while let Some(data) = body.next().await {
+        let res = match data {
+            Ok(data) => send_stream.send_data(data).await,
+            Err(err) => {
+                tracing::debug!(error = %err, "failed to read response body");
+                return;
+            }
+        };
+        if let Err(err) = res {
+            tracing::debug!(error = %err, "failed to send http3 response body");
+            return;
+        }
+    }
+
+    let _ = send_stream.finish().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::poll_fn;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+    use tokio_rustls::rustls::{ClientConfig, RootCertStore, pki_types::ServerName};
+
+    use super::*;
+    use crate::{
+        Server, handler,
+        listener::{AcceptorExt, RustlsCertificate, TcpListener},
+    };
+
+    fn rustls_config() -> RustlsConfig {
+        RustlsConfig::new().fallback(
+            RustlsCertificate::new()
+                .cert(include_bytes!("certs/cert1.pem").as_ref())
+                .key(include_bytes!("certs/key1.pem").as_ref()),
+        )
+    }
+
+    #[handler(internal)]
+    fn index(body: String) -> String {
+        format!("hello {body}")
+    }
+
+    #[tokio::test]
+    async fn http3_listener() {
+        let acceptor = Http3Listener::bind("127.0.0.1:0", rustls_config())
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        tokio::spawn(Server::new_with_acceptor(acceptor).run(index));
+
+        let mut roots = RootCertStore::empty();
+        for cert in rustls_pemfile::certs(&mut include_bytes!("certs/chain1.pem").as_ref()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let mut client_config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        client_config.alpn_protocols = vec![b"h3".to_vec()];
+
+        let mut endpoint = quinn::Endpoint::client("127.0.0.1:0".parse().unwrap()).unwrap();
+        endpoint.set_default_client_config(quinn::ClientConfig::new(Arc::new(
+            quinn::crypto::rustls::QuicClientConfig::try_from(client_config).unwrap(),
+        )));
+        let conn = endpoint
+            .connect(addr, "testserver.com")
+            .unwrap()
+            .await
+            .unwrap();
+
+        let (mut driver, mut send_request) = h3::client::new(h3_quinn::Connection::new(conn))
+            .await
+            .unwrap();
+        tokio::spawn(async move { poll_fn(|cx| driver.poll_close(cx)).await });
+
+        let req = http::Request::post("https://testserver.com/")
+            .body(())
+            .unwrap();
+        let mut stream = send_request.send_request(req).await.unwrap();
+        stream.send_data(Bytes::from_static(b"h3")).await.unwrap();
+        stream.finish().await.unwrap();
+
+        let resp = stream.recv_response().await.unwrap();
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let mut body = Vec::new();
+        while let Some(mut data) = stream.recv_data().await.unwrap() {
+            body.extend_from_slice(&data.copy_to_bytes(data.remaining()));
+        }
+        assert_eq!(body, b"hello h3");
+    }
+
+    #[tokio::test]
+    async fn combined_alt_svc() {
+        let mut acceptor = TcpListener::bind("127.0.0.1:0")
+            .rustls(rustls_config())
+            .combine(Http3Listener::bind("127.0.0.1:0", rustls_config()))
+            .into_acceptor()
+            .await
+            .unwrap()
+            .boxed();
+        let port = acceptor.local_addr()[1].as_socket_addr().unwrap().port();
+
+        let http3_acceptors = acceptor.take_http3_acceptors();
+        assert_eq!(http3_acceptors.len(), 1);
+        assert_eq!(
+            Http3Acceptor::alt_svc(&http3_acceptors).unwrap(),
+            format!("h3=\":{port}\"; ma=86400")
+        );
+        assert!(acceptor.take_http3_acceptors().is_empty());
+    }
+
+    #[tokio::test]
+    async fn https_response_alt_svc() {
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .rustls(rustls_config())
+            .combine(Http3Listener::bind("127.0.0.1:0", rustls_config()))
+            .into_acceptor()
+            .await
+            .unwrap();
+        let tcp_addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        let port = acceptor.local_addr()[1].as_socket_addr().unwrap().port();
+        tokio::spawn(Server::new_with_acceptor(acceptor).run(index));
+
+        let mut roots = RootCertStore::empty();
+        for cert in rustls_pemfile::certs(&mut include_bytes!("certs/chain1.pem").as_ref()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let client_config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+        let stream = TcpStream::connect(tcp_addr).await.unwrap();
+        let mut stream = connector
+            .connect(ServerName::try_from("testserver.com").unwrap(), stream)
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: testserver.com\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut resp = String::new();
+        stream.read_to_string(&mut resp).await.unwrap();
+
+        assert!(resp.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(
+            resp.to_ascii_lowercase()
+                .contains(&format!("\r\nalt-svc: h3=\":{port}\"; ma=86400\r\n"))
+        );
+    }
+}
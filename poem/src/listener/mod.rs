@@ -6,12 +6,17 @@ pub mod acme;
 mod combined;
 #[cfg(any(feature = "native-tls", feature = "rustls", feature = "openssl-tls"))]
 mod handshake_stream;
+#[cfg(feature = "http3")]
+mod http3;
 #[cfg(feature = "native-tls")]
 mod native_tls;
 #[cfg(feature = "openssl-tls")]
 mod openssl_tls;
+mod proxy_protocol;
 #[cfg(feature = "rustls")]
 mod rustls;
+#[cfg(all(unix, feature = "socket-activation"))]
+mod socket_activation;
 mod tcp;
 #[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl-tls"))]
 mod tls;
@@ -22,45 +27,83 @@ use std::{
     convert::Infallible,
     io::Error,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
 use futures_util::{Future, FutureExt, TryFutureExt, future::BoxFuture};
-use http::uri::Scheme;
+use http::{Extensions, uri::Scheme};
+use parking_lot::Mutex;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf, Result as IoResult};
 
 #[cfg(feature = "acme-base")]
 use self::acme::{AutoCert, AutoCertListener};
 #[cfg(any(feature = "native-tls", feature = "rustls", feature = "openssl-tls"))]
 pub use self::handshake_stream::HandshakeStream;
+#[cfg(feature = "http3")]
+pub use self::http3::{Http3Acceptor, Http3Listener};
 #[cfg(feature = "native-tls")]
 pub use self::native_tls::{NativeTlsAcceptor, NativeTlsConfig, NativeTlsListener};
 #[cfg(feature = "openssl-tls")]
 pub use self::openssl_tls::{OpensslTlsAcceptor, OpensslTlsConfig, OpensslTlsListener};
 #[cfg(feature = "rustls")]
 pub use self::rustls::{RustlsAcceptor, RustlsCertificate, RustlsConfig, RustlsListener};
+#[cfg(all(unix, feature = "socket-activation"))]
+pub use self::socket_activation::{Handoff, ListenerFd};
 #[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl-tls"))]
 pub use self::tls::IntoTlsConfigStream;
 #[cfg(unix)]
 pub use self::unix::{UnixAcceptor, UnixListener};
 pub use self::{
     combined::{Combined, CombinedStream},
+    proxy_protocol::{
+        ProxyProtocolAcceptor, ProxyProtocolHeader, ProxyProtocolListener, ProxyProtocolMode,
+        ProxyProtocolStream, ProxyProtocolVersion,
+    },
     tcp::{TcpAcceptor, TcpListener},
 };
 use crate::web::{LocalAddr, RemoteAddr};
 
+/// Extensions of an accepted connection.
+///
+/// The extensions are added to every request received from the connection, so
+/// they can be extracted with [`Data`](crate::web::Data).
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionExtensions(Arc<Mutex<Extensions>>);
+
+impl ConnectionExtensions {
+    /// Inserts a value to the extensions.
+    pub fn insert<T: Clone + Send + Sync + 'static>(&self, value: T) {
+        self.0.lock().insert(value);
+    }
+
+    /// Returns a clone of the value of type `T` in the extensions.
+    pub fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.0.lock().get::<T>().cloned()
+    }
+
+    pub(crate) fn to_extensions(&self) -> Extensions {
+        self.0.lock().clone()
+    }
+}
+
 /// An IO type for BoxAcceptor.
 pub struct BoxIo {
     reader: Box<dyn AsyncRead + Send + Unpin + 'static>,
     writer: Box<dyn AsyncWrite + Send + Unpin + 'static>,
+    extensions: ConnectionExtensions,
 }
 
 impl BoxIo {
-    fn new(io: impl AsyncRead + AsyncWrite + Send + Unpin + 'static) -> Self {
+    fn new(
+        io: impl AsyncRead + AsyncWrite + Send + Unpin + 'static,
+        extensions: ConnectionExtensions,
+    ) -> Self {
         let (reader, writer) = tokio::io::split(io);
         Self {
             reader: Box::new(reader),
             writer: Box::new(writer),
+            extensions,
         }
     }
 }
@@ -108,6 +151,20 @@ pub trait DynAcceptor: Send {
     /// established, the corresponding IO stream and the remote peer’s
     /// address will be returned.
     fn accept(&mut self) -> BoxFuture<'_, IoResult<(BoxIo, LocalAddr, RemoteAddr, Scheme)>>;
+
+    /// Takes the HTTP/3 acceptors contained in this acceptor.
+    #[cfg(feature = "http3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http3")))]
+    fn take_http3_acceptors(&mut self) -> Vec<Http3Acceptor> {
+        Vec::new()
+    }
+
+    /// Returns the listening sockets of this acceptor.
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    fn listener_fds(&self) -> IoResult<Vec<ListenerFd>> {
+        Ok(Vec::new())
+    }
 }
 
 /// A [`Acceptor`] wrapper used to implement [`DynAcceptor`].
@@ -123,11 +180,24 @@ impl<A: Acceptor> DynAcceptor for ToDynAcceptor<A> {
     fn accept(&mut self) -> BoxFuture<'_, IoResult<(BoxIo, LocalAddr, RemoteAddr, Scheme)>> {
         async move {
             let (io, local_addr, remote_addr, scheme) = self.0.accept().await?;
-            let io = BoxIo::new(io);
+            let extensions = A::connection_extensions(&io);
+            let io = BoxIo::new(io, extensions);
             Ok((io, local_addr, remote_addr, scheme))
         }
         .boxed()
     }
+
+    #[cfg(feature = "http3")]
+    #[inline]
+    fn take_http3_acceptors(&mut self) -> Vec<Http3Acceptor> {
+        self.0.take_http3_acceptors()
+    }
+
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[inline]
+    fn listener_fds(&self) -> IoResult<Vec<ListenerFd>> {
+        self.0.listener_fds()
+    }
 }
 
 impl Acceptor for dyn DynAcceptor + '_ {
@@ -142,6 +212,23 @@ impl Acceptor for dyn DynAcceptor + '_ {
     async fn accept(&mut self) -> IoResult<(BoxIo, LocalAddr, RemoteAddr, Scheme)> {
         DynAcceptor::accept(self).await
     }
+
+    #[inline]
+    fn connection_extensions(io: &BoxIo) -> ConnectionExtensions {
+        io.extensions.clone()
+    }
+
+    #[cfg(feature = "http3")]
+    #[inline]
+    fn take_http3_acceptors(&mut self) -> Vec<Http3Acceptor> {
+        DynAcceptor::take_http3_acceptors(self)
+    }
+
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[inline]
+    fn listener_fds(&self) -> IoResult<Vec<ListenerFd>> {
+        DynAcceptor::listener_fds(self)
+    }
 }
 
 /// Represents a acceptor type.
@@ -160,6 +247,36 @@ pub trait Acceptor: Send {
     fn accept(
         &mut self,
     ) -> impl Future<Output = IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)>> + Send;
+
+    /// Returns the extensions of a connection accepted by this acceptor.
+    ///
+    /// Acceptors that wrap other acceptors should include the extensions of
+    /// the inner connection.
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        let _ = io;
+        ConnectionExtensions::default()
+    }
+
+    /// Takes the HTTP/3 acceptors contained in this acceptor.
+    ///
+    /// HTTP/3 connections are not served through [`Acceptor::accept`], so the
+    /// [`Server`](crate::Server) takes them out and serves them separately.
+    /// Acceptors that wrap other acceptors should forward this call.
+    #[cfg(feature = "http3")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http3")))]
+    fn take_http3_acceptors(&mut self) -> Vec<Http3Acceptor> {
+        Vec::new()
+    }
+
+    /// Returns duplicates of the listening sockets of this acceptor, which
+    /// are passed to the new process by a [`Handoff`].
+    ///
+    /// Acceptors that wrap other acceptors should forward this call.
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    fn listener_fds(&self) -> IoResult<Vec<ListenerFd>> {
+        Ok(Vec::new())
+    }
 }
 
 /// An owned dynamically typed Acceptor for use in cases where you can’t
@@ -177,6 +294,20 @@ pub trait AcceptorExt: Acceptor {
         Combined::new(self, other)
     }
 
+    /// Consume this acceptor and return a new acceptor that reads the
+    /// [`PROXY protocol`](https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt)
+    /// header of the connections.
+    ///
+    /// The header must be read before the TLS handshake, so this adapter must
+    /// be applied before the TLS adapters.
+    #[must_use]
+    fn proxy_protocol(self, mode: ProxyProtocolMode) -> ProxyProtocolAcceptor<Self>
+    where
+        Self: Sized,
+    {
+        ProxyProtocolAcceptor::new(self, mode)
+    }
+
     /// Wrap the acceptor in a `Box`.
     fn boxed(self) -> BoxAcceptor
     where
@@ -248,6 +379,25 @@ pub trait Listener: Send {
         Combined::new(self, other)
     }
 
+    /// Consume this listener and return a new listener that reads the
+    /// [`PROXY protocol`](https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt)
+    /// header of the connections.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::listener::{Listener, ProxyProtocolMode, TcpListener};
+    ///
+    /// let listener = TcpListener::bind("0.0.0.0:80").proxy_protocol(ProxyProtocolMode::Strict);
+    /// ```
+    #[must_use]
+    fn proxy_protocol(self, mode: ProxyProtocolMode) -> ProxyProtocolListener<Self>
+    where
+        Self: Sized,
+    {
+        ProxyProtocolListener::new(self, mode)
+    }
+
     /// Consume this listener and return a new TLS listener with [`rustls`](https://crates.io/crates/rustls).
     #[cfg(feature = "rustls")]
     #[cfg_attr(docsrs, doc(cfg(feature = "rustls")))]
@@ -353,6 +503,20 @@ impl<T: Acceptor + ?Sized> Acceptor for Box<T> {
     async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
         self.as_mut().accept().await
     }
+
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        T::connection_extensions(io)
+    }
+
+    #[cfg(feature = "http3")]
+    fn take_http3_acceptors(&mut self) -> Vec<Http3Acceptor> {
+        self.as_mut().take_http3_acceptors()
+    }
+
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> IoResult<Vec<ListenerFd>> {
+        self.as_ref().listener_fds()
+    }
 }
 
 impl Acceptor for Infallible {
@@ -7,8 +7,8 @@ use tokio::io::{Error as IoError, Result as IoResult};
 use tokio_native_tls::{TlsStream, native_tls::Identity};
 
 use crate::{
-    listener::{Acceptor, HandshakeStream, IntoTlsConfigStream, Listener},
-    web::{LocalAddr, RemoteAddr},
+    listener::{Acceptor, ConnectionExtensions, HandshakeStream, IntoTlsConfigStream, Listener},
+    web::{LocalAddr, RemoteAddr, TlsInfo},
 };
 
 /// Native TLS Config.
@@ -146,6 +146,15 @@ where
         self.inner.local_addr()
     }
 
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> IoResult<Vec<crate::listener::ListenerFd>> {
+        self.inner.listener_fds()
+    }
+
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        io.extensions().clone()
+    }
+
     async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
         loop {
             tokio::select! {
@@ -168,12 +177,20 @@ where
                 }
                 res = self.inner.accept() => {
                     let (stream, local_addr, remote_addr, _) = res?;
+                    let extensions = T::connection_extensions(&stream);
                     let tls_acceptor = match &self.current_tls_acceptor {
                         Some(tls_acceptor) => tls_acceptor.clone(),
                         None => return Err(IoError::other("no valid tls config.")),
                     };
-                    let fut = async move { tls_acceptor.accept(stream).map_err(|err| IoError::other(err.to_string())).await };
-                    let stream = HandshakeStream::new(fut);
+                    let fut = {
+                        let extensions = extensions.clone();
+                        async move {
+                            let stream = tls_acceptor.accept(stream).map_err(|err| IoError::other(err.to_string())).await?;
+                            extensions.insert(TlsInfo::from_native_tls(stream.get_ref()));
+                            Ok(stream)
+                        }
+                    };
+                    let stream = HandshakeStream::new(fut, extensions);
                     return Ok((stream, local_addr, remote_addr, Scheme::HTTPS));
                 }
             }
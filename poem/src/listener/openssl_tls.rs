@@ -18,8 +18,8 @@ use tokio_openssl::SslStream;
 use tokio_util::either::Either;
 
 use crate::{
-    listener::{Acceptor, HandshakeStream, IntoTlsConfigStream, Listener},
-    web::{LocalAddr, RemoteAddr},
+    listener::{Acceptor, ConnectionExtensions, HandshakeStream, IntoTlsConfigStream, Listener},
+    web::{LocalAddr, RemoteAddr, TlsInfo},
 };
 
 /// Openssl configuration contains certificate's chain and private key.
@@ -191,6 +191,15 @@ where
         self.inner.local_addr()
     }
 
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> IoResult<Vec<crate::listener::ListenerFd>> {
+        self.inner.listener_fds()
+    }
+
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        io.extensions().clone()
+    }
+
     async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
         loop {
             tokio::select! {
@@ -213,11 +222,14 @@ where
                 }
                 res = self.inner.accept() => {
                     let (stream, local_addr, remote_addr, _) = res?;
+                    let extensions = T::connection_extensions(&stream);
                     let tls_acceptor = match &self.current_tls_acceptor {
                         Some(tls_acceptor) => tls_acceptor.clone(),
                         None => return Err(IoError::other("no valid tls config.")),
                     };
-                    let fut = async move {
+                    let fut = {
+                        let extensions = extensions.clone();
+                        async move {
                         let ssl = Ssl::new(tls_acceptor.context()).map_err(|err|
                             IoError::other(err.to_string()))?;
                         let mut tls_stream = SslStream::new(ssl, stream).map_err(|err|
@@ -225,8 +237,10 @@ where
                         use std::pin::Pin;
                         Pin::new(&mut tls_stream).accept().await.map_err(|err|
                             IoError::other(err.to_string()))?;
-                        Ok(tls_stream) };
-                    let stream = HandshakeStream::new(fut);
+                        extensions.insert(TlsInfo::from_openssl(tls_stream.ssl()));
+                        Ok(tls_stream) }
+                    };
+                    let stream = HandshakeStream::new(fut, extensions);
                     return Ok((stream, local_addr, remote_addr, Scheme::HTTPS));
                 }
             }
@@ -0,0 +1,737 @@
+use std::{
+    io::{Error as IoError, ErrorKind, IoSlice},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_util::{StreamExt, future::BoxFuture, stream::FuturesUnordered};
+use http::uri::Scheme;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf, Result as IoResult};
+
+use crate::{
+    listener::{Acceptor, ConnectionExtensions, Listener},
+    web::{LocalAddr, RemoteAddr},
+};
+
+const V1_PREFIX: &[u8] = b"PROXY ";
+const V1_MAX_LENGTH: usize = 107;
+const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+const V2_HEADER_LENGTH: usize = 16;
+const DEFAULT_HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Specifies whether the PROXY protocol header is required.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProxyProtocolMode {
+    /// Every connection must start with a PROXY protocol header, connections
+    /// without it are rejected.
+    Strict,
+    /// The PROXY protocol header is optional, connections without it keep the
+    /// address of the peer.
+    ///
+    /// # Security
+    ///
+    /// A client that connects directly can send its own PROXY protocol header
+    /// to spoof its address, so this mode must only be used when the port is
+    /// unreachable except through a trusted proxy.
+    Optional,
+}
+
+/// The version of a PROXY protocol header.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProxyProtocolVersion {
+    /// The human-readable header format.
+    V1,
+    /// The binary header format.
+    V2,
+}
+
+/// A PROXY protocol header received from a connection.
+///
+/// The header is added to the extensions of every request received from the
+/// connection, so it can be extracted with [`Data`](crate::web::Data).
+///
+/// # Example
+///
+/// ```
+/// use poem::{handler, listener::ProxyProtocolHeader, web::Data};
+///
+/// #[handler]
+/// fn index(header: Option<Data<&ProxyProtocolHeader>>) -> String {
+///     header
+///         .and_then(|header| header.authority().map(ToString::to_string))
+///         .unwrap_or_default()
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProxyProtocolHeader {
+    version: ProxyProtocolVersion,
+    source: Option<SocketAddr>,
+    destination: Option<SocketAddr>,
+    tlvs: Vec<(u8, Bytes)>,
+}
+
+impl ProxyProtocolHeader {
+    /// The TLV type of the host name sent by the client.
+    pub const TYPE_AUTHORITY: u8 = 0x02;
+
+    /// The TLV type of the unique identifier of the connection.
+    pub const TYPE_UNIQUE_ID: u8 = 0x05;
+
+    /// Returns the version of the header.
+    pub fn version(&self) -> ProxyProtocolVersion {
+        self.version
+    }
+
+    /// Returns the address of the client, if the proxy provided it.
+    pub fn source(&self) -> Option<SocketAddr> {
+        self.source
+    }
+
+    /// Returns the address the client connected to, if the proxy provided it.
+    pub fn destination(&self) -> Option<SocketAddr> {
+        self.destination
+    }
+
+    /// Returns the value of the first TLV of the specified type.
+    pub fn tlv(&self, ty: u8) -> Option<&[u8]> {
+        self.tlvs
+            .iter()
+            .find(|(t, _)| *t == ty)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Returns an iterator over the type and the value of all TLVs.
+    ///
+    /// Only the binary format (v2) can carry TLVs.
+    pub fn tlvs(&self) -> impl Iterator<Item = (u8, &[u8])> {
+        self.tlvs.iter().map(|(ty, value)| (*ty, value.as_ref()))
+    }
+
+    /// Returns the host name sent by the client. (`PP2_TYPE_AUTHORITY`)
+    pub fn authority(&self) -> Option<&str> {
+        self.tlv(Self::TYPE_AUTHORITY)
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    /// Returns the unique identifier of the connection.
+    /// (`PP2_TYPE_UNIQUE_ID`)
+    pub fn unique_id(&self) -> Option<&[u8]> {
+        self.tlv(Self::TYPE_UNIQUE_ID)
+    }
+}
+
+/// Listener for the
+/// [`Listener::proxy_protocol`](crate::listener::Listener::proxy_protocol)
+/// method.
+pub struct ProxyProtocolListener<T> {
+    inner: T,
+    mode: ProxyProtocolMode,
+    header_timeout: Duration,
+}
+
+impl<T> ProxyProtocolListener<T> {
+    pub(crate) fn new(inner: T, mode: ProxyProtocolMode) -> Self {
+        Self {
+            inner,
+            mode,
+            header_timeout: DEFAULT_HEADER_TIMEOUT,
+        }
+    }
+
+    /// Sets the maximum time to receive the header after a connection is
+    /// accepted. (defaults to 5 seconds)
+    #[must_use]
+    pub fn header_timeout(self, timeout: Duration) -> Self {
+        Self {
+            header_timeout: timeout,
+            ..self
+        }
+    }
+}
+
+impl<T: Listener> Listener for ProxyProtocolListener<T> {
+    type Acceptor = ProxyProtocolAcceptor<T::Acceptor>;
+
+    async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
+        Ok(
+            ProxyProtocolAcceptor::new(self.inner.into_acceptor().await?, self.mode)
+                .header_timeout(self.header_timeout),
+        )
+    }
+}
+
+type PendingConnection<Io> =
+    BoxFuture<'static, IoResult<(ProxyProtocolStream<Io>, LocalAddr, RemoteAddr, Scheme)>>;
+
+/// Acceptor for the
+/// [`AcceptorExt::proxy_protocol`](crate::listener::AcceptorExt::proxy_protocol)
+/// method.
+///
+/// The headers are read concurrently, so a slow client does not prevent other
+/// connections from being accepted. Connections with an invalid header, or
+/// without a header in [`ProxyProtocolMode::Strict`] mode, are closed.
+pub struct ProxyProtocolAcceptor<T: Acceptor> {
+    inner: T,
+    mode: ProxyProtocolMode,
+    header_timeout: Duration,
+    pending: FuturesUnordered<PendingConnection<T::Io>>,
+}
+
+impl<T: Acceptor> ProxyProtocolAcceptor<T> {
+    pub(crate) fn new(inner: T, mode: ProxyProtocolMode) -> Self {
+        Self {
+            inner,
+            mode,
+            header_timeout: DEFAULT_HEADER_TIMEOUT,
+            pending: FuturesUnordered::new(),
+        }
+    }
+
+    /// Sets the maximum time to receive the header after a connection is
+    /// accepted. (defaults to 5 seconds)
+    #[must_use]
+    pub fn header_timeout(self, timeout: Duration) -> Self {
+        Self {
+            header_timeout: timeout,
+            ..self
+        }
+    }
+}
+
+impl<T: Acceptor> Acceptor for ProxyProtocolAcceptor<T> {
+    type Io = ProxyProtocolStream<T::Io>;
+
+    fn local_addr(&self) -> Vec<LocalAddr> {
+        self.inner.local_addr()
+    }
+
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> IoResult<Vec<crate::listener::ListenerFd>> {
+        self.inner.listener_fds()
+    }
+
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        io.extensions.clone()
+    }
+
+    async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
+        loop {
+            tokio::select! {
+                res = self.inner.accept() => {
+                    let (mut stream, local_addr, remote_addr, scheme) = res?;
+                    let extensions = T::connection_extensions(&stream);
+                    let mode = self.mode;
+                    let header_timeout = self.header_timeout;
+
+                    self.pending.push(Box::pin(async move {
+                        let (header, buffer) =
+                            tokio::time::timeout(header_timeout, read_header(&mut stream, mode))
+                                .await
+                                .map_err(|_| {
+                                    IoError::new(ErrorKind::TimedOut, "PROXY protocol header timed out")
+                                })??;
+                        let remote_addr = match header.as_ref().and_then(|header| header.source) {
+                            Some(addr) => RemoteAddr(addr.into()),
+                            None => remote_addr,
+                        };
+                        if let Some(header) = header {
+                            extensions.insert(header);
+                        }
+                        let stream = ProxyProtocolStream {
+                            inner: stream,
+                            buffer,
+                            extensions,
+                        };
+                        Ok((stream, local_addr, remote_addr, scheme))
+                    }));
+                }
+                Some(res) = self.pending.next(), if !self.pending.is_empty() => match res {
+                    Ok(conn) => return Ok(conn),
+                    Err(err) => tracing::debug!(error = %err, "failed to read PROXY protocol header"),
+                }
+            }
+        }
+    }
+}
+
+/// A IO stream for ProxyProtocolAcceptor.
+pub struct ProxyProtocolStream<S> {
+    inner: S,
+    buffer: Bytes,
+    extensions: ConnectionExtensions,
+}
+
+impl<S> AsyncRead for ProxyProtocolStream<S>
+where
+    S: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<IoResult<()>> {
+        let this = &mut *self;
+
+        // yields the data received after the header first
+        if !this.buffer.is_empty() {
+            let len = this.buffer.len().min(buf.remaining());
+            buf.put_slice(&this.buffer.split_to(len));
+            return Poll::Ready(Ok(()));
+        }
+
+        Pin::new(&mut this.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S> AsyncWrite for ProxyProtocolStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+/// Reads the header from the stream, and returns it with the data received
+/// after it.
+async fn read_header<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    mode: ProxyProtocolMode,
+) -> IoResult<(Option<ProxyProtocolHeader>, Bytes)> {
+    let mut buf = BytesMut::with_capacity(512);
+
+    loop {
+        match parse_header(&buf)? {
+            Parsed::Header(header, len) => {
+                buf.advance(len);
+                return Ok((Some(header), buf.freeze()));
+            }
+            Parsed::NotProxy => {
+                return match mode {
+                    ProxyProtocolMode::Strict => Err(invalid_header("missing header")),
+                    ProxyProtocolMode::Optional => Ok((None, buf.freeze())),
+                };
+            }
+            Parsed::Incomplete => {
+                if stream.read_buf(&mut buf).await? == 0 {
+                    return Err(ErrorKind::UnexpectedEof.into());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Parsed {
+    Incomplete,
+    NotProxy,
+    Header(ProxyProtocolHeader, usize),
+}
+
+fn parse_header(buf: &[u8]) -> IoResult<Parsed> {
+    if buf.starts_with(V2_SIGNATURE) {
+        parse_v2(buf)
+    } else if buf.starts_with(V1_PREFIX) {
+        parse_v1(buf)
+    } else if V2_SIGNATURE.starts_with(buf) || V1_PREFIX.starts_with(buf) {
+        Ok(Parsed::Incomplete)
+    } else {
+        Ok(Parsed::NotProxy)
+    }
+}
+
+fn parse_v1(buf: &[u8]) -> IoResult<Parsed> {
+    let Some(end) = buf
+        .windows(2)
+        .take(V1_MAX_LENGTH - 1)
+        .position(|window| window == b"\r\n")
+    else {
+        return if buf.len() >= V1_MAX_LENGTH {
+            Err(invalid_header("header is too long"))
+        } else {
+            Ok(Parsed::Incomplete)
+        };
+    };
+
+    let line = std::str::from_utf8(&buf[V1_PREFIX.len()..end])
+        .map_err(|_| invalid_header("header is not valid UTF-8"))?;
+    let mut parts = line.split(' ');
+    let (source, destination) = match parts.next() {
+        Some("UNKNOWN") => (None, None),
+        Some(protocol @ ("TCP4" | "TCP6")) => {
+            let mut next = || parts.next().ok_or_else(|| invalid_header("missing field"));
+            let source_ip = parse_v1_ip(next()?, protocol)?;
+            let destination_ip = parse_v1_ip(next()?, protocol)?;
+            let source_port = parse_v1_port(next()?)?;
+            let destination_port = parse_v1_port(next()?)?;
+            if parts.next().is_some() {
+                return Err(invalid_header("unexpected field"));
+            }
+            (
+                Some(SocketAddr::new(source_ip, source_port)),
+                Some(SocketAddr::new(destination_ip, destination_port)),
+            )
+        }
+        _ => return Err(invalid_header("unknown protocol")),
+    };
+
+    Ok(Parsed::Header(
+        ProxyProtocolHeader {
+            version: ProxyProtocolVersion::V1,
+            source,
+            destination,
+            tlvs: Vec::new(),
+        },
+        end + 2,
+    ))
+}
+
+fn parse_v1_ip(value: &str, protocol: &str) -> IoResult<IpAddr> {
+    let ip = match protocol {
+        "TCP4" => value.parse::<Ipv4Addr>().map(IpAddr::V4),
+        _ => value.parse::<Ipv6Addr>().map(IpAddr::V6),
+    };
+    ip.map_err(|_| invalid_header("invalid address"))
+}
+
+fn parse_v1_port(value: &str) -> IoResult<u16> {
+    // leading zeros are not allowed
+    if value.len() > 1 && value.starts_with('0') {
+        return Err(invalid_header("invalid port"));
+    }
+    value.parse().map_err(|_| invalid_header("invalid port"))
+}
+
+fn parse_v2(buf: &[u8]) -> IoResult<Parsed> {
+    if buf.len() < V2_HEADER_LENGTH {
+        return Ok(Parsed::Incomplete);
+    }
+
+    let version_command = buf[12];
+    let family = buf[13];
+    let len = u16::from_be_bytes([buf[14], buf[15]]) as usize;
+
+    if version_command >> 4 != 2 {
+        return Err(invalid_header("unsupported version"));
+    }
+    if buf.len() < V2_HEADER_LENGTH + len {
+        return Ok(Parsed::Incomplete);
+    }
+
+    let mut payload = &buf[V2_HEADER_LENGTH..V2_HEADER_LENGTH + len];
+    let is_local = match version_command & 0x0f {
+        0x0 => true,
+        0x1 => false,
+        _ => return Err(invalid_header("unsupported command")),
+    };
+
+    let address_len = match family >> 4 {
+        0x0 => 0,
+        0x1 => 12,
+        0x2 => 36,
+        0x3 => 216,
+        _ => return Err(invalid_header("unsupported address family")),
+    };
+    if payload.len() < address_len {
+        return Err(invalid_header("address is too short"));
+    }
+
+    let (source, destination) = match family >> 4 {
+        0x1 if !is_local => {
+            let source_ip = Ipv4Addr::from(payload.get_u32());
+            let destination_ip = Ipv4Addr::from(payload.get_u32());
+            (
+                Some(SocketAddr::new(source_ip.into(), payload.get_u16())),
+                Some(SocketAddr::new(destination_ip.into(), payload.get_u16())),
+            )
+        }
+        0x2 if !is_local => {
+            let source_ip = Ipv6Addr::from(payload.get_u128());
+            let destination_ip = Ipv6Addr::from(payload.get_u128());
+            (
+                Some(SocketAddr::new(source_ip.into(), payload.get_u16())),
+                Some(SocketAddr::new(destination_ip.into(), payload.get_u16())),
+            )
+        }
+        _ => {
+            // the addresses of `LOCAL` connections and unix sockets are ignored
+            payload.advance(address_len);
+            (None, None)
+        }
+    };
+
+    let mut tlvs = Vec::new();
+    while payload.has_remaining() {
+        if payload.len() < 3 {
+            return Err(invalid_header("TLV is too short"));
+        }
+        let ty = payload.get_u8();
+        let len = payload.get_u16() as usize;
+        if payload.len() < len {
+            return Err(invalid_header("TLV is too short"));
+        }
+        tlvs.push((ty, Bytes::copy_from_slice(&payload[..len])));
+        payload.advance(len);
+    }
+
+    Ok(Parsed::Header(
+        ProxyProtocolHeader {
+            version: ProxyProtocolVersion::V2,
+            source,
+            destination,
+            tlvs,
+        },
+        V2_HEADER_LENGTH + len,
+    ))
+}
+
+fn invalid_header(msg: &str) -> IoError {
+    IoError::new(
+        ErrorKind::InvalidData,
+        format!("invalid PROXY protocol header: {msg}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    use super::*;
+    use crate::listener::{AcceptorExt, TcpListener};
+
+    fn v2_header(command: u8, tlvs: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&[192, 168, 0, 1, 10, 0, 0, 1]);
+        payload.extend_from_slice(&56324u16.to_be_bytes());
+        payload.extend_from_slice(&443u16.to_be_bytes());
+        for (ty, value) in tlvs {
+            payload.push(*ty);
+            payload.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            payload.extend_from_slice(value);
+        }
+
+        let mut header = V2_SIGNATURE.to_vec();
+        header.push(0x20 | command);
+        header.push(0x11);
+        header.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        header.extend_from_slice(&payload);
+        header
+    }
+
+    #[test]
+    fn parse_v1_header() {
+        let data = b"PROXY TCP4 192.168.0.1 10.0.0.1 56324 443\r\nGET /";
+        let Parsed::Header(header, len) = parse_header(data).unwrap() else {
+            panic!()
+        };
+        assert_eq!(&data[len..], b"GET /");
+        assert_eq!(header.version(), ProxyProtocolVersion::V1);
+        assert_eq!(header.source(), Some("192.168.0.1:56324".parse().unwrap()));
+        assert_eq!(header.destination(), Some("10.0.0.1:443".parse().unwrap()));
+
+        let Parsed::Header(header, _) = parse_header(b"PROXY TCP6 ::1 ::2 56324 443\r\n").unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!(header.source(), Some("[::1]:56324".parse().unwrap()));
+
+        let Parsed::Header(header, _) = parse_header(b"PROXY UNKNOWN\r\n").unwrap() else {
+            panic!()
+        };
+        assert_eq!(header.source(), None);
+
+        assert!(matches!(
+            parse_header(b"PROXY TCP4 192.168.0.1").unwrap(),
+            Parsed::Incomplete
+        ));
+        assert!(parse_header(b"PROXY TCP4 192.168.0.1 10.0.0.1 56324\r\n").is_err());
+        assert!(parse_header(b"PROXY TCP4 ::1 ::2 56324 443\r\n").is_err());
+        assert!(parse_header(b"PROXY TCP4 192.168.0.1 10.0.0.1 056324 443\r\n").is_err());
+        assert!(parse_header(format!("PROXY {}", "1".repeat(200)).as_bytes()).is_err());
+    }
+
+    #[test]
+    fn parse_v2_header() {
+        let mut data = v2_header(0x1, &[(0x02, b"example.com"), (0xea, b"vpce-1234")]);
+        let header_len = data.len();
+        data.extend_from_slice(b"GET /");
+
+        let Parsed::Header(header, len) = parse_header(&data).unwrap() else {
+            panic!()
+        };
+        assert_eq!(len, header_len);
+        assert_eq!(header.version(), ProxyProtocolVersion::V2);
+        assert_eq!(header.source(), Some("192.168.0.1:56324".parse().unwrap()));
+        assert_eq!(header.destination(), Some("10.0.0.1:443".parse().unwrap()));
+        assert_eq!(header.authority(), Some("example.com"));
+        assert_eq!(header.tlv(0xea), Some(b"vpce-1234".as_ref()));
+        assert_eq!(header.tlvs().count(), 2);
+
+        for len in [0, 5, 14, header_len - 1] {
+            assert!(matches!(
+                parse_header(&data[..len]).unwrap(),
+                Parsed::Incomplete
+            ));
+        }
+
+        let Parsed::Header(header, _) = parse_header(&v2_header(0x0, &[])).unwrap() else {
+            panic!()
+        };
+        assert_eq!(header.source(), None);
+
+        assert!(parse_header(&v2_header(0x2, &[])).is_err());
+    }
+
+    #[test]
+    fn parse_not_proxy() {
+        assert!(matches!(
+            parse_header(b"GET / HTTP/1.1\r\n").unwrap(),
+            Parsed::NotProxy
+        ));
+        assert!(matches!(
+            parse_header(b"\x16\x03\x01").unwrap(),
+            Parsed::NotProxy
+        ));
+    }
+
+    #[tokio::test]
+    async fn proxy_protocol_acceptor() {
+        let mut acceptor = TcpListener::bind("127.0.0.1:0")
+            .proxy_protocol(ProxyProtocolMode::Strict)
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let mut data = v2_header(0x1, &[(0x02, b"example.com")]);
+            data.extend_from_slice(b"hello");
+            stream.write_all(&data).await.unwrap();
+        });
+
+        let (mut stream, _, remote_addr, _) = acceptor.accept().await.unwrap();
+        assert_eq!(
+            remote_addr.as_socket_addr(),
+            Some(&"192.168.0.1:56324".parse().unwrap())
+        );
+        let header = stream.extensions.get::<ProxyProtocolHeader>().unwrap();
+        assert_eq!(header.authority(), Some("example.com"));
+
+        let mut s = String::new();
+        stream.read_to_string(&mut s).await.unwrap();
+        assert_eq!(s, "hello");
+    }
+
+    #[tokio::test]
+    async fn strict_mode_rejects_connections_without_header() {
+        let mut acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap()
+            .proxy_protocol(ProxyProtocolMode::Strict);
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(b"GET / HTTP/1.1\r\n").await.unwrap();
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"PROXY TCP4 192.168.0.1 10.0.0.1 56324 443\r\n")
+                .await
+                .unwrap();
+        });
+
+        // the first connection is rejected
+        let (_, _, remote_addr, _) = acceptor.accept().await.unwrap();
+        assert_eq!(
+            remote_addr.as_socket_addr(),
+            Some(&"192.168.0.1:56324".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn optional_mode() {
+        let mut acceptor = TcpListener::bind("127.0.0.1:0")
+            .proxy_protocol(ProxyProtocolMode::Optional)
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+
+        let client = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let local_addr = stream.local_addr().unwrap();
+            stream.write_all(b"hello").await.unwrap();
+            local_addr
+        });
+
+        let (mut stream, _, remote_addr, _) = acceptor.accept().await.unwrap();
+        assert_eq!(remote_addr.as_socket_addr(), Some(&client.await.unwrap()));
+
+        let mut s = String::new();
+        stream.read_to_string(&mut s).await.unwrap();
+        assert_eq!(s, "hello");
+    }
+
+    #[tokio::test]
+    async fn header_in_request_extensions() {
+        use crate::{
+            Server, handler,
+            web::{Data, RemoteAddr},
+        };
+
+        #[handler(internal)]
+        fn index(remote_addr: &RemoteAddr, header: Data<&ProxyProtocolHeader>) -> String {
+            format!("{} {}", remote_addr, header.authority().unwrap())
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .proxy_protocol(ProxyProtocolMode::Strict)
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+        tokio::spawn(Server::new_with_acceptor(acceptor).run(index));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut data = v2_header(0x1, &[(0x02, b"example.com")]);
+        data.extend_from_slice(b"GET / HTTP/1.1\r\nhost: example.com\r\nconnection: close\r\n\r\n");
+        stream.write_all(&data).await.unwrap();
+
+        let mut resp = String::new();
+        stream.read_to_string(&mut resp).await.unwrap();
+        assert!(resp.starts_with("HTTP/1.1 200 OK"));
+        assert!(resp.ends_with("socket://192.168.0.1:56324 example.com"));
+    }
+}
@@ -18,8 +18,8 @@ use tokio_rustls::{
 };
 
 use crate::{
-    listener::{Acceptor, HandshakeStream, IntoTlsConfigStream, Listener},
-    web::{LocalAddr, RemoteAddr},
+    listener::{Acceptor, ConnectionExtensions, HandshakeStream, IntoTlsConfigStream, Listener},
+    web::{LocalAddr, RemoteAddr, TlsInfo},
 };
 
 #[cfg_attr(docsrs, doc(cfg(feature = "rustls")))]
@@ -212,7 +212,7 @@ impl RustlsConfig {
         self
     }
 
-    fn create_server_config(&self) -> IoResult<ServerConfig> {
+    pub(crate) fn create_server_config(&self) -> IoResult<ServerConfig> {
         let fallback = self
             .fallback
             .as_ref()
@@ -377,6 +377,15 @@ where
         self.inner.local_addr()
     }
 
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> IoResult<Vec<crate::listener::ListenerFd>> {
+        self.inner.listener_fds()
+    }
+
+    fn connection_extensions(io: &Self::Io) -> ConnectionExtensions {
+        io.extensions().clone()
+    }
+
     async fn accept(&mut self) -> IoResult<(Self::Io, LocalAddr, RemoteAddr, Scheme)> {
         loop {
             tokio::select! {
@@ -405,7 +414,17 @@ where
                         None => return Err(IoError::other("no valid tls config.")),
                     };
 
-                    let stream = HandshakeStream::new(tls_acceptor.accept(stream));
+                    let extensions = T::connection_extensions(&stream);
+                    let handshake = tls_acceptor.accept(stream);
+                    let fut = {
+                        let extensions = extensions.clone();
+                        async move {
+                            let stream = handshake.await?;
+                            extensions.insert(TlsInfo::from_rustls(stream.get_ref().1));
+                            Ok(stream)
+                        }
+                    };
+                    let stream = HandshakeStream::new(fut, extensions);
                     return Ok((stream, local_addr, remote_addr, Scheme::HTTPS));
                 }
             }
@@ -470,4 +489,66 @@ mod tests {
         let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
         assert_eq!(stream.read_i32().await.unwrap(), 10);
     }
+
+    #[tokio::test]
+    async fn tls_info() {
+        let listener = TcpListener::bind("127.0.0.1:0").rustls(
+            RustlsConfig::new()
+                .fallback(
+                    RustlsCertificate::new()
+                        .cert(include_bytes!("certs/cert1.pem").as_ref())
+                        .key(include_bytes!("certs/key1.pem").as_ref()),
+                )
+                .client_auth_required(include_bytes!("certs/chain1.pem").as_ref()),
+        );
+        let mut acceptor = listener.into_acceptor().await.unwrap();
+        let local_addr = acceptor.local_addr().pop().unwrap();
+
+        tokio::spawn(async move {
+            let client_certs =
+                rustls_pemfile::certs(&mut include_bytes!("certs/cert1.pem").as_ref())
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+            let client_key =
+                rustls_pemfile::private_key(&mut include_bytes!("certs/key1.pem").as_ref())
+                    .unwrap()
+                    .unwrap();
+            let mut config = ClientConfig::builder()
+                .with_root_certificates(
+                    read_trust_anchor(include_bytes!("certs/chain1.pem")).unwrap(),
+                )
+                .with_client_auth_cert(client_certs, client_key)
+                .unwrap();
+            config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+            let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+            let domain = ServerName::try_from("testserver.com").unwrap();
+            let stream = TcpStream::connect(*local_addr.as_socket_addr().unwrap())
+                .await
+                .unwrap();
+            let mut stream = connector.connect(domain, stream).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+
+        let tls_info = stream.extensions().get::<TlsInfo>().unwrap();
+        assert_eq!(tls_info.server_name(), Some("testserver.com"));
+        assert_eq!(tls_info.alpn_protocol(), Some(b"http/1.1".as_ref()));
+        assert_eq!(tls_info.version(), Some(crate::web::TlsVersion::Tls1_3));
+        assert!(tls_info.peer_certificate().is_some());
+        #[cfg(feature = "x509")]
+        {
+            assert_eq!(
+                tls_info.peer_subject().as_deref(),
+                Some("CN=testserver.com")
+            );
+            assert!(
+                tls_info
+                    .peer_subject_alt_names()
+                    .contains(&crate::web::SubjectAltName::Dns("localhost".to_string()))
+            );
+        }
+    }
 }
@@ -0,0 +1,340 @@
+use std::{
+    ffi::OsString,
+    future::Future,
+    io::{Error, ErrorKind, Result},
+    os::fd::{AsFd, OwnedFd, RawFd},
+    process::{Child, Command},
+    sync::LazyLock,
+};
+
+use command_fds::{CommandFdExt, FdMapping};
+use futures_util::future::BoxFuture;
+use listenfd::ListenFd;
+use parking_lot::Mutex;
+
+/// The first file descriptor of the passed sockets, as defined by systemd.
+const SD_LISTEN_FDS_START: RawFd = 3;
+
+/// The name systemd uses for sockets without a name.
+const UNKNOWN_NAME: &str = "unknown";
+
+struct InheritedFds {
+    fds: ListenFd,
+    names: Vec<String>,
+}
+
+/// The sockets passed to this process with the `LISTEN_FDS` and
+/// `LISTEN_FDNAMES` environment variables.
+static INHERITED_FDS: LazyLock<Mutex<InheritedFds>> = LazyLock::new(|| {
+    let names = std::env::var("LISTEN_FDNAMES")
+        .map(|names| names.split(':').map(ToString::to_string).collect())
+        .unwrap_or_default();
+    Mutex::new(InheritedFds {
+        fds: ListenFd::from_env(),
+        names,
+    })
+});
+
+/// Takes the inherited socket at the specified index, and returns it with its
+/// name.
+pub(crate) fn take_listen_fd<T>(
+    index: usize,
+    take: impl FnOnce(&mut ListenFd, usize) -> Result<Option<T>>,
+) -> Result<(T, Option<String>)> {
+    let mut inherited = INHERITED_FDS.lock();
+    let name = inherited
+        .names
+        .get(index)
+        .filter(|name| *name != UNKNOWN_NAME)
+        .cloned();
+    match take(&mut inherited.fds, index)? {
+        Some(listener) => Ok((listener, name)),
+        None => Err(Error::new(
+            ErrorKind::NotFound,
+            format!("no inherited socket at index {index}"),
+        )),
+    }
+}
+
+/// Takes the first inherited socket with the specified name.
+pub(crate) fn take_listen_fd_by_name<T>(
+    name: &str,
+    mut take: impl FnMut(&mut ListenFd, usize) -> Result<Option<T>>,
+) -> Result<T> {
+    let mut inherited = INHERITED_FDS.lock();
+    let InheritedFds { fds, names } = &mut *inherited;
+    for (index, _) in names.iter().enumerate().filter(|(_, n)| *n == name) {
+        if let Some(listener) = take(fds, index)? {
+            return Ok(listener);
+        }
+    }
+    Err(Error::new(
+        ErrorKind::NotFound,
+        format!("no inherited socket named `{name}`"),
+    ))
+}
+
+/// A listening socket of an acceptor, which can be passed to another process.
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+pub struct ListenerFd {
+    /// A duplicate of the file descriptor of the socket.
+    pub fd: OwnedFd,
+    /// The name of the socket, which is passed in `LISTEN_FDNAMES`.
+    pub name: Option<String>,
+}
+
+/// Hands the listening sockets of a [`Server`](crate::Server) off to a new
+/// process, to restart the server without refusing any connections.
+///
+/// When the signal is received, the new process is spawned with the listening
+/// sockets, which are passed with the `LISTEN_FDS` and `LISTEN_FDNAMES`
+/// environment variables like systemd does, so it can take them with
+/// [`TcpListener::from_listen_fd`](crate::listener::TcpListener::from_listen_fd)
+/// or
+/// [`TcpListener::from_listen_fd_name`](crate::listener::TcpListener::from_listen_fd_name).
+/// The server then stops accepting connections and starts the graceful
+/// shutdown, while the connections waiting to be accepted are accepted by the
+/// new process.
+///
+/// By default, the program is executed again with the same arguments. If the
+/// new process cannot be spawned, the error is logged and the server keeps
+/// running.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::net::SocketAddr;
+///
+/// use poem::{
+///     Server, handler,
+///     listener::{Handoff, TcpListener},
+/// };
+/// use tokio::signal::unix::{SignalKind, signal};
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let listener = match TcpListener::from_listen_fd_name("http") {
+///     Ok(listener) => listener,
+///     Err(_) => TcpListener::bind(SocketAddr::from(([0, 0, 0, 0], 3000))).with_fd_name("http"),
+/// };
+/// let mut sighup = signal(SignalKind::hangup())?;
+/// Server::new(listener)
+///     .handoff(Handoff::new(async move {
+///         sighup.recv().await;
+///     }))
+///     .run(index)
+///     .await
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+pub struct Handoff {
+    signal: BoxFuture<'static, ()>,
+    command: Option<Command>,
+}
+
+impl Handoff {
+    /// Create a `Handoff` which is started when the signal is received.
+    pub fn new(signal: impl Future<Output = ()> + Send + 'static) -> Self {
+        Self {
+            signal: Box::pin(signal),
+            command: None,
+        }
+    }
+
+    /// Sets the command used to spawn the new process.
+    ///
+    /// The `LISTEN_FDS` and `LISTEN_FDNAMES` environment variables are added
+    /// to the command.
+    #[must_use]
+    pub fn command(self, command: Command) -> Self {
+        Self {
+            command: Some(command),
+            ..self
+        }
+    }
+
+    /// Waits for the signal, then spawns the new process.
+    ///
+    /// The returned future only completes if the new process has been
+    /// spawned.
+    pub(crate) async fn run(self, fds: Vec<ListenerFd>, name: Option<&str>) {
+        self.signal.await;
+        match spawn(self.command, fds) {
+            Ok(child) => {
+                tracing::info!(name = name, pid = child.id(), "listeners handed off");
+            }
+            Err(err) => {
+                tracing::error!(name = name, error = %err, "failed to hand off listeners");
+                futures_util::future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+fn spawn(command: Option<Command>, fds: Vec<ListenerFd>) -> Result<Child> {
+    if fds.is_empty() {
+        return Err(Error::other("no listening sockets to hand off"));
+    }
+
+    let mut command = match command {
+        Some(command) => command,
+        None => {
+            let mut args = std::env::args_os();
+            let program = match args.next() {
+                Some(program) => program,
+                None => OsString::from(std::env::current_exe()?),
+            };
+            let mut command = Command::new(program);
+            command.args(args);
+            command
+        }
+    };
+
+    let names = fds
+        .iter()
+        .map(|fd| fd.name.as_deref().unwrap_or(UNKNOWN_NAME))
+        .collect::<Vec<_>>()
+        .join(":");
+    let mappings = fds
+        .into_iter()
+        .enumerate()
+        .map(|(index, fd)| FdMapping {
+            child_fd: SD_LISTEN_FDS_START + index as RawFd,
+            parent_fd: fd.fd,
+        })
+        .collect::<Vec<_>>();
+
+    command
+        .env("LISTEN_FDS", mappings.len().to_string())
+        .env("LISTEN_FDNAMES", names)
+        .env_remove("LISTEN_PID")
+        .env_remove("LISTEN_FDS_FIRST_FD")
+        .fd_mappings(mappings)
+        .map_err(|_| Error::other("file descriptor mapping collision"))?;
+    command.spawn()
+}
+
+/// Duplicates the file descriptor of a listening socket.
+pub(crate) fn dup_listener_fd(fd: &impl AsFd, name: Option<&str>) -> Result<ListenerFd> {
+    Ok(ListenerFd {
+        fd: fd.as_fd().try_clone_to_owned()?,
+        name: name.map(ToString::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+        sync::{Notify, oneshot},
+    };
+
+    use super::*;
+    use crate::{
+        Server,
+        endpoint::make_sync,
+        listener::{Acceptor, Listener, TcpListener},
+    };
+
+    /// Set for the process spawned by the `handoff` test.
+    const CHILD_ENV: &str = "POEM_TEST_HANDOFF_CHILD";
+
+    async fn get(addr: std::net::SocketAddr) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut resp = String::new();
+        tokio::time::timeout(Duration::from_secs(30), stream.read_to_string(&mut resp))
+            .await
+            .unwrap()
+            .unwrap();
+        resp.rsplit("\r\n\r\n").next().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn handoff() {
+        if std::env::var_os(CHILD_ENV).is_some() {
+            return;
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .with_fd_name("http")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = *acceptor.local_addr()[0].as_socket_addr().unwrap();
+
+        let mut command = Command::new(std::env::current_exe().unwrap());
+        command
+            .args([
+                "listener::socket_activation::tests::handoff_child",
+                "--exact",
+                "--nocapture",
+            ])
+            .env(CHILD_ENV, "1");
+        let (tx, rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(
+            Server::new_with_acceptor(acceptor)
+                .handoff(
+                    Handoff::new(async move {
+                        let _ = rx.await;
+                    })
+                    .command(command),
+                )
+                .run(make_sync(|_| "parent")),
+        );
+
+        assert_eq!(get(addr).await, "parent");
+
+        tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(10), server)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        // the socket is still listening, and the connection is accepted by the
+        // new process
+        assert_eq!(get(addr).await, "child");
+    }
+
+    /// The new process spawned by the `handoff` test, which serves a single
+    /// request with the inherited socket.
+    #[tokio::test]
+    async fn handoff_child() {
+        if std::env::var_os(CHILD_ENV).is_none() {
+            return;
+        }
+
+        let listener = TcpListener::from_listen_fd_name("http").unwrap();
+        assert!(TcpListener::from_listen_fd_name("http").is_err());
+
+        let served = Arc::new(Notify::new());
+        let ep = make_sync({
+            let served = served.clone();
+            move |_| {
+                served.notify_one();
+                "child"
+            }
+        });
+        Server::new(listener)
+            .run_with_graceful_shutdown(
+                ep,
+                async move {
+                    let _ = tokio::time::timeout(Duration::from_secs(30), served.notified()).await;
+                },
+                Some(Duration::from_secs(5)),
+            )
+            .await
+            .unwrap();
+    }
+}
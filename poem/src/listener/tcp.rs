@@ -1,4 +1,4 @@
-use std::io::Result;
+use std::{io::Result, net::SocketAddr};
 
 use http::uri::Scheme;
 use tokio::{
@@ -11,15 +11,87 @@ use crate::{
     web::{LocalAddr, RemoteAddr},
 };
 
+enum Source<T> {
+    Bind(T),
+    Std(std::net::TcpListener),
+}
+
 /// A TCP listener.
 pub struct TcpListener<T> {
-    addr: T,
+    source: Source<T>,
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fd_name: Option<String>,
 }
 
 impl<T> TcpListener<T> {
     /// Binds to the provided address, and returns a [`TcpListener<T>`].
     pub fn bind(addr: T) -> Self {
-        Self { addr }
+        Self {
+            source: Source::Bind(addr),
+            #[cfg(all(unix, feature = "socket-activation"))]
+            fd_name: None,
+        }
+    }
+
+    /// Sets the name of the socket, which is passed in `LISTEN_FDNAMES` when
+    /// the socket is handed off to a new process.
+    ///
+    /// See also [`Handoff`](crate::listener::Handoff).
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    #[must_use]
+    pub fn with_fd_name(self, name: impl Into<String>) -> Self {
+        Self {
+            fd_name: Some(name.into()),
+            ..self
+        }
+    }
+}
+
+impl TcpListener<SocketAddr> {
+    /// Creates a [`TcpListener`] from a `std::net::TcpListener` which is
+    /// already bound.
+    pub fn from_std(listener: std::net::TcpListener) -> Self {
+        Self {
+            source: Source::Std(listener),
+            #[cfg(all(unix, feature = "socket-activation"))]
+            fd_name: None,
+        }
+    }
+
+    /// Creates a [`TcpListener`] from the socket at the specified index of the
+    /// sockets passed by systemd with the `LISTEN_FDS` environment variable.
+    ///
+    /// Returns an error of kind [`NotFound`](std::io::ErrorKind::NotFound) if
+    /// there is no such socket, or if it has already been taken.
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    pub fn from_listen_fd(index: usize) -> Result<Self> {
+        let (listener, fd_name) = super::socket_activation::take_listen_fd(index, |fds, index| {
+            fds.take_tcp_listener(index)
+        })?;
+        Ok(Self {
+            source: Source::Std(listener),
+            fd_name,
+        })
+    }
+
+    /// Creates a [`TcpListener`] from the first socket with the specified name
+    /// in the `LISTEN_FDNAMES` environment variable, of the sockets passed by
+    /// systemd with the `LISTEN_FDS` environment variable.
+    ///
+    /// Returns an error of kind [`NotFound`](std::io::ErrorKind::NotFound) if
+    /// there is no such socket, or if it has already been taken.
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    pub fn from_listen_fd_name(name: &str) -> Result<Self> {
+        let listener = super::socket_activation::take_listen_fd_by_name(name, |fds, index| {
+            fds.take_tcp_listener(index)
+        })?;
+        Ok(Self {
+            source: Source::Std(listener),
+            fd_name: Some(name.to_string()),
+        })
     }
 }
 
@@ -27,11 +99,19 @@ impl<T: ToSocketAddrs + Send> Listener for TcpListener<T> {
     type Acceptor = TcpAcceptor;
 
     async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
-        let listener = TokioTcpListener::bind(self.addr).await?;
+        let listener = match self.source {
+            Source::Bind(addr) => TokioTcpListener::bind(addr).await?,
+            Source::Std(listener) => {
+                listener.set_nonblocking(true)?;
+                TokioTcpListener::from_std(listener)?
+            }
+        };
         let local_addr = listener.local_addr().map(|addr| LocalAddr(addr.into()))?;
         Ok(TcpAcceptor {
             local_addr,
             listener,
+            #[cfg(all(unix, feature = "socket-activation"))]
+            fd_name: self.fd_name,
         })
     }
 }
@@ -40,6 +120,8 @@ impl<T: ToSocketAddrs + Send> Listener for TcpListener<T> {
 pub struct TcpAcceptor {
     local_addr: LocalAddr,
     listener: TokioTcpListener,
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fd_name: Option<String>,
 }
 
 impl TcpAcceptor {
@@ -49,6 +131,8 @@ impl TcpAcceptor {
         Ok(Self {
             local_addr,
             listener: TokioTcpListener::from_std(listener)?,
+            #[cfg(all(unix, feature = "socket-activation"))]
+            fd_name: None,
         })
     }
 
@@ -58,6 +142,8 @@ impl TcpAcceptor {
         Ok(Self {
             local_addr,
             listener,
+            #[cfg(all(unix, feature = "socket-activation"))]
+            fd_name: None,
         })
     }
 }
@@ -83,6 +169,14 @@ impl Acceptor for TcpAcceptor {
             )
         })
     }
+
+    #[cfg(all(unix, feature = "socket-activation"))]
+    fn listener_fds(&self) -> Result<Vec<super::ListenerFd>> {
+        Ok(vec![super::socket_activation::dup_listener_fd(
+            &self.listener,
+            self.fd_name.as_deref(),
+        )?])
+    }
 }
 
 #[cfg(test)]
@@ -107,4 +201,23 @@ mod tests {
         let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
         assert_eq!(stream.read_i32().await.unwrap(), 10);
     }
+
+    #[tokio::test]
+    async fn tcp_listener_from_std() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut acceptor = TcpListener::from_std(listener)
+            .into_acceptor()
+            .await
+            .unwrap();
+        assert_eq!(acceptor.local_addr()[0].as_socket_addr(), Some(&addr));
+
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+    }
 }
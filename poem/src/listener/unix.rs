@@ -1,7 +1,7 @@
 use std::{
     fs::{Permissions, set_permissions},
     io::Result,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use http::uri::Scheme;
@@ -16,21 +16,30 @@ use crate::{
     web::{LocalAddr, RemoteAddr},
 };
 
+enum Source<T> {
+    Bind(T),
+    Std(std::os::unix::net::UnixListener),
+}
+
 /// A Unix domain socket listener.
 #[cfg_attr(docsrs, doc(cfg(unix)))]
 pub struct UnixListener<T> {
-    path: T,
+    source: Source<T>,
     permissions: Option<Permissions>,
     owner: Option<(Option<Uid>, Option<Gid>)>,
+    #[cfg(feature = "socket-activation")]
+    fd_name: Option<String>,
 }
 
 impl<T> UnixListener<T> {
     /// Binds to the provided address, and returns a [`UnixListener<T>`].
     pub fn bind(path: T) -> Self {
         Self {
-            path,
+            source: Source::Bind(path),
             permissions: None,
             owner: None,
+            #[cfg(feature = "socket-activation")]
+            fd_name: None,
         }
     }
 
@@ -50,36 +59,102 @@ impl<T> UnixListener<T> {
             ..self
         }
     }
+
+    /// Sets the name of the socket, which is passed in `LISTEN_FDNAMES` when
+    /// the socket is handed off to a new process.
+    ///
+    /// See also [`Handoff`](crate::listener::Handoff).
+    #[cfg(feature = "socket-activation")]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    #[must_use]
+    pub fn with_fd_name(self, name: impl Into<String>) -> Self {
+        Self {
+            fd_name: Some(name.into()),
+            ..self
+        }
+    }
+}
+
+impl UnixListener<PathBuf> {
+    /// Creates a [`UnixListener`] from a `std::os::unix::net::UnixListener`
+    /// which is already bound.
+    ///
+    /// The permissions and the owner are only set when binding, so they are
+    /// ignored for this listener.
+    pub fn from_std(listener: std::os::unix::net::UnixListener) -> Self {
+        Self {
+            source: Source::Std(listener),
+            permissions: None,
+            owner: None,
+            #[cfg(feature = "socket-activation")]
+            fd_name: None,
+        }
+    }
+
+    /// Creates a [`UnixListener`] from the socket at the specified index of
+    /// the sockets passed by systemd with the `LISTEN_FDS` environment
+    /// variable.
+    ///
+    /// Returns an error of kind [`NotFound`](std::io::ErrorKind::NotFound) if
+    /// there is no such socket, or if it has already been taken.
+    #[cfg(feature = "socket-activation")]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    pub fn from_listen_fd(index: usize) -> Result<Self> {
+        let (listener, fd_name) = super::socket_activation::take_listen_fd(index, |fds, index| {
+            fds.take_unix_listener(index)
+        })?;
+        Ok(Self {
+            fd_name,
+            ..Self::from_std(listener)
+        })
+    }
+
+    /// Creates a [`UnixListener`] from the first socket with the specified
+    /// name in the `LISTEN_FDNAMES` environment variable, of the sockets
+    /// passed by systemd with the `LISTEN_FDS` environment variable.
+    ///
+    /// Returns an error of kind [`NotFound`](std::io::ErrorKind::NotFound) if
+    /// there is no such socket, or if it has already been taken.
+    #[cfg(feature = "socket-activation")]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    pub fn from_listen_fd_name(name: &str) -> Result<Self> {
+        let listener = super::socket_activation::take_listen_fd_by_name(name, |fds, index| {
+            fds.take_unix_listener(index)
+        })?;
+        Ok(Self {
+            fd_name: Some(name.to_string()),
+            ..Self::from_std(listener)
+        })
+    }
 }
 
 impl<T: AsRef<Path> + Send + Clone> Listener for UnixListener<T> {
     type Acceptor = UnixAcceptor;
 
     async fn into_acceptor(self) -> IoResult<Self::Acceptor> {
-        let listener = match (self.permissions, self.owner) {
-            (Some(permissions), Some((uid, gid))) => {
-                let listener = TokioUnixListener::bind(self.path.clone())?;
-                set_permissions(self.path.clone(), permissions)?;
-                chown(self.path.as_ref().as_os_str(), uid, gid)?;
+        let listener = match self.source {
+            Source::Bind(path) => {
+                let listener = TokioUnixListener::bind(path.clone())?;
+                if let Some(permissions) = self.permissions {
+                    set_permissions(path.clone(), permissions)?;
+                }
+                if let Some((uid, gid)) = self.owner {
+                    chown(path.as_ref().as_os_str(), uid, gid)?;
+                }
                 listener
             }
-            (Some(permissions), None) => {
-                let listener = TokioUnixListener::bind(self.path.clone())?;
-                set_permissions(self.path.clone(), permissions)?;
-                listener
-            }
-            (None, Some((uid, gid))) => {
-                let listener = TokioUnixListener::bind(self.path.clone())?;
-                chown(self.path.as_ref().as_os_str(), uid, gid)?;
-                listener
+            Source::Std(listener) => {
+                listener.set_nonblocking(true)?;
+                TokioUnixListener::from_std(listener)?
             }
-            (None, None) => TokioUnixListener::bind(self.path)?,
         };
 
         let local_addr = listener.local_addr().map(|addr| LocalAddr(addr.into()))?;
         Ok(UnixAcceptor {
             local_addr,
             listener,
+            #[cfg(feature = "socket-activation")]
+            fd_name: self.fd_name,
         })
     }
 }
@@ -89,6 +164,8 @@ impl<T: AsRef<Path> + Send + Clone> Listener for UnixListener<T> {
 pub struct UnixAcceptor {
     local_addr: LocalAddr,
     listener: TokioUnixListener,
+    #[cfg(feature = "socket-activation")]
+    fd_name: Option<String>,
 }
 
 impl UnixAcceptor {
@@ -99,6 +176,8 @@ impl UnixAcceptor {
         Ok(Self {
             local_addr,
             listener,
+            #[cfg(feature = "socket-activation")]
+            fd_name: None,
         })
     }
 }
@@ -121,6 +200,14 @@ impl Acceptor for UnixAcceptor {
             Scheme::HTTP,
         ))
     }
+
+    #[cfg(feature = "socket-activation")]
+    fn listener_fds(&self) -> Result<Vec<super::ListenerFd>> {
+        Ok(vec![super::socket_activation::dup_listener_fd(
+            &self.listener,
+            self.fd_name.as_deref(),
+        )?])
+    }
 }
 
 #[cfg(test)]
@@ -149,4 +236,24 @@ mod tests {
         drop(acceptor);
         std::fs::remove_file("test-socket").unwrap();
     }
+
+    #[tokio::test]
+    async fn unix_listener_from_std() {
+        let listener = std::os::unix::net::UnixListener::bind("test-socket-std").unwrap();
+        let mut acceptor = UnixListener::from_std(listener)
+            .into_acceptor()
+            .await
+            .unwrap();
+
+        tokio::spawn(async move {
+            let mut stream = UnixStream::connect("test-socket-std").await.unwrap();
+            stream.write_i32(10).await.unwrap();
+        });
+
+        let (mut stream, _, _, _) = acceptor.accept().await.unwrap();
+        assert_eq!(stream.read_i32().await.unwrap(), 10);
+
+        drop(acceptor);
+        std::fs::remove_file("test-socket-std").unwrap();
+    }
 }
@@ -0,0 +1,131 @@
+use std::{
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use parking_lot::Mutex;
+
+use crate::{
+    Result,
+    middleware::{CacheStorage, CachedResponse},
+};
+
+struct Entry {
+    responses: Vec<CachedResponse>,
+    expires_at: Instant,
+}
+
+/// A cache storage using memory, which holds up to a fixed number of
+/// resources and evicts the least recently used one when it is full.
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub struct MemoryCacheStorage {
+    entries: Mutex<LruCache<String, Entry>>,
+}
+
+impl MemoryCacheStorage {
+    /// Create a `MemoryCacheStorage` which holds up to `capacity` resources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).expect("the capacity must be greater than zero");
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl CacheStorage for MemoryCacheStorage {
+    async fn load<'a>(&'a self, key: &'a str) -> Result<Option<Vec<CachedResponse>>> {
+        let mut entries = self.entries.lock();
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Ok(Some(entry.responses.clone())),
+            Some(_) => {
+                entries.pop(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn store<'a>(
+        &'a self,
+        key: &'a str,
+        responses: Vec<CachedResponse>,
+        ttl: Duration,
+    ) -> Result<()> {
+        self.entries.lock().put(
+            key.to_string(),
+            Entry {
+                responses,
+                expires_at: Instant::now() + ttl,
+            },
+        );
+        Ok(())
+    }
+
+    async fn remove<'a>(&'a self, key: &'a str) -> Result<()> {
+        self.entries.lock().pop(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use bytes::Bytes;
+    use http::{HeaderMap, StatusCode};
+
+    use super::*;
+
+    fn response(body: &'static str) -> Vec<CachedResponse> {
+        vec![CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+            vary: Vec::new(),
+            stored_at: SystemTime::now(),
+            max_age: Duration::from_secs(60),
+            stale_while_revalidate: Duration::ZERO,
+        }]
+    }
+
+    async fn load_body(storage: &MemoryCacheStorage, key: &str) -> Option<Bytes> {
+        storage
+            .load(key)
+            .await
+            .unwrap()
+            .map(|responses| responses[0].body.clone())
+    }
+
+    #[tokio::test]
+    async fn evict_least_recently_used() {
+        let storage = MemoryCacheStorage::new(2);
+        let ttl = Duration::from_secs(60);
+
+        storage.store("a", response("a"), ttl).await.unwrap();
+        storage.store("b", response("b"), ttl).await.unwrap();
+        assert_eq!(load_body(&storage, "a").await.as_deref(), Some(&b"a"[..]));
+
+        storage.store("c", response("c"), ttl).await.unwrap();
+        assert_eq!(load_body(&storage, "a").await.as_deref(), Some(&b"a"[..]));
+        assert_eq!(load_body(&storage, "b").await, None);
+        assert_eq!(load_body(&storage, "c").await.as_deref(), Some(&b"c"[..]));
+
+        storage.remove("a").await.unwrap();
+        assert_eq!(load_body(&storage, "a").await, None);
+    }
+
+    #[tokio::test]
+    async fn expired() {
+        let storage = MemoryCacheStorage::new(1);
+        storage
+            .store("a", response("a"), Duration::ZERO)
+            .await
+            .unwrap();
+        assert_eq!(load_body(&storage, "a").await, None);
+    }
+}
@@ -0,0 +1,617 @@
+mod memory_storage;
+mod storage;
+
+use std::{
+    collections::{HashMap, hash_map},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+pub use memory_storage::MemoryCacheStorage;
+use parking_lot::Mutex;
+pub use storage::{CacheStorage, CachedResponse};
+use tokio::sync::watch;
+
+use self::storage::join_header_values;
+use crate::{
+    Body, Endpoint, IntoResponse, Middleware, Request, Response, Result,
+    http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, header},
+};
+
+/// The status codes of the responses that can be stored.
+const CACHEABLE_STATUS: &[StatusCode] = &[
+    StatusCode::OK,
+    StatusCode::NON_AUTHORITATIVE_INFORMATION,
+    StatusCode::NO_CONTENT,
+    StatusCode::MULTIPLE_CHOICES,
+    StatusCode::MOVED_PERMANENTLY,
+    StatusCode::PERMANENT_REDIRECT,
+    StatusCode::NOT_FOUND,
+    StatusCode::METHOD_NOT_ALLOWED,
+    StatusCode::GONE,
+    StatusCode::URI_TOO_LONG,
+    StatusCode::NOT_IMPLEMENTED,
+];
+
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    private: bool,
+    public: bool,
+    must_revalidate: bool,
+    max_age: Option<Duration>,
+    s_maxage: Option<Duration>,
+    stale_while_revalidate: Option<Duration>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut cache_control = Self::default();
+        let directives = headers
+            .get_all(header::CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+
+        for directive in directives {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive.trim(), None),
+            };
+            let seconds = || {
+                value
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .map(Duration::from_secs)
+            };
+
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" => cache_control.no_store = true,
+                "no-cache" => cache_control.no_cache = true,
+                "private" => cache_control.private = true,
+                "public" => cache_control.public = true,
+                "must-revalidate" => cache_control.must_revalidate = true,
+                "max-age" => cache_control.max_age = seconds(),
+                "s-maxage" => cache_control.s_maxage = seconds(),
+                "stale-while-revalidate" => cache_control.stale_while_revalidate = seconds(),
+                _ => {}
+            }
+        }
+
+        cache_control
+    }
+}
+
+/// Middleware for caching responses, which acts as a shared cache.
+///
+/// `GET` and `HEAD` responses are stored with a key made of the method, the
+/// host and the URI of the request, and the values of the request headers
+/// named in the `Vary` header of the response. The `Cache-Control` header of
+/// the response is respected:
+///
+/// - `no-store`, `no-cache` and `private` responses are not stored.
+/// - `s-maxage` or `max-age` sets how long the response is fresh. Responses
+///   without them are only stored if a
+///   [`default_max_age`](Cache::default_max_age) is set.
+/// - `stale-while-revalidate` sets how long a stale response can still be
+///   served, while it is fetched again in the background.
+///
+/// Responses with a `Set-Cookie` header or with `Vary: *` are never stored,
+/// and responses to requests with an `Authorization` header are only stored
+/// if they are marked as `public`, `s-maxage` or `must-revalidate`.
+///
+/// Requests with `Cache-Control: no-cache` or `max-age=0` skip the stored
+/// responses, and requests with `Cache-Control: no-store` bypass the cache.
+/// A successful request with an unsafe method, such as `POST`, removes the
+/// stored responses of its URI.
+///
+/// Concurrent requests that miss the cache are coalesced, so the inner
+/// endpoint is only called once and the other requests are answered with the
+/// stored response.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Response, Route, get, handler,
+///     http::header,
+///     middleware::{Cache, MemoryCacheStorage},
+///     test::TestClient,
+/// };
+///
+/// #[handler]
+/// fn index() -> Response {
+///     Response::builder()
+///         .header(header::CACHE_CONTROL, "max-age=60")
+///         .body("hello")
+/// }
+///
+/// let app = Route::new()
+///     .at("/", get(index))
+///     .with(Cache::new(MemoryCacheStorage::new(1024)));
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// cli.get("/").send().await.assert_text("hello").await;
+///
+/// let resp = cli.get("/").send().await;
+/// resp.assert_header(header::AGE, "0");
+/// resp.assert_text("hello").await;
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub struct Cache<S> {
+    storage: Arc<S>,
+    default_max_age: Option<Duration>,
+}
+
+impl<S> Cache<S> {
+    /// Create a `Cache` middleware.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage: Arc::new(storage),
+            default_max_age: None,
+        }
+    }
+
+    /// Sets how long the responses without `s-maxage` or `max-age` are fresh.
+    ///
+    /// By default, these responses are not stored.
+    #[must_use]
+    pub fn default_max_age(self, max_age: Duration) -> Self {
+        Self {
+            default_max_age: Some(max_age),
+            ..self
+        }
+    }
+}
+
+impl<S, E> Middleware<E> for Cache<S>
+where
+    S: CacheStorage + 'static,
+    E: Endpoint + 'static,
+{
+    type Output = CacheEndpoint<S, E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        CacheEndpoint {
+            inner: Arc::new(Inner {
+                ep,
+                storage: self.storage.clone(),
+                default_max_age: self.default_max_age,
+                inflight: Default::default(),
+            }),
+        }
+    }
+}
+
+/// Endpoint for the `Cache` middleware.
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub struct CacheEndpoint<S, E> {
+    inner: Arc<Inner<S, E>>,
+}
+
+struct Inner<S, E> {
+    ep: E,
+    storage: Arc<S>,
+    default_max_age: Option<Duration>,
+    inflight: Mutex<HashMap<String, watch::Receiver<()>>>,
+}
+
+/// Marks a key as being fetched, until it is dropped.
+struct InflightGuard<S, E> {
+    inner: Arc<Inner<S, E>>,
+    key: String,
+    // the waiting requests are notified when the sender is dropped
+    _tx: watch::Sender<()>,
+}
+
+impl<S, E> Drop for InflightGuard<S, E> {
+    fn drop(&mut self) {
+        self.inner.inflight.lock().remove(&self.key);
+    }
+}
+
+impl<S, E> Inner<S, E>
+where
+    S: CacheStorage + 'static,
+    E: Endpoint + 'static,
+{
+    /// Returns a stored response which can be used for the request.
+    async fn lookup(&self, key: &str, headers: &HeaderMap) -> Result<Option<CachedResponse>> {
+        let now = SystemTime::now();
+        Ok(self
+            .storage
+            .load(key)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .find(|cached| cached.expires_at() > now && cached.matches(headers)))
+    }
+
+    /// Marks the key as being fetched, or returns a receiver which is notified
+    /// when the fetch in progress completes.
+    fn begin_fetch(
+        self: &Arc<Self>,
+        key: &str,
+    ) -> Result<InflightGuard<S, E>, watch::Receiver<()>> {
+        match self.inflight.lock().entry(key.to_string()) {
+            hash_map::Entry::Occupied(entry) => Err(entry.get().clone()),
+            hash_map::Entry::Vacant(entry) => {
+                let (tx, rx) = watch::channel(());
+                entry.insert(rx);
+                Ok(InflightGuard {
+                    inner: self.clone(),
+                    key: key.to_string(),
+                    _tx: tx,
+                })
+            }
+        }
+    }
+
+    /// Fetches the response again in the background.
+    fn revalidate(self: &Arc<Self>, key: String, req: &Request) {
+        let Ok(guard) = self.begin_fetch(&key) else {
+            return;
+        };
+        let req = req.clone_without_body();
+        let inner = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = inner.fetch(&key, req).await {
+                tracing::debug!(error = %err, "failed to revalidate the cached response");
+            }
+            drop(guard);
+        });
+    }
+
+    /// Calls the inner endpoint and stores the response if it is cacheable.
+    async fn fetch(&self, key: &str, req: Request) -> Result<Response> {
+        let headers = req.headers().clone();
+        let resp = self.ep.call(req).await?.into_response();
+        let Some(mut cached) = self.cacheable(&headers, &resp) else {
+            return Ok(resp);
+        };
+
+        let (parts, body) = resp.into_parts();
+        let body = body.into_bytes().await?;
+        cached.headers = parts.headers.clone();
+        cached.body = body.clone();
+
+        // replace the variant selected by the request, and remove the expired ones
+        let now = cached.stored_at;
+        let mut responses = self.storage.load(key).await?.unwrap_or_default();
+        responses.retain(|response| response.expires_at() > now && !response.matches(&headers));
+        responses.push(cached);
+        let ttl = responses
+            .iter()
+            .filter_map(|response| response.expires_at().duration_since(now).ok())
+            .max()
+            .unwrap_or_default();
+        self.storage.store(key, responses, ttl).await?;
+
+        Ok(Response::from_parts(parts, Body::from(body)))
+    }
+
+    /// Returns a `CachedResponse` without the headers and body if the response
+    /// can be stored.
+    fn cacheable(&self, req_headers: &HeaderMap, resp: &Response) -> Option<CachedResponse> {
+        if !CACHEABLE_STATUS.contains(&resp.status())
+            || resp.headers().contains_key(header::SET_COOKIE)
+        {
+            return None;
+        }
+
+        let cache_control = CacheControl::parse(resp.headers());
+        if cache_control.no_store || cache_control.no_cache || cache_control.private {
+            return None;
+        }
+        if req_headers.contains_key(header::AUTHORIZATION)
+            && !cache_control.public
+            && !cache_control.must_revalidate
+            && cache_control.s_maxage.is_none()
+        {
+            return None;
+        }
+
+        let max_age = cache_control
+            .s_maxage
+            .or(cache_control.max_age)
+            .or(self.default_max_age)?;
+        let stale_while_revalidate = if cache_control.must_revalidate {
+            Duration::ZERO
+        } else {
+            cache_control.stale_while_revalidate.unwrap_or_default()
+        };
+        if max_age.is_zero() && stale_while_revalidate.is_zero() {
+            return None;
+        }
+
+        let mut vary = Vec::new();
+        let names = resp
+            .headers()
+            .get_all(header::VARY)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        for name in names {
+            let name = HeaderName::try_from(name).ok()?;
+            let value = join_header_values(req_headers, &name);
+            vary.push((name, value));
+        }
+
+        Some(CachedResponse {
+            status: resp.status(),
+            headers: HeaderMap::new(),
+            body: Default::default(),
+            vary,
+            stored_at: SystemTime::now(),
+            max_age,
+            stale_while_revalidate,
+        })
+    }
+}
+
+impl<S, E> Endpoint for CacheEndpoint<S, E>
+where
+    S: CacheStorage + 'static,
+    E: Endpoint + 'static,
+{
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let inner = &self.inner;
+        let method = req.method().clone();
+
+        if method != Method::GET && method != Method::HEAD {
+            let keys = [
+                cache_key(&Method::GET, &req),
+                cache_key(&Method::HEAD, &req),
+            ];
+            let resp = inner.ep.call(req).await?.into_response();
+            if !method.is_safe() && (resp.status().is_success() || resp.status().is_redirection()) {
+                for key in &keys {
+                    inner.storage.remove(key).await?;
+                }
+            }
+            return Ok(resp);
+        }
+
+        let cache_control = CacheControl::parse(req.headers());
+        if cache_control.no_store {
+            return inner.ep.call(req).await.map(IntoResponse::into_response);
+        }
+
+        let key = cache_key(&method, &req);
+        if !cache_control.no_cache && cache_control.max_age != Some(Duration::ZERO) {
+            if let Some(cached) = inner.lookup(&key, req.headers()).await? {
+                let now = SystemTime::now();
+                if cached.age(now) >= cached.max_age {
+                    inner.revalidate(key, &req);
+                }
+                return Ok(cached_response(cached, now));
+            }
+        }
+
+        match inner.begin_fetch(&key) {
+            Ok(guard) => {
+                let resp = inner.fetch(&key, req).await;
+                drop(guard);
+                resp
+            }
+            Err(mut rx) => {
+                // another request is fetching the same resource, wait for it to be stored
+                let _ = rx.changed().await;
+                match inner.lookup(&key, req.headers()).await? {
+                    Some(cached) => Ok(cached_response(cached, SystemTime::now())),
+                    None => inner.fetch(&key, req).await,
+                }
+            }
+        }
+    }
+}
+
+fn cache_key(method: &Method, req: &Request) -> String {
+    let uri = req.original_uri();
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .or_else(|| uri.authority().map(|authority| authority.as_str()))
+        .unwrap_or_default();
+    let path_and_query = uri
+        .path_and_query()
+        .map(|path_and_query| path_and_query.as_str())
+        .unwrap_or("/");
+    format!("{method} {host}{path_and_query}")
+}
+
+fn cached_response(cached: CachedResponse, now: SystemTime) -> Response {
+    let age = cached.age(now);
+    let mut resp = Response::builder().status(cached.status).body(cached.body);
+    *resp.headers_mut() = cached.headers;
+    resp.headers_mut()
+        .insert(header::AGE, HeaderValue::from(age.as_secs()));
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures_util::future::join_all;
+
+    use super::*;
+    use crate::{EndpointExt, endpoint::make, test::TestClient};
+
+    fn counter_endpoint(
+        cache_control: &'static str,
+    ) -> (Arc<AtomicUsize>, impl Endpoint<Output = Response>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let ep = make({
+            let count = count.clone();
+            move |req| {
+                let count = count.clone();
+                async move {
+                    let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+                    let language = req
+                        .header("accept-language")
+                        .unwrap_or_default()
+                        .to_string();
+                    Response::builder()
+                        .header(header::CACHE_CONTROL, cache_control)
+                        .header(header::VARY, "accept-language")
+                        .body(format!("{n}{language}"))
+                }
+            }
+        });
+        (count, ep)
+    }
+
+    #[tokio::test]
+    async fn cache_response() {
+        let (count, ep) = counter_endpoint("max-age=60");
+        let cli = TestClient::new(ep.with(Cache::new(MemoryCacheStorage::new(16))));
+
+        let resp = cli.get("/").send().await;
+        resp.assert_header_is_not_exist(header::AGE);
+        resp.assert_text("1").await;
+
+        let resp = cli.get("/").send().await;
+        resp.assert_header(header::AGE, "0");
+        resp.assert_header(header::CACHE_CONTROL, "max-age=60");
+        resp.assert_text("1").await;
+
+        cli.get("/a").send().await.assert_text("2").await;
+        cli.get("/")
+            .query("a", &1)
+            .send()
+            .await
+            .assert_text("3")
+            .await;
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        // the request bypasses the stored response
+        cli.get("/")
+            .header(header::CACHE_CONTROL, "no-cache")
+            .send()
+            .await
+            .assert_text("4")
+            .await;
+        cli.get("/").send().await.assert_text("4").await;
+    }
+
+    #[tokio::test]
+    async fn vary() {
+        let (count, ep) = counter_endpoint("max-age=60");
+        let cli = TestClient::new(ep.with(Cache::new(MemoryCacheStorage::new(16))));
+
+        for _ in 0..2 {
+            cli.get("/")
+                .header("accept-language", "en")
+                .send()
+                .await
+                .assert_text("1en")
+                .await;
+            cli.get("/")
+                .header("accept-language", "fr")
+                .send()
+                .await
+                .assert_text("2fr")
+                .await;
+            cli.get("/").send().await.assert_text("3").await;
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn not_cacheable() {
+        for cache_control in ["no-store", "no-cache", "private, max-age=60", "max-age=0"] {
+            let (count, ep) = counter_endpoint(cache_control);
+            let cli = TestClient::new(ep.with(Cache::new(MemoryCacheStorage::new(16))));
+            cli.get("/").send().await.assert_text("1").await;
+            cli.get("/").send().await.assert_text("2").await;
+            assert_eq!(count.load(Ordering::SeqCst), 2);
+        }
+
+        // requests with credentials
+        let (_, ep) = counter_endpoint("max-age=60");
+        let cli = TestClient::new(ep.with(Cache::new(MemoryCacheStorage::new(16))));
+        for expected in ["1", "2"] {
+            cli.get("/")
+                .header(header::AUTHORIZATION, "Bearer abc")
+                .send()
+                .await
+                .assert_text(expected)
+                .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn default_max_age() {
+        let (count, ep) = counter_endpoint("public");
+        let cli = TestClient::new(ep.with(
+            Cache::new(MemoryCacheStorage::new(16)).default_max_age(Duration::from_secs(60)),
+        ));
+        cli.get("/").send().await.assert_text("1").await;
+        cli.get("/").send().await.assert_text("1").await;
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn invalidate() {
+        let (count, ep) = counter_endpoint("max-age=60");
+        let cli = TestClient::new(ep.with(Cache::new(MemoryCacheStorage::new(16))));
+
+        cli.get("/").send().await.assert_text("1").await;
+        cli.post("/").send().await.assert_text("2").await;
+        cli.get("/").send().await.assert_text("3").await;
+        cli.get("/").send().await.assert_text("3").await;
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn stale_while_revalidate() {
+        let (count, ep) = counter_endpoint("max-age=1, stale-while-revalidate=60");
+        let cli = TestClient::new(ep.with(Cache::new(MemoryCacheStorage::new(16))));
+
+        cli.get("/").send().await.assert_text("1").await;
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        // the stale response is served, and fetched again in the background
+        let resp = cli.get("/").send().await;
+        resp.assert_header(header::AGE, "1");
+        resp.assert_text("1").await;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        cli.get("/").send().await.assert_text("2").await;
+    }
+
+    #[tokio::test]
+    async fn coalesce_requests() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let ep = make({
+            let count = count.clone();
+            move |_| {
+                let count = count.clone();
+                async move {
+                    count.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    Response::builder()
+                        .header(header::CACHE_CONTROL, "max-age=60")
+                        .body("hello")
+                }
+            }
+        });
+        let cli = TestClient::new(ep.with(Cache::new(MemoryCacheStorage::new(16))));
+
+        let responses = join_all((0..10).map(|_| cli.get("/").send())).await;
+        for resp in responses {
+            resp.assert_status_is_ok();
+            resp.assert_text("hello").await;
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    future::Future,
+    time::{Duration, SystemTime},
+};
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+use crate::Result;
+
+/// A response stored by the [`Cache`](super::Cache) middleware.
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub struct CachedResponse {
+    /// The status code of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Bytes,
+    /// The request headers named in the `Vary` header of the response, with
+    /// the values they had in the request that produced the response.
+    pub vary: Vec<(HeaderName, Option<HeaderValue>)>,
+    /// The time at which the response was stored.
+    pub stored_at: SystemTime,
+    /// How long the response is fresh after it was stored.
+    pub max_age: Duration,
+    /// How long the response can be served after it became stale, while it is
+    /// revalidated in the background.
+    pub stale_while_revalidate: Duration,
+}
+
+impl CachedResponse {
+    /// Returns `true` if the response can be used for a request with the
+    /// specified headers.
+    pub fn matches(&self, headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, value)| join_header_values(headers, name).as_ref() == value.as_ref())
+    }
+
+    /// Returns the age of the response.
+    pub fn age(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.stored_at).unwrap_or_default()
+    }
+
+    /// Returns the time after which the response can no longer be used.
+    pub fn expires_at(&self) -> SystemTime {
+        self.stored_at + self.max_age + self.stale_while_revalidate
+    }
+}
+
+/// Returns all the values of a header, joined with commas.
+pub(crate) fn join_header_values(headers: &HeaderMap, name: &HeaderName) -> Option<HeaderValue> {
+    let mut values = headers.get_all(name).iter();
+    let first = values.next()?;
+    let mut joined = first.as_bytes().to_vec();
+    for value in values {
+        joined.extend_from_slice(b", ");
+        joined.extend_from_slice(value.as_bytes());
+    }
+    HeaderValue::from_bytes(&joined).ok()
+}
+
+/// Represents a back-end cache storage.
+///
+/// All the variants of a resource, which are the responses selected by
+/// different values of the headers named in `Vary`, are stored under the same
+/// key.
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub trait CacheStorage: Send + Sync {
+    /// Load the variants stored under `key`.
+    fn load<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> impl Future<Output = Result<Option<Vec<CachedResponse>>>> + Send + 'a;
+
+    /// Insert or update the variants stored under `key`, which can be removed
+    /// after `ttl`.
+    fn store<'a>(
+        &'a self,
+        key: &'a str,
+        responses: Vec<CachedResponse>,
+        ttl: Duration,
+    ) -> impl Future<Output = Result<()>> + Send + 'a;
+
+    /// Remove the variants stored under `key`.
+    fn remove<'a>(&'a self, key: &'a str) -> impl Future<Output = Result<()>> + Send + 'a;
+}
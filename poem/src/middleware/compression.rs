@@ -1,6 +1,6 @@
 use std::{collections::HashSet, str::FromStr};
 
-use headers::HeaderMap;
+use headers::{HeaderMap, HeaderMapExt};
 
 use crate::{
     Body, Endpoint, IntoResponse, Middleware, Request, Response, Result,
@@ -118,16 +118,82 @@ fn parse_accept_encoding(
         .map(|(coding, _)| coding)
 }
 
+/// Returns `true` if the content type matches any of the patterns, such as
+/// `text/html`, or `image/*` for all the subtypes.
+fn match_content_type(patterns: &HashSet<String>, content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    patterns.contains(&essence)
+        || essence
+            .split_once('/')
+            .is_some_and(|(ty, _)| patterns.contains(&format!("{ty}/*")))
+}
+
+fn normalize_content_types(
+    content_types: impl IntoIterator<Item = impl AsRef<str>>,
+) -> HashSet<String> {
+    content_types
+        .into_iter()
+        .map(|content_type| content_type.as_ref().trim().to_ascii_lowercase())
+        .collect()
+}
+
 /// Middleware to decompress the request body and compress the response body.
 ///
 /// The decompression algorithm is selected according to the request
 /// `Content-Encoding` header, and the compression algorithm is selected
 /// according to the request `Accept-Encoding` header.
+///
+/// Responses which already have a `Content-Encoding` header are never
+/// compressed. Use [`Compression::min_size`],
+/// [`Compression::content_types`] and [`Compression::exclude_content_types`]
+/// to skip the small bodies and the content types that do not benefit from
+/// compression, such as images.
+///
+/// # Example
+///
+/// ```
+/// use poem::{EndpointExt, Route, get, handler, middleware::Compression, test::TestClient};
+///
+/// #[handler]
+/// fn index() -> String {
+///     "hello".repeat(100)
+/// }
+///
+/// #[handler]
+/// fn small() -> &'static str {
+///     "hello"
+/// }
+///
+/// let app = Route::new().at("/", get(index)).at("/small", get(small)).with(
+///     Compression::new()
+///         .min_size(64)
+///         .exclude_content_types(["image/*", "video/*"]),
+/// );
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = cli.get("/").header("accept-encoding", "gzip").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_header("content-encoding", "gzip");
+///
+/// let resp = cli.get("/small").header("accept-encoding", "gzip").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_header_is_not_exist("content-encoding");
+/// # });
+/// ```
 #[cfg_attr(docsrs, doc(cfg(feature = "compression")))]
 #[derive(Default)]
 pub struct Compression {
     level: Option<CompressionLevel>,
     algorithms: HashSet<CompressionAlgo>,
+    min_size: usize,
+    content_types: HashSet<String>,
+    exclude_content_types: HashSet<String>,
 }
 
 impl Compression {
@@ -156,6 +222,46 @@ impl Compression {
             ..self
         }
     }
+
+    /// Specify the minimum size of the response body to compress (defaults to
+    /// `0`)
+    ///
+    /// The size is taken from the `Content-Length` header or the body itself,
+    /// and the responses with an unknown size, such as streams, are always
+    /// compressed.
+    #[must_use]
+    #[inline]
+    pub fn min_size(self, min_size: usize) -> Self {
+        Self { min_size, ..self }
+    }
+
+    /// Specify the content types to compress (defaults to all)
+    ///
+    /// Each content type can be a MIME type such as `text/html`, or `text/*`
+    /// for all the subtypes. If this is set, the responses without a
+    /// `Content-Type` header are not compressed.
+    #[must_use]
+    pub fn content_types(self, content_types: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        Self {
+            content_types: normalize_content_types(content_types),
+            ..self
+        }
+    }
+
+    /// Specify the content types that are never compressed, such as
+    /// `image/*`
+    ///
+    /// This takes precedence over [`Compression::content_types`].
+    #[must_use]
+    pub fn exclude_content_types(
+        self,
+        content_types: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        Self {
+            exclude_content_types: normalize_content_types(content_types),
+            ..self
+        }
+    }
 }
 
 impl<E: Endpoint> Middleware<E> for Compression {
@@ -166,6 +272,9 @@ impl<E: Endpoint> Middleware<E> for Compression {
             ep,
             level: self.level,
             algorithms: self.algorithms.clone(),
+            min_size: self.min_size,
+            content_types: self.content_types.clone(),
+            exclude_content_types: self.exclude_content_types.clone(),
         }
     }
 }
@@ -176,6 +285,41 @@ pub struct CompressionEndpoint<E: Endpoint> {
     ep: E,
     level: Option<CompressionLevel>,
     algorithms: HashSet<CompressionAlgo>,
+    min_size: usize,
+    content_types: HashSet<String>,
+    exclude_content_types: HashSet<String>,
+}
+
+impl<E: Endpoint> CompressionEndpoint<E> {
+    fn should_compress(&self, resp: &mut Response) -> bool {
+        if resp.headers().contains_key(header::CONTENT_ENCODING) {
+            return false;
+        }
+
+        if self.min_size > 0 {
+            let size = match resp.headers().typed_get::<headers::ContentLength>() {
+                Some(content_length) => Some(content_length.0),
+                None => {
+                    let body = resp.take_body();
+                    let size = body.exact_size();
+                    resp.set_body(body);
+                    size
+                }
+            };
+            if size.is_some_and(|size| size < self.min_size as u64) {
+                return false;
+            }
+        }
+
+        match resp.content_type() {
+            Some(content_type) => {
+                !match_content_type(&self.exclude_content_types, content_type)
+                    && (self.content_types.is_empty()
+                        || match_content_type(&self.content_types, content_type))
+            }
+            None => self.content_types.is_empty(),
+        }
+    }
 }
 
 impl<E: Endpoint> Endpoint for CompressionEndpoint<E> {
@@ -197,16 +341,16 @@ impl<E: Endpoint> Endpoint for CompressionEndpoint<E> {
         let compress_algo = parse_accept_encoding(req.headers(), &self.algorithms)
             .and_then(|coding| coding.to_compression_algo(&self.algorithms));
 
-        let resp = self.ep.call(req).await?;
+        let mut resp = self.ep.call(req).await?.into_response();
         match compress_algo {
-            Some(algo) => {
+            Some(algo) if self.should_compress(&mut resp) => {
                 let mut compress = Compress::new(resp, algo);
                 if let Some(level) = self.level {
                     compress = compress.with_quality(level);
                 }
                 Ok(compress.into_response())
             }
-            None => Ok(resp.into_response()),
+            _ => Ok(resp),
         }
     }
 }
@@ -380,4 +524,78 @@ mod tests {
         resp.assert_status_is_ok();
         resp.assert_header("Content-Encoding", "br");
     }
+
+    #[tokio::test]
+    async fn test_predicates() {
+        #[handler(internal)]
+        fn file(req: &Request) -> Response {
+            let content_type = req
+                .uri()
+                .path()
+                .trim_start_matches('/')
+                .replacen('-', "/", 1);
+            Response::builder().content_type(content_type).body(DATA)
+        }
+
+        let ep = file.with(
+            Compression::default()
+                .content_types(["text/*", "application/json"])
+                .exclude_content_types(["text/csv"]),
+        );
+        let cli = TestClient::new(ep);
+
+        for (path, compressed) in [
+            ("/text-plain", true),
+            ("/application-json", true),
+            ("/text-csv", false),
+            ("/image-png", false),
+        ] {
+            let resp = cli.get(path).header("Accept-Encoding", "gzip").send().await;
+            resp.assert_status_is_ok();
+            if compressed {
+                resp.assert_header("Content-Encoding", "gzip");
+            } else {
+                resp.assert_header_is_not_exist("Content-Encoding");
+                resp.assert_text(DATA).await;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_min_size() {
+        let cli = TestClient::new(index.with(Compression::default().min_size(DATA.len())));
+        let resp = cli
+            .post("/")
+            .header("Accept-Encoding", "gzip")
+            .body(DATA)
+            .send()
+            .await;
+        resp.assert_header("Content-Encoding", "gzip");
+
+        let cli = TestClient::new(index.with(Compression::default().min_size(DATA.len() + 1)));
+        let resp = cli
+            .post("/")
+            .header("Accept-Encoding", "gzip")
+            .body(DATA)
+            .send()
+            .await;
+        resp.assert_header_is_not_exist("Content-Encoding");
+        resp.assert_text(DATA_REV).await;
+    }
+
+    #[tokio::test]
+    async fn test_already_encoded() {
+        #[handler(internal)]
+        fn encoded() -> Response {
+            Response::builder()
+                .header(header::CONTENT_ENCODING, "br")
+                .body(DATA)
+        }
+
+        let cli = TestClient::new(encoded.with(Compression::default()));
+        let resp = cli.get("/").header("Accept-Encoding", "gzip").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header("Content-Encoding", "br");
+        resp.assert_text(DATA).await;
+    }
 }
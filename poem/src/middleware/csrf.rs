@@ -12,6 +12,7 @@ use crate::{
     web::{
         CsrfToken, CsrfVerifier,
         cookie::{Cookie, SameSite},
+        parse_csrf_with_keys,
     },
 };
 
@@ -78,6 +79,7 @@ use crate::{
 pub struct Csrf {
     cookie_name: String,
     key: [u8; 32],
+    previous_keys: Vec<[u8; 32]>,
     secure: bool,
     http_only: bool,
     same_site: Option<SameSite>,
@@ -93,6 +95,7 @@ impl Default for Csrf {
         Self {
             cookie_name: "poem-csrf-token".to_string(),
             key: Default::default(),
+            previous_keys: Vec::new(),
             secure: true,
             http_only: true,
             same_site: Some(SameSite::Strict),
@@ -123,6 +126,20 @@ impl Csrf {
         Self { key, ..self }
     }
 
+    /// Sets the previous AES256 keys for key rotation, ordered from the newest
+    /// to the oldest.
+    ///
+    /// The tokens and cookies are always encrypted with the current key, but
+    /// those created with a previous key are still accepted, and the cookie is
+    /// re-issued with the current key.
+    #[must_use]
+    pub fn previous_keys(self, keys: impl IntoIterator<Item = [u8; 32]>) -> Self {
+        Self {
+            previous_keys: keys.into_iter().collect(),
+            ..self
+        }
+    }
+
     /// Sets, whether `Secure` is set for the csrf cookie. Defaults to `true`.
     #[must_use]
     pub fn secure(self, value: bool) -> Self {
@@ -179,7 +196,10 @@ impl<E: Endpoint> Middleware<E> for Csrf {
     fn transform(&self, ep: E) -> Self::Output {
         CookieJarManager::new().transform(CsrfEndpoint {
             inner: ep,
-            protect: Arc::new(AesGcmCsrfProtection::from_key(self.key)),
+            protect: std::iter::once(&self.key)
+                .chain(&self.previous_keys)
+                .map(|key| AesGcmCsrfProtection::from_key(*key))
+                .collect(),
             cookie_name: self.cookie_name.clone(),
             secure: self.secure,
             http_only: self.http_only,
@@ -194,7 +214,8 @@ impl<E: Endpoint> Middleware<E> for Csrf {
 #[cfg_attr(docsrs, doc(cfg(feature = "csrf")))]
 pub struct CsrfEndpoint<E> {
     inner: E,
-    protect: Arc<AesGcmCsrfProtection>,
+    // the current key first, then the previous keys
+    protect: Arc<[AesGcmCsrfProtection]>,
     cookie_name: String,
     secure: bool,
     http_only: bool,
@@ -219,7 +240,7 @@ impl<E> CsrfEndpoint<E> {
             }
         });
 
-        self.protect
+        self.protect[0]
             .generate_token_pair(existing_cookie_bytes.as_ref(), self.ttl.as_secs() as i64)
             .expect("couldn't generate token/cookie pair")
     }
@@ -233,7 +254,9 @@ impl<E: Endpoint> Endpoint for CsrfEndpoint<E> {
             .cookie()
             .get(&self.cookie_name)
             .and_then(|cookie| STANDARD.decode(cookie.value_str()).ok())
-            .and_then(|value| self.protect.parse_cookie(&value).ok());
+            .and_then(|value| {
+                parse_csrf_with_keys(&self.protect, |protect| protect.parse_cookie(&value)).ok()
+            });
 
         let (token, cookie) = self.generate_token(existing_cookie.as_ref());
         let csrf_cookie = {
@@ -335,4 +358,67 @@ mod tests {
             "invalid token"
         );
     }
+
+    #[tokio::test]
+    async fn previous_keys() {
+        #[handler(internal)]
+        fn login_ui(token: &CsrfToken) -> impl IntoResponse {
+            token.0.to_string()
+        }
+
+        #[handler(internal)]
+        fn login(verifier: &CsrfVerifier, req: &Request) -> impl IntoResponse {
+            verifier
+                .is_valid(req.header(CSRF_TOKEN_NAME).unwrap_or_default())
+                .to_string()
+        }
+
+        let old_key = [1; 32];
+        let new_key = [2; 32];
+        let old_app = get(login_ui).post(login).with(Csrf::new().key(old_key));
+        let new_app = get(login_ui)
+            .post(login)
+            .with(Csrf::new().key(new_key).previous_keys([old_key]));
+        let new_only_app = get(login_ui).post(login).with(Csrf::new().key(new_key));
+
+        let post = |token: &str, cookie: &str| {
+            Request::builder()
+                .method(Method::POST)
+                .header(CSRF_TOKEN_NAME, token)
+                .header(header::COOKIE, cookie)
+                .finish()
+        };
+        let cookie_of = |resp: &crate::Response| {
+            Cookie::parse(resp.header(header::SET_COOKIE).unwrap())
+                .map(|cookie| format!("{}={}", cookie.name(), cookie.value_str()))
+                .unwrap()
+        };
+
+        // a token and a cookie created with the old key
+        let resp = old_app.call(Request::default()).await.unwrap();
+        let old_cookie = cookie_of(&resp);
+        let old_token = resp.into_body().into_string().await.unwrap();
+
+        let resp = new_app.call(post(&old_token, &old_cookie)).await.unwrap();
+        let new_cookie = cookie_of(&resp);
+        assert_eq!(resp.into_body().into_string().await.unwrap(), "true");
+        let resp = new_only_app
+            .call(post(&old_token, &old_cookie))
+            .await
+            .unwrap();
+        assert_eq!(resp.into_body().into_string().await.unwrap(), "false");
+
+        // the cookie is re-issued with the new key
+        let resp = new_only_app
+            .call(
+                Request::builder()
+                    .header(header::COOKIE, &new_cookie)
+                    .finish(),
+            )
+            .await
+            .unwrap();
+        let token = resp.into_body().into_string().await.unwrap();
+        let resp = new_only_app.call(post(&token, &new_cookie)).await.unwrap();
+        assert_eq!(resp.into_body().into_string().await.unwrap(), "true");
+    }
 }
@@ -0,0 +1,476 @@
+use std::time::SystemTime;
+
+use headers::{HeaderMapExt, IfMatch, IfModifiedSince, IfNoneMatch, LastModified};
+use http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+
+use crate::{
+    Body, Endpoint, IntoResponse, Middleware, Request, Response, Result,
+    error::PreconditionFailedError,
+};
+
+/// Headers that are kept in `304 Not Modified` responses.
+const NOT_MODIFIED_HEADERS: &[header::HeaderName] = &[
+    header::CACHE_CONTROL,
+    header::CONTENT_LOCATION,
+    header::DATE,
+    header::ETAG,
+    header::EXPIRES,
+    header::LAST_MODIFIED,
+    header::VARY,
+];
+
+/// Middleware that adds entity tags to responses, and evaluates conditional
+/// requests.
+///
+/// The entity tag of a `200 OK` response is taken from the `ETag` header set by
+/// the endpoint, so the endpoint can supply a strong or weak tag. Otherwise it
+/// is computed by hashing the response body of `GET` requests, if the body is
+/// buffered in memory. Streaming bodies are not hashed, and neither are the
+/// empty bodies of `HEAD` responses, so `HEAD` requests only get the tags
+/// supplied by the endpoint.
+///
+/// For `GET` and `HEAD` requests, `If-None-Match` and `If-Modified-Since`
+/// (compared with the `Last-Modified` header set by the endpoint) respond with
+/// `304 Not Modified`.
+///
+/// For unsafe methods, such as `PUT` or `DELETE`, `If-Match` is evaluated
+/// against the current entity tag of the resource, which is obtained by
+/// calling the endpoint with a `GET` request for the same URI. If it does not
+/// match, the request is rejected without calling the endpoint. The weak tags
+/// never match, so the resources updated this way need strong tags.
+///
+/// # Errors
+///
+/// - [`PreconditionFailedError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Route, get, handler,
+///     http::{StatusCode, header},
+///     middleware::ETag,
+///     test::TestClient,
+/// };
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// #[handler]
+/// fn update() {}
+///
+/// let app = Route::new()
+///     .at("/", get(index).put(update))
+///     .with(ETag::new());
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cli = TestClient::new(app);
+/// let resp = cli.get("/").send().await;
+/// resp.assert_status_is_ok();
+/// let etag = resp.0.headers().get(header::ETAG).unwrap().clone();
+///
+/// cli.get("/")
+///     .header(header::IF_NONE_MATCH, etag.clone())
+///     .send()
+///     .await
+///     .assert_status(StatusCode::NOT_MODIFIED);
+///
+/// cli.put("/")
+///     .header(header::IF_MATCH, "\"other\"")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::PRECONDITION_FAILED);
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "etag")))]
+#[derive(Default)]
+pub struct ETag {
+    weak: bool,
+}
+
+impl ETag {
+    /// Create `ETag` middleware.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Specifies whether the computed entity tags are weak. (defaults to
+    /// `false`)
+    ///
+    /// Use weak tags when the body may be encoded differently, for example by
+    /// the [`Compression`](crate::middleware::Compression) middleware.
+    #[must_use]
+    pub fn weak(self, weak: bool) -> Self {
+        Self { weak }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ETag {
+    type Output = ETagEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        ETagEndpoint {
+            inner: ep,
+            weak: self.weak,
+        }
+    }
+}
+
+/// Endpoint for the ETag middleware.
+#[cfg_attr(docsrs, doc(cfg(feature = "etag")))]
+pub struct ETagEndpoint<E> {
+    inner: E,
+    weak: bool,
+}
+
+impl<E: Endpoint> ETagEndpoint<E> {
+    /// Calls the inner endpoint, and adds the entity tag to the response.
+    async fn call_with_etag(&self, req: Request) -> Result<Response> {
+        let hash_body = req.method() == Method::GET;
+        let mut resp = self.inner.call(req).await?.into_response();
+        if !hash_body
+            || resp.status() != StatusCode::OK
+            || resp.headers().contains_key(header::ETAG)
+        {
+            return Ok(resp);
+        }
+
+        let body = resp.take_body();
+        if !is_buffered(&body) {
+            resp.set_body(body);
+            return Ok(resp);
+        }
+
+        let data = body.into_bytes().await?;
+        // the hash must not change between builds and instances of the server
+        let etag = format!(
+            "{}\"{:x}-{:016x}\"",
+            if self.weak { "W/" } else { "" },
+            data.len(),
+            xxhash_rust::xxh3::xxh3_64(&data)
+        );
+        if let Ok(etag) = HeaderValue::from_str(&etag) {
+            resp.headers_mut().insert(header::ETAG, etag);
+        }
+        resp.set_body(data);
+        Ok(resp)
+    }
+
+    /// Returns `true` if the `If-Match` header matches the current entity tag
+    /// of the resource.
+    async fn if_match_passes(&self, req: &Request, if_match: &IfMatch) -> bool {
+        let mut current_req = req.clone_without_body();
+        current_req.set_method(Method::GET);
+        for name in [
+            header::IF_MATCH,
+            header::IF_NONE_MATCH,
+            header::IF_MODIFIED_SINCE,
+            header::IF_UNMODIFIED_SINCE,
+            header::IF_RANGE,
+            header::RANGE,
+            header::CONTENT_TYPE,
+            header::CONTENT_LENGTH,
+            header::CONTENT_ENCODING,
+            header::TRANSFER_ENCODING,
+        ] {
+            current_req.headers_mut().remove(name);
+        }
+
+        // the resource has no current representation if the request fails
+        let Ok(current) = self.call_with_etag(current_req).await else {
+            return false;
+        };
+        current.status().is_success()
+            && match current.headers().typed_get::<headers::ETag>() {
+                Some(etag) => if_match.precondition_passes(&etag),
+                None => *if_match == IfMatch::any(),
+            }
+    }
+}
+
+impl<E: Endpoint> Endpoint for ETagEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        if req.method() != Method::GET && req.method() != Method::HEAD {
+            if !req.method().is_safe() {
+                if let Some(if_match) = req.headers().typed_get::<IfMatch>() {
+                    if !self.if_match_passes(&req, &if_match).await {
+                        return Err(PreconditionFailedError.into());
+                    }
+                }
+            }
+            return Ok(self.inner.call(req).await?.into_response());
+        }
+
+        let if_none_match = req.headers().typed_get::<IfNoneMatch>();
+        let if_modified_since = req.headers().typed_get::<IfModifiedSince>();
+        let resp = self.call_with_etag(req).await?;
+        if resp.status() != StatusCode::OK {
+            return Ok(resp);
+        }
+
+        let not_modified = match (if_none_match, if_modified_since) {
+            (Some(if_none_match), _) => resp
+                .headers()
+                .typed_get::<headers::ETag>()
+                .is_some_and(|etag| !if_none_match.precondition_passes(&etag)),
+            (None, Some(if_modified_since)) => resp
+                .headers()
+                .typed_get::<LastModified>()
+                .is_some_and(|last_modified| {
+                    !if_modified_since.is_modified(SystemTime::from(last_modified))
+                }),
+            (None, None) => false,
+        };
+
+        if not_modified {
+            let mut headers = HeaderMap::new();
+            for name in NOT_MODIFIED_HEADERS {
+                for value in resp.headers().get_all(name) {
+                    headers.append(name.clone(), value.clone());
+                }
+            }
+            let mut resp = StatusCode::NOT_MODIFIED.into_response();
+            *resp.headers_mut() = headers;
+            return Ok(resp);
+        }
+
+        Ok(resp)
+    }
+}
+
+/// Returns `true` if the size of the body is known, which means the body is
+/// already in memory.
+fn is_buffered(body: &Body) -> bool {
+    hyper::body::Body::size_hint(&body.0).exact().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+    use crate::{EndpointExt, Route, get, handler, put, test::TestClient, web::Data};
+
+    #[tokio::test]
+    async fn hash_body() {
+        #[handler(internal)]
+        fn index() -> &'static str {
+            "hello"
+        }
+
+        let cli = TestClient::new(index.with(ETag::new()));
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        let etag = resp.0.headers().get(header::ETAG).unwrap().clone();
+        assert!(!etag.to_str().unwrap().starts_with("W/"));
+        resp.assert_text("hello").await;
+
+        // the tag is stable
+        cli.get("/")
+            .send()
+            .await
+            .assert_header(header::ETAG, etag.to_str().unwrap());
+
+        let resp = cli
+            .get("/")
+            .header(header::IF_NONE_MATCH, etag.clone())
+            .send()
+            .await;
+        resp.assert_status(StatusCode::NOT_MODIFIED);
+        resp.assert_header(header::ETAG, etag.to_str().unwrap());
+        resp.assert_text("").await;
+
+        cli.get("/")
+            .header(header::IF_NONE_MATCH, "\"other\"")
+            .send()
+            .await
+            .assert_status_is_ok();
+
+        let cli = TestClient::new(index.with(ETag::new().weak(true)));
+        let resp = cli.get("/").send().await;
+        let etag = resp.0.headers().get(header::ETAG).unwrap().clone();
+        assert!(etag.to_str().unwrap().starts_with("W/"));
+        cli.get("/")
+            .header(header::IF_NONE_MATCH, etag)
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn streaming_body_is_not_hashed() {
+        #[handler(internal)]
+        fn index() -> Body {
+            Body::from_bytes_stream(futures_util::stream::once(async {
+                Ok::<_, std::io::Error>(bytes::Bytes::from_static(b"hello"))
+            }))
+        }
+
+        let cli = TestClient::new(index.with(ETag::new()));
+        let resp = cli.get("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header_is_not_exist(header::ETAG);
+        resp.assert_text("hello").await;
+    }
+
+    #[tokio::test]
+    async fn handler_supplied_tag() {
+        #[handler(internal)]
+        fn index() -> impl IntoResponse {
+            "hello"
+                .with_header(header::ETAG, "W/\"v1\"")
+                .with_header(header::LAST_MODIFIED, "Wed, 21 Oct 2015 07:28:00 GMT")
+        }
+
+        let cli = TestClient::new(index.with(ETag::new()));
+        cli.get("/")
+            .send()
+            .await
+            .assert_header(header::ETAG, "W/\"v1\"");
+
+        // weak comparison
+        cli.get("/")
+            .header(header::IF_NONE_MATCH, "\"v1\"")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_MODIFIED);
+
+        cli.get("/")
+            .header(header::IF_MODIFIED_SINCE, "Wed, 21 Oct 2015 07:28:00 GMT")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_MODIFIED);
+
+        cli.get("/")
+            .header(header::IF_MODIFIED_SINCE, "Tue, 20 Oct 2015 07:28:00 GMT")
+            .send()
+            .await
+            .assert_status_is_ok();
+    }
+
+    #[tokio::test]
+    async fn head() {
+        #[handler(internal)]
+        fn index() -> &'static str {
+            "hello"
+        }
+
+        #[handler(internal)]
+        fn tagged() -> impl IntoResponse {
+            "hello".with_header(header::ETAG, "\"v1\"")
+        }
+
+        // the empty body of a `HEAD` response is not hashed
+        let cli = TestClient::new(get(index).with(ETag::new()));
+        let resp = cli.get("/").send().await;
+        let etag = resp.0.headers().get(header::ETAG).unwrap().clone();
+        let resp = cli
+            .head("/")
+            .header(header::IF_NONE_MATCH, etag)
+            .send()
+            .await;
+        resp.assert_status_is_ok();
+        resp.assert_header_is_not_exist(header::ETAG);
+
+        let cli = TestClient::new(get(tagged).with(ETag::new()));
+        let resp = cli.head("/").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_header(header::ETAG, "\"v1\"");
+        cli.head("/")
+            .header(header::IF_NONE_MATCH, "\"v1\"")
+            .send()
+            .await
+            .assert_status(StatusCode::NOT_MODIFIED);
+    }
+
+    #[tokio::test]
+    async fn if_match() {
+        #[handler(internal)]
+        fn show() -> &'static str {
+            "hello"
+        }
+
+        #[handler(internal)]
+        fn update(counter: Data<&Arc<AtomicUsize>>) {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let gets = Arc::new(AtomicUsize::new(0));
+        let cli = TestClient::new(
+            get(show)
+                .put(update)
+                .delete(update)
+                .around({
+                    let gets = gets.clone();
+                    move |ep, req| {
+                        let gets = gets.clone();
+                        async move {
+                            if req.method() == Method::GET {
+                                gets.fetch_add(1, Ordering::SeqCst);
+                            }
+                            ep.call(req).await
+                        }
+                    }
+                })
+                .with(ETag::new())
+                .data(counter.clone()),
+        );
+        let etag = cli.get("/").send().await.0.headers()[header::ETAG].clone();
+
+        cli.put("/")
+            .header(header::IF_MATCH, etag.clone())
+            .send()
+            .await
+            .assert_status_is_ok();
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        cli.put("/")
+            .header(header::IF_MATCH, "\"v0\"")
+            .send()
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        // weak tags never match
+        cli.delete("/")
+            .header(header::IF_MATCH, format!("W/{}", etag.to_str().unwrap()))
+            .send()
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+
+        cli.delete("/")
+            .header(header::IF_MATCH, "*")
+            .send()
+            .await
+            .assert_status_is_ok();
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+        // the current representation is only requested with `If-Match`
+        assert_eq!(gets.load(Ordering::SeqCst), 5);
+        cli.put("/").send().await.assert_status_is_ok();
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+        assert_eq!(gets.load(Ordering::SeqCst), 5);
+
+        // the resource does not exist
+        let cli = TestClient::new(
+            Route::new()
+                .at("/", put(update))
+                .with(ETag::new())
+                .data(counter.clone()),
+        );
+        cli.put("/")
+            .header(header::IF_MATCH, "*")
+            .send()
+            .await
+            .assert_status(StatusCode::PRECONDITION_FAILED);
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+    }
+}
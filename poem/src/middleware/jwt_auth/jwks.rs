@@ -0,0 +1,139 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use jsonwebtoken::{Header, jwk::JwkSet};
+use parking_lot::RwLock;
+
+use crate::{Result, error::JwtError, middleware::JwtKey};
+
+#[derive(Default)]
+struct State {
+    keys: Arc<Vec<JwtKey>>,
+    fetched_at: Option<Instant>,
+}
+
+/// A JSON Web Key Set loaded from an URL, such as the `jwks_uri` of an
+/// OpenID Connect provider.
+///
+/// The keys are fetched when the first token is validated, and fetched again
+/// after the [`refresh_interval`](Jwks::refresh_interval). When a token is
+/// signed with an unknown key, the keys are fetched again at most once per
+/// [`min_refresh_interval`](Jwks::min_refresh_interval), so that rotated keys
+/// are picked up immediately. If fetching the keys fails, the previous keys
+/// are kept.
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub struct Jwks {
+    url: String,
+    client: reqwest::Client,
+    refresh_interval: Duration,
+    min_refresh_interval: Duration,
+    state: RwLock<State>,
+    refresh_lock: tokio::sync::Mutex<()>,
+}
+
+impl Jwks {
+    /// Create a `Jwks` which loads the keys from the specified URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+            refresh_interval: Duration::from_secs(60 * 60),
+            min_refresh_interval: Duration::from_secs(60),
+            state: Default::default(),
+            refresh_lock: Default::default(),
+        }
+    }
+
+    /// Sets the interval at which the keys are fetched again. (defaults to 1
+    /// hour)
+    #[must_use]
+    pub fn refresh_interval(self, refresh_interval: Duration) -> Self {
+        Self {
+            refresh_interval,
+            ..self
+        }
+    }
+
+    /// Sets the minimum interval at which the keys are fetched again when a
+    /// token is signed with an unknown key. (defaults to 1 minute)
+    #[must_use]
+    pub fn min_refresh_interval(self, min_refresh_interval: Duration) -> Self {
+        Self {
+            min_refresh_interval,
+            ..self
+        }
+    }
+
+    /// Returns the keys which can be used to verify the token with the
+    /// specified header.
+    pub(super) async fn keys(&self, header: &Header) -> Result<Arc<Vec<JwtKey>>> {
+        let (keys, fetched_at) = {
+            let state = self.state.read();
+            (state.keys.clone(), state.fetched_at)
+        };
+
+        let Some(fetched_at) = fetched_at else {
+            return self.refresh(None).await;
+        };
+        if fetched_at.elapsed() >= self.refresh_interval
+            || (!keys.iter().any(|key| key.matches(header))
+                && fetched_at.elapsed() >= self.min_refresh_interval)
+        {
+            return self.refresh(Some(fetched_at)).await;
+        }
+        Ok(keys)
+    }
+
+    /// Fetches the keys, unless they have been fetched by another task since
+    /// `fetched_at`.
+    async fn refresh(&self, fetched_at: Option<Instant>) -> Result<Arc<Vec<JwtKey>>> {
+        let _guard = self.refresh_lock.lock().await;
+
+        {
+            let state = self.state.read();
+            if state.fetched_at != fetched_at {
+                return Ok(state.keys.clone());
+            }
+        }
+
+        match self.fetch().await {
+            Ok(keys) => {
+                let keys = Arc::new(keys);
+                *self.state.write() = State {
+                    keys: keys.clone(),
+                    fetched_at: Some(Instant::now()),
+                };
+                Ok(keys)
+            }
+            Err(err) if fetched_at.is_some() => {
+                tracing::warn!(url = %self.url, error = %err, "failed to refresh the json web key set");
+                let mut state = self.state.write();
+                state.fetched_at = Some(Instant::now());
+                Ok(state.keys.clone())
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn fetch(&self) -> Result<Vec<JwtKey>, JwtError> {
+        let jwks = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .map_err(|err| JwtError::Jwks(err.to_string()))?
+            .json::<JwkSet>()
+            .await
+            .map_err(|err| JwtError::Jwks(err.to_string()))?;
+
+        // ignore the keys which are not supported
+        Ok(jwks
+            .keys
+            .iter()
+            .filter_map(|jwk| JwtKey::from_jwk(jwk).ok())
+            .collect())
+    }
+}
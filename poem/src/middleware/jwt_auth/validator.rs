@@ -0,0 +1,208 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use jsonwebtoken::{Algorithm, DecodingKey, Header, Validation, jwk::Jwk};
+use serde::de::DeserializeOwned;
+
+use crate::{Result, error::JwtError, middleware::Jwks};
+
+/// A key used to verify the signature of tokens.
+#[derive(Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub struct JwtKey {
+    kid: Option<String>,
+    algorithm: Option<Algorithm>,
+    key: DecodingKey,
+}
+
+impl JwtKey {
+    /// Create a key for the `HS256`, `HS384` or `HS512` algorithms from a
+    /// secret.
+    pub fn hmac(algorithm: Algorithm, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            kid: None,
+            algorithm: Some(algorithm),
+            key: DecodingKey::from_secret(secret.as_ref()),
+        }
+    }
+
+    /// Create a key for the `RS*` or `PS*` algorithms from a PEM encoded RSA
+    /// public key.
+    pub fn rsa_pem(algorithm: Algorithm, pem: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self {
+            kid: None,
+            algorithm: Some(algorithm),
+            key: DecodingKey::from_rsa_pem(pem.as_ref()).map_err(JwtError::InvalidKey)?,
+        })
+    }
+
+    /// Create a key for the `ES256` or `ES384` algorithms from a PEM encoded
+    /// EC public key.
+    pub fn ec_pem(algorithm: Algorithm, pem: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self {
+            kid: None,
+            algorithm: Some(algorithm),
+            key: DecodingKey::from_ec_pem(pem.as_ref()).map_err(JwtError::InvalidKey)?,
+        })
+    }
+
+    /// Create a key for the `EdDSA` algorithm from a PEM encoded Ed25519
+    /// public key.
+    pub fn ed_pem(pem: impl AsRef<[u8]>) -> Result<Self, JwtError> {
+        Ok(Self {
+            kid: None,
+            algorithm: Some(Algorithm::EdDSA),
+            key: DecodingKey::from_ed_pem(pem.as_ref()).map_err(JwtError::InvalidKey)?,
+        })
+    }
+
+    /// Create a key from a JSON Web Key.
+    ///
+    /// If the JSON Web Key has no `alg` parameter, the key can be used with
+    /// any algorithm of its family.
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, JwtError> {
+        let algorithm = jwk
+            .common
+            .key_algorithm
+            .map(|algorithm| {
+                Algorithm::from_str(&algorithm.to_string()).map_err(JwtError::InvalidKey)
+            })
+            .transpose()?;
+        Ok(Self {
+            kid: jwk.common.key_id.clone(),
+            algorithm,
+            key: DecodingKey::from_jwk(jwk).map_err(JwtError::InvalidKey)?,
+        })
+    }
+
+    /// Sets the key id, which is matched with the `kid` header of the tokens.
+    #[must_use]
+    pub fn kid(self, kid: impl Into<String>) -> Self {
+        Self {
+            kid: Some(kid.into()),
+            ..self
+        }
+    }
+
+    pub(super) fn matches(&self, header: &Header) -> bool {
+        let kid_matches = match (&header.kid, &self.kid) {
+            (Some(kid), Some(key_kid)) => kid == key_kid,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        let algorithm_matches = self
+            .algorithm
+            .is_none_or(|algorithm| algorithm == header.alg);
+        kid_matches && algorithm_matches
+    }
+}
+
+#[derive(Clone)]
+enum KeySource {
+    Static(Arc<Vec<JwtKey>>),
+    Jwks(Arc<Jwks>),
+}
+
+/// A validator for JSON Web Tokens, which verifies the signature and the
+/// `exp`, `nbf`, `aud` and `iss` claims.
+///
+/// The validator is used by the [`JwtAuth`](crate::middleware::JwtAuth)
+/// middleware, and can also be used directly, for example in the checker of a
+/// `poem-openapi` security scheme.
+///
+/// The `exp` claim is required. The `aud` and `iss` claims are only checked if
+/// an [`audience`](JwtValidator::audience) or an
+/// [`issuer`](JwtValidator::issuer) is set.
+///
+/// # Errors
+///
+/// - [`JwtError`]
+#[derive(Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub struct JwtValidator {
+    keys: KeySource,
+    validation: Validation,
+}
+
+impl JwtValidator {
+    /// Create a `JwtValidator` with a static set of keys.
+    pub fn new(keys: impl IntoIterator<Item = JwtKey>) -> Self {
+        Self::with_source(KeySource::Static(Arc::new(keys.into_iter().collect())))
+    }
+
+    /// Create a `JwtValidator` which loads the keys from a JSON Web Key Set.
+    pub fn with_jwks(jwks: Jwks) -> Self {
+        Self::with_source(KeySource::Jwks(Arc::new(jwks)))
+    }
+
+    fn with_source(keys: KeySource) -> Self {
+        let mut validation = Validation::default();
+        validation.validate_nbf = true;
+        validation.validate_aud = false;
+        Self { keys, validation }
+    }
+
+    /// Sets the accepted values of the `aud` claim, which becomes required.
+    #[must_use]
+    pub fn audience<I, T>(mut self, audience: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        let audience = audience.into_iter().collect::<Vec<_>>();
+        self.validation.set_audience(&audience);
+        self.validation.validate_aud = true;
+        self.validation
+            .required_spec_claims
+            .insert("aud".to_string());
+        self
+    }
+
+    /// Sets the accepted values of the `iss` claim, which becomes required.
+    #[must_use]
+    pub fn issuer<I, T>(mut self, issuer: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        let issuer = issuer.into_iter().collect::<Vec<_>>();
+        self.validation.set_issuer(&issuer);
+        self.validation
+            .required_spec_claims
+            .insert("iss".to_string());
+        self
+    }
+
+    /// Sets the allowed clock skew when checking the `exp` and `nbf` claims.
+    /// (defaults to 60 seconds)
+    #[must_use]
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.validation.leeway = leeway.as_secs();
+        self
+    }
+
+    /// Validates the token and returns its claims.
+    pub async fn validate<T: DeserializeOwned>(&self, token: &str) -> Result<T> {
+        let header = jsonwebtoken::decode_header(token).map_err(JwtError::InvalidToken)?;
+        let keys = match &self.keys {
+            KeySource::Static(keys) => keys.clone(),
+            KeySource::Jwks(jwks) => jwks.keys(&header).await?,
+        };
+
+        let mut validation = self.validation.clone();
+        validation.algorithms = vec![header.alg];
+
+        // without a `kid` header, several keys may match
+        let mut last_error = None;
+        for key in keys.iter().filter(|key| key.matches(&header)) {
+            match jsonwebtoken::decode::<T>(token, &key.key, &validation) {
+                Ok(data) => return Ok(data.claims),
+                Err(err) => last_error = Some(err),
+            }
+        }
+
+        Err(match last_error {
+            Some(err) => JwtError::InvalidToken(err).into(),
+            None => JwtError::UnknownKey.into(),
+        })
+    }
+}
@@ -1,6 +1,8 @@
 //! Commonly used middleware.
 
 mod add_data;
+#[cfg(feature = "cache")]
+mod cache;
 mod catch_panic;
 #[cfg(feature = "compression")]
 mod compression;
@@ -9,18 +11,26 @@ mod cookie_jar_manager;
 mod cors;
 #[cfg(feature = "csrf")]
 mod csrf;
+#[cfg(feature = "etag")]
+mod etag;
 mod force_https;
+#[cfg(feature = "jwt")]
+mod jwt_auth;
 mod normalize_path;
 #[cfg(feature = "opentelemetry")]
 mod opentelemetry_metrics;
 #[cfg(feature = "opentelemetry")]
 mod opentelemetry_tracing;
+mod problem_json;
 mod propagate_header;
+#[cfg(feature = "rate-limit")]
+mod rate_limit;
 #[cfg(feature = "requestid")]
 mod requestid;
 mod sensitive_header;
 mod set_header;
 mod size_limit;
+mod timeout;
 #[cfg(feature = "tokio-metrics")]
 mod tokio_metrics_mw;
 #[cfg(feature = "tower-compat")]
@@ -29,16 +39,31 @@ mod tracing_mw;
 
 use std::marker::PhantomData;
 
+#[cfg(feature = "cache")]
+pub use self::cache::{Cache, CacheEndpoint, CacheStorage, CachedResponse, MemoryCacheStorage};
 #[cfg(feature = "compression")]
 pub use self::compression::{Compression, CompressionEndpoint};
 #[cfg(feature = "cookie")]
 pub use self::cookie_jar_manager::{CookieJarManager, CookieJarManagerEndpoint};
 #[cfg(feature = "csrf")]
 pub use self::csrf::{Csrf, CsrfEndpoint};
+#[cfg(feature = "etag")]
+pub use self::etag::{ETag, ETagEndpoint};
+#[cfg(feature = "jwt")]
+pub use self::jwt_auth::{Jwk, Jwks, JwtAlgorithm, JwtAuth, JwtAuthEndpoint, JwtKey, JwtValidator};
 #[cfg(feature = "opentelemetry")]
 pub use self::opentelemetry_metrics::{OpenTelemetryMetrics, OpenTelemetryMetricsEndpoint};
 #[cfg(feature = "opentelemetry")]
 pub use self::opentelemetry_tracing::{OpenTelemetryTracing, OpenTelemetryTracingEndpoint};
+#[cfg(feature = "redis-rate-limit")]
+pub use self::rate_limit::RedisRateLimitStore;
+#[cfg(feature = "rate-limit")]
+pub(crate) use self::rate_limit::set_rate_limit_headers;
+#[cfg(feature = "rate-limit")]
+pub use self::rate_limit::{
+    MemoryRateLimitStore, Quota, RateLimit, RateLimitAlgorithm, RateLimitDecision,
+    RateLimitEndpoint, RateLimitStore,
+};
 #[cfg(feature = "requestid")]
 pub use self::requestid::{ReqId, RequestId, RequestIdEndpoint, ReuseId};
 #[cfg(feature = "tokio-metrics")]
@@ -51,10 +76,12 @@ pub use self::{
     cors::{Cors, CorsEndpoint},
     force_https::ForceHttps,
     normalize_path::{NormalizePath, NormalizePathEndpoint, TrailingSlash},
+    problem_json::{ProblemJson, ProblemJsonEndpoint},
     propagate_header::{PropagateHeader, PropagateHeaderEndpoint},
     sensitive_header::{SensitiveHeader, SensitiveHeaderEndpoint},
     set_header::{SetHeader, SetHeaderEndpoint},
     size_limit::{SizeLimit, SizeLimitEndpoint},
+    timeout::{Timeout, TimeoutEndpoint},
     tracing_mw::{Tracing, TracingEndpoint},
 };
 use crate::endpoint::{EitherEndpoint, Endpoint};
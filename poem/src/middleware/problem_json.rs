@@ -0,0 +1,246 @@
+use http::{HeaderValue, header};
+
+use crate::{
+    Endpoint, Error, IntoResponse, Middleware, Request, Response, Result, error::ProblemDetails,
+};
+
+/// Middleware that renders errors as [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457)
+/// problem details.
+///
+/// The errors returned by the inner endpoint, including the errors of the
+/// extractors and the errors implementing
+/// [`ResponseError`](crate::error::ResponseError), are rendered as
+/// `application/problem+json` with [`Error::problem_details`]. The status
+/// code and the headers of the error response are kept, only the body is
+/// replaced. Errors created with [`Error::from_response`] are left unchanged.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Route, get, handler, http::StatusCode, middleware::ProblemJson,
+///     test::TestClient, web::Query,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Params {
+///     name: String,
+/// }
+///
+/// #[handler]
+/// fn index(Query(params): Query<Params>) -> String {
+///     format!("hello {}", params.name)
+/// }
+///
+/// let app = Route::new()
+///     .at("/", get(index))
+///     .with(ProblemJson::new().with_instance());
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let resp = TestClient::new(app).get("/").send().await;
+/// resp.assert_status(StatusCode::BAD_REQUEST);
+/// resp.assert_content_type("application/problem+json");
+/// resp.assert_json(serde_json::json!({
+///     "type": "about:blank",
+///     "title": "Bad Request",
+///     "status": 400,
+///     "detail": "missing field `name`",
+///     "instance": "/",
+/// }))
+/// .await;
+/// # });
+/// ```
+#[derive(Default)]
+pub struct ProblemJson {
+    instance: bool,
+}
+
+impl ProblemJson {
+    /// Create `ProblemJson` middleware.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the `instance` member to the path of the request, unless it is
+    /// already set.
+    #[must_use]
+    pub fn with_instance(self) -> Self {
+        Self { instance: true }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ProblemJson {
+    type Output = ProblemJsonEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        ProblemJsonEndpoint {
+            inner: ep,
+            instance: self.instance,
+        }
+    }
+}
+
+/// Endpoint for the `ProblemJson` middleware.
+pub struct ProblemJsonEndpoint<E> {
+    inner: E,
+    instance: bool,
+}
+
+impl<E: Endpoint> Endpoint for ProblemJsonEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let instance = self.instance.then(|| req.original_uri().path().to_string());
+
+        match self.inner.call(req).await {
+            Ok(resp) => Ok(resp.into_response()),
+            Err(err) if err.is_from_response() => Err(err),
+            Err(err) => {
+                let mut problem = err.problem_details();
+                if problem.instance.is_none() {
+                    problem.instance = instance;
+                }
+
+                let mut resp = err.into_response();
+                let headers = resp.headers_mut();
+                headers.remove(header::CONTENT_LENGTH);
+                headers.insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(ProblemDetails::CONTENT_TYPE),
+                );
+                resp.set_body(serde_json::to_vec(&problem).expect("valid json value"));
+                Err(Error::from_response(resp))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{EndpointExt, error::ResponseError, handler, test::TestClient, web::Json};
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("token expired")]
+    struct AuthError;
+
+    impl ResponseError for AuthError {
+        fn status(&self) -> StatusCode {
+            StatusCode::UNAUTHORIZED
+        }
+
+        fn as_response(&self) -> Response {
+            Response::builder()
+                .status(self.status())
+                .header(header::WWW_AUTHENTICATE, "Bearer")
+                .body(self.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn extractor_error() {
+        #[handler(internal)]
+        fn index(Json(value): Json<i32>) -> String {
+            value.to_string()
+        }
+
+        let cli = TestClient::new(index.with(ProblemJson::new().with_instance()));
+        let resp = cli
+            .post("/a/b?c=1")
+            .content_type("application/json")
+            .body("abc")
+            .send()
+            .await;
+        resp.assert_status(StatusCode::BAD_REQUEST);
+        resp.assert_content_type("application/problem+json");
+        let value = resp.json().await;
+        let value = value.value().object();
+        value.get("type").assert_string("about:blank");
+        value.get("title").assert_string("Bad Request");
+        value.get("status").assert_i64(400);
+        value.get("instance").assert_string("/a/b");
+
+        cli.post("/")
+            .content_type("application/json")
+            .body("1")
+            .send()
+            .await
+            .assert_text("1")
+            .await;
+    }
+
+    #[tokio::test]
+    async fn response_error() {
+        #[handler(internal)]
+        fn index() -> Result<()> {
+            Err(AuthError.into())
+        }
+
+        let resp = TestClient::new(index.with(ProblemJson::new()))
+            .get("/")
+            .send()
+            .await;
+        resp.assert_status(StatusCode::UNAUTHORIZED);
+        resp.assert_header(header::WWW_AUTHENTICATE, "Bearer");
+        resp.assert_json(json!({
+            "type": "about:blank",
+            "title": "Unauthorized",
+            "status": 401,
+            "detail": "token expired",
+        }))
+        .await;
+    }
+
+    #[tokio::test]
+    async fn error_data() {
+        #[handler(internal)]
+        fn index() -> Result<()> {
+            let mut err = Error::from(AuthError);
+            err.set_data(
+                ProblemDetails::new(StatusCode::UNAUTHORIZED)
+                    .ty("https://example.com/probs/expired")
+                    .instance("/tokens/1")
+                    .extension("expired_at", 1700000000),
+            );
+            Err(err)
+        }
+
+        let resp = TestClient::new(index.with(ProblemJson::new().with_instance()))
+            .get("/")
+            .send()
+            .await;
+        resp.assert_status(StatusCode::UNAUTHORIZED);
+        resp.assert_json(json!({
+            "type": "https://example.com/probs/expired",
+            "title": "Unauthorized",
+            "status": 401,
+            "detail": "token expired",
+            "instance": "/tokens/1",
+            "expired_at": 1700000000,
+        }))
+        .await;
+    }
+
+    #[tokio::test]
+    async fn from_response() {
+        #[handler(internal)]
+        fn index() -> Result<()> {
+            Err(Error::from_response(
+                Response::builder()
+                    .status(StatusCode::CONFLICT)
+                    .body("conflict"),
+            ))
+        }
+
+        let resp = TestClient::new(index.with(ProblemJson::new()))
+            .get("/")
+            .send()
+            .await;
+        resp.assert_status(StatusCode::CONFLICT);
+        resp.assert_text("conflict").await;
+    }
+}
@@ -0,0 +1,200 @@
+use std::time::Duration;
+
+use crate::middleware::{Quota, RateLimitAlgorithm, RateLimitDecision};
+
+/// The state of a single rate limit key.
+///
+/// All timestamps are relative to an arbitrary monotonic epoch chosen by the
+/// store.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum State {
+    Gcra { tat: Duration },
+    TokenBucket { tokens: f64, updated_at: Duration },
+}
+
+/// Applies the algorithm to the current state, and returns the decision and
+/// the state that should be stored.
+pub(crate) fn check(
+    algorithm: RateLimitAlgorithm,
+    quota: &Quota,
+    state: Option<State>,
+    now: Duration,
+) -> (RateLimitDecision, State) {
+    match algorithm {
+        RateLimitAlgorithm::Gcra => gcra(quota, state, now),
+        RateLimitAlgorithm::TokenBucket => token_bucket(quota, state, now),
+    }
+}
+
+fn gcra(quota: &Quota, state: Option<State>, now: Duration) -> (RateLimitDecision, State) {
+    let interval = quota.emission_interval();
+    let tolerance = interval * quota.burst();
+    let tat = match state {
+        Some(State::Gcra { tat }) => tat.max(now),
+        _ => now,
+    };
+    let new_tat = tat + interval;
+
+    if now + tolerance < new_tat {
+        let decision = RateLimitDecision {
+            allowed: false,
+            limit: quota.limit(),
+            period: quota.period(),
+            remaining: 0,
+            reset_after: tat - now,
+            retry_after: Some(new_tat - tolerance - now),
+        };
+        return (decision, State::Gcra { tat });
+    }
+
+    let remaining = (now + tolerance - new_tat).as_nanos() / interval.as_nanos();
+    let decision = RateLimitDecision {
+        allowed: true,
+        limit: quota.limit(),
+        period: quota.period(),
+        remaining: remaining as u32,
+        reset_after: new_tat - now,
+        retry_after: None,
+    };
+    (decision, State::Gcra { tat: new_tat })
+}
+
+fn token_bucket(quota: &Quota, state: Option<State>, now: Duration) -> (RateLimitDecision, State) {
+    let capacity = quota.burst() as f64;
+    let limit = quota.limit() as f64;
+    let period = quota.period();
+    let mut tokens = match state {
+        Some(State::TokenBucket { tokens, updated_at }) => {
+            let elapsed = now.saturating_sub(updated_at).as_secs_f64();
+            (tokens + elapsed / period.as_secs_f64() * limit).min(capacity)
+        }
+        _ => capacity,
+    };
+
+    let (allowed, retry_after) = if tokens >= 1.0 {
+        tokens -= 1.0;
+        (true, None)
+    } else {
+        (false, Some(period.mul_f64((1.0 - tokens) / limit)))
+    };
+
+    let decision = RateLimitDecision {
+        allowed,
+        limit: quota.limit(),
+        period: quota.period(),
+        remaining: tokens.floor() as u32,
+        reset_after: period.mul_f64((capacity - tokens) / limit),
+        retry_after,
+    };
+    (
+        decision,
+        State::TokenBucket {
+            tokens,
+            updated_at: now,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(
+        algorithm: RateLimitAlgorithm,
+        quota: &Quota,
+        state: &mut Option<State>,
+        now: Duration,
+    ) -> RateLimitDecision {
+        let (decision, new_state) = check(algorithm, quota, *state, now);
+        *state = Some(new_state);
+        decision
+    }
+
+    #[test]
+    fn gcra() {
+        let quota = Quota::per_minute(2);
+        let mut state = None;
+
+        let decision = run(RateLimitAlgorithm::Gcra, &quota, &mut state, Duration::ZERO);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 1);
+        assert_eq!(decision.reset_after, Duration::from_secs(30));
+
+        let decision = run(RateLimitAlgorithm::Gcra, &quota, &mut state, Duration::ZERO);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.reset_after, Duration::from_secs(60));
+
+        let decision = run(RateLimitAlgorithm::Gcra, &quota, &mut state, Duration::ZERO);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(30)));
+        assert_eq!(decision.reset_after, Duration::from_secs(60));
+
+        let decision = run(
+            RateLimitAlgorithm::Gcra,
+            &quota,
+            &mut state,
+            Duration::from_secs(30),
+        );
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+    }
+
+    #[test]
+    fn gcra_burst() {
+        let quota = Quota::per_second(1).with_burst(3);
+        let mut state = None;
+
+        for remaining in [2, 1, 0] {
+            let decision = run(RateLimitAlgorithm::Gcra, &quota, &mut state, Duration::ZERO);
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, remaining);
+        }
+        let decision = run(RateLimitAlgorithm::Gcra, &quota, &mut state, Duration::ZERO);
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn token_bucket() {
+        let quota = Quota::per_minute(2);
+        let mut state = None;
+
+        let decision = run(
+            RateLimitAlgorithm::TokenBucket,
+            &quota,
+            &mut state,
+            Duration::ZERO,
+        );
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 1);
+
+        let decision = run(
+            RateLimitAlgorithm::TokenBucket,
+            &quota,
+            &mut state,
+            Duration::ZERO,
+        );
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.reset_after, Duration::from_secs(60));
+
+        let decision = run(
+            RateLimitAlgorithm::TokenBucket,
+            &quota,
+            &mut state,
+            Duration::ZERO,
+        );
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(30)));
+
+        let decision = run(
+            RateLimitAlgorithm::TokenBucket,
+            &quota,
+            &mut state,
+            Duration::from_secs(30),
+        );
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use super::algorithm::{self, State};
+use crate::{
+    Result,
+    middleware::{Quota, RateLimitAlgorithm, RateLimitDecision, RateLimitStore},
+};
+
+struct Entry {
+    state: State,
+    expires_at: Instant,
+}
+
+/// A rate limit storage using memory.
+///
+/// Keys whose quota has been fully replenished are removed in the background.
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+pub struct MemoryRateLimitStore {
+    epoch: Instant,
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl Default for MemoryRateLimitStore {
+    fn default() -> Self {
+        let entries = Arc::new(Mutex::new(HashMap::<String, Entry>::new()));
+        tokio::spawn({
+            let entries = Arc::downgrade(&entries);
+            async move {
+                loop {
+                    match entries.upgrade() {
+                        Some(entries) => {
+                            let now = Instant::now();
+                            entries.lock().retain(|_, entry| entry.expires_at > now);
+                        }
+                        None => return,
+                    }
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        });
+        Self {
+            epoch: Instant::now(),
+            entries,
+        }
+    }
+}
+
+impl MemoryRateLimitStore {
+    /// Create a `MemoryRateLimitStore`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn check<'a>(
+        &'a self,
+        key: &'a str,
+        algorithm: RateLimitAlgorithm,
+        quota: &'a Quota,
+    ) -> Result<RateLimitDecision> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        let state = entries
+            .get(key)
+            .filter(|entry| entry.expires_at > now)
+            .map(|entry| entry.state);
+        let (decision, state) =
+            algorithm::check(algorithm, quota, state, now.duration_since(self.epoch));
+        entries.insert(
+            key.to_string(),
+            Entry {
+                state,
+                expires_at: now + decision.reset_after,
+            },
+        );
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn keys_are_independent() {
+        let store = MemoryRateLimitStore::new();
+        let quota = Quota::per_minute(1);
+
+        assert!(
+            store
+                .check("a", RateLimitAlgorithm::Gcra, &quota)
+                .await
+                .unwrap()
+                .allowed
+        );
+        assert!(
+            !store
+                .check("a", RateLimitAlgorithm::Gcra, &quota)
+                .await
+                .unwrap()
+                .allowed
+        );
+        assert!(
+            store
+                .check("b", RateLimitAlgorithm::Gcra, &quota)
+                .await
+                .unwrap()
+                .allowed
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_keys_are_removed() {
+        let store = MemoryRateLimitStore::new();
+        let quota = Quota::new(1, Duration::from_millis(100));
+
+        store
+            .check("a", RateLimitAlgorithm::TokenBucket, &quota)
+            .await
+            .unwrap();
+        assert_eq!(store.entries.lock().len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(store.entries.lock().len(), 0);
+        assert!(
+            store
+                .check("a", RateLimitAlgorithm::TokenBucket, &quota)
+                .await
+                .unwrap()
+                .allowed
+        );
+    }
+}
@@ -0,0 +1,442 @@
+mod algorithm;
+mod memory_store;
+#[cfg(feature = "redis-rate-limit")]
+mod redis_store;
+mod store;
+
+use std::{sync::Arc, time::Duration};
+
+pub use memory_store::MemoryRateLimitStore;
+#[cfg(feature = "redis-rate-limit")]
+pub use redis_store::RedisRateLimitStore;
+pub use store::{RateLimitDecision, RateLimitStore};
+
+use crate::{
+    Endpoint, FromRequest, IntoResponse, Middleware, Request, Response, Result,
+    error::RateLimitError,
+    http::{HeaderMap, HeaderName, HeaderValue, header},
+    web::RealIp,
+};
+
+/// The algorithm used by the [`RateLimit`] middleware.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+pub enum RateLimitAlgorithm {
+    /// Token bucket, tokens are refilled at a constant rate up to the burst
+    /// size, and each request consumes one token.
+    TokenBucket,
+    /// Generic cell rate algorithm, which tracks the theoretical arrival time
+    /// of the next request and only needs to store a single timestamp per key.
+    #[default]
+    Gcra,
+}
+
+/// The quota of the [`RateLimit`] middleware.
+///
+/// A quota allows `limit` requests per `period`, and by default up to `limit`
+/// requests can be made in a burst.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+pub struct Quota {
+    limit: u32,
+    period: Duration,
+    burst: u32,
+}
+
+impl Quota {
+    /// Create a quota which allows `limit` requests per `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` or `period` is zero.
+    pub fn new(limit: u32, period: Duration) -> Self {
+        assert!(
+            limit > 0,
+            "the limit of the quota must be greater than zero"
+        );
+        assert!(
+            !period.is_zero(),
+            "the period of the quota must be greater than zero"
+        );
+        Self {
+            limit,
+            period,
+            burst: limit,
+        }
+    }
+
+    /// Create a quota which allows `limit` requests per second.
+    pub fn per_second(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(1))
+    }
+
+    /// Create a quota which allows `limit` requests per minute.
+    pub fn per_minute(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(60))
+    }
+
+    /// Create a quota which allows `limit` requests per hour.
+    pub fn per_hour(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(60 * 60))
+    }
+
+    /// Sets the maximum number of requests that can be made in a burst.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    #[must_use]
+    pub fn with_burst(self, burst: u32) -> Self {
+        assert!(
+            burst > 0,
+            "the burst of the quota must be greater than zero"
+        );
+        Self { burst, ..self }
+    }
+
+    /// Returns the number of requests allowed per period.
+    #[inline]
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    /// Returns the period of the quota.
+    #[inline]
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Returns the maximum number of requests that can be made in a burst.
+    #[inline]
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Returns the interval between two requests when the quota is spread
+    /// evenly over the period.
+    #[inline]
+    pub fn emission_interval(&self) -> Duration {
+        self.period / self.limit
+    }
+}
+
+type KeyFn = Arc<dyn Fn(&Request) -> Option<String> + Send + Sync>;
+
+#[derive(Clone)]
+enum RateLimitKey {
+    RealIp,
+    Header(HeaderName),
+    Fn(KeyFn),
+}
+
+impl RateLimitKey {
+    async fn extract(&self, req: &Request) -> Result<Option<String>> {
+        match self {
+            RateLimitKey::RealIp => Ok(RealIp::from_request_without_body(req)
+                .await?
+                .0
+                .map(|ip| ip.to_string())),
+            RateLimitKey::Header(name) => Ok(req
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string)),
+            RateLimitKey::Fn(f) => Ok(f(req)),
+        }
+    }
+}
+
+/// Middleware for rate limiting.
+///
+/// Requests are grouped by a key, which defaults to the client address
+/// extracted with [`RealIp`], and each key is limited by the same [`Quota`].
+/// Requests for which no key can be determined are not limited.
+///
+/// Every response carries the `RateLimit`, `RateLimit-Policy`,
+/// `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` headers.
+/// Rejected requests are answered with `429 Too Many Requests`, which carries
+/// the same headers and a `Retry-After` header.
+///
+/// # Errors
+///
+/// - [`RateLimitError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Route, get, handler,
+///     http::StatusCode,
+///     middleware::{MemoryRateLimitStore, Quota, RateLimit},
+///     test::TestClient,
+/// };
+///
+/// #[handler]
+/// fn index() -> &'static str {
+///     "hello"
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let app = Route::new().at("/", get(index)).with(
+///     RateLimit::new(Quota::per_minute(1), MemoryRateLimitStore::new())
+///         .key_by_header("x-api-key"),
+/// );
+/// let cli = TestClient::new(app);
+///
+/// let resp = cli.get("/").header("x-api-key", "abc").send().await;
+/// resp.assert_status_is_ok();
+/// resp.assert_header("RateLimit-Remaining", "0");
+///
+/// let resp = cli.get("/").header("x-api-key", "abc").send().await;
+/// resp.assert_status(StatusCode::TOO_MANY_REQUESTS);
+/// resp.assert_header("RateLimit-Policy", "1;w=60");
+/// resp.assert_header("Retry-After", "60");
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+pub struct RateLimit<S> {
+    store: Arc<S>,
+    quota: Quota,
+    algorithm: RateLimitAlgorithm,
+    key: RateLimitKey,
+}
+
+impl<S> RateLimit<S> {
+    /// Create a `RateLimit` middleware.
+    pub fn new(quota: Quota, store: S) -> Self {
+        Self {
+            store: Arc::new(store),
+            quota,
+            algorithm: RateLimitAlgorithm::default(),
+            key: RateLimitKey::RealIp,
+        }
+    }
+
+    /// Sets the algorithm. (defaults to [`RateLimitAlgorithm::Gcra`])
+    #[must_use]
+    pub fn algorithm(self, algorithm: RateLimitAlgorithm) -> Self {
+        Self { algorithm, ..self }
+    }
+
+    /// Use the client address extracted with [`RealIp`] as the key.
+    ///
+    /// This is the default.
+    #[must_use]
+    pub fn key_by_real_ip(self) -> Self {
+        Self {
+            key: RateLimitKey::RealIp,
+            ..self
+        }
+    }
+
+    /// Use the value of the specified header as the key.
+    #[must_use]
+    pub fn key_by_header<T>(self, name: T) -> Self
+    where
+        HeaderName: TryFrom<T>,
+    {
+        let name = match <HeaderName as TryFrom<T>>::try_from(name) {
+            Ok(name) => name,
+            Err(_) => panic!("illegal header"),
+        };
+        Self {
+            key: RateLimitKey::Header(name),
+            ..self
+        }
+    }
+
+    /// Use a function to extract the key from the request.
+    ///
+    /// If the function returns `None`, the request is not limited.
+    #[must_use]
+    pub fn key_fn<F>(self, f: F) -> Self
+    where
+        F: Fn(&Request) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            key: RateLimitKey::Fn(Arc::new(f)),
+            ..self
+        }
+    }
+}
+
+impl<S: RateLimitStore, E: Endpoint> Middleware<E> for RateLimit<S> {
+    type Output = RateLimitEndpoint<S, E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        RateLimitEndpoint {
+            inner: ep,
+            store: self.store.clone(),
+            quota: self.quota,
+            algorithm: self.algorithm,
+            key: self.key.clone(),
+        }
+    }
+}
+
+/// Endpoint for the `RateLimit` middleware.
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+pub struct RateLimitEndpoint<S, E> {
+    inner: E,
+    store: Arc<S>,
+    quota: Quota,
+    algorithm: RateLimitAlgorithm,
+    key: RateLimitKey,
+}
+
+impl<S: RateLimitStore, E: Endpoint> Endpoint for RateLimitEndpoint<S, E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let Some(key) = self.key.extract(&req).await? else {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        };
+
+        let decision = self.store.check(&key, self.algorithm, &self.quota).await?;
+        if !decision.allowed {
+            return Err(RateLimitError::TooManyRequests(decision).into());
+        }
+
+        let mut resp = self.inner.call(req).await?.into_response();
+        set_rate_limit_headers(resp.headers_mut(), &decision);
+        Ok(resp)
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+pub(crate) fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    let reset = ceil_secs(decision.reset_after);
+    headers.insert(
+        HeaderName::from_static("ratelimit-policy"),
+        HeaderValue::try_from(format!(
+            "{};w={}",
+            decision.limit,
+            ceil_secs(decision.period)
+        ))
+        .expect("valid header value"),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit"),
+        HeaderValue::try_from(format!(
+            "limit={}, remaining={}, reset={}",
+            decision.limit, decision.remaining, reset
+        ))
+        .expect("valid header value"),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit-limit"),
+        HeaderValue::from(decision.limit),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit-remaining"),
+        HeaderValue::from(decision.remaining),
+    );
+    headers.insert(
+        HeaderName::from_static("ratelimit-reset"),
+        HeaderValue::from(reset),
+    );
+    if let Some(retry_after) = decision.retry_after {
+        headers.insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(ceil_secs(retry_after)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::*;
+    use crate::{EndpointExt, handler, test::TestClient};
+
+    #[handler(internal)]
+    fn index() -> &'static str {
+        "hello"
+    }
+
+    #[tokio::test]
+    async fn rate_limit() {
+        for algorithm in [RateLimitAlgorithm::Gcra, RateLimitAlgorithm::TokenBucket] {
+            let ep = index.with(
+                RateLimit::new(Quota::per_minute(2), MemoryRateLimitStore::new())
+                    .algorithm(algorithm),
+            );
+            let cli = TestClient::new(ep);
+
+            let resp = cli.get("/").header("x-real-ip", "1.1.1.1").send().await;
+            resp.assert_status_is_ok();
+            resp.assert_header("ratelimit-limit", "2");
+            resp.assert_header("ratelimit-remaining", "1");
+            resp.assert_header("ratelimit-policy", "2;w=60");
+            resp.assert_header_is_not_exist(header::RETRY_AFTER);
+
+            let resp = cli.get("/").header("x-real-ip", "1.1.1.1").send().await;
+            resp.assert_status_is_ok();
+            resp.assert_header("ratelimit-remaining", "0");
+            resp.assert_header("ratelimit-reset", "60");
+
+            let resp = cli.get("/").header("x-real-ip", "1.1.1.1").send().await;
+            resp.assert_status(StatusCode::TOO_MANY_REQUESTS);
+            resp.assert_header("ratelimit-remaining", "0");
+            resp.assert_header("ratelimit-policy", "2;w=60");
+            resp.assert_header("ratelimit", "limit=2, remaining=0, reset=60");
+            resp.assert_header(header::RETRY_AFTER, "30");
+
+            let resp = cli.get("/").header("x-real-ip", "2.2.2.2").send().await;
+            resp.assert_status_is_ok();
+            resp.assert_header("ratelimit-remaining", "1");
+        }
+    }
+
+    #[tokio::test]
+    async fn key_by_header() {
+        let ep = index.with(
+            RateLimit::new(Quota::per_hour(1), MemoryRateLimitStore::new())
+                .key_by_header("x-api-key"),
+        );
+        let cli = TestClient::new(ep);
+
+        cli.get("/")
+            .header("x-api-key", "a")
+            .send()
+            .await
+            .assert_status_is_ok();
+        cli.get("/")
+            .header("x-api-key", "a")
+            .send()
+            .await
+            .assert_status(StatusCode::TOO_MANY_REQUESTS);
+        cli.get("/")
+            .header("x-api-key", "b")
+            .send()
+            .await
+            .assert_status_is_ok();
+
+        // requests without a key are not limited
+        for _ in 0..3 {
+            let resp = cli.get("/").send().await;
+            resp.assert_status_is_ok();
+            resp.assert_header_is_not_exist("ratelimit-limit");
+        }
+    }
+
+    #[tokio::test]
+    async fn key_fn() {
+        let ep = index.with(
+            RateLimit::new(Quota::per_hour(1), MemoryRateLimitStore::new())
+                .key_fn(|req| Some(req.uri().path().to_string())),
+        );
+        let cli = TestClient::new(ep);
+
+        cli.get("/a").send().await.assert_status_is_ok();
+        cli.get("/a")
+            .send()
+            .await
+            .assert_status(StatusCode::TOO_MANY_REQUESTS);
+        cli.get("/b").send().await.assert_status_is_ok();
+    }
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use redis::{Script, aio::ConnectionLike};
+
+use crate::{
+    Result,
+    error::RateLimitError,
+    middleware::{Quota, RateLimitAlgorithm, RateLimitDecision, RateLimitStore},
+};
+
+const GCRA_SCRIPT: &str = r#"
+local interval = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000000 + tonumber(time[2])
+local tat = tonumber(redis.call('GET', KEYS[1]))
+if tat == nil or tat < now then
+    tat = now
+end
+local new_tat = tat + interval
+local allow_at = new_tat - burst * interval
+if now < allow_at then
+    return {0, 0, tat - now, allow_at - now}
+end
+redis.call('SET', KEYS[1], new_tat, 'PX', math.ceil((new_tat - now) / 1000))
+return {1, math.floor((now - allow_at) / interval), new_tat - now, 0}
+"#;
+
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local limit = tonumber(ARGV[1])
+local period = tonumber(ARGV[2])
+local capacity = tonumber(ARGV[3])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000000 + tonumber(time[2])
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'updated_at')
+local tokens = tonumber(state[1])
+local updated_at = tonumber(state[2])
+if tokens == nil or updated_at == nil then
+    tokens = capacity
+else
+    tokens = math.min(capacity, tokens + math.max(0, now - updated_at) / period * limit)
+end
+local allowed = 0
+local retry_after = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_after = math.ceil((1 - tokens) / limit * period)
+end
+local reset_after = math.ceil((capacity - tokens) / limit * period)
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated_at', now)
+redis.call('PEXPIRE', KEYS[1], math.max(1, math.ceil(reset_after / 1000)))
+return {allowed, math.floor(tokens), reset_after, retry_after}
+"#;
+
+/// A rate limit storage using redis.
+///
+/// The algorithms are executed atomically with Lua scripts, and the clock of
+/// the redis server is used, so the same storage can be shared by multiple
+/// processes.
+///
+/// # Errors
+///
+/// - [`RateLimitError`]
+#[cfg_attr(docsrs, doc(cfg(feature = "redis-rate-limit")))]
+pub struct RedisRateLimitStore<T> {
+    connection: T,
+    key_prefix: String,
+    gcra: Script,
+    token_bucket: Script,
+}
+
+impl<T> RedisRateLimitStore<T> {
+    /// Create a `RedisRateLimitStore`.
+    pub fn new(connection: T) -> Self {
+        Self {
+            connection,
+            key_prefix: "rate-limit:".to_string(),
+            gcra: Script::new(GCRA_SCRIPT),
+            token_bucket: Script::new(TOKEN_BUCKET_SCRIPT),
+        }
+    }
+
+    /// Sets the prefix of the redis keys. (defaults to `rate-limit:`)
+    #[must_use]
+    pub fn key_prefix(self, key_prefix: impl Into<String>) -> Self {
+        Self {
+            key_prefix: key_prefix.into(),
+            ..self
+        }
+    }
+}
+
+impl<T: ConnectionLike + Clone + Sync + Send> RateLimitStore for RedisRateLimitStore<T> {
+    async fn check<'a>(
+        &'a self,
+        key: &'a str,
+        algorithm: RateLimitAlgorithm,
+        quota: &'a Quota,
+    ) -> Result<RateLimitDecision> {
+        let key = format!("{}{}", self.key_prefix, key);
+        let invocation = match algorithm {
+            RateLimitAlgorithm::Gcra => {
+                let mut invocation = self.gcra.prepare_invoke();
+                invocation
+                    .key(key)
+                    .arg(quota.emission_interval().as_micros() as u64)
+                    .arg(quota.burst());
+                invocation
+            }
+            RateLimitAlgorithm::TokenBucket => {
+                let mut invocation = self.token_bucket.prepare_invoke();
+                invocation
+                    .key(key)
+                    .arg(quota.limit())
+                    .arg(quota.period().as_micros() as u64)
+                    .arg(quota.burst());
+                invocation
+            }
+        };
+        let (allowed, remaining, reset_after, retry_after): (i64, i64, i64, i64) = invocation
+            .invoke_async(&mut self.connection.clone())
+            .await
+            .map_err(RateLimitError::Redis)?;
+
+        Ok(RateLimitDecision {
+            allowed: allowed == 1,
+            limit: quota.limit(),
+            period: quota.period(),
+            remaining: remaining.max(0) as u32,
+            reset_after: Duration::from_micros(reset_after.max(0) as u64),
+            retry_after: (allowed != 1).then(|| Duration::from_micros(retry_after.max(0) as u64)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use redis::{Client, ConnectionLike, aio::ConnectionManager};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn redis_rate_limit() {
+        let mut client = match Client::open("redis://127.0.0.1/") {
+            Ok(client) => client,
+            Err(_) => return,
+        };
+        if !client.check_connection() {
+            panic!("redis server is not running");
+        }
+
+        let store = RedisRateLimitStore::new(ConnectionManager::new(client).await.unwrap())
+            .key_prefix(format!("poem-test-{}:", std::process::id()));
+        let quota = Quota::per_minute(2);
+
+        for algorithm in [RateLimitAlgorithm::Gcra, RateLimitAlgorithm::TokenBucket] {
+            let key = format!("{algorithm:?}");
+
+            let decision = store.check(&key, algorithm, &quota).await.unwrap();
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, 1);
+
+            let decision = store.check(&key, algorithm, &quota).await.unwrap();
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, 0);
+
+            let decision = store.check(&key, algorithm, &quota).await.unwrap();
+            assert!(!decision.allowed);
+            assert!(decision.retry_after.unwrap() <= Duration::from_secs(30));
+        }
+    }
+}
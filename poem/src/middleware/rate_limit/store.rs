@@ -0,0 +1,40 @@
+use std::{future::Future, time::Duration};
+
+use crate::{
+    Result,
+    middleware::{Quota, RateLimitAlgorithm},
+};
+
+/// The result of checking a request against a [`Quota`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+pub struct RateLimitDecision {
+    /// Whether the request is allowed.
+    pub allowed: bool,
+    /// The number of requests allowed in the quota period.
+    pub limit: u32,
+    /// The period of the quota.
+    pub period: Duration,
+    /// The number of requests that can still be made immediately.
+    pub remaining: u32,
+    /// The time after which the quota is fully replenished.
+    pub reset_after: Duration,
+    /// The time the client has to wait before retrying, if the request is
+    /// rejected.
+    pub retry_after: Option<Duration>,
+}
+
+/// Represents a back-end rate limit storage.
+///
+/// The storage must apply the algorithm atomically, so that concurrent
+/// requests with the same key can never exceed the quota.
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+pub trait RateLimitStore: Send + Sync {
+    /// Consumes one request from the quota of `key`.
+    fn check<'a>(
+        &'a self,
+        key: &'a str,
+        algorithm: RateLimitAlgorithm,
+        quota: &'a Quota,
+    ) -> impl Future<Output = Result<RateLimitDecision>> + Send + 'a;
+}
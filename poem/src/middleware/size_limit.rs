@@ -1,23 +1,123 @@
+use std::{
+    collections::HashMap,
+    io::Error as IoError,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+use futures_util::StreamExt;
+
 use crate::{
-    Endpoint, Middleware, Request, Result, error::SizedLimitError, web::headers::HeaderMapExt,
+    Body, Endpoint, Middleware, Request, Result, error::SizedLimitError, web::headers::HeaderMapExt,
 };
 
 /// Middleware to limit the request payload size.
 ///
 /// If the incoming request does not contain the `Content-Length` header, the
-/// middleware will return the `LENGTH_REQUIRED` status code.
+/// middleware will return the `LENGTH_REQUIRED` status code, unless it is
+/// disabled with [`SizeLimit::require_content_length`].
+///
+/// If the `Content-Length` header exceeds the limit, the middleware returns
+/// the `PAYLOAD_TOO_LARGE` status code without calling the endpoint. The limit
+/// is also enforced while the body is being read, so that reading the body
+/// fails as soon as the data received so far exceeds the limit, and the
+/// middleware returns the same status code. This also applies to the chunked
+/// requests, which have no `Content-Length` header.
+///
+/// Different limits can be set for different content types. When the
+/// middleware is applied to a route which is nested in another route that
+/// also uses this middleware, the smaller of the two limits applies.
 ///
 /// # Errors
 ///
 /// - [`SizedLimitError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Route, handler,
+///     http::StatusCode,
+///     middleware::SizeLimit,
+///     post,
+///     test::TestClient,
+/// };
+///
+/// #[handler]
+/// fn index(body: String) -> String {
+///     body
+/// }
+///
+/// let app = Route::new()
+///     .at("/", post(index))
+///     .at(
+///         "/small",
+///         post(index).with(SizeLimit::new(3).require_content_length(false)),
+///     )
+///     .with(
+///         SizeLimit::new(5)
+///             .content_type("application/json", 10)
+///             .require_content_length(false),
+///     );
+/// let cli = TestClient::new(app);
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// cli.post("/")
+///     .body("123456")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+/// cli.post("/")
+///     .content_type("application/json")
+///     .body("123456")
+///     .send()
+///     .await
+///     .assert_status_is_ok();
+/// cli.post("/small")
+///     .body("1234")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+/// # });
+/// ```
 pub struct SizeLimit {
     max_size: usize,
+    content_types: HashMap<String, usize>,
+    require_content_length: bool,
 }
 
 impl SizeLimit {
     /// Create `SizeLimit` middleware.
     pub fn new(max_size: usize) -> Self {
-        Self { max_size }
+        Self {
+            max_size,
+            content_types: HashMap::new(),
+            require_content_length: true,
+        }
+    }
+
+    /// Sets the limit for the requests with the specified content type, such
+    /// as `application/json`, or `image/*` for all the subtypes.
+    #[must_use]
+    pub fn content_type(mut self, content_type: impl AsRef<str>, max_size: usize) -> Self {
+        self.content_types
+            .insert(content_type.as_ref().to_ascii_lowercase(), max_size);
+        self
+    }
+
+    /// Sets whether the `Content-Length` header is required. Default is
+    /// `true`.
+    ///
+    /// If it is required and the incoming request does not contain it, the
+    /// middleware will return the `LENGTH_REQUIRED` status code.
+    #[must_use]
+    pub fn require_content_length(self, value: bool) -> Self {
+        Self {
+            require_content_length: value,
+            ..self
+        }
     }
 }
 
@@ -28,6 +128,8 @@ impl<E: Endpoint> Middleware<E> for SizeLimit {
         SizeLimitEndpoint {
             inner: ep,
             max_size: self.max_size,
+            content_types: self.content_types.clone(),
+            require_content_length: self.require_content_length,
         }
     }
 }
@@ -36,32 +138,110 @@ impl<E: Endpoint> Middleware<E> for SizeLimit {
 pub struct SizeLimitEndpoint<E> {
     inner: E,
     max_size: usize,
+    content_types: HashMap<String, usize>,
+    require_content_length: bool,
+}
+
+impl<E> SizeLimitEndpoint<E> {
+    fn max_size(&self, req: &Request) -> usize {
+        let Some(essence) = req
+            .content_type()
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|essence| essence.trim().to_ascii_lowercase())
+        else {
+            return self.max_size;
+        };
+
+        self.content_types
+            .get(&essence)
+            .or_else(|| {
+                let (ty, _) = essence.split_once('/')?;
+                self.content_types.get(&format!("{ty}/*"))
+            })
+            .copied()
+            .unwrap_or(self.max_size)
+    }
+}
+
+/// The limit of the request body, shared by the nested `SizeLimit`
+/// middlewares so that the body is only wrapped once.
+struct BodyLimit {
+    max_size: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+impl BodyLimit {
+    fn is_exceeded(&self, size: u64) -> bool {
+        size > self.max_size.load(Ordering::Relaxed) as u64
+    }
+}
+
+fn limit_body(body: Body, limit: Arc<BodyLimit>) -> Body {
+    let mut size = 0u64;
+    Body::from_bytes_stream(body.into_bytes_stream().map(move |res| {
+        let data = res?;
+        size = size.saturating_add(data.len() as u64);
+        if limit.is_exceeded(size) {
+            limit.exceeded.store(true, Ordering::Relaxed);
+            return Err(IoError::other(SizedLimitError::PayloadTooLarge));
+        }
+        Ok(data)
+    }))
 }
 
 impl<E: Endpoint> Endpoint for SizeLimitEndpoint<E> {
     type Output = E::Output;
 
-    async fn call(&self, req: Request) -> Result<Self::Output> {
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        let max_size = self.max_size(&req);
         let content_length = req
             .headers()
             .typed_get::<headers::ContentLength>()
-            .ok_or(SizedLimitError::MissingContentLength)?;
+            .map(|content_length| content_length.0);
+        match content_length {
+            None if self.require_content_length => {
+                return Err(SizedLimitError::MissingContentLength.into());
+            }
+            Some(content_length) if content_length > max_size as u64 => {
+                return Err(SizedLimitError::PayloadTooLarge.into());
+            }
+            _ => {}
+        }
 
-        if content_length.0 as usize > self.max_size {
-            return Err(SizedLimitError::PayloadTooLarge.into());
+        // a nested middleware can only lower the limit
+        if let Some(limit) = req.extensions().get::<Arc<BodyLimit>>() {
+            limit.max_size.fetch_min(max_size, Ordering::Relaxed);
+            return self.inner.call(req).await;
         }
 
-        self.inner.call(req).await
+        let limit = Arc::new(BodyLimit {
+            max_size: AtomicUsize::new(max_size),
+            exceeded: AtomicBool::new(false),
+        });
+        let body = req.take_body();
+        req.set_body(limit_body(body, limit.clone()));
+        req.extensions_mut().insert(limit.clone());
+
+        let res = self.inner.call(req).await;
+        if limit.exceeded.load(Ordering::Relaxed) {
+            // the error may have been converted by the extractors, such as
+            // `ReadBodyError::Io`
+            return Err(SizedLimitError::PayloadTooLarge.into());
+        }
+        res
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures_util::stream;
     use http::StatusCode;
 
     use super::*;
     use crate::{
+        Route,
         endpoint::{EndpointExt, make_sync},
+        handler, post,
         test::TestClient,
     };
 
@@ -96,4 +276,119 @@ mod tests {
             .await
             .assert_status_is_ok();
     }
+
+    #[tokio::test]
+    async fn reject_before_call() {
+        let called = Arc::new(AtomicBool::new(false));
+        let ep = make_sync({
+            let called = called.clone();
+            move |_| called.store(true, Ordering::Relaxed)
+        })
+        .with(SizeLimit::new(5));
+        let cli = TestClient::new(ep);
+
+        cli.post("/")
+            .header("content-length", 6)
+            .body(&b"123456"[..])
+            .send()
+            .await
+            .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(!called.load(Ordering::Relaxed));
+    }
+
+    fn chunked(chunks: &[&'static str]) -> Body {
+        Body::from_bytes_stream(stream::iter(
+            chunks
+                .iter()
+                .map(|chunk| Ok::<_, IoError>(chunk.as_bytes()))
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    #[handler(internal)]
+    fn echo(body: String) -> String {
+        body
+    }
+
+    #[tokio::test]
+    async fn streaming() {
+        let cli = TestClient::new(post(echo).with(SizeLimit::new(5).require_content_length(false)));
+
+        cli.post("/")
+            .body(chunked(&["12", "34", "5"]))
+            .send()
+            .await
+            .assert_text("12345")
+            .await;
+        cli.post("/")
+            .body(chunked(&["12", "34", "56"]))
+            .send()
+            .await
+            .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+        cli.post("/").send().await.assert_status_is_ok();
+    }
+
+    #[tokio::test]
+    async fn content_types() {
+        let cli = TestClient::new(
+            post(echo).with(
+                SizeLimit::new(2)
+                    .content_type("application/json", 4)
+                    .content_type("image/*", 6)
+                    .require_content_length(false),
+            ),
+        );
+
+        for (content_type, ok, too_large) in [
+            (None, "12", "123"),
+            (Some("application/json; charset=utf-8"), "1234", "12345"),
+            (Some("Image/PNG"), "123456", "1234567"),
+            (Some("text/plain"), "12", "123"),
+        ] {
+            for (body, status) in [
+                (ok, StatusCode::OK),
+                (too_large, StatusCode::PAYLOAD_TOO_LARGE),
+            ] {
+                let mut req = cli.post("/").body(body);
+                if let Some(content_type) = content_type {
+                    req = req.content_type(content_type);
+                }
+                req.send().await.assert_status(status);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn nested() {
+        let app = Route::new()
+            .at("/", post(echo))
+            .at(
+                "/small",
+                post(echo).with(SizeLimit::new(2).require_content_length(false)),
+            )
+            .at(
+                "/large",
+                post(echo).with(SizeLimit::new(10).require_content_length(false)),
+            )
+            .with(SizeLimit::new(5).require_content_length(false));
+        let cli = TestClient::new(app);
+
+        for (path, ok, too_large) in [
+            ("/", "12345", "123456"),
+            ("/small", "12", "123"),
+            ("/large", "12345", "123456"),
+        ] {
+            cli.post(path)
+                .body(chunked(&[ok]))
+                .send()
+                .await
+                .assert_text(ok)
+                .await;
+            cli.post(path)
+                .body(chunked(&[too_large]))
+                .send()
+                .await
+                .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+    }
 }
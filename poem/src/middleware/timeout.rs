@@ -0,0 +1,323 @@
+use std::{
+    future::Future,
+    io::{Error as IoError, ErrorKind},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures_util::Stream;
+use http::StatusCode;
+use tokio::time::{Instant, Sleep};
+
+use crate::{
+    Body, Endpoint, IntoResponse, Middleware, Request, Response, Result, error::TimeoutError,
+};
+
+/// Middleware to limit the time spent processing a request.
+///
+/// If the inner endpoint does not respond within the timeout, it is cancelled
+/// and a [`TimeoutError::Endpoint`] is returned, which responds with
+/// `503 Service Unavailable` by default.
+///
+/// Apply the middleware to individual routes to use different timeouts per
+/// route.
+///
+/// Optionally, the middleware can also limit the time spent reading the
+/// request body, and the time between two chunks of a streamed response body.
+///
+/// # Errors
+///
+/// - [`TimeoutError`]
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use poem::{
+///     EndpointExt, Route, get, handler, http::StatusCode, middleware::Timeout,
+///     test::TestClient,
+/// };
+///
+/// #[handler]
+/// async fn slow() {
+///     tokio::time::sleep(Duration::from_secs(10)).await;
+/// }
+///
+/// let app = Route::new().at(
+///     "/slow",
+///     get(slow).with(Timeout::new(Duration::from_millis(100))),
+/// );
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cli = TestClient::new(app);
+/// cli.get("/slow")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+/// # });
+/// ```
+pub struct Timeout {
+    timeout: Duration,
+    status: StatusCode,
+    read_body_timeout: Option<Duration>,
+    response_chunk_timeout: Option<Duration>,
+}
+
+impl Timeout {
+    /// Create `Timeout` middleware.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            read_body_timeout: None,
+            response_chunk_timeout: None,
+        }
+    }
+
+    /// Sets the status code returned when the endpoint times out. (defaults to
+    /// `503 Service Unavailable`)
+    ///
+    /// Use [`StatusCode::GATEWAY_TIMEOUT`] when the endpoint is waiting for an
+    /// upstream service.
+    #[must_use]
+    pub fn status(self, status: StatusCode) -> Self {
+        Self { status, ..self }
+    }
+
+    /// Sets the maximum time to read the request body, measured from the
+    /// moment the request is received.
+    ///
+    /// When it is exceeded, reading the body fails and the extractors respond
+    /// with `408 Request Timeout`.
+    #[must_use]
+    pub fn read_body_timeout(self, timeout: Duration) -> Self {
+        Self {
+            read_body_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets the maximum time between two chunks of the response body.
+    ///
+    /// When it is exceeded, the response body is aborted.
+    #[must_use]
+    pub fn response_chunk_timeout(self, timeout: Duration) -> Self {
+        Self {
+            response_chunk_timeout: Some(timeout),
+            ..self
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for Timeout {
+    type Output = TimeoutEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        TimeoutEndpoint {
+            inner: ep,
+            timeout: self.timeout,
+            status: self.status,
+            read_body_timeout: self.read_body_timeout,
+            response_chunk_timeout: self.response_chunk_timeout,
+        }
+    }
+}
+
+/// Endpoint for the Timeout middleware.
+pub struct TimeoutEndpoint<E> {
+    inner: E,
+    timeout: Duration,
+    status: StatusCode,
+    read_body_timeout: Option<Duration>,
+    response_chunk_timeout: Option<Duration>,
+}
+
+impl<E: Endpoint> Endpoint for TimeoutEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        if let Some(timeout) = self.read_body_timeout {
+            let body = req.take_body().into_bytes_stream();
+            req.set_body(Body::from_bytes_stream(TimeoutStream::new(
+                body,
+                timeout,
+                false,
+                TimeoutError::ReadBody,
+            )));
+        }
+
+        let mut resp = match tokio::time::timeout(self.timeout, self.inner.call(req)).await {
+            Ok(resp) => resp?.into_response(),
+            Err(_) => return Err(TimeoutError::Endpoint(self.status).into()),
+        };
+
+        if let Some(timeout) = self.response_chunk_timeout {
+            let body = resp.take_body().into_bytes_stream();
+            resp.set_body(Body::from_bytes_stream(TimeoutStream::new(
+                body,
+                timeout,
+                true,
+                TimeoutError::ResponseBody,
+            )));
+        }
+
+        Ok(resp)
+    }
+}
+
+pin_project_lite::pin_project! {
+    struct TimeoutStream<S> {
+        #[pin]
+        stream: S,
+        #[pin]
+        sleep: Sleep,
+        timeout: Duration,
+        reset_on_chunk: bool,
+        error: TimeoutError,
+        finished: bool,
+    }
+}
+
+impl<S> TimeoutStream<S> {
+    fn new(stream: S, timeout: Duration, reset_on_chunk: bool, error: TimeoutError) -> Self {
+        Self {
+            stream,
+            sleep: tokio::time::sleep(timeout),
+            timeout,
+            reset_on_chunk,
+            error,
+            finished: false,
+        }
+    }
+}
+
+impl<S> Stream for TimeoutStream<S>
+where
+    S: Stream<Item = Result<Bytes, IoError>>,
+{
+    type Item = Result<Bytes, IoError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if *this.finished {
+            return Poll::Ready(None);
+        }
+
+        match this.stream.poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                if *this.reset_on_chunk {
+                    this.sleep.as_mut().reset(Instant::now() + *this.timeout);
+                }
+                Poll::Ready(Some(item))
+            }
+            Poll::Ready(None) => {
+                *this.finished = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => match this.sleep.poll(cx) {
+                Poll::Ready(()) => {
+                    *this.finished = true;
+                    Poll::Ready(Some(Err(IoError::new(ErrorKind::TimedOut, *this.error))))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::{EndpointExt, handler, test::TestClient};
+
+    #[tokio::test]
+    async fn endpoint_timeout() {
+        #[handler(internal)]
+        async fn index(req: &Request) -> &'static str {
+            let ms = req.header("x-delay").unwrap().parse().unwrap();
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+            "hello"
+        }
+
+        let cli = TestClient::new(index.with(Timeout::new(Duration::from_millis(100))));
+        let resp = cli.get("/").header("x-delay", 10).send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("hello").await;
+
+        cli.get("/")
+            .header("x-delay", 500)
+            .send()
+            .await
+            .assert_status(StatusCode::SERVICE_UNAVAILABLE);
+
+        let cli = TestClient::new(
+            index
+                .with(Timeout::new(Duration::from_millis(100)).status(StatusCode::GATEWAY_TIMEOUT)),
+        );
+        cli.get("/")
+            .header("x-delay", 500)
+            .send()
+            .await
+            .assert_status(StatusCode::GATEWAY_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn read_body_timeout() {
+        #[handler(internal)]
+        async fn index(body: String) -> String {
+            body
+        }
+
+        let ep = index.with(
+            Timeout::new(Duration::from_secs(5)).read_body_timeout(Duration::from_millis(100)),
+        );
+        let cli = TestClient::new(ep);
+
+        let resp = cli.post("/").body("hello").send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("hello").await;
+
+        let body = futures_util::stream::once(async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok::<_, IoError>(Bytes::from_static(b"hello"))
+        });
+        cli.post("/")
+            .body(Body::from_bytes_stream(body))
+            .send()
+            .await
+            .assert_status(StatusCode::REQUEST_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn response_chunk_timeout() {
+        #[handler(internal)]
+        async fn index(req: &Request) -> Body {
+            let ms = req.header("x-delay").unwrap().parse().unwrap();
+            Body::from_bytes_stream(futures_util::stream::iter(0..3).then(move |_| async move {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+                Ok::<_, IoError>(Bytes::from_static(b"a"))
+            }))
+        }
+
+        let ep = index.with(
+            Timeout::new(Duration::from_secs(5)).response_chunk_timeout(Duration::from_millis(200)),
+        );
+        let cli = TestClient::new(ep);
+
+        // the total time exceeds the timeout, but every chunk arrives in time
+        let resp = cli.get("/").header("x-delay", 100).send().await;
+        resp.assert_status_is_ok();
+        resp.assert_text("aaa").await;
+
+        let resp = cli.get("/").header("x-delay", 500).send().await;
+        resp.assert_status_is_ok();
+        let err = resp.0.into_body().into_bytes().await.unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+    }
+}
@@ -90,7 +90,7 @@ impl From<(http::request::Parts, LocalAddr, RemoteAddr, Scheme)> for RequestPart
             parts
                 .extensions
                 .remove::<hyper::upgrade::OnUpgrade>()
-                .map(|fut| OnUpgrade { fut }),
+                .map(OnUpgrade::from_hyper),
         );
 
         Self {
@@ -160,7 +160,7 @@ impl From<(http::Request<Incoming>, LocalAddr, RemoteAddr, Scheme)> for Request
             parts
                 .extensions
                 .remove::<hyper::upgrade::OnUpgrade>()
-                .map(|fut| OnUpgrade { fut }),
+                .map(OnUpgrade::from_hyper),
         );
 
         Self {
@@ -461,6 +461,32 @@ impl Request {
         &mut self.state
     }
 
+    /// Returns a copy of the request with an empty body, so that an endpoint
+    /// can be called again with the same request.
+    #[cfg(any(feature = "cache", feature = "etag"))]
+    pub(crate) fn clone_without_body(&self) -> Request {
+        let mut req = Request::builder()
+            .method(self.method.clone())
+            .uri(self.uri.clone())
+            .version(self.version)
+            .finish();
+        req.headers = self.headers.clone();
+        req.extensions = self.extensions.clone();
+
+        let state = &mut req.state;
+        state.local_addr = self.state.local_addr.clone();
+        state.remote_addr = self.state.remote_addr.clone();
+        state.scheme = self.state.scheme.clone();
+        state.original_uri = self.state.original_uri.clone();
+        state.match_params = self.state.match_params.clone();
+        #[cfg(feature = "cookie")]
+        {
+            state.cookie_jar = self.state.cookie_jar.clone();
+        }
+
+        req
+    }
+
     /// Returns the parameters used by the extractor.
     pub fn split(mut self) -> (Request, RequestBody) {
         let body = self.take_body();
@@ -492,10 +518,37 @@ impl Request {
     }
 }
 
-pin_project_lite::pin_project! {
-    /// A future for a possible HTTP upgrade.
-    pub struct OnUpgrade {
-        #[pin] fut: hyper::upgrade::OnUpgrade,
+/// A stream which can be returned by [`OnUpgrade`].
+trait UpgradedStream: AsyncRead + AsyncWrite + Send + Sync + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Sync + Unpin> UpgradedStream for T {}
+
+enum OnUpgradeInner {
+    Hyper(hyper::upgrade::OnUpgrade),
+    Stream(Option<Box<dyn UpgradedStream>>),
+}
+
+/// A future for a possible HTTP upgrade.
+pub struct OnUpgrade {
+    inner: OnUpgradeInner,
+}
+
+impl OnUpgrade {
+    fn from_hyper(fut: hyper::upgrade::OnUpgrade) -> Self {
+        Self {
+            inner: OnUpgradeInner::Hyper(fut),
+        }
+    }
+
+    /// Create an `OnUpgrade` which resolves to the specified stream instead
+    /// of an upgraded HTTP connection, for example an in-memory stream in
+    /// the tests.
+    pub fn from_stream(
+        stream: impl AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static,
+    ) -> Self {
+        Self {
+            inner: OnUpgradeInner::Stream(Some(Box::new(stream))),
+        }
     }
 }
 
@@ -504,19 +557,33 @@ impl Future for OnUpgrade {
 
     #[inline]
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let this = self.project();
-        this.fut
-            .poll(cx)
-            .map_ok(|stream| Upgraded { stream })
-            .map_err(|err| UpgradeError::Other(err.to_string()))
+        match &mut self.get_mut().inner {
+            OnUpgradeInner::Hyper(fut) => Pin::new(fut)
+                .poll(cx)
+                .map_ok(|stream| Upgraded {
+                    inner: UpgradedInner::Hyper(stream),
+                })
+                .map_err(|err| UpgradeError::Other(err.to_string())),
+            OnUpgradeInner::Stream(stream) => Poll::Ready(
+                stream
+                    .take()
+                    .map(|stream| Upgraded {
+                        inner: UpgradedInner::Stream(stream),
+                    })
+                    .ok_or_else(|| UpgradeError::Other("already upgraded".to_string())),
+            ),
+        }
     }
 }
 
-pin_project_lite::pin_project! {
-    /// An upgraded HTTP connection.
-    pub struct Upgraded {
-        #[pin] stream: hyper::upgrade::Upgraded,
-    }
+enum UpgradedInner {
+    Hyper(hyper::upgrade::Upgraded),
+    Stream(Box<dyn UpgradedStream>),
+}
+
+/// An upgraded HTTP connection.
+pub struct Upgraded {
+    inner: UpgradedInner,
 }
 
 impl AsyncRead for Upgraded {
@@ -526,7 +593,12 @@ impl AsyncRead for Upgraded {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<std::io::Result<()>> {
-        Pin::new(&mut TokioIo::new(self.project().stream)).poll_read(cx, buf)
+        match &mut self.get_mut().inner {
+            UpgradedInner::Hyper(stream) => {
+                Pin::new(&mut TokioIo::new(Pin::new(stream))).poll_read(cx, buf)
+            }
+            UpgradedInner::Stream(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
     }
 }
 
@@ -537,17 +609,26 @@ impl AsyncWrite for Upgraded {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<Result<usize, Error>> {
-        self.project().stream.poll_write(cx, buf)
+        match &mut self.get_mut().inner {
+            UpgradedInner::Hyper(stream) => Pin::new(stream).poll_write(cx, buf),
+            UpgradedInner::Stream(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
     }
 
     #[inline]
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
-        self.project().stream.poll_flush(cx)
+        match &mut self.get_mut().inner {
+            UpgradedInner::Hyper(stream) => Pin::new(stream).poll_flush(cx),
+            UpgradedInner::Stream(stream) => Pin::new(stream).poll_flush(cx),
+        }
     }
 
     #[inline]
     fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
-        self.project().stream.poll_shutdown(cx)
+        match &mut self.get_mut().inner {
+            UpgradedInner::Hyper(stream) => Pin::new(stream).poll_shutdown(cx),
+            UpgradedInner::Stream(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
     }
 }
 
@@ -13,7 +13,7 @@ use std::{
 };
 
 use futures_util::FutureExt;
-use http::uri::Scheme;
+use http::{HeaderValue, header, uri::Scheme};
 use hyper::body::Incoming;
 use hyper_util::server::conn::auto;
 use pin_project_lite::pin_project;
@@ -27,7 +27,7 @@ use tokio_util::sync::CancellationToken;
 use crate::{
     Endpoint, EndpointExt, IntoEndpoint, Response,
     endpoint::{DynEndpoint, ToDynEndpoint},
-    listener::{Acceptor, AcceptorExt, Listener},
+    listener::{Acceptor, AcceptorExt, BoxAcceptor, ConnectionExtensions, Listener},
     web::{LocalAddr, RemoteAddr},
 };
 
@@ -45,6 +45,8 @@ pub struct Server<L, A> {
     http2_max_concurrent_streams: Option<u32>,
     http2_max_pending_accept_reset_streams: Option<u32>,
     http2_max_header_list_size: u32,
+    #[cfg(all(unix, feature = "socket-activation"))]
+    handoff: Option<crate::listener::Handoff>,
 }
 
 impl<L: Listener> Server<L, Infallible> {
@@ -57,6 +59,8 @@ impl<L: Listener> Server<L, Infallible> {
             http2_max_concurrent_streams: None,
             http2_max_pending_accept_reset_streams: Some(20),
             http2_max_header_list_size: 16384,
+            #[cfg(all(unix, feature = "socket-activation"))]
+            handoff: None,
         }
     }
 }
@@ -71,6 +75,8 @@ impl<A: Acceptor> Server<Infallible, A> {
             http2_max_concurrent_streams: None,
             http2_max_pending_accept_reset_streams: Some(20),
             http2_max_header_list_size: 16384,
+            #[cfg(all(unix, feature = "socket-activation"))]
+            handoff: None,
         }
     }
 }
@@ -135,6 +141,19 @@ where
         }
     }
 
+    /// Hands the listening sockets off to a new process when the signal of
+    /// the [`Handoff`](crate::listener::Handoff) is received, then starts the
+    /// graceful shutdown.
+    #[cfg(all(unix, feature = "socket-activation"))]
+    #[cfg_attr(docsrs, doc(cfg(all(unix, feature = "socket-activation"))))]
+    #[must_use]
+    pub fn handoff(self, handoff: crate::listener::Handoff) -> Self {
+        Self {
+            handoff: Some(handoff),
+            ..self
+        }
+    }
+
     /// Run this server.
     pub async fn run<E>(self, ep: E) -> IoResult<()>
     where
@@ -164,6 +183,8 @@ where
             http2_max_concurrent_streams,
             http2_max_pending_accept_reset_streams,
             http2_max_header_list_size,
+            #[cfg(all(unix, feature = "socket-activation"))]
+            handoff,
         } = self;
         let name = name.as_deref();
         let alive_connections = Arc::new(AtomicUsize::new(0));
@@ -176,6 +197,25 @@ where
             Either::Acceptor(acceptor) => acceptor.boxed(),
         };
 
+        #[cfg(all(unix, feature = "socket-activation"))]
+        let signal = {
+            let handoff = match handoff {
+                Some(handoff) => Some((handoff, acceptor.listener_fds()?)),
+                None => None,
+            };
+            async move {
+                match handoff {
+                    Some((handoff, fds)) => {
+                        tokio::select! {
+                            _ = signal => {}
+                            _ = handoff.run(fds, name) => {}
+                        }
+                    }
+                    None => signal.await,
+                }
+            }
+        };
+
         tokio::pin!(signal);
 
         for addr in acceptor.local_addr() {
@@ -183,6 +223,40 @@ where
         }
         tracing::info!(name = name, "server started");
 
+        #[cfg(feature = "http3")]
+        let alt_svc = {
+            let http3_acceptors = acceptor.take_http3_acceptors();
+            let alt_svc = crate::listener::Http3Acceptor::alt_svc(&http3_acceptors);
+
+            for http3_acceptor in http3_acceptors {
+                alive_connections.fetch_add(1, Ordering::Release);
+
+                let serve =
+                    http3_acceptor.serve(ep.clone(), server_graceful_shutdown_token.clone());
+                let alive_connections = alive_connections.clone();
+                let notify = notify.clone();
+                let timeout_token = timeout_token.clone();
+                let server_graceful_shutdown_token = server_graceful_shutdown_token.clone();
+
+                tokio::spawn(async move {
+                    tokio::select! {
+                        _ = serve => {}
+                        _ = timeout_token.cancelled() => {}
+                    }
+
+                    if alive_connections.fetch_sub(1, Ordering::Acquire) == 1
+                        && server_graceful_shutdown_token.is_cancelled()
+                    {
+                        notify.notify_one();
+                    }
+                });
+            }
+
+            alt_svc
+        };
+        #[cfg(not(feature = "http3"))]
+        let alt_svc: Option<HeaderValue> = None;
+
         loop {
             tokio::select! {
                 _ = &mut signal => {
@@ -208,7 +282,9 @@ where
                     if let Ok((socket, local_addr, remote_addr, scheme)) = res {
                         alive_connections.fetch_add(1, Ordering::Release);
 
+                        let extensions = BoxAcceptor::connection_extensions(&socket);
                         let ep = ep.clone();
+                        let alt_svc = alt_svc.clone();
                         let alive_connections = alive_connections.clone();
                         let notify = notify.clone();
                         let timeout_token = timeout_token.clone();
@@ -221,7 +297,9 @@ where
                                 local_addr,
                                 remote_addr,
                                 scheme,
+                                extensions,
                                 ep,
+                                alt_svc,
                                 server_graceful_shutdown_token: server_graceful_shutdown_token.clone(),
                                 idle_connection_close_timeout: idle_timeout,
                                 http2_max_concurrent_streams,
@@ -386,7 +464,9 @@ struct ConnectionOptions<Io> {
     local_addr: LocalAddr,
     remote_addr: RemoteAddr,
     scheme: Scheme,
+    extensions: ConnectionExtensions,
     ep: Arc<dyn DynEndpoint<Output = Response>>,
+    alt_svc: Option<HeaderValue>,
     server_graceful_shutdown_token: CancellationToken,
     idle_connection_close_timeout: Option<Duration>,
     http2_max_concurrent_streams: Option<u32>,
@@ -403,7 +483,9 @@ where
         local_addr,
         remote_addr,
         scheme,
+        extensions,
         ep,
+        alt_svc,
         server_graceful_shutdown_token,
         idle_connection_close_timeout,
         http2_max_concurrent_streams,
@@ -411,6 +493,9 @@ where
         http2_max_header_list_size,
     } = opts;
 
+    // advertise the HTTP/3 endpoints on secure connections
+    let alt_svc = alt_svc.filter(|_| scheme == Scheme::HTTPS);
+
     let connection_shutdown_token = CancellationToken::new();
 
     let service = hyper::service::service_fn({
@@ -421,12 +506,16 @@ where
             let local_addr = local_addr.clone();
             let remote_addr = remote_addr.clone();
             let scheme = scheme.clone();
+            let extensions = extensions.clone();
+            let alt_svc = alt_svc.clone();
             async move {
-                Ok::<http::Response<_>, Infallible>(
-                    ep.get_response((req, local_addr, remote_addr, scheme).into())
-                        .await
-                        .into(),
-                )
+                let mut req: crate::Request = (req, local_addr, remote_addr, scheme).into();
+                req.extensions_mut().extend(extensions.to_extensions());
+                let mut resp = ep.get_response(req).await;
+                if let Some(alt_svc) = alt_svc {
+                    resp.headers_mut().entry(header::ALT_SVC).or_insert(alt_svc);
+                }
+                Ok::<http::Response<_>, Infallible>(resp.into())
             }
         }
     });
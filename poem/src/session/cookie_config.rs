@@ -19,6 +19,7 @@ pub enum CookieSecurity {
 /// Cookie configuration for session.
 pub struct CookieConfig {
     security: CookieSecurity,
+    previous_keys: Vec<CookieKey>,
     name: String,
     path: String,
     domain: Option<String>,
@@ -33,6 +34,7 @@ impl Default for CookieConfig {
     fn default() -> Self {
         Self {
             security: CookieSecurity::Plain,
+            previous_keys: Vec::new(),
             name: "poem-session".to_string(),
             path: "/".to_string(),
             domain: None,
@@ -67,6 +69,33 @@ impl CookieConfig {
         }
     }
 
+    /// Sets the previous keys for key rotation, ordered from the newest to the
+    /// oldest.
+    ///
+    /// The session cookie is always encrypted or signed with the current key,
+    /// but a cookie created with a previous key is still accepted, and then
+    /// re-issued with the current key. So the key can be rotated without
+    /// invalidating the existing sessions, and a previous key can be removed
+    /// once the cookies using it have expired.
+    ///
+    /// This has no effect on a `plain` CookieSession.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{session::CookieConfig, web::cookie::CookieKey};
+    ///
+    /// # let (new_key, old_key) = (CookieKey::generate(), CookieKey::generate());
+    /// let config = CookieConfig::private(new_key).previous_keys([old_key]);
+    /// ```
+    #[must_use]
+    pub fn previous_keys(self, keys: impl IntoIterator<Item = CookieKey>) -> Self {
+        Self {
+            previous_keys: keys.into_iter().collect(),
+            ..self
+        }
+    }
+
     /// Sets the `name` to the session cookie.
     #[must_use]
     pub fn name(self, value: impl Into<String>) -> Self {
@@ -182,12 +211,111 @@ impl CookieConfig {
     }
 
     /// Gets the cookie value from `CookieJar`.
+    ///
+    /// If the cookie was created with one of the
+    /// [previous keys](Self::previous_keys), it is re-issued with the current
+    /// key.
     pub fn get_cookie_value(&self, cookie_jar: &CookieJar) -> Option<String> {
-        let cookie = match &self.security {
-            CookieSecurity::Plain => cookie_jar.get(&self.name),
-            CookieSecurity::Private(key) => cookie_jar.private_with_key(key).get(&self.name),
-            CookieSecurity::Signed(key) => cookie_jar.signed_with_key(key).get(&self.name),
+        let (cookie, current) = match &self.security {
+            CookieSecurity::Plain => (cookie_jar.get(&self.name)?, true),
+            CookieSecurity::Private(key) => {
+                self.find_cookie(key, |key| cookie_jar.private_with_key(key).get(&self.name))?
+            }
+            CookieSecurity::Signed(key) => {
+                self.find_cookie(key, |key| cookie_jar.signed_with_key(key).get(&self.name))?
+            }
         };
-        cookie.map(|cookie| cookie.value_str().to_string())
+        let value = cookie.value_str().to_string();
+        if !current {
+            self.set_cookie_value(cookie_jar, &value);
+        }
+        Some(value)
+    }
+
+    /// Tries the current key, then the previous keys, and returns the cookie
+    /// and whether it was created with the current key.
+    fn find_cookie(
+        &self,
+        key: &CookieKey,
+        get: impl Fn(&CookieKey) -> Option<Cookie>,
+    ) -> Option<(Cookie, bool)> {
+        std::iter::once(key)
+            .chain(&self.previous_keys)
+            .enumerate()
+            .find_map(|(idx, key)| Some((get(key)?, idx == 0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+
+    /// Returns the cookie jar of the next request, and whether the cookie has
+    /// been set.
+    fn next_request(cookie_jar: &CookieJar, name: &str) -> (CookieJar, bool) {
+        let mut headers = HeaderMap::new();
+        cookie_jar.append_delta_to_headers(&mut headers);
+        let cookie = cookie_jar.get(name).unwrap();
+        let cookie_jar = format!("{}={}", cookie.name(), cookie.value_str())
+            .parse()
+            .unwrap();
+        (cookie_jar, !headers.is_empty())
+    }
+
+    #[test]
+    fn previous_keys() {
+        let old_key = CookieKey::generate();
+        let new_key = CookieKey::generate();
+
+        for (old_config, new_config, current_config) in [
+            (
+                CookieConfig::private(old_key.clone()),
+                CookieConfig::private(new_key.clone()).previous_keys([old_key.clone()]),
+                CookieConfig::private(new_key.clone()),
+            ),
+            (
+                CookieConfig::signed(old_key.clone()),
+                CookieConfig::signed(new_key.clone()).previous_keys([old_key.clone()]),
+                CookieConfig::signed(new_key.clone()),
+            ),
+        ] {
+            let cookie_jar = CookieJar::default();
+            old_config.set_cookie_value(&cookie_jar, "value");
+            let (cookie_jar, _) = next_request(&cookie_jar, "poem-session");
+            assert_eq!(current_config.get_cookie_value(&cookie_jar), None);
+
+            // verified with the previous key, and re-issued with the new key
+            assert_eq!(
+                new_config.get_cookie_value(&cookie_jar).as_deref(),
+                Some("value")
+            );
+            let (cookie_jar, reissued) = next_request(&cookie_jar, "poem-session");
+            assert!(reissued);
+            assert_eq!(
+                current_config.get_cookie_value(&cookie_jar).as_deref(),
+                Some("value")
+            );
+            assert_eq!(old_config.get_cookie_value(&cookie_jar), None);
+
+            // the cookie with the new key is not re-issued again
+            assert_eq!(
+                new_config.get_cookie_value(&cookie_jar).as_deref(),
+                Some("value")
+            );
+            let (_, reissued) = next_request(&cookie_jar, "poem-session");
+            assert!(!reissued);
+        }
+
+        let cookie_jar = CookieJar::default();
+        CookieConfig::private(CookieKey::generate()).set_cookie_value(&cookie_jar, "value");
+        let (cookie_jar, _) = next_request(&cookie_jar, "poem-session");
+        assert_eq!(
+            CookieConfig::private(new_key)
+                .previous_keys([old_key])
+                .get_cookie_value(&cookie_jar),
+            None
+        );
     }
 }
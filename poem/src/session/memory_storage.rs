@@ -111,10 +111,15 @@ mod tests {
         EndpointExt, Route,
         session::{
             CookieConfig, ServerSession,
-            test_harness::{TestClient, index},
+            test_harness::{self, TestClient, index},
         },
     };
 
+    #[tokio::test]
+    async fn conformance() {
+        test_harness::storage_conformance(MemoryStorage::new()).await;
+    }
+
     #[tokio::test]
     async fn memory_session() {
         let app = Route::new().at("/:action", index).with(ServerSession::new(
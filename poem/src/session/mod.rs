@@ -3,20 +3,31 @@
 mod cookie_config;
 mod cookie_session;
 mod memory_storage;
+#[cfg(feature = "postgres-session")]
+mod postgres_storage;
 #[cfg(feature = "redis-session")]
 mod redis_storage;
 mod server_session;
 #[allow(clippy::module_inception)]
 mod session;
 mod session_storage;
-#[cfg(test)]
-pub(crate) mod test_harness;
+#[cfg(feature = "sqlite-session")]
+mod sqlite_storage;
+#[cfg(any(feature = "sqlite-session", feature = "postgres-session"))]
+mod sqlx_storage;
+#[cfg(any(test, feature = "test"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "test")))]
+pub mod test_harness;
 
 pub use cookie_config::{CookieConfig, CookieSecurity};
 pub use cookie_session::{CookieSession, CookieSessionEndpoint};
 pub use memory_storage::MemoryStorage;
+#[cfg(feature = "postgres-session")]
+pub use postgres_storage::PostgresStorage;
 #[cfg(feature = "redis-session")]
 pub use redis_storage::RedisStorage;
 pub use server_session::{ServerSession, ServerSessionEndpoint};
 pub use session::{Session, SessionStatus};
 pub use session_storage::SessionStorage;
+#[cfg(feature = "sqlite-session")]
+pub use sqlite_storage::SqliteStorage;
@@ -0,0 +1,189 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use serde_json::Value;
+use sqlx::PgPool;
+
+use crate::{
+    Result,
+    error::SqlxSessionError,
+    session::{
+        session_storage::SessionStorage,
+        sqlx_storage::{
+            DEFAULT_TABLE_NAME, check_table_name, decode_entries, encode_entries, expires_at, now,
+            spawn_sweeper,
+        },
+    },
+};
+
+struct Inner {
+    pool: PgPool,
+    load_sql: String,
+    update_sql: String,
+    remove_sql: String,
+    cleanup_sql: String,
+}
+
+impl Inner {
+    async fn cleanup(&self) -> Result<u64> {
+        let res = sqlx::query(&self.cleanup_sql)
+            .bind(now())
+            .execute(&self.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(res.rows_affected())
+    }
+}
+
+/// A session storage using PostgreSQL.
+///
+/// The sessions are stored in a table which is created if it does not exist,
+/// with the entries serialized as JSON and the expiration time in milliseconds
+/// since the Unix epoch. The expired sessions are never loaded, and they are
+/// removed from the table every minute by a background task, which stops when
+/// the storage is dropped.
+///
+/// # Errors
+///
+/// - [`SqlxSessionError`]
+///
+/// # Example
+///
+/// ```no_run
+/// use poem::{
+///     EndpointExt, Route,
+///     session::{CookieConfig, ServerSession, PostgresStorage},
+/// };
+/// use sqlx::PgPool;
+///
+/// # async fn run() -> poem::Result<()> {
+/// let pool = PgPool::connect("postgres://localhost/app")
+///     .await
+///     .unwrap();
+/// let app = Route::new().with(ServerSession::new(
+///     CookieConfig::default(),
+///     PostgresStorage::new(pool).await?,
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "postgres-session")))]
+pub struct PostgresStorage {
+    inner: Arc<Inner>,
+}
+
+impl PostgresStorage {
+    /// Create a `PostgresStorage` using the `poem_sessions` table.
+    pub async fn new(pool: PgPool) -> Result<Self> {
+        Self::with_table_name(pool, DEFAULT_TABLE_NAME).await
+    }
+
+    /// Create a `PostgresStorage` using the specified table.
+    ///
+    /// The table name may only contain ASCII letters, digits and underscores.
+    pub async fn with_table_name(pool: PgPool, table_name: &str) -> Result<Self> {
+        check_table_name(table_name)?;
+
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {table_name} (
+                id TEXT NOT NULL PRIMARY KEY,
+                entries TEXT NOT NULL,
+                expires_at BIGINT
+            )"
+        ))
+        .execute(&pool)
+        .await
+        .map_err(SqlxSessionError::Sqlx)?;
+        sqlx::query(&format!(
+            "CREATE INDEX IF NOT EXISTS {table_name}_expires_at ON {table_name} (expires_at)"
+        ))
+        .execute(&pool)
+        .await
+        .map_err(SqlxSessionError::Sqlx)?;
+
+        let inner = Arc::new(Inner {
+            pool,
+            load_sql: format!(
+                "SELECT entries FROM {table_name} WHERE id = $1 AND (expires_at IS NULL OR expires_at > $2)"
+            ),
+            update_sql: format!(
+                "INSERT INTO {table_name} (id, entries, expires_at) VALUES ($1, $2, $3)
+                ON CONFLICT (id) DO UPDATE SET entries = EXCLUDED.entries, expires_at = EXCLUDED.expires_at"
+            ),
+            remove_sql: format!("DELETE FROM {table_name} WHERE id = $1"),
+            cleanup_sql: format!(
+                "DELETE FROM {table_name} WHERE expires_at IS NOT NULL AND expires_at <= $1"
+            ),
+        });
+        spawn_sweeper(&inner, |inner| async move {
+            if let Err(err) = inner.cleanup().await {
+                tracing::warn!(error = %err, "failed to remove the expired sessions");
+            }
+        });
+        Ok(Self { inner })
+    }
+
+    /// Removes the expired sessions, and returns the number of the removed
+    /// sessions.
+    pub async fn cleanup(&self) -> Result<u64> {
+        self.inner.cleanup().await
+    }
+}
+
+impl SessionStorage for PostgresStorage {
+    async fn load_session<'a>(
+        &'a self,
+        session_id: &'a str,
+    ) -> Result<Option<BTreeMap<String, Value>>> {
+        let data: Option<String> = sqlx::query_scalar(&self.inner.load_sql)
+            .bind(session_id)
+            .bind(now())
+            .fetch_optional(&self.inner.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(data.as_deref().and_then(decode_entries))
+    }
+
+    async fn update_session<'a>(
+        &'a self,
+        session_id: &'a str,
+        entries: &'a BTreeMap<String, Value>,
+        expires: Option<Duration>,
+    ) -> Result<()> {
+        sqlx::query(&self.inner.update_sql)
+            .bind(session_id)
+            .bind(encode_entries(entries))
+            .bind(expires_at(expires))
+            .execute(&self.inner.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(())
+    }
+
+    async fn remove_session<'a>(&'a self, session_id: &'a str) -> Result<()> {
+        sqlx::query(&self.inner.remove_sql)
+            .bind(session_id)
+            .execute(&self.inner.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::test_harness;
+
+    #[tokio::test]
+    async fn postgres_session() {
+        let Ok(url) = std::env::var("POSTGRES_URL") else {
+            return;
+        };
+        let pool = PgPool::connect(&url).await.unwrap();
+        let storage = PostgresStorage::with_table_name(pool, "poem_test_sessions")
+            .await
+            .unwrap();
+        storage.cleanup().await.unwrap();
+        test_harness::storage_conformance(storage).await;
+    }
+}
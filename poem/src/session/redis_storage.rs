@@ -85,7 +85,7 @@ mod tests {
         EndpointExt, Route,
         session::{
             CookieConfig, ServerSession,
-            test_harness::{TestClient, index},
+            test_harness::{self, TestClient, index},
         },
     };
 
@@ -117,4 +117,16 @@ mod tests {
         client.call(&app, 5).await;
         client.assert_cookies(vec![]);
     }
+
+    #[tokio::test]
+    async fn conformance() {
+        let client = match Client::open("redis://127.0.0.1/") {
+            Ok(client) => client,
+            Err(_) => return,
+        };
+        test_harness::storage_conformance(RedisStorage::new(
+            ConnectionManager::new(client).await.unwrap(),
+        ))
+        .await;
+    }
 }
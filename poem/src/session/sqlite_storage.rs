@@ -0,0 +1,226 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use serde_json::Value;
+use sqlx::SqlitePool;
+
+use crate::{
+    Result,
+    error::SqlxSessionError,
+    session::{
+        session_storage::SessionStorage,
+        sqlx_storage::{
+            DEFAULT_TABLE_NAME, check_table_name, decode_entries, encode_entries, expires_at, now,
+            spawn_sweeper,
+        },
+    },
+};
+
+struct Inner {
+    pool: SqlitePool,
+    load_sql: String,
+    update_sql: String,
+    remove_sql: String,
+    cleanup_sql: String,
+}
+
+impl Inner {
+    async fn cleanup(&self) -> Result<u64> {
+        let res = sqlx::query(&self.cleanup_sql)
+            .bind(now())
+            .execute(&self.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(res.rows_affected())
+    }
+}
+
+/// A session storage using SQLite.
+///
+/// The sessions are stored in a table which is created if it does not exist,
+/// with the entries serialized as JSON and the expiration time in milliseconds
+/// since the Unix epoch. The expired sessions are never loaded, and they are
+/// removed from the table every minute by a background task, which stops when
+/// the storage is dropped.
+///
+/// # Errors
+///
+/// - [`SqlxSessionError`]
+///
+/// # Example
+///
+/// ```no_run
+/// use poem::{
+///     EndpointExt, Route,
+///     session::{CookieConfig, ServerSession, SqliteStorage},
+/// };
+/// use sqlx::SqlitePool;
+///
+/// # async fn run() -> poem::Result<()> {
+/// let pool = SqlitePool::connect("sqlite://sessions.db?mode=rwc")
+///     .await
+///     .unwrap();
+/// let app = Route::new().with(ServerSession::new(
+///     CookieConfig::default(),
+///     SqliteStorage::new(pool).await?,
+/// ));
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlite-session")))]
+pub struct SqliteStorage {
+    inner: Arc<Inner>,
+}
+
+impl SqliteStorage {
+    /// Create a `SqliteStorage` using the `poem_sessions` table.
+    pub async fn new(pool: SqlitePool) -> Result<Self> {
+        Self::with_table_name(pool, DEFAULT_TABLE_NAME).await
+    }
+
+    /// Create a `SqliteStorage` using the specified table.
+    ///
+    /// The table name may only contain ASCII letters, digits and underscores.
+    pub async fn with_table_name(pool: SqlitePool, table_name: &str) -> Result<Self> {
+        check_table_name(table_name)?;
+
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {table_name} (
+                id TEXT NOT NULL PRIMARY KEY,
+                entries TEXT NOT NULL,
+                expires_at INTEGER
+            )"
+        ))
+        .execute(&pool)
+        .await
+        .map_err(SqlxSessionError::Sqlx)?;
+        sqlx::query(&format!(
+            "CREATE INDEX IF NOT EXISTS {table_name}_expires_at ON {table_name} (expires_at)"
+        ))
+        .execute(&pool)
+        .await
+        .map_err(SqlxSessionError::Sqlx)?;
+
+        let inner = Arc::new(Inner {
+            pool,
+            load_sql: format!(
+                "SELECT entries FROM {table_name} WHERE id = ? AND (expires_at IS NULL OR expires_at > ?)"
+            ),
+            update_sql: format!(
+                "INSERT INTO {table_name} (id, entries, expires_at) VALUES (?, ?, ?)
+                ON CONFLICT (id) DO UPDATE SET entries = excluded.entries, expires_at = excluded.expires_at"
+            ),
+            remove_sql: format!("DELETE FROM {table_name} WHERE id = ?"),
+            cleanup_sql: format!(
+                "DELETE FROM {table_name} WHERE expires_at IS NOT NULL AND expires_at <= ?"
+            ),
+        });
+        spawn_sweeper(&inner, |inner| async move {
+            if let Err(err) = inner.cleanup().await {
+                tracing::warn!(error = %err, "failed to remove the expired sessions");
+            }
+        });
+        Ok(Self { inner })
+    }
+
+    /// Removes the expired sessions, and returns the number of the removed
+    /// sessions.
+    pub async fn cleanup(&self) -> Result<u64> {
+        self.inner.cleanup().await
+    }
+}
+
+impl SessionStorage for SqliteStorage {
+    async fn load_session<'a>(
+        &'a self,
+        session_id: &'a str,
+    ) -> Result<Option<BTreeMap<String, Value>>> {
+        let data: Option<String> = sqlx::query_scalar(&self.inner.load_sql)
+            .bind(session_id)
+            .bind(now())
+            .fetch_optional(&self.inner.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(data.as_deref().and_then(decode_entries))
+    }
+
+    async fn update_session<'a>(
+        &'a self,
+        session_id: &'a str,
+        entries: &'a BTreeMap<String, Value>,
+        expires: Option<Duration>,
+    ) -> Result<()> {
+        sqlx::query(&self.inner.update_sql)
+            .bind(session_id)
+            .bind(encode_entries(entries))
+            .bind(expires_at(expires))
+            .execute(&self.inner.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(())
+    }
+
+    async fn remove_session<'a>(&'a self, session_id: &'a str) -> Result<()> {
+        sqlx::query(&self.inner.remove_sql)
+            .bind(session_id)
+            .execute(&self.inner.pool)
+            .await
+            .map_err(SqlxSessionError::Sqlx)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::session::test_harness;
+
+    async fn create_pool() -> SqlitePool {
+        // every connection opens a new in-memory database
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn conformance() {
+        let storage = SqliteStorage::new(create_pool().await).await.unwrap();
+        test_harness::storage_conformance(storage).await;
+    }
+
+    #[tokio::test]
+    async fn cleanup() {
+        let pool = create_pool().await;
+        let storage = SqliteStorage::with_table_name(pool.clone(), "sessions")
+            .await
+            .unwrap();
+        assert!(
+            SqliteStorage::with_table_name(pool, "sessions; --")
+                .await
+                .is_err()
+        );
+
+        let entries = BTreeMap::from([("value".to_string(), Value::from(1))]);
+        storage
+            .update_session("a", &entries, Some(Duration::ZERO))
+            .await
+            .unwrap();
+        storage
+            .update_session("b", &entries, Some(Duration::from_secs(60)))
+            .await
+            .unwrap();
+        storage.update_session("c", &entries, None).await.unwrap();
+
+        assert_eq!(storage.cleanup().await.unwrap(), 1);
+        assert_eq!(storage.cleanup().await.unwrap(), 0);
+        assert_eq!(storage.load_session("a").await.unwrap(), None);
+        assert_eq!(
+            storage.load_session("b").await.unwrap(),
+            Some(entries.clone())
+        );
+        assert_eq!(storage.load_session("c").await.unwrap(), Some(entries));
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{Arc, Weak},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde_json::Value;
+
+use crate::{Result, error::SqlxSessionError};
+
+/// The table created by the SQL session storages by default.
+pub(crate) const DEFAULT_TABLE_NAME: &str = "poem_sessions";
+
+/// The interval between the removals of the expired sessions.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Table names are interpolated into the queries, so only ASCII letters,
+/// digits and underscores are allowed.
+pub(crate) fn check_table_name(table_name: &str) -> Result<()> {
+    let valid = table_name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && table_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(SqlxSessionError::InvalidTableName(table_name.to_string()).into());
+    }
+    Ok(())
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+pub(crate) fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+pub(crate) fn expires_at(expires: Option<Duration>) -> Option<i64> {
+    expires
+        .map(|expires| now().saturating_add(i64::try_from(expires.as_millis()).unwrap_or(i64::MAX)))
+}
+
+pub(crate) fn encode_entries(entries: &BTreeMap<String, Value>) -> String {
+    #[cfg(not(feature = "sonic-rs"))]
+    let value = serde_json::to_string(entries).unwrap_or_default();
+    #[cfg(feature = "sonic-rs")]
+    let value = sonic_rs::to_string(entries).unwrap_or_default();
+    value
+}
+
+pub(crate) fn decode_entries(data: &str) -> Option<BTreeMap<String, Value>> {
+    #[cfg(not(feature = "sonic-rs"))]
+    let map = serde_json::from_str::<BTreeMap<String, Value>>(data);
+    #[cfg(feature = "sonic-rs")]
+    let map = sonic_rs::from_str::<BTreeMap<String, Value>>(data);
+    map.ok()
+}
+
+/// Spawns a task that periodically calls `cleanup`, until the storage is
+/// dropped.
+pub(crate) fn spawn_sweeper<T, F, Fut>(inner: &Arc<T>, cleanup: F)
+where
+    T: Send + Sync + 'static,
+    F: Fn(Arc<T>) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    let inner: Weak<T> = Arc::downgrade(inner);
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(CLEANUP_INTERVAL).await;
+            match inner.upgrade() {
+                Some(inner) => cleanup(inner).await,
+                None => return,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_name() {
+        assert!(check_table_name("poem_sessions").is_ok());
+        assert!(check_table_name("_sessions2").is_ok());
+        assert!(check_table_name("").is_err());
+        assert!(check_table_name("2sessions").is_err());
+        assert!(check_table_name("sessions; DROP TABLE users").is_err());
+        assert!(check_table_name("public.sessions").is_err());
+    }
+}
@@ -1,9 +1,26 @@
-use std::collections::BTreeMap;
+//! Conformance tests for session storages.
+//!
+//! Any [`SessionStorage`] implementation can check its behavior with
+//! [`storage_conformance`].
+//!
+//! # Example
+//!
+//! ```
+//! use poem::session::{MemoryStorage, test_harness};
+//!
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! test_harness::storage_conformance(MemoryStorage::new()).await;
+//! # });
+//! ```
+
+use std::{collections::BTreeMap, time::Duration};
+
+use serde_json::Value;
 
 use crate::{
-    Endpoint, IntoResponse, Request, handler,
+    Endpoint, EndpointExt, IntoResponse, Request, Route, handler,
     http::{HeaderValue, header},
-    session::Session,
+    session::{CookieConfig, ServerSession, Session, SessionStorage},
     web::{Path, cookie::Cookie},
 };
 
@@ -92,3 +109,114 @@ pub(crate) fn index(Path(action): Path<i32>, session: &Session) {
         _ => {}
     }
 }
+
+/// Runs the conformance tests against a session storage.
+///
+/// The tests load, update, expire and remove sessions directly, then use the
+/// storage with [`ServerSession`]. The sessions are created with random ids,
+/// so the storage may contain other sessions. The expiration of the sessions
+/// is checked after waiting for about two and a half seconds.
+///
+/// # Panics
+///
+/// Panics if the storage does not behave as expected.
+pub async fn storage_conformance<T: SessionStorage + 'static>(storage: T) {
+    let id = |name: &str| format!("{name}-{}", rand::random::<u64>());
+    let entries = |value: i32| {
+        BTreeMap::from([
+            ("a".to_string(), Value::from(value)),
+            ("b".to_string(), Value::from(format!("value{value}"))),
+        ])
+    };
+
+    // load, update and remove
+    let session_id = id("session");
+    assert_eq!(storage.load_session(&session_id).await.unwrap(), None);
+    storage
+        .update_session(&session_id, &entries(1), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.load_session(&session_id).await.unwrap(),
+        Some(entries(1))
+    );
+    storage
+        .update_session(&session_id, &entries(2), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.load_session(&session_id).await.unwrap(),
+        Some(entries(2))
+    );
+    storage
+        .update_session(&session_id, &BTreeMap::new(), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.load_session(&session_id).await.unwrap(),
+        Some(BTreeMap::new())
+    );
+    storage.remove_session(&session_id).await.unwrap();
+    assert_eq!(storage.load_session(&session_id).await.unwrap(), None);
+    storage.remove_session(&session_id).await.unwrap();
+
+    // expiration
+    let expired_id = id("expired");
+    let renewed_id = id("renewed");
+    let persistent_id = id("persistent");
+    storage
+        .update_session(&expired_id, &entries(1), Some(Duration::from_secs(1)))
+        .await
+        .unwrap();
+    storage
+        .update_session(&renewed_id, &entries(2), Some(Duration::from_secs(1)))
+        .await
+        .unwrap();
+    storage
+        .update_session(&renewed_id, &entries(2), Some(Duration::from_secs(60)))
+        .await
+        .unwrap();
+    storage
+        .update_session(&persistent_id, &entries(3), Some(Duration::from_secs(1)))
+        .await
+        .unwrap();
+    storage
+        .update_session(&persistent_id, &entries(3), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.load_session(&expired_id).await.unwrap(),
+        Some(entries(1))
+    );
+
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert_eq!(storage.load_session(&expired_id).await.unwrap(), None);
+    assert_eq!(
+        storage.load_session(&renewed_id).await.unwrap(),
+        Some(entries(2))
+    );
+    assert_eq!(
+        storage.load_session(&persistent_id).await.unwrap(),
+        Some(entries(3))
+    );
+    storage.remove_session(&renewed_id).await.unwrap();
+    storage.remove_session(&persistent_id).await.unwrap();
+
+    // server session
+    let app = Route::new()
+        .at("/:action", index)
+        .with(ServerSession::new(CookieConfig::default(), storage));
+    let mut client = TestClient::default();
+
+    client.call(&app, 0).await;
+    client.assert_cookies(vec![]);
+
+    client.call(&app, 1).await;
+    client.call(&app, 2).await;
+    client.call(&app, 7).await;
+    client.call(&app, 6).await;
+    client.call(&app, 3).await;
+    client.call(&app, 4).await;
+    client.call(&app, 5).await;
+    client.assert_cookies(vec![]);
+}
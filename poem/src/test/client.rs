@@ -1,5 +1,7 @@
 use http::{HeaderMap, HeaderValue, Method, header, header::HeaderName};
 
+#[cfg(feature = "cookie")]
+use crate::test::TestCookieJar;
 use crate::{Endpoint, IntoEndpoint, test::TestRequestBuilder};
 
 macro_rules! impl_methods {
@@ -17,6 +19,8 @@ macro_rules! impl_methods {
 pub struct TestClient<E> {
     pub(crate) ep: E,
     pub(crate) default_headers: HeaderMap,
+    #[cfg(feature = "cookie")]
+    pub(crate) cookie_jar: Option<TestCookieJar>,
 }
 
 impl<E: Endpoint> TestClient<E> {
@@ -28,6 +32,8 @@ impl<E: Endpoint> TestClient<E> {
         TestClient {
             ep: ep.into_endpoint(),
             default_headers: Default::default(),
+            #[cfg(feature = "cookie")]
+            cookie_jar: None,
         }
     }
 
@@ -123,6 +129,61 @@ impl<E: Endpoint> TestClient<E> {
         self.default_header(header::CONTENT_TYPE, content_type.as_ref())
     }
 
+    /// Enables the cookie jar, which stores the cookies set by the responses
+    /// and sends them with the following requests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use poem::{
+    ///     EndpointExt, Route, get, handler,
+    ///     middleware::CookieJarManager,
+    ///     test::TestClient,
+    ///     web::cookie::{Cookie, CookieJar},
+    /// };
+    ///
+    /// #[handler]
+    /// fn login(cookie_jar: &CookieJar) {
+    ///     cookie_jar.add(Cookie::new_with_str("user", "alice"));
+    /// }
+    ///
+    /// #[handler]
+    /// fn index(cookie_jar: &CookieJar) -> String {
+    ///     cookie_jar
+    ///         .get("user")
+    ///         .map(|cookie| cookie.value_str().to_string())
+    ///         .unwrap_or_default()
+    /// }
+    ///
+    /// let app = Route::new()
+    ///     .at("/login", get(login))
+    ///     .at("/", get(index))
+    ///     .with(CookieJarManager::new());
+    /// let cli = TestClient::new(app).with_cookie_jar();
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// cli.get("/login").send().await.assert_status_is_ok();
+    /// cli.get("/").send().await.assert_text("alice").await;
+    /// # });
+    /// ```
+    #[cfg(feature = "cookie")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+    #[must_use]
+    pub fn with_cookie_jar(self) -> Self {
+        Self {
+            cookie_jar: Some(Default::default()),
+            ..self
+        }
+    }
+
+    /// Returns the cookie jar, if it has been enabled with
+    /// [`TestClient::with_cookie_jar`].
+    #[cfg(feature = "cookie")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+    pub fn cookie_jar(&self) -> Option<&TestCookieJar> {
+        self.cookie_jar.as_ref()
+    }
+
     /// Create a [`TestRequestBuilder`].
     pub fn request(&self, method: Method, uri: impl Into<String>) -> TestRequestBuilder<'_, E> {
         TestRequestBuilder::new(self, method, uri.into())
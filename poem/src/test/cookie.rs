@@ -0,0 +1,489 @@
+use std::{cmp::Reverse, time::Duration};
+
+use chrono::{DateTime, Utc};
+use http::{HeaderMap, HeaderValue, header};
+use parking_lot::Mutex;
+
+use crate::{
+    Request,
+    web::cookie::{Cookie, SameSite},
+};
+
+enum CookieDomain {
+    /// Added with [`TestCookieJar::add`] without a domain.
+    Any,
+    /// Set without the `Domain` attribute.
+    Host(String),
+    /// Set with the `Domain` attribute.
+    Domain(String),
+}
+
+impl CookieDomain {
+    fn matches(&self, host: &str) -> bool {
+        match self {
+            CookieDomain::Any => true,
+            CookieDomain::Host(domain) => domain == host,
+            CookieDomain::Domain(domain) => domain_match(host, domain),
+        }
+    }
+
+    fn same(&self, other: &CookieDomain) -> bool {
+        match (self, other) {
+            (CookieDomain::Any, CookieDomain::Any) => true,
+            (CookieDomain::Host(a), CookieDomain::Host(b))
+            | (CookieDomain::Domain(a), CookieDomain::Domain(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+struct StoredCookie {
+    cookie: Cookie,
+    domain: CookieDomain,
+    path: String,
+    expires: Option<DateTime<Utc>>,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+}
+
+/// Reference: <https://www.rfc-editor.org/rfc/rfc6265#section-5.1.3>
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.ends_with(domain)
+            && host[..host.len() - domain.len()].ends_with('.')
+            && host.parse::<std::net::IpAddr>().is_err())
+}
+
+/// Reference: <https://www.rfc-editor.org/rfc/rfc6265#section-5.1.4>
+fn path_match(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Reference: <https://www.rfc-editor.org/rfc/rfc6265#section-5.1.4>
+fn default_path(request_path: &str) -> &str {
+    match request_path.rfind('/') {
+        Some(0) | None => "/",
+        Some(idx) => &request_path[..idx],
+    }
+}
+
+/// Returns the host and the path used to match the cookies of a request.
+pub(crate) fn request_origin(req: &Request) -> (String, String) {
+    let host = req
+        .uri()
+        .host()
+        .map(ToString::to_string)
+        .or_else(|| {
+            let host = req.headers().get(header::HOST)?.to_str().ok()?;
+            let host = host.parse::<http::uri::Authority>().ok()?;
+            Some(host.host().to_string())
+        })
+        .unwrap_or_else(|| "localhost".to_string());
+    (host.to_ascii_lowercase(), req.uri().path().to_string())
+}
+
+/// A cookie jar of a [`TestClient`](crate::test::TestClient), enabled with
+/// [`TestClient::with_cookie_jar`](crate::test::TestClient::with_cookie_jar).
+///
+/// The cookies set by the responses are stored, and sent with the following
+/// requests whose host and path match the cookies, until they expire. The
+/// `Secure` attribute is ignored, so that the cookies are also sent to the
+/// endpoints in the tests.
+#[derive(Default)]
+pub struct TestCookieJar {
+    cookies: Mutex<Vec<StoredCookie>>,
+}
+
+impl TestCookieJar {
+    fn insert(&self, stored: StoredCookie) {
+        let mut cookies = self.cookies.lock();
+        cookies.retain(|c| {
+            c.cookie.name() != stored.cookie.name()
+                || !c.domain.same(&stored.domain)
+                || c.path != stored.path
+        });
+        if !stored.is_expired(Utc::now()) {
+            cookies.push(stored);
+        }
+    }
+
+    /// Adds a cookie, which is sent to any host if it has no domain.
+    pub fn add(&self, cookie: Cookie) {
+        let domain = match cookie.domain() {
+            Some(domain) => {
+                CookieDomain::Domain(domain.trim_start_matches('.').to_ascii_lowercase())
+            }
+            None => CookieDomain::Any,
+        };
+        let path = cookie.path().unwrap_or("/").to_string();
+        let expires = cookie_expires(&cookie);
+        self.insert(StoredCookie {
+            cookie,
+            domain,
+            path,
+            expires,
+        });
+    }
+
+    /// Returns the cookie named `name`, if it has not expired.
+    pub fn get(&self, name: &str) -> Option<Cookie> {
+        let now = Utc::now();
+        self.cookies
+            .lock()
+            .iter()
+            .find(|c| c.cookie.name() == name && !c.is_expired(now))
+            .map(|c| c.cookie.clone())
+    }
+
+    /// Returns all the cookies which have not expired.
+    pub fn cookies(&self) -> Vec<Cookie> {
+        let now = Utc::now();
+        self.cookies
+            .lock()
+            .iter()
+            .filter(|c| !c.is_expired(now))
+            .map(|c| c.cookie.clone())
+            .collect()
+    }
+
+    /// Removes all the cookies named `name`.
+    pub fn remove(&self, name: &str) {
+        self.cookies.lock().retain(|c| c.cookie.name() != name);
+    }
+
+    /// Removes all the cookies.
+    pub fn clear(&self) {
+        self.cookies.lock().clear();
+    }
+
+    /// Stores the cookies of the `Set-Cookie` headers of a response.
+    pub(crate) fn store(&self, host: &str, request_path: &str, headers: &HeaderMap) {
+        for value in headers.get_all(header::SET_COOKIE) {
+            let Some(cookie) = value.to_str().ok().and_then(|v| Cookie::parse(v).ok()) else {
+                continue;
+            };
+
+            let domain = match cookie.domain() {
+                Some(domain) => {
+                    let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+                    if !domain_match(host, &domain) {
+                        continue;
+                    }
+                    CookieDomain::Domain(domain)
+                }
+                None => CookieDomain::Host(host.to_string()),
+            };
+            let path = match cookie.path() {
+                Some(path) if path.starts_with('/') => path.to_string(),
+                _ => default_path(request_path).to_string(),
+            };
+            let expires = cookie_expires(&cookie);
+            self.insert(StoredCookie {
+                cookie,
+                domain,
+                path,
+                expires,
+            });
+        }
+    }
+
+    /// Adds the matching cookies to the `Cookie` header of a request.
+    pub(crate) fn apply(&self, req: &mut Request) {
+        let (host, path) = request_origin(req);
+        let now = Utc::now();
+        let cookies = self.cookies.lock();
+        let mut matched = cookies
+            .iter()
+            .filter(|c| !c.is_expired(now) && c.domain.matches(&host) && path_match(&path, &c.path))
+            .collect::<Vec<_>>();
+        if matched.is_empty() {
+            return;
+        }
+        // the cookies with longer paths are listed first
+        matched.sort_by_key(|c| Reverse(c.path.len()));
+
+        let mut value = req
+            .headers()
+            .get(header::COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| vec![value.to_string()])
+            .unwrap_or_default();
+        value.extend(
+            matched
+                .iter()
+                .map(|c| Cookie::new_with_str(c.cookie.name(), c.cookie.value_str()).to_string()),
+        );
+        if let Ok(value) = HeaderValue::from_str(&value.join("; ")) {
+            req.headers_mut().insert(header::COOKIE, value);
+        }
+    }
+}
+
+fn cookie_expires(cookie: &Cookie) -> Option<DateTime<Utc>> {
+    match cookie.max_age() {
+        Some(max_age) => chrono::Duration::from_std(max_age)
+            .ok()
+            .and_then(|max_age| Utc::now().checked_add_signed(max_age)),
+        None => cookie.expires(),
+    }
+}
+
+/// A cookie set by a [`TestResponse`](crate::test::TestResponse), returned
+/// by [`TestResponse::cookie`](crate::test::TestResponse::cookie).
+pub struct TestCookie(pub Cookie);
+
+impl TestCookie {
+    /// Asserts that the value of the cookie equals to `value`.
+    #[track_caller]
+    pub fn assert_value(&self, value: impl AsRef<str>) -> &Self {
+        assert_eq!(self.0.value_str(), value.as_ref());
+        self
+    }
+
+    /// Asserts that the `Path` attribute equals to `path`.
+    #[track_caller]
+    pub fn assert_path(&self, path: impl AsRef<str>) -> &Self {
+        assert_eq!(self.0.path(), Some(path.as_ref()));
+        self
+    }
+
+    /// Asserts that the `Domain` attribute equals to `domain`.
+    #[track_caller]
+    pub fn assert_domain(&self, domain: impl AsRef<str>) -> &Self {
+        assert_eq!(self.0.domain(), Some(domain.as_ref()));
+        self
+    }
+
+    /// Asserts that the `HttpOnly` attribute is set or not.
+    #[track_caller]
+    pub fn assert_http_only(&self, http_only: bool) -> &Self {
+        assert_eq!(self.0.http_only(), http_only);
+        self
+    }
+
+    /// Asserts that the `Secure` attribute is set or not.
+    #[track_caller]
+    pub fn assert_secure(&self, secure: bool) -> &Self {
+        assert_eq!(self.0.secure(), secure);
+        self
+    }
+
+    /// Asserts that the `SameSite` attribute equals to `same_site`.
+    #[track_caller]
+    pub fn assert_same_site(&self, same_site: SameSite) -> &Self {
+        assert_eq!(self.0.same_site(), Some(same_site));
+        self
+    }
+
+    /// Asserts that the `Max-Age` attribute equals to `max_age`.
+    #[track_caller]
+    pub fn assert_max_age(&self, max_age: Duration) -> &Self {
+        assert_eq!(self.0.max_age(), Some(max_age));
+        self
+    }
+
+    /// Asserts that the cookie has neither the `Max-Age` nor the `Expires`
+    /// attribute, so it is removed when the browser is closed.
+    #[track_caller]
+    pub fn assert_session(&self) -> &Self {
+        assert_eq!(self.0.max_age(), None);
+        assert_eq!(self.0.expires(), None);
+        self
+    }
+
+    /// Asserts that the cookie removes the cookie of the client, with a zero
+    /// `Max-Age` or an `Expires` in the past.
+    #[track_caller]
+    pub fn assert_removal(&self) -> &Self {
+        assert!(
+            cookie_expires(&self.0).is_some_and(|expires| expires <= Utc::now()),
+            "expect a removal cookie"
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EndpointExt, Route, get, handler, middleware::CookieJarManager, test::TestClient,
+        web::cookie::CookieJar,
+    };
+
+    #[tokio::test]
+    async fn replay_cookies() {
+        #[handler(internal)]
+        fn login(cookie_jar: &CookieJar) {
+            let mut cookie = Cookie::new_with_str("user", "alice");
+            cookie.set_path("/");
+            cookie.set_http_only(true);
+            cookie.set_same_site(SameSite::Strict);
+            cookie_jar.add(cookie);
+
+            let mut cookie = Cookie::new_with_str("token", "abc");
+            cookie.set_path("/admin");
+            cookie.set_max_age(Duration::from_secs(3600));
+            cookie_jar.add(cookie);
+        }
+
+        #[handler(internal)]
+        fn logout(cookie_jar: &CookieJar) {
+            let mut cookie = Cookie::named("user");
+            cookie.set_path("/");
+            cookie.make_removal();
+            cookie_jar.add(cookie);
+        }
+
+        #[handler(internal)]
+        fn cookies(req: &Request) -> String {
+            req.header(header::COOKIE).unwrap_or_default().to_string()
+        }
+
+        let app = Route::new()
+            .at("/login", get(login))
+            .at("/logout", get(logout))
+            .at("/", get(cookies))
+            .at("/admin/users", get(cookies))
+            .with(CookieJarManager::new());
+        let cli = TestClient::new(app).with_cookie_jar();
+
+        let resp = cli.get("/login").send().await;
+        resp.assert_status_is_ok();
+        resp.cookie("user")
+            .assert_value("alice")
+            .assert_path("/")
+            .assert_http_only(true)
+            .assert_secure(false)
+            .assert_same_site(SameSite::Strict)
+            .assert_session();
+        resp.cookie("token")
+            .assert_path("/admin")
+            .assert_max_age(Duration::from_secs(3600));
+        resp.assert_cookie_not_exist("session");
+
+        cli.get("/").send().await.assert_text("user=alice").await;
+        cli.get("/admin/users")
+            .send()
+            .await
+            .assert_text("token=abc; user=alice")
+            .await;
+        // the cookies of the request are kept
+        cli.get("/")
+            .header(header::COOKIE, "theme=dark")
+            .send()
+            .await
+            .assert_text("theme=dark; user=alice")
+            .await;
+
+        let resp = cli.get("/logout").send().await;
+        resp.cookie("user").assert_removal();
+        cli.get("/").send().await.assert_text("").await;
+        cli.get("/admin/users")
+            .send()
+            .await
+            .assert_text("token=abc")
+            .await;
+
+        let jar = cli.cookie_jar().unwrap();
+        assert_eq!(jar.get("token").unwrap().value_str(), "abc");
+        jar.clear();
+        assert!(jar.cookies().is_empty());
+        cli.get("/admin/users").send().await.assert_text("").await;
+
+        // without the cookie jar, the cookies are not stored
+        let cli = TestClient::new(
+            Route::new()
+                .at("/login", get(login))
+                .at("/", get(cookies))
+                .with(CookieJarManager::new()),
+        );
+        assert!(cli.cookie_jar().is_none());
+        cli.get("/login").send().await.assert_status_is_ok();
+        cli.get("/").send().await.assert_text("").await;
+    }
+
+    #[test]
+    fn matching() {
+        let jar = TestCookieJar::default();
+        let mut headers = HeaderMap::new();
+        for value in [
+            "a=1",
+            "b=2; Domain=.example.com; Path=/",
+            "c=3; Domain=other.com",
+            "d=4; Path=/docs",
+            "e=5; Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+            "f=6; Max-Age=0",
+        ] {
+            headers.append(header::SET_COOKIE, HeaderValue::from_static(value));
+        }
+        jar.store("www.example.com", "/api/users", &headers);
+
+        let mut names = jar
+            .cookies()
+            .iter()
+            .map(|cookie| cookie.name().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["a", "b", "d"]);
+
+        let cookie_header = |uri: &str| {
+            let mut req = Request::builder().uri_str(uri).finish();
+            jar.apply(&mut req);
+            req.headers()
+                .get(header::COOKIE)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+
+        // the default path of `a` is `/api`
+        assert_eq!(
+            cookie_header("http://www.example.com/api/items").as_deref(),
+            Some("a=1; b=2")
+        );
+        assert_eq!(
+            cookie_header("http://www.example.com/apis").as_deref(),
+            Some("b=2")
+        );
+        assert_eq!(
+            cookie_header("http://www.example.com/docs/intro").as_deref(),
+            Some("d=4; b=2")
+        );
+        assert_eq!(
+            cookie_header("http://example.com/docsearch").as_deref(),
+            Some("b=2")
+        );
+        assert_eq!(cookie_header("http://example.org/api"), None);
+
+        jar.add(Cookie::new_with_str("g", "7"));
+        assert_eq!(
+            cookie_header("http://example.org/api").as_deref(),
+            Some("g=7")
+        );
+
+        jar.remove("b");
+        assert_eq!(cookie_header("http://example.com/").as_deref(), Some("g=7"));
+    }
+
+    #[test]
+    fn domain_and_path_match() {
+        assert!(domain_match("example.com", "example.com"));
+        assert!(domain_match("www.example.com", "example.com"));
+        assert!(!domain_match("badexample.com", "example.com"));
+        assert!(!domain_match("127.0.0.1", "0.0.1"));
+
+        assert!(path_match("/", "/"));
+        assert!(path_match("/docs/intro", "/docs"));
+        assert!(path_match("/docs/intro", "/docs/"));
+        assert!(!path_match("/docsearch", "/docs"));
+
+        assert_eq!(default_path("/"), "/");
+        assert_eq!(default_path("/login"), "/");
+        assert_eq!(default_path("/api/users"), "/api");
+    }
+}
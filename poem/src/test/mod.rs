@@ -110,13 +110,28 @@
 //! ```
 
 mod client;
+#[cfg(feature = "cookie")]
+mod cookie;
 mod form;
 mod json;
 mod request_builder;
 mod response;
+mod sse;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+use std::time::Duration;
 
 pub use client::TestClient;
+#[cfg(feature = "cookie")]
+pub use cookie::{TestCookie, TestCookieJar};
 pub use form::{TestForm, TestFormField};
 pub use json::{TestJson, TestJsonArray, TestJsonObject, TestJsonValue};
 pub use request_builder::TestRequestBuilder;
 pub use response::TestResponse;
+pub use sse::TestSseStream;
+#[cfg(feature = "websocket")]
+pub use websocket::TestWebSocket;
+
+/// The default time to wait for each SSE event or WebSocket message.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
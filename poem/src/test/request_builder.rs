@@ -1,10 +1,10 @@
 use headers::{Header, HeaderMapExt};
-use http::{Extensions, HeaderMap, HeaderValue, Method, header, header::HeaderName};
+use http::{Extensions, HeaderMap, HeaderValue, Method, Uri, header, header::HeaderName};
 use serde::Serialize;
 use serde_json::Value;
 
 use crate::{
-    Body, Endpoint, Request,
+    Body, Endpoint, Request, Response,
     test::{TestClient, TestForm, TestResponse},
 };
 
@@ -164,14 +164,21 @@ impl<'a, E> TestRequestBuilder<'a, E> {
             )
         };
 
+        let uri: Uri = uri.parse().expect("valid uri");
         let mut req = Request::builder()
             .method(self.method)
-            .uri(uri.parse().expect("valid uri"))
+            .uri(uri.clone())
             .finish();
+        // the server keeps the uri before it is rewritten by `Nest`
+        req.state_mut().original_uri = uri;
         req.headers_mut().extend(self.cli.default_headers.clone());
         req.headers_mut().extend(self.headers);
         *req.extensions_mut() = self.extensions;
         req.set_body(self.body);
+        #[cfg(feature = "cookie")]
+        if let Some(cookie_jar) = &self.cli.cookie_jar {
+            cookie_jar.apply(&mut req);
+        }
 
         req
     }
@@ -206,14 +213,64 @@ impl<'a, E> TestRequestBuilder<'a, E> {
         self
     }
 
+    /// Upgrades the connection to a WebSocket in memory, and returns a
+    /// [`TestWebSocket`](crate::test::TestWebSocket) to exchange the messages
+    /// with the endpoint.
+    ///
+    /// The WebSocket handshake headers are added to the request, unless they
+    /// are already set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the endpoint does not accept the upgrade.
+    #[cfg(feature = "websocket")]
+    pub async fn websocket(mut self) -> crate::test::TestWebSocket
+    where
+        E: Endpoint,
+    {
+        const KEY: &str = "dGhlIHNhbXBsZSBub25jZQ==";
+
+        for (name, value) in [
+            (header::CONNECTION, "upgrade"),
+            (header::UPGRADE, "websocket"),
+            (header::SEC_WEBSOCKET_VERSION, "13"),
+            (header::SEC_WEBSOCKET_KEY, KEY),
+        ] {
+            self.headers
+                .entry(name)
+                .or_insert(HeaderValue::from_static(value));
+        }
+
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let cli = self.cli;
+        let req = self.make_request();
+        *req.state().on_upgrade.lock() = Some(crate::OnUpgrade::from_stream(server));
+        let resp = TestResponse::new(get_response(cli, req).await);
+        resp.assert_status(http::StatusCode::SWITCHING_PROTOCOLS);
+
+        crate::test::TestWebSocket::new(resp, client).await
+    }
+
     /// Send this request to endpoint to get the response.
     pub async fn send(self) -> TestResponse
     where
         E: Endpoint,
     {
-        let ep = &self.cli.ep;
+        let cli = self.cli;
         let req = self.make_request();
-        let resp = ep.get_response(req).await;
-        TestResponse::new(resp)
+        TestResponse::new(get_response(cli, req).await)
+    }
+}
+
+/// Sends the request to the endpoint, and stores the cookies of the response
+/// in the cookie jar of the client.
+async fn get_response<E: Endpoint>(cli: &TestClient<E>, req: Request) -> Response {
+    #[cfg(feature = "cookie")]
+    let origin = crate::test::cookie::request_origin(&req);
+    let resp = cli.ep.get_response(req).await;
+    #[cfg(feature = "cookie")]
+    if let Some(cookie_jar) = &cli.cookie_jar {
+        cookie_jar.store(&origin.0, &origin.1, resp.headers());
     }
+    resp
 }
@@ -6,7 +6,11 @@ use serde::{Serialize, de::DeserializeOwned};
 use serde_json::Value;
 use tokio_util::compat::TokioAsyncReadCompatExt;
 
-use crate::{Response, test::json::TestJson, web::sse::Event};
+use crate::{
+    Response,
+    test::{TestSseStream, json::TestJson},
+    web::sse::Event,
+};
 
 /// A response object for testing.
 pub struct TestResponse(pub Response);
@@ -22,6 +26,42 @@ impl TestResponse {
         assert_eq!(self.0.status(), status);
     }
 
+    /// Returns the cookie named `name` set by the `Set-Cookie` headers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cookie is not set.
+    #[cfg(feature = "cookie")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+    #[track_caller]
+    pub fn cookie(&self, name: &str) -> crate::test::TestCookie {
+        self.find_cookie(name)
+            .map(crate::test::TestCookie)
+            .unwrap_or_else(|| panic!("expect cookie `{name}`"))
+    }
+
+    /// Asserts that the cookie `name` is not set.
+    #[cfg(feature = "cookie")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cookie")))]
+    #[track_caller]
+    pub fn assert_cookie_not_exist(&self, name: &str) {
+        assert!(
+            self.find_cookie(name).is_none(),
+            "unexpected cookie `{name}`"
+        );
+    }
+
+    #[cfg(feature = "cookie")]
+    fn find_cookie(&self, name: &str) -> Option<crate::web::cookie::Cookie> {
+        self.0
+            .headers()
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| crate::web::cookie::Cookie::parse(value).ok())
+            .find(|cookie| cookie.name() == name)
+    }
+
     /// Asserts that the status code is `200 OK`.
     #[track_caller]
     pub fn assert_status_is_ok(&self) {
@@ -211,6 +251,12 @@ impl TestResponse {
             .boxed()
     }
 
+    /// Consumes this object and return the [`TestSseStream`], which reads the
+    /// events as they are sent with a timeout.
+    pub fn sse(self) -> TestSseStream {
+        TestSseStream::new(self.sse_stream().boxed())
+    }
+
     /// Consumes this object and return the SSE events stream which deserialize
     /// the message data to `T`.
     pub fn typed_sse_stream<T: DeserializeOwned + 'static>(
@@ -0,0 +1,191 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_util::{Stream, StreamExt, stream::BoxStream};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{test::DEFAULT_TIMEOUT, web::sse::Event};
+
+/// A live SSE stream for testing, which reads the events as they are sent by
+/// the endpoint.
+///
+/// Each read waits for the next event for up to 5 seconds by default, then
+/// panics.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use futures_util::{StreamExt, stream};
+/// use poem::{
+///     Route, handler,
+///     test::TestClient,
+///     web::sse::{Event, SSE},
+/// };
+///
+/// #[handler]
+/// fn index() -> SSE {
+///     let events = stream::iter(vec![Event::message("a"), Event::message("b")]);
+///     SSE::new(events.chain(stream::pending()))
+/// }
+///
+/// let cli = TestClient::new(Route::new().at("/", index));
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut stream = cli.get("/").send().await.sse();
+/// stream.assert_next_data("a").await;
+/// stream.assert_next_data("b").await;
+/// stream.assert_no_event(Duration::from_millis(100)).await;
+/// # });
+/// ```
+pub struct TestSseStream {
+    inner: BoxStream<'static, Event>,
+    timeout: Duration,
+}
+
+impl TestSseStream {
+    pub(crate) fn new(inner: BoxStream<'static, Event>) -> Self {
+        Self {
+            inner,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the maximum time to wait for each event.
+    #[must_use]
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Waits for the next event, returns `None` if the stream has ended.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no event is received before the timeout.
+    pub async fn next_event(&mut self) -> Option<Event> {
+        tokio::time::timeout(self.timeout, self.inner.next())
+            .await
+            .unwrap_or_else(|_| panic!("no event received within {:?}", self.timeout))
+    }
+
+    /// Waits for the next message, and returns its data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the stream has ended, or no message is received before the
+    /// timeout.
+    pub async fn next_data(&mut self) -> String {
+        loop {
+            match self.next_event().await {
+                Some(Event::Message { data, .. }) => return data,
+                Some(Event::Retry { .. }) => continue,
+                None => panic!("the stream has ended"),
+            }
+        }
+    }
+
+    /// Asserts that the data of the next message equals to `data`.
+    pub async fn assert_next_data(&mut self, data: impl AsRef<str>) {
+        assert_eq!(self.next_data().await, data.as_ref());
+    }
+
+    /// Asserts that the data of the next message is JSON and it equals to
+    /// `json`.
+    pub async fn assert_next_json(&mut self, json: impl Serialize) {
+        assert_eq!(
+            serde_json::from_str::<Value>(&self.next_data().await).expect("valid json"),
+            serde_json::to_value(json).expect("valid json")
+        );
+    }
+
+    /// Asserts that no event is received for `duration`.
+    pub async fn assert_no_event(&mut self, duration: Duration) {
+        if let Ok(event) = tokio::time::timeout(duration, self.inner.next()).await {
+            panic!("unexpected event: {event:?}");
+        }
+    }
+
+    /// Asserts that the stream has ended.
+    pub async fn assert_closed(&mut self) {
+        if let Some(event) = self.next_event().await {
+            panic!("unexpected event: {event:?}");
+        }
+    }
+}
+
+impl Stream for TestSseStream {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EndpointExt, handler,
+        test::TestClient,
+        web::{
+            Data,
+            sse::{LastEventId, SSE, SseHub},
+        },
+    };
+
+    #[tokio::test]
+    async fn live_stream() {
+        #[handler(internal)]
+        fn events(hub: Data<&SseHub>, last_event_id: LastEventId) -> SSE {
+            hub.sse(last_event_id.as_deref())
+        }
+
+        let hub = SseHub::new(10);
+        hub.publish(Event::message("a"));
+        let cli = TestClient::new(events.data(hub.clone()));
+
+        let mut stream = cli
+            .get("/")
+            .header("Last-Event-ID", "0")
+            .send()
+            .await
+            .sse()
+            .timeout(Duration::from_secs(1));
+        stream.assert_next_data("a").await;
+        stream.assert_no_event(Duration::from_millis(50)).await;
+
+        hub.publish(Event::message(r#"{"value": 1}"#));
+        stream
+            .assert_next_json(serde_json::json!({"value": 1}))
+            .await;
+        hub.publish(Event::message("c").id("c"));
+        // the parsed messages have the default event type
+        assert_eq!(
+            stream.next_event().await,
+            Some(Event::message("c").id("c").event_type("message"))
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no event received")]
+    async fn timeout() {
+        #[handler(internal)]
+        fn events() -> SSE {
+            SSE::new(futures_util::stream::pending())
+        }
+
+        TestClient::new(events)
+            .get("/")
+            .send()
+            .await
+            .sse()
+            .timeout(Duration::from_millis(50))
+            .next_event()
+            .await;
+    }
+}
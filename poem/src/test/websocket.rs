@@ -0,0 +1,355 @@
+use std::{
+    io::{Error as IoError, Result as IoResult},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::DuplexStream;
+use tokio_tungstenite::tungstenite::protocol::Role;
+
+use crate::{
+    test::{DEFAULT_TIMEOUT, TestResponse},
+    web::websocket::{DeflateConfig, DeflateStream, Message, negotiate},
+};
+
+type InnerStream = tokio_tungstenite::WebSocketStream<DeflateStream<DuplexStream>>;
+
+/// A WebSocket connection for testing, returned by
+/// [`TestRequestBuilder::websocket`](crate::test::TestRequestBuilder::websocket).
+///
+/// The connection is upgraded in memory, without a server. It implements
+/// [`Stream<Message>`] and [`Sink<Message>`], and each read waits for the next
+/// message for up to 5 seconds by default, then panics.
+///
+/// # Example
+///
+/// ```
+/// use futures_util::{SinkExt, StreamExt};
+/// use poem::{
+///     IntoResponse, Route, get, handler,
+///     test::TestClient,
+///     web::websocket::{Message, WebSocket},
+/// };
+///
+/// #[handler]
+/// async fn index(ws: WebSocket) -> impl IntoResponse {
+///     ws.on_upgrade(|mut socket| async move {
+///         while let Some(Ok(Message::Text(text))) = socket.next().await {
+///             let _ = socket.send(Message::Text(text.to_uppercase())).await;
+///         }
+///     })
+/// }
+///
+/// let cli = TestClient::new(Route::new().at("/", get(index)));
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let mut ws = cli.get("/").websocket().await;
+/// ws.send_text("hello").await;
+/// ws.assert_next_text("HELLO").await;
+/// ws.close().await;
+/// # });
+/// ```
+pub struct TestWebSocket {
+    response: TestResponse,
+    inner: InnerStream,
+    timeout: Duration,
+}
+
+impl TestWebSocket {
+    pub(crate) async fn new(response: TestResponse, stream: DuplexStream) -> Self {
+        let deflate = response
+            .0
+            .headers()
+            .get(http::header::SEC_WEBSOCKET_EXTENSIONS)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| negotiate(value, &DeflateConfig::new()));
+        let inner = tokio_tungstenite::WebSocketStream::from_raw_socket(
+            DeflateStream::new(stream, Role::Client, deflate, None),
+            Role::Client,
+            None,
+        )
+        .await;
+
+        Self {
+            response,
+            inner,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Returns the upgrade response.
+    pub fn response(&self) -> &TestResponse {
+        &self.response
+    }
+
+    /// Sets the maximum time to wait for each message.
+    #[must_use]
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Sends a message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the connection has been closed.
+    pub async fn send_message(&mut self, msg: Message) {
+        self.inner.send(msg.into()).await.expect("send message");
+    }
+
+    /// Sends a text message.
+    pub async fn send_text(&mut self, text: impl Into<String>) {
+        self.send_message(Message::text(text)).await;
+    }
+
+    /// Sends a binary message.
+    pub async fn send_binary(&mut self, data: impl Into<Vec<u8>>) {
+        self.send_message(Message::binary(data)).await;
+    }
+
+    /// Sends a JSON text message.
+    pub async fn send_json(&mut self, json: &impl Serialize) {
+        self.send_text(serde_json::to_string(json).expect("valid json"))
+            .await;
+    }
+
+    /// Waits for the next message, returns `None` if the connection has been
+    /// closed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no message is received before the timeout, or the connection
+    /// fails.
+    pub async fn next_message(&mut self) -> Option<Message> {
+        tokio::time::timeout(self.timeout, self.inner.next())
+            .await
+            .unwrap_or_else(|_| panic!("no message received within {:?}", self.timeout))
+            .map(|msg| msg.expect("receive message").into())
+    }
+
+    /// Waits for the next text or binary message, skipping the pings and
+    /// pongs.
+    async fn next_data_message(&mut self) -> Message {
+        loop {
+            match self.next_message().await {
+                Some(Message::Ping(_) | Message::Pong(_)) => continue,
+                Some(msg) => return msg,
+                None => panic!("the connection has been closed"),
+            }
+        }
+    }
+
+    /// Asserts that the next message is a text message and it equals to
+    /// `text`.
+    pub async fn assert_next_text(&mut self, text: impl AsRef<str>) {
+        match self.next_data_message().await {
+            Message::Text(value) => assert_eq!(value, text.as_ref()),
+            msg => panic!("expect a text message, got {msg:?}"),
+        }
+    }
+
+    /// Asserts that the next message is a binary message and it equals to
+    /// `data`.
+    pub async fn assert_next_binary(&mut self, data: impl AsRef<[u8]>) {
+        match self.next_data_message().await {
+            Message::Binary(value) => assert_eq!(value, data.as_ref()),
+            msg => panic!("expect a binary message, got {msg:?}"),
+        }
+    }
+
+    /// Asserts that the next message is a JSON text message and it equals to
+    /// `json`.
+    pub async fn assert_next_json(&mut self, json: impl Serialize) {
+        match self.next_data_message().await {
+            Message::Text(value) => assert_eq!(
+                serde_json::from_str::<Value>(&value).expect("valid json"),
+                serde_json::to_value(json).expect("valid json")
+            ),
+            msg => panic!("expect a text message, got {msg:?}"),
+        }
+    }
+
+    /// Asserts that no message is received for `duration`.
+    pub async fn assert_no_message(&mut self, duration: Duration) {
+        if let Ok(Some(msg)) = tokio::time::timeout(duration, self.inner.next()).await {
+            panic!("unexpected message: {msg:?}");
+        }
+    }
+
+    /// Asserts that the endpoint closes the connection.
+    pub async fn assert_closed(&mut self) {
+        loop {
+            match self.next_message().await {
+                Some(Message::Ping(_) | Message::Pong(_)) => continue,
+                Some(Message::Close(_)) | None => return,
+                Some(msg) => panic!("unexpected message: {msg:?}"),
+            }
+        }
+    }
+
+    /// Closes the connection.
+    pub async fn close(&mut self) {
+        let _ = self.inner.close(None).await;
+    }
+}
+
+impl Stream for TestWebSocket {
+    type Item = Message;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner
+            .poll_next_unpin(cx)
+            .map(|msg| msg.and_then(Result::ok).map(Into::into))
+    }
+}
+
+impl Sink<Message> for TestWebSocket {
+    type Error = IoError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.inner.poll_ready_unpin(cx).map_err(IoError::other)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Message) -> IoResult<()> {
+        self.inner
+            .start_send_unpin(item.into())
+            .map_err(IoError::other)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.inner.poll_flush_unpin(cx).map_err(IoError::other)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        self.inner.poll_close_unpin(cx).map_err(IoError::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header;
+
+    use super::*;
+    use crate::{
+        IntoResponse, handler,
+        test::TestClient,
+        web::websocket::{CloseCode, WebSocket},
+    };
+
+    #[handler(internal)]
+    async fn echo(ws: WebSocket) -> impl IntoResponse {
+        ws.protocols(["echo"])
+            .deflate(DeflateConfig::new())
+            .on_upgrade(|mut socket| async move {
+                while let Some(Ok(msg)) = socket.next().await {
+                    match msg {
+                        Message::Text(text) if text == "bye" => {
+                            let _ = socket
+                                .send(Message::close_with(CloseCode::Normal, "bye"))
+                                .await;
+                        }
+                        Message::Text(text) => {
+                            let _ = socket.send(Message::Text(text.to_uppercase())).await;
+                        }
+                        Message::Binary(data) => {
+                            let _ = socket.send(Message::Binary(data)).await;
+                        }
+                        _ => {}
+                    }
+                }
+            })
+    }
+
+    #[tokio::test]
+    async fn websocket() {
+        let cli = TestClient::new(echo);
+        let mut ws = cli
+            .get("/")
+            .header(header::SEC_WEBSOCKET_PROTOCOL, "echo")
+            .websocket()
+            .await;
+        ws.response()
+            .assert_header(header::SEC_WEBSOCKET_PROTOCOL, "echo");
+        ws.response()
+            .assert_header_is_not_exist(header::SEC_WEBSOCKET_EXTENSIONS);
+
+        ws.send_text("hello").await;
+        ws.assert_next_text("HELLO").await;
+        ws.send_binary([1u8, 2, 3]).await;
+        ws.assert_next_binary([1u8, 2, 3]).await;
+        ws.send_json(&serde_json::json!({"a": 1})).await;
+        ws.assert_next_json(serde_json::json!({"A": 1})).await;
+        ws.assert_no_message(Duration::from_millis(50)).await;
+
+        ws.send_text("bye").await;
+        ws.assert_closed().await;
+    }
+
+    #[tokio::test]
+    async fn websocket_deflate() {
+        let cli = TestClient::new(echo);
+        let mut ws = cli
+            .get("/")
+            .header(
+                header::SEC_WEBSOCKET_EXTENSIONS,
+                "permessage-deflate; client_no_context_takeover",
+            )
+            .websocket()
+            .await;
+        ws.response().assert_header(
+            header::SEC_WEBSOCKET_EXTENSIONS,
+            "permessage-deflate; client_no_context_takeover",
+        );
+
+        for _ in 0..3 {
+            ws.send_text("hello hello hello").await;
+            ws.assert_next_text("HELLO HELLO HELLO").await;
+        }
+        ws.close().await;
+    }
+
+    #[cfg(feature = "cookie")]
+    #[tokio::test]
+    async fn websocket_cookies() {
+        use crate::{
+            EndpointExt, Request, Route, get,
+            middleware::CookieJarManager,
+            web::cookie::{Cookie, CookieJar},
+        };
+
+        #[handler(internal)]
+        async fn session(
+            req: &Request,
+            cookie_jar: &CookieJar,
+            ws: WebSocket,
+        ) -> impl IntoResponse {
+            let cookie = req.header(header::COOKIE).unwrap_or_default().to_string();
+            cookie_jar.add(Cookie::new_with_str("session", "abc"));
+            ws.on_upgrade(move |mut socket| async move {
+                let _ = socket.send(Message::Text(cookie)).await;
+            })
+        }
+
+        let cli = TestClient::new(
+            Route::new()
+                .at("/", get(session))
+                .with(CookieJarManager::new()),
+        )
+        .with_cookie_jar();
+
+        let mut ws = cli.get("/").websocket().await;
+        ws.assert_next_text("").await;
+        let mut ws = cli.get("/").websocket().await;
+        ws.assert_next_text("session=abc").await;
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn websocket_not_upgraded() {
+        TestClient::new(echo).post("/").websocket().await;
+    }
+}
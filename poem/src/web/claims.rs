@@ -0,0 +1,72 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::de::DeserializeOwned;
+
+use crate::{FromRequest, Request, RequestBody, Result, error::JwtError};
+
+/// The claims of a validated token, which are inserted into the request by the
+/// [`JwtAuth`](crate::middleware::JwtAuth) middleware.
+#[derive(Clone)]
+pub(crate) struct RawClaims(pub(crate) serde_json::Value);
+
+/// An extractor that deserializes the claims of the token validated by the
+/// [`JwtAuth`](crate::middleware::JwtAuth) middleware.
+///
+/// # Errors
+///
+/// - [`JwtError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Route, get, handler,
+///     middleware::{JwtAlgorithm, JwtAuth, JwtKey, JwtValidator},
+///     web::Claims,
+/// };
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     sub: String,
+/// }
+///
+/// #[handler]
+/// fn index(Claims(user): Claims<User>) -> String {
+///     format!("hello {}", user.sub)
+/// }
+///
+/// let validator = JwtValidator::new([JwtKey::hmac(JwtAlgorithm::HS256, "secret")]);
+/// let app = Route::new()
+///     .at("/", get(index))
+///     .with(JwtAuth::new(validator));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub struct Claims<T>(pub T);
+
+impl<T> Deref for Claims<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Claims<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a, T: DeserializeOwned> FromRequest<'a> for Claims<T> {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        let claims = req
+            .extensions()
+            .get::<RawClaims>()
+            .ok_or(JwtError::MissingClaims)?;
+        Ok(Claims(
+            serde_json::from_value(claims.0.clone()).map_err(JwtError::InvalidClaims)?,
+        ))
+    }
+}
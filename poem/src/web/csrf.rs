@@ -36,7 +36,7 @@ impl<'a> FromRequest<'a> for &'a CsrfToken {
 #[cfg_attr(docsrs, doc(cfg(feature = "csrf")))]
 pub struct CsrfVerifier {
     cookie: Option<UnencryptedCsrfCookie>,
-    protect: Arc<AesGcmCsrfProtection>,
+    protect: Arc<[AesGcmCsrfProtection]>,
 }
 
 /// Enum representing CSRF validation error
@@ -54,12 +54,28 @@ pub enum CsrfError {
 impl CsrfVerifier {
     pub(crate) fn new(
         cookie: Option<UnencryptedCsrfCookie>,
-        protect: Arc<AesGcmCsrfProtection>,
+        protect: Arc<[AesGcmCsrfProtection]>,
     ) -> Self {
         Self { cookie, protect }
     }
 }
 
+/// Parses with the current key first, then with the previous keys, and
+/// returns the error of the current key if all of them fail.
+pub(crate) fn parse_with_keys<T>(
+    protect: &[AesGcmCsrfProtection],
+    parse: impl Fn(&AesGcmCsrfProtection) -> Result<T, libcsrf::CsrfError>,
+) -> Result<T, libcsrf::CsrfError> {
+    let res = parse(&protect[0]);
+    if res.is_ok() {
+        return res;
+    }
+    protect[1..]
+        .iter()
+        .find_map(|protect| parse(protect).ok())
+        .map_or(res, Ok)
+}
+
 impl<'a> FromRequest<'a> for &'a CsrfVerifier {
     async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
         Ok(req
@@ -89,9 +105,9 @@ impl CsrfVerifier {
             Err(_) => return Err(CsrfError::CannotBeDecoded),
         };
 
-        let token = self.protect.parse_token(&token_data)?;
+        let token = parse_with_keys(&self.protect, |protect| protect.parse_token(&token_data))?;
 
-        self.protect
+        self.protect[0]
             .verify_token_pair(&token, cookie)
             .map_err(Into::into)
     }
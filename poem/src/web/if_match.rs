@@ -0,0 +1,82 @@
+use headers::HeaderMapExt;
+
+use crate::{FromRequest, Request, RequestBody, Result, error::PreconditionFailedError};
+
+/// An extractor for the `If-Match` header, which lets an endpoint enforce
+/// optimistic concurrency on unsafe methods.
+///
+/// The `ETag` middleware already enforces it by requesting the current
+/// representation of the resource. This extractor is for the endpoints that
+/// know the current entity tag of the resource without it, so they compare
+/// the header with that tag before applying the changes. Weak tags never
+/// match.
+///
+/// # Errors
+///
+/// - [`PreconditionFailedError`] (returned by [`IfMatch::check`])
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     Result, handler,
+///     http::{StatusCode, header},
+///     put,
+///     test::TestClient,
+///     web::IfMatch,
+/// };
+///
+/// #[handler]
+/// fn update(if_match: IfMatch) -> Result<()> {
+///     if_match.check(Some("\"v1\""))?;
+///     // apply the changes
+///     Ok(())
+/// }
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let cli = TestClient::new(put(update));
+/// cli.put("/")
+///     .header(header::IF_MATCH, "\"v1\"")
+///     .send()
+///     .await
+///     .assert_status_is_ok();
+/// cli.put("/")
+///     .header(header::IF_MATCH, "\"v0\"")
+///     .send()
+///     .await
+///     .assert_status(StatusCode::PRECONDITION_FAILED);
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IfMatch(pub Option<headers::IfMatch>);
+
+impl IfMatch {
+    /// Returns `true` if the request has no `If-Match` header, or the header
+    /// matches the current entity tag of the resource.
+    ///
+    /// `current` is `None` if the resource has no current representation, in
+    /// which case the precondition only passes without the header.
+    pub fn precondition_passes(&self, current: Option<&str>) -> bool {
+        let Some(if_match) = &self.0 else {
+            return true;
+        };
+        current
+            .and_then(|etag| etag.parse::<headers::ETag>().ok())
+            .is_some_and(|etag| if_match.precondition_passes(&etag))
+    }
+
+    /// Returns an error if the precondition does not pass.
+    pub fn check(&self, current: Option<&str>) -> Result<()> {
+        if self.precondition_passes(current) {
+            Ok(())
+        } else {
+            Err(PreconditionFailedError.into())
+        }
+    }
+}
+
+impl<'a> FromRequest<'a> for IfMatch {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(IfMatch(req.headers().typed_get()))
+    }
+}
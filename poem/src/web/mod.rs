@@ -2,6 +2,8 @@
 
 mod accept;
 mod addr;
+#[cfg(feature = "jwt")]
+mod claims;
 #[cfg(feature = "compression")]
 mod compress;
 #[cfg(feature = "cookie")]
@@ -9,6 +11,7 @@ mod compress;
 pub mod cookie;
 mod data;
 mod form;
+mod if_match;
 mod json;
 #[cfg(feature = "multipart")]
 mod multipart;
@@ -23,6 +26,8 @@ pub mod sse;
 mod static_file;
 #[cfg(feature = "tempfile")]
 mod tempfile;
+#[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl-tls"))]
+mod tls_info;
 #[cfg(feature = "xml")]
 mod xml;
 #[cfg(feature = "yaml")]
@@ -44,17 +49,30 @@ use bytes::Bytes;
 use futures_util::FutureExt;
 use http::header;
 
+#[cfg(feature = "jwt")]
+pub use self::claims::Claims;
+#[cfg(feature = "jwt")]
+pub(crate) use self::claims::RawClaims;
 #[cfg(feature = "compression")]
 pub use self::compress::{Compress, CompressionAlgo};
 #[cfg(feature = "csrf")]
+pub(crate) use self::csrf::parse_with_keys as parse_csrf_with_keys;
+#[cfg(feature = "csrf")]
 pub use self::csrf::{CsrfToken, CsrfVerifier};
 #[cfg(feature = "multipart")]
-pub use self::multipart::{Field, Multipart};
+pub use self::multipart::{Field, Multipart, MultipartLimits};
 pub(crate) use self::path::PathDeserializer;
 #[cfg(feature = "static-files")]
 pub use self::static_file::{StaticFileRequest, StaticFileResponse};
 #[cfg(feature = "tempfile")]
 pub use self::tempfile::TempFile;
+#[cfg(all(
+    feature = "x509",
+    any(feature = "rustls", feature = "native-tls", feature = "openssl-tls")
+))]
+pub use self::tls_info::SubjectAltName;
+#[cfg(any(feature = "rustls", feature = "native-tls", feature = "openssl-tls"))]
+pub use self::tls_info::{TlsInfo, TlsVersion};
 #[cfg(feature = "xml")]
 pub use self::xml::Xml;
 #[cfg(feature = "yaml")]
@@ -64,6 +82,7 @@ pub use self::{
     addr::{LocalAddr, RemoteAddr},
     data::Data,
     form::Form,
+    if_match::IfMatch,
     json::Json,
     path::Path,
     query::Query,
@@ -92,15 +92,100 @@ impl Field {
 
     /// Consume this field to return a reader.
     pub fn into_async_read(self) -> impl AsyncRead + Send {
-        tokio_util::io::StreamReader::new(
-            self.0.map_err(|err| std::io::Error::other(err.to_string())),
-        )
+        tokio_util::io::StreamReader::new(self.0.map_err(std::io::Error::other))
+    }
+}
+
+/// The limits of the [`Multipart`] extractor, which are added to the
+/// endpoint with [`EndpointExt::data`](crate::EndpointExt::data).
+///
+/// The requests that exceed the limits are rejected with the
+/// `PAYLOAD_TOO_LARGE` status code. By default, there is no limit.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Result, Route, handler,
+///     http::StatusCode,
+///     post,
+///     test::TestClient,
+///     web::{Multipart, MultipartLimits},
+/// };
+///
+/// #[handler]
+/// async fn upload(mut multipart: Multipart) -> Result<()> {
+///     while let Some(field) = multipart.next_field().await? {
+///         field.bytes().await?;
+///     }
+///     Ok(())
+/// }
+///
+/// let app = Route::new().at("/", post(upload)).data(
+///     MultipartLimits::new()
+///         .max_fields(10)
+///         .field_size(1024 * 1024)
+///         .total_size(4 * 1024 * 1024),
+/// );
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+#[derive(Debug, Clone, Default)]
+pub struct MultipartLimits {
+    max_fields: Option<usize>,
+    field_size: Option<u64>,
+    total_size: Option<u64>,
+}
+
+impl MultipartLimits {
+    /// Create a `MultipartLimits` without any limit.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the maximum number of fields.
+    #[must_use]
+    pub fn max_fields(self, value: usize) -> Self {
+        Self {
+            max_fields: Some(value),
+            ..self
+        }
+    }
+
+    /// Sets the maximum size of each field in bytes.
+    #[must_use]
+    pub fn field_size(self, value: u64) -> Self {
+        Self {
+            field_size: Some(value),
+            ..self
+        }
+    }
+
+    /// Sets the maximum size of the whole body in bytes.
+    #[must_use]
+    pub fn total_size(self, value: u64) -> Self {
+        Self {
+            total_size: Some(value),
+            ..self
+        }
+    }
+
+    fn constraints(&self) -> multer::Constraints {
+        let mut size_limit = multer::SizeLimit::new();
+        if let Some(field_size) = self.field_size {
+            size_limit = size_limit.per_field(field_size);
+        }
+        if let Some(total_size) = self.total_size {
+            size_limit = size_limit.whole_stream(total_size);
+        }
+        multer::Constraints::new().size_limit(size_limit)
     }
 }
 
 /// An extractor that parses `multipart/form-data` requests commonly used with
 /// file uploads.
 ///
+/// The limits of the request can be set with [`MultipartLimits`].
+///
 /// # Errors
 ///
 /// - [`ReadBodyError`](crate::error::ReadBodyError)
@@ -126,6 +211,8 @@ impl Field {
 #[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
 pub struct Multipart {
     inner: multer::Multipart<'static>,
+    fields: usize,
+    max_fields: Option<usize>,
 }
 
 impl<'a> FromRequest<'a> for Multipart {
@@ -146,11 +233,15 @@ impl<'a> FromRequest<'a> for Multipart {
 
         let boundary = multer::parse_boundary(content_type.as_ref())
             .map_err(ParseMultipartError::Multipart)?;
+        let limits = req.data::<MultipartLimits>().cloned().unwrap_or_default();
         Ok(Self {
-            inner: multer::Multipart::new(
+            inner: multer::Multipart::with_constraints(
                 tokio_util::io::ReaderStream::new(body.take()?.into_async_read()),
                 boundary,
+                limits.constraints(),
             ),
+            fields: 0,
+            max_fields: limits.max_fields,
         })
     }
 }
@@ -159,7 +250,15 @@ impl Multipart {
     /// Yields the next [`Field`] if available.
     pub async fn next_field(&mut self) -> Result<Option<Field>, ParseMultipartError> {
         match self.inner.next_field().await? {
-            Some(field) => Ok(Some(Field(field))),
+            Some(field) => {
+                self.fields += 1;
+                if let Some(max_fields) = self.max_fields {
+                    if self.fields > max_fields {
+                        return Err(ParseMultipartError::TooManyFields(max_fields));
+                    }
+                }
+                Ok(Some(Field(field)))
+            }
             None => Ok(None),
         }
     }
@@ -168,7 +267,7 @@ impl Multipart {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{handler, http::StatusCode, test::TestClient};
+    use crate::{EndpointExt, handler, http::StatusCode, test::TestClient};
 
     #[tokio::test]
     async fn test_multipart_extractor_content_type() {
@@ -216,4 +315,53 @@ mod tests {
             .await;
         resp.assert_status_is_ok();
     }
+
+    #[tokio::test]
+    async fn limits() {
+        #[handler(internal)]
+        async fn index(mut multipart: Multipart) -> Result<String> {
+            let mut fields = Vec::new();
+            while let Some(field) = multipart.next_field().await? {
+                fields.push(field.text().await?);
+            }
+            Ok(fields.join(","))
+        }
+
+        let send = |limits: MultipartLimits, data: &'static str| {
+            let cli = TestClient::new(index.data(limits));
+            async move {
+                cli.post("/")
+                    .header("content-type", "multipart/form-data; boundary=X-BOUNDARY")
+                    .body(data)
+                    .send()
+                    .await
+            }
+        };
+        let data = "--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nabcd\r\n--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"b\"\r\n\r\nefghijkl\r\n--X-BOUNDARY--\r\n";
+
+        send(MultipartLimits::new(), data)
+            .await
+            .assert_text("abcd,efghijkl")
+            .await;
+        send(
+            MultipartLimits::new()
+                .max_fields(2)
+                .field_size(8)
+                .total_size(data.len() as u64),
+            data,
+        )
+        .await
+        .assert_text("abcd,efghijkl")
+        .await;
+
+        for limits in [
+            MultipartLimits::new().max_fields(1),
+            MultipartLimits::new().field_size(7),
+            MultipartLimits::new().total_size(data.len() as u64 - 1),
+        ] {
+            send(limits, data)
+                .await
+                .assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+    }
 }
@@ -0,0 +1,225 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use futures_util::{Stream, StreamExt, stream};
+use parking_lot::Mutex;
+use tokio::sync::broadcast;
+
+use super::{Event, SSE};
+
+struct State {
+    buffer: VecDeque<Event>,
+    next_id: u64,
+}
+
+struct Inner {
+    capacity: usize,
+    state: Mutex<State>,
+    sender: broadcast::Sender<Event>,
+}
+
+/// A hub that broadcasts server-sent events to the connected clients, and
+/// keeps the last events in a bounded replay buffer.
+///
+/// When a client reconnects with the `Last-Event-ID` header, which can be
+/// extracted with [`LastEventId`](super::LastEventId), it first receives the
+/// buffered events published after that event, then the live events. If the
+/// event is no longer in the buffer, all the buffered events are sent.
+///
+/// A client that falls behind by more than the capacity of the buffer is
+/// disconnected, so that it reconnects and receives the missed events from the
+/// buffer.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     EndpointExt, Route, get, handler, post,
+///     web::{
+///         Data,
+///         sse::{Event, LastEventId, SSE, SseHub},
+///     },
+/// };
+///
+/// #[handler]
+/// fn events(hub: Data<&SseHub>, last_event_id: LastEventId) -> SSE {
+///     hub.sse(last_event_id.as_deref())
+/// }
+///
+/// #[handler]
+/// fn publish(hub: Data<&SseHub>, body: String) {
+///     hub.publish(Event::message(body));
+/// }
+///
+/// let app = Route::new()
+///     .at("/events", get(events))
+///     .at("/publish", post(publish))
+///     .data(SseHub::new(100));
+/// ```
+#[derive(Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "sse")))]
+pub struct SseHub {
+    inner: Arc<Inner>,
+}
+
+impl SseHub {
+    /// Create an `SseHub` which keeps up to `capacity` events in the replay
+    /// buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity must be greater than zero");
+        Self {
+            inner: Arc::new(Inner {
+                capacity,
+                state: Mutex::new(State {
+                    buffer: VecDeque::with_capacity(capacity),
+                    next_id: 1,
+                }),
+                sender: broadcast::channel(capacity).0,
+            }),
+        }
+    }
+
+    /// Publishes an event to the connected clients, and returns its ID.
+    ///
+    /// If the message has no ID, it is assigned a sequential number. Retry
+    /// events are not kept in the replay buffer, and have no ID.
+    pub fn publish(&self, mut event: Event) -> Option<String> {
+        let mut state = self.inner.state.lock();
+        let mut event_id = None;
+        if let Event::Message { id, .. } = &mut event {
+            if id.is_empty() {
+                *id = state.next_id.to_string();
+                state.next_id += 1;
+            }
+            event_id = Some(id.clone());
+
+            if state.buffer.len() == self.inner.capacity {
+                state.buffer.pop_front();
+            }
+            state.buffer.push_back(event.clone());
+        }
+
+        // send while holding the lock, so that the subscribers receive each
+        // event either from the buffer or from the channel
+        let _ = self.inner.sender.send(event);
+        event_id
+    }
+
+    /// Returns the number of connected clients.
+    pub fn receiver_count(&self) -> usize {
+        self.inner.sender.receiver_count()
+    }
+
+    /// Subscribes to the events published after the specified event.
+    ///
+    /// If `last_event_id` is `None`, only the live events are received.
+    pub fn subscribe(
+        &self,
+        last_event_id: Option<&str>,
+    ) -> impl Stream<Item = Event> + Send + 'static {
+        let (replay, receiver) = {
+            let state = self.inner.state.lock();
+            let replay = match last_event_id {
+                Some(last_event_id) => {
+                    let skip = state
+                        .buffer
+                        .iter()
+                        .position(|event| {
+                            matches!(event, Event::Message { id, .. } if id == last_event_id)
+                        })
+                        .map(|pos| pos + 1)
+                        .unwrap_or_default();
+                    state.buffer.iter().skip(skip).cloned().collect()
+                }
+                None => Vec::new(),
+            };
+            (replay, self.inner.sender.subscribe())
+        };
+
+        let live = stream::unfold(receiver, |mut receiver| async move {
+            // if the client is lagging, the stream is closed
+            let event = receiver.recv().await.ok()?;
+            Some((event, receiver))
+        });
+        stream::iter(replay).chain(live)
+    }
+
+    /// Create an [`SSE`] response with the events published after the
+    /// specified event.
+    pub fn sse(&self, last_event_id: Option<&str>) -> SSE {
+        SSE::new(self.subscribe(last_event_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    async fn next_data(stream: &mut (impl Stream<Item = Event> + Unpin)) -> Option<String> {
+        match tokio::time::timeout(Duration::from_secs(1), stream.next()).await {
+            Ok(Some(Event::Message { data, .. })) => Some(data),
+            Ok(Some(Event::Retry { .. })) => panic!("unexpected retry event"),
+            Ok(None) => None,
+            Err(_) => panic!("timed out"),
+        }
+    }
+
+    #[tokio::test]
+    async fn replay() {
+        let hub = SseHub::new(3);
+        for i in 1..=5 {
+            assert_eq!(
+                hub.publish(Event::message(i.to_string())),
+                Some(i.to_string())
+            );
+        }
+
+        let mut stream = Box::pin(hub.subscribe(Some("3")));
+        assert_eq!(next_data(&mut stream).await.as_deref(), Some("4"));
+        assert_eq!(next_data(&mut stream).await.as_deref(), Some("5"));
+
+        // the event is no longer in the buffer
+        let mut stream2 = Box::pin(hub.subscribe(Some("1")));
+        for data in ["3", "4", "5"] {
+            assert_eq!(next_data(&mut stream2).await.as_deref(), Some(data));
+        }
+
+        // live events only
+        let mut stream3 = Box::pin(hub.subscribe(None));
+        assert_eq!(hub.receiver_count(), 3);
+
+        assert_eq!(
+            hub.publish(Event::message("6").id("a")),
+            Some("a".to_string())
+        );
+        assert_eq!(hub.publish(Event::retry(1000)), None);
+        for stream in [&mut stream, &mut stream2] {
+            assert_eq!(next_data(stream).await.as_deref(), Some("6"));
+            assert_eq!(stream.next().await, Some(Event::retry(1000)));
+        }
+        assert_eq!(next_data(&mut stream3).await.as_deref(), Some("6"));
+
+        let mut stream = Box::pin(hub.subscribe(Some("a")));
+        hub.publish(Event::message("7"));
+        assert_eq!(next_data(&mut stream).await.as_deref(), Some("7"));
+    }
+
+    #[tokio::test]
+    async fn lagging() {
+        let hub = SseHub::new(2);
+        let mut stream = Box::pin(hub.subscribe(None));
+        for i in 1..=3 {
+            hub.publish(Event::message(i.to_string()));
+        }
+        assert_eq!(next_data(&mut stream).await, None);
+
+        let mut stream = Box::pin(hub.subscribe(Some("1")));
+        assert_eq!(next_data(&mut stream).await.as_deref(), Some("2"));
+        assert_eq!(next_data(&mut stream).await.as_deref(), Some("3"));
+    }
+}
@@ -0,0 +1,45 @@
+use std::ops::Deref;
+
+use crate::{FromRequest, Request, RequestBody, Result};
+
+/// An extractor for the ID of the last event received by the client, which is
+/// sent in the `Last-Event-ID` header when the client reconnects.
+///
+/// # Example
+///
+/// ```
+/// use poem::{
+///     handler,
+///     web::{
+///         Data,
+///         sse::{LastEventId, SSE, SseHub},
+///     },
+/// };
+///
+/// #[handler]
+/// fn events(hub: Data<&SseHub>, last_event_id: LastEventId) -> SSE {
+///     hub.sse(last_event_id.as_deref())
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "sse")))]
+pub struct LastEventId(pub Option<String>);
+
+impl Deref for LastEventId {
+    type Target = Option<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> FromRequest<'a> for LastEventId {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(LastEventId(
+            req.headers()
+                .get("last-event-id")
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string),
+        ))
+    }
+}
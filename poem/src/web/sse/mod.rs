@@ -1,9 +1,13 @@
 //! Server-Sent Events (SSE) types.
 
 mod event;
+mod hub;
+mod last_event_id;
 mod response;
 
 pub use event::Event;
+pub use hub::SseHub;
+pub use last_event_id::LastEventId;
 pub use response::SSE;
 
 #[cfg(test)]
@@ -13,7 +17,7 @@ mod tests {
     use tokio::{io::AsyncReadExt, time::Instant};
 
     use super::*;
-    use crate::IntoResponse;
+    use crate::{FromRequest, IntoResponse, Request};
 
     #[tokio::test]
     async fn sse() {
@@ -71,4 +75,19 @@ data: 5
             s = now;
         }
     }
+
+    #[tokio::test]
+    async fn last_event_id() {
+        let req = Request::builder().header("Last-Event-ID", "42").finish();
+        assert_eq!(
+            LastEventId::from_request_without_body(&req).await.unwrap(),
+            LastEventId(Some("42".to_string()))
+        );
+
+        let req = Request::builder().finish();
+        assert_eq!(
+            LastEventId::from_request_without_body(&req).await.unwrap(),
+            LastEventId(None)
+        );
+    }
 }
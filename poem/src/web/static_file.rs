@@ -2,7 +2,7 @@ use std::{
     collections::Bound,
     fs::Metadata,
     io::{Seek, SeekFrom},
-    path::Path,
+    path::{Path, PathBuf},
     str::FromStr,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -12,7 +12,7 @@ use headers::{
     ContentRange, ETag, HeaderMapExt, IfMatch, IfModifiedSince, IfNoneMatch, IfUnmodifiedSince,
     Range,
 };
-use http::{StatusCode, header};
+use http::{HeaderValue, StatusCode, header};
 use httpdate::HttpDate;
 use mime::Mime;
 use tokio::{fs::File, io::AsyncReadExt};
@@ -104,6 +104,7 @@ pub struct StaticFileRequest {
     if_none_match: Option<IfNoneMatch>,
     if_modified_since: Option<IfModifiedSince>,
     range: Option<Range>,
+    accept_encoding: Vec<(String, u16)>,
 }
 
 impl<'a> FromRequest<'a> for StaticFileRequest {
@@ -114,6 +115,22 @@ impl<'a> FromRequest<'a> for StaticFileRequest {
             if_none_match: req.headers().typed_get::<IfNoneMatch>(),
             if_modified_since: req.headers().typed_get::<IfModifiedSince>(),
             range: req.headers().typed_get::<Range>(),
+            accept_encoding: req
+                .headers()
+                .get_all(header::ACCEPT_ENCODING)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .filter_map(|value| {
+                    let mut parts = value.split(';').map(str::trim);
+                    let coding = parts.next()?.to_ascii_lowercase();
+                    let q = match parts.find_map(|param| param.strip_prefix("q=")) {
+                        Some(q) => (q.parse::<f32>().ok()? * 1000.0) as u16,
+                        None => 1000,
+                    };
+                    Some((coding, q))
+                })
+                .collect(),
         })
     }
 }
@@ -191,7 +208,6 @@ impl StaticFileRequest {
         if !path.exists() || !path.is_file() {
             return Err(StaticFileError::NotFound);
         }
-        let guess = mime_guess::from_path(path);
         let mut file = std::fs::File::open(path)?;
         let metadata = file.metadata()?;
 
@@ -199,13 +215,7 @@ impl StaticFileRequest {
         let mut content_length = metadata.len();
 
         // content type
-        let content_type = guess.first().map(|mime| {
-            if prefer_utf8 {
-                equiv_utf8_text(mime).to_string()
-            } else {
-                mime.to_string()
-            }
-        });
+        let content_type = guess_content_type(path, prefer_utf8);
 
         // etag and last modified
         let mut etag_str = String::new();
@@ -295,6 +305,96 @@ impl StaticFileRequest {
             },
         })
     }
+
+    /// Create static file response, serving the precompressed version of the
+    /// file instead if it exists and its encoding is accepted by the client.
+    ///
+    /// The precompressed versions are the files with the same name and the
+    /// `.zst`, `.br` or `.gz` extension in the same directory, such as
+    /// `index.html.gz`. The response has the content type of the original file
+    /// and the `Content-Encoding` header of the precompressed version. It
+    /// always has the `Vary: accept-encoding` header, because it depends on
+    /// the encodings accepted by the client.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefer_utf8` - Specifies whether text responses should signal a UTF-8
+    ///   encoding.
+    /// * `no_cache` - Specifies whether to set the `Cache-Control` header to
+    ///   `no-cache`.
+    pub fn create_precompressed_response(
+        self,
+        path: impl AsRef<Path>,
+        prefer_utf8: bool,
+        no_cache: bool,
+    ) -> Result<Response, StaticFileError> {
+        let path = path.as_ref();
+        if !path.exists() || !path.is_file() {
+            return Err(StaticFileError::NotFound);
+        }
+
+        let mut resp = match self.precompressed_file(path) {
+            Some((encoding, compressed_path)) => {
+                let mut resp = self.create_response(compressed_path, prefer_utf8, no_cache)?;
+                let is_ok = matches!(resp, StaticFileResponse::Ok { .. });
+                if let StaticFileResponse::Ok { content_type, .. } = &mut resp {
+                    *content_type = guess_content_type(path, prefer_utf8);
+                }
+                let mut resp = resp.into_response();
+                if is_ok {
+                    resp.headers_mut()
+                        .insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding));
+                }
+                resp
+            }
+            None => self
+                .create_response(path, prefer_utf8, no_cache)?
+                .into_response(),
+        };
+        resp.headers_mut()
+            .insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+        Ok(resp)
+    }
+
+    /// Returns the encoding and the path of the preferred precompressed
+    /// version of the file that exists.
+    fn precompressed_file(&self, path: &Path) -> Option<(&'static str, PathBuf)> {
+        let quality = |coding: &str| {
+            self.accept_encoding
+                .iter()
+                .find(|(name, _)| name == coding)
+                .or_else(|| self.accept_encoding.iter().find(|(name, _)| name == "*"))
+                .map(|(_, q)| *q)
+                .unwrap_or_default()
+        };
+
+        let mut candidates = [("zstd", "zst"), ("br", "br"), ("gzip", "gz")]
+            .into_iter()
+            .map(|(encoding, ext)| (encoding, ext, quality(encoding)))
+            .filter(|(_, _, q)| *q > 0)
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|(_, _, q)| std::cmp::Reverse(*q));
+
+        candidates.into_iter().find_map(|(encoding, ext, _)| {
+            let mut compressed_path = path.as_os_str().to_owned();
+            compressed_path.push(".");
+            compressed_path.push(ext);
+            let compressed_path = PathBuf::from(compressed_path);
+            compressed_path
+                .is_file()
+                .then_some((encoding, compressed_path))
+        })
+    }
+}
+
+fn guess_content_type(path: &Path, prefer_utf8: bool) -> Option<String> {
+    mime_guess::from_path(path).first().map(|mime| {
+        if prefer_utf8 {
+            equiv_utf8_text(mime).to_string()
+        } else {
+            mime.to_string()
+        }
+    })
 }
 
 fn equiv_utf8_text(ct: Mime) -> Mime {
@@ -604,4 +704,51 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_precompressed() {
+        let dir = std::env::temp_dir().join(format!("poem-precompressed-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("index.html");
+        std::fs::write(&path, "<html></html>").unwrap();
+        std::fs::write(dir.join("index.html.gz"), "gzip").unwrap();
+        std::fs::write(dir.join("index.html.br"), "br").unwrap();
+
+        for (accept_encoding, content_encoding, body) in [
+            (None, None, "<html></html>"),
+            (Some("deflate"), None, "<html></html>"),
+            (Some("gzip"), Some("gzip"), "gzip"),
+            (Some("gzip, br"), Some("br"), "br"),
+            (Some("zstd, br;q=0.5, gzip;q=0.8"), Some("gzip"), "gzip"),
+            (Some("*"), Some("br"), "br"),
+            (Some("br;q=0, *"), Some("gzip"), "gzip"),
+        ] {
+            let mut req = Request::builder();
+            if let Some(accept_encoding) = accept_encoding {
+                req = req.header(header::ACCEPT_ENCODING, accept_encoding);
+            }
+            let resp = StaticFileRequest::from_request_without_body(&req.finish())
+                .await
+                .unwrap()
+                .create_precompressed_response(&path, true, false)
+                .unwrap();
+
+            assert_eq!(resp.content_type(), Some("text/html; charset=utf-8"));
+            assert_eq!(
+                resp.headers()
+                    .get(header::VARY)
+                    .and_then(|value| value.to_str().ok()),
+                Some("accept-encoding")
+            );
+            assert_eq!(
+                resp.headers()
+                    .get(header::CONTENT_ENCODING)
+                    .and_then(|value| value.to_str().ok()),
+                content_encoding
+            );
+            assert_eq!(resp.into_body().into_string().await.unwrap(), body);
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
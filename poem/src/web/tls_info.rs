@@ -0,0 +1,260 @@
+#[cfg(feature = "x509")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use bytes::Bytes;
+#[cfg(feature = "x509")]
+use x509_parser::{certificate::X509Certificate, extensions::GeneralName, prelude::FromDer};
+
+use crate::{FromRequest, Request, RequestBody, Result, error::MissingTlsInfoError};
+
+/// The version of the TLS protocol.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum TlsVersion {
+    /// TLS 1.0
+    Tls1_0,
+    /// TLS 1.1
+    Tls1_1,
+    /// TLS 1.2
+    Tls1_2,
+    /// TLS 1.3
+    Tls1_3,
+}
+
+/// A subject alternative name of a certificate.
+#[cfg(feature = "x509")]
+#[cfg_attr(docsrs, doc(cfg(feature = "x509")))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SubjectAltName {
+    /// A DNS name.
+    Dns(String),
+    /// An email address.
+    Email(String),
+    /// An URI.
+    Uri(String),
+    /// An IP address.
+    Ip(IpAddr),
+}
+
+/// An extractor that extracts the information of the TLS connection that the
+/// request was received from.
+///
+/// The information is recorded by the [`rustls`](crate::listener::RustlsAcceptor),
+/// [`native-tls`](crate::listener::NativeTlsAcceptor) and
+/// [`openssl`](crate::listener::OpensslTlsAcceptor) acceptors. `native-tls`
+/// does not expose the handshake details, so for its connections only the
+/// leaf certificate of the peer is recorded, and [`TlsInfo::server_name`],
+/// [`TlsInfo::alpn_protocol`] and [`TlsInfo::version`] always return `None`.
+///
+/// Parsing the certificate of the peer requires the `x509` feature.
+///
+/// # Errors
+///
+/// - [`MissingTlsInfoError`]
+///
+/// # Example
+///
+/// ```
+/// use poem::{Error, Result, handler, http::StatusCode, web::TlsInfo};
+///
+/// #[handler]
+/// fn index(tls_info: &TlsInfo) -> Result<String> {
+///     if tls_info.peer_certificate().is_none() {
+///         return Err(Error::from_status(StatusCode::FORBIDDEN));
+///     }
+///     Ok(tls_info.server_name().unwrap_or_default().to_string())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TlsInfo {
+    peer_certificates: Vec<Bytes>,
+    server_name: Option<String>,
+    alpn_protocol: Option<Bytes>,
+    version: Option<TlsVersion>,
+}
+
+impl TlsInfo {
+    /// Returns the DER-encoded certificate chain presented by the peer, the
+    /// first one is the certificate of the peer.
+    ///
+    /// Returns an empty slice if the peer did not present a certificate.
+    pub fn peer_certificates(&self) -> &[Bytes] {
+        &self.peer_certificates
+    }
+
+    /// Returns the DER-encoded certificate presented by the peer.
+    pub fn peer_certificate(&self) -> Option<&[u8]> {
+        self.peer_certificates.first().map(|cert| cert.as_ref())
+    }
+
+    /// Returns the subject of the certificate presented by the peer, for
+    /// example `CN=client, O=Example`.
+    #[cfg(feature = "x509")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "x509")))]
+    pub fn peer_subject(&self) -> Option<String> {
+        let (_, cert) = X509Certificate::from_der(self.peer_certificate()?).ok()?;
+        Some(cert.subject().to_string())
+    }
+
+    /// Returns the subject alternative names of the certificate presented by
+    /// the peer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use poem::{
+    ///     Error, Result, handler,
+    ///     http::StatusCode,
+    ///     web::{SubjectAltName, TlsInfo},
+    /// };
+    ///
+    /// #[handler]
+    /// fn index(tls_info: &TlsInfo) -> Result<String> {
+    ///     let is_admin = tls_info
+    ///         .peer_subject_alt_names()
+    ///         .contains(&SubjectAltName::Dns("admin.example.com".to_string()));
+    ///     if !is_admin {
+    ///         return Err(Error::from_status(StatusCode::FORBIDDEN));
+    ///     }
+    ///     Ok(tls_info.peer_subject().unwrap_or_default())
+    /// }
+    /// ```
+    #[cfg(feature = "x509")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "x509")))]
+    pub fn peer_subject_alt_names(&self) -> Vec<SubjectAltName> {
+        let Some(Ok((_, cert))) = self.peer_certificate().map(X509Certificate::from_der) else {
+            return Vec::new();
+        };
+        let Ok(Some(san)) = cert.subject_alternative_name() else {
+            return Vec::new();
+        };
+
+        san.value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(name) => Some(SubjectAltName::Dns(name.to_string())),
+                GeneralName::RFC822Name(email) => Some(SubjectAltName::Email(email.to_string())),
+                GeneralName::URI(uri) => Some(SubjectAltName::Uri(uri.to_string())),
+                GeneralName::IPAddress(ip) => match *ip {
+                    [a, b, c, d] => Some(SubjectAltName::Ip(Ipv4Addr::new(*a, *b, *c, *d).into())),
+                    ip => <[u8; 16]>::try_from(ip)
+                        .ok()
+                        .map(|ip| SubjectAltName::Ip(Ipv6Addr::from(ip).into())),
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the server name that the client requested with SNI.
+    ///
+    /// Always returns `None` for the connections of the `native-tls`
+    /// acceptor.
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// Returns the negotiated ALPN protocol.
+    ///
+    /// Always returns `None` for the connections of the `native-tls`
+    /// acceptor.
+    pub fn alpn_protocol(&self) -> Option<&[u8]> {
+        self.alpn_protocol.as_deref()
+    }
+
+    /// Returns the negotiated TLS version.
+    ///
+    /// Always returns `None` for the connections of the `native-tls`
+    /// acceptor.
+    pub fn version(&self) -> Option<TlsVersion> {
+        self.version
+    }
+
+    #[cfg(feature = "rustls")]
+    pub(crate) fn from_rustls(conn: &tokio_rustls::rustls::ServerConnection) -> Self {
+        use tokio_rustls::rustls::ProtocolVersion;
+
+        Self {
+            peer_certificates: conn
+                .peer_certificates()
+                .unwrap_or_default()
+                .iter()
+                .map(|cert| Bytes::copy_from_slice(cert))
+                .collect(),
+            server_name: conn.server_name().map(ToString::to_string),
+            alpn_protocol: conn.alpn_protocol().map(Bytes::copy_from_slice),
+            version: conn.protocol_version().and_then(|version| match version {
+                ProtocolVersion::TLSv1_0 => Some(TlsVersion::Tls1_0),
+                ProtocolVersion::TLSv1_1 => Some(TlsVersion::Tls1_1),
+                ProtocolVersion::TLSv1_2 => Some(TlsVersion::Tls1_2),
+                ProtocolVersion::TLSv1_3 => Some(TlsVersion::Tls1_3),
+                _ => None,
+            }),
+        }
+    }
+
+    #[cfg(feature = "native-tls")]
+    pub(crate) fn from_native_tls<S>(stream: &tokio_native_tls::native_tls::TlsStream<S>) -> Self
+    where
+        S: std::io::Read + std::io::Write,
+    {
+        Self {
+            peer_certificates: stream
+                .peer_certificate()
+                .ok()
+                .flatten()
+                .and_then(|cert| cert.to_der().ok())
+                .map(Bytes::from)
+                .into_iter()
+                .collect(),
+            // native-tls does not expose the SNI, ALPN and version
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "openssl-tls")]
+    pub(crate) fn from_openssl(ssl: &openssl::ssl::SslRef) -> Self {
+        use openssl::ssl::{NameType, SslVersion};
+
+        // on the server side, the chain does not contain the certificate of the peer
+        let peer_certificates = ssl
+            .peer_certificate()
+            .into_iter()
+            .chain(
+                ssl.peer_cert_chain()
+                    .into_iter()
+                    .flatten()
+                    .map(ToOwned::to_owned),
+            )
+            .filter_map(|cert| cert.to_der().ok())
+            .map(Bytes::from)
+            .collect();
+
+        Self {
+            peer_certificates,
+            server_name: ssl.servername(NameType::HOST_NAME).map(ToString::to_string),
+            alpn_protocol: ssl.selected_alpn_protocol().map(Bytes::copy_from_slice),
+            version: ssl.version2().and_then(|version| {
+                [
+                    (SslVersion::TLS1, TlsVersion::Tls1_0),
+                    (SslVersion::TLS1_1, TlsVersion::Tls1_1),
+                    (SslVersion::TLS1_2, TlsVersion::Tls1_2),
+                    (SslVersion::TLS1_3, TlsVersion::Tls1_3),
+                ]
+                .into_iter()
+                .find(|(v, _)| *v == version)
+                .map(|(_, version)| version)
+            }),
+        }
+    }
+}
+
+impl<'a> FromRequest<'a> for &'a TlsInfo {
+    async fn from_request(req: &'a Request, _body: &mut RequestBody) -> Result<Self> {
+        Ok(req
+            .extensions()
+            .get::<TlsInfo>()
+            .ok_or(MissingTlsInfoError)?)
+    }
+}
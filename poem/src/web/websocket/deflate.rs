@@ -0,0 +1,768 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{Cursor, Error as IoError, ErrorKind, Result as IoResult},
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use bytes::{Buf, BytesMut};
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_tungstenite::tungstenite::protocol::{
+    Role,
+    frame::{
+        FrameHeader,
+        coding::{Data, OpCode},
+    },
+};
+
+use super::utils::tungstenite_error_to_io_error;
+
+const EXTENSION_NAME: &str = "permessage-deflate";
+
+/// The bytes removed from the end of each compressed message.
+const TRAILER: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// The size of the encoded frames above which the writes wait for the
+/// underlying stream.
+const MAX_WRITE_BUFFER_SIZE: usize = 128 * 1024;
+
+/// Configuration of the `permessage-deflate` extension.
+///
+/// Reference: <https://www.rfc-editor.org/rfc/rfc7692>
+#[derive(Debug, Clone, Copy)]
+pub struct DeflateConfig {
+    level: u32,
+    server_no_context_takeover: bool,
+    client_no_context_takeover: bool,
+}
+
+impl Default for DeflateConfig {
+    fn default() -> Self {
+        Self {
+            level: Compression::default().level(),
+            server_no_context_takeover: false,
+            client_no_context_takeover: false,
+        }
+    }
+}
+
+impl DeflateConfig {
+    /// Create a `DeflateConfig` with the default compression level.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the compression level of the messages sent by the server, in the
+    /// range `0..=9`.
+    #[must_use]
+    pub fn compression_level(self, level: u32) -> Self {
+        Self {
+            level: level.min(9),
+            ..self
+        }
+    }
+
+    /// If `true`, the server resets its compression context after each
+    /// message, which uses less memory but compresses similar messages less
+    /// efficiently.
+    ///
+    /// The server also resets it if the client requests it.
+    #[must_use]
+    pub fn server_no_context_takeover(self, enable: bool) -> Self {
+        Self {
+            server_no_context_takeover: enable,
+            ..self
+        }
+    }
+
+    /// If `true`, the client is asked to reset its compression context after
+    /// each message, so that the server does not need to keep the
+    /// decompression context between messages.
+    #[must_use]
+    pub fn client_no_context_takeover(self, enable: bool) -> Self {
+        Self {
+            client_no_context_takeover: enable,
+            ..self
+        }
+    }
+}
+
+/// The negotiated parameters of the `permessage-deflate` extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DeflateParams {
+    pub(crate) level: u32,
+    pub(crate) server_no_context_takeover: bool,
+    pub(crate) client_no_context_takeover: bool,
+    pub(crate) server_max_window_bits: bool,
+}
+
+impl Display for DeflateParams {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(EXTENSION_NAME)?;
+        if self.server_no_context_takeover {
+            f.write_str("; server_no_context_takeover")?;
+        }
+        if self.client_no_context_takeover {
+            f.write_str("; client_no_context_takeover")?;
+        }
+        if self.server_max_window_bits {
+            f.write_str("; server_max_window_bits=15")?;
+        }
+        Ok(())
+    }
+}
+
+/// Selects the first acceptable `permessage-deflate` offer of the
+/// `Sec-WebSocket-Extensions` header.
+pub(crate) fn negotiate(offers: &str, config: &DeflateConfig) -> Option<DeflateParams> {
+    offers.split(',').find_map(|offer| {
+        let mut params = offer.split(';').map(str::trim);
+        if !params
+            .next()
+            .is_some_and(|name| name.eq_ignore_ascii_case(EXTENSION_NAME))
+        {
+            return None;
+        }
+
+        let mut negotiated = DeflateParams {
+            level: config.level,
+            server_no_context_takeover: config.server_no_context_takeover,
+            client_no_context_takeover: config.client_no_context_takeover,
+            server_max_window_bits: false,
+        };
+        let mut seen = Vec::new();
+
+        for param in params {
+            let (name, value) = match param.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (param, None),
+            };
+            if seen.contains(&name) {
+                return None;
+            }
+            seen.push(name);
+
+            match (name, value) {
+                ("server_no_context_takeover", None) => {
+                    negotiated.server_no_context_takeover = true
+                }
+                ("client_no_context_takeover", None) => {
+                    negotiated.client_no_context_takeover = true
+                }
+                // the decompressor accepts any window size
+                ("client_max_window_bits", None) => {}
+                ("client_max_window_bits", Some(bits)) if parse_window_bits(bits).is_some() => {}
+                // smaller windows are not supported by the compressor
+                ("server_max_window_bits", Some(bits)) if parse_window_bits(bits) == Some(15) => {
+                    negotiated.server_max_window_bits = true
+                }
+                _ => return None,
+            }
+        }
+
+        Some(negotiated)
+    })
+}
+
+fn parse_window_bits(value: &str) -> Option<u8> {
+    if !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok().filter(|bits| (8..=15).contains(bits))
+}
+
+fn invalid_data(msg: &'static str) -> IoError {
+    IoError::new(ErrorKind::InvalidData, msg)
+}
+
+fn apply_mask(data: &mut [u8], mask: Option<[u8; 4]>) {
+    if let Some(mask) = mask {
+        for (i, b) in data.iter_mut().enumerate() {
+            *b ^= mask[i % 4];
+        }
+    }
+}
+
+fn encode_frame(header: &FrameHeader, payload: &[u8], output: &mut BytesMut) -> IoResult<()> {
+    let mut buf = Vec::with_capacity(header.len(payload.len() as u64));
+    header
+        .format(payload.len() as u64, &mut buf)
+        .map_err(tungstenite_error_to_io_error)?;
+    output.extend_from_slice(&buf);
+    output.extend_from_slice(payload);
+    Ok(())
+}
+
+/// A compressed message whose final frame has not been received yet.
+struct PartialMessage {
+    header: FrameHeader,
+    payload: Vec<u8>,
+}
+
+struct Codec {
+    role: Role,
+    params: DeflateParams,
+    max_message_size: Option<usize>,
+    compress: Compress,
+    decompress: Decompress,
+    read_in: BytesMut,
+    read_out: BytesMut,
+    message: Option<PartialMessage>,
+    write_in: BytesMut,
+    write_out: BytesMut,
+}
+
+impl Codec {
+    fn reset_compress(&self) -> bool {
+        match self.role {
+            Role::Server => self.params.server_no_context_takeover,
+            Role::Client => self.params.client_no_context_takeover,
+        }
+    }
+
+    fn reset_decompress(&self) -> bool {
+        match self.role {
+            Role::Server => self.params.client_no_context_takeover,
+            Role::Client => self.params.server_no_context_takeover,
+        }
+    }
+
+    fn check_message_size(&self, size: usize) -> IoResult<()> {
+        match self.max_message_size {
+            Some(max_size) if size > max_size => Err(invalid_data("message too long")),
+            _ => Ok(()),
+        }
+    }
+
+    fn deflate(&mut self, input: &[u8]) -> IoResult<Vec<u8>> {
+        let mut output = Vec::with_capacity(input.len() / 2 + 64);
+        let start = self.compress.total_in();
+
+        loop {
+            let consumed = (self.compress.total_in() - start) as usize;
+            if output.len() == output.capacity() {
+                output.reserve(output.capacity());
+            }
+            self.compress
+                .compress_vec(&input[consumed..], &mut output, FlushCompress::Sync)
+                .map_err(IoError::other)?;
+
+            let consumed = (self.compress.total_in() - start) as usize;
+            if consumed == input.len() && output.len() < output.capacity() {
+                break;
+            }
+        }
+
+        if output.ends_with(&TRAILER) {
+            output.truncate(output.len() - TRAILER.len());
+        }
+        if self.reset_compress() {
+            self.compress.reset();
+        }
+        Ok(output)
+    }
+
+    fn inflate(&mut self, mut input: Vec<u8>) -> IoResult<Vec<u8>> {
+        input.extend_from_slice(&TRAILER);
+        let mut output = Vec::with_capacity(input.len() * 2);
+        let start = self.decompress.total_in();
+
+        loop {
+            let consumed = (self.decompress.total_in() - start) as usize;
+            if output.len() == output.capacity() {
+                output.reserve(output.capacity());
+            }
+            let before = (self.decompress.total_in(), self.decompress.total_out());
+            let status = self
+                .decompress
+                .decompress_vec(&input[consumed..], &mut output, FlushDecompress::Sync)
+                .map_err(|_| invalid_data("invalid compressed message"))?;
+            self.check_message_size(output.len())?;
+
+            let consumed = (self.decompress.total_in() - start) as usize;
+            let progress = before != (self.decompress.total_in(), self.decompress.total_out());
+            if status == Status::StreamEnd {
+                // the peer finished the deflate stream, the next message starts a new one
+                self.decompress.reset(false);
+                break;
+            }
+            if consumed == input.len() && output.len() < output.capacity() {
+                break;
+            }
+            if !progress && output.len() < output.capacity() {
+                return Err(invalid_data("invalid compressed message"));
+            }
+        }
+
+        if self.reset_decompress() {
+            self.decompress.reset(false);
+        }
+        Ok(output)
+    }
+
+    /// Decodes a frame of `read_in` into `read_out`, returns `false` if the
+    /// frame is incomplete.
+    fn decode(&mut self) -> IoResult<bool> {
+        let mut cursor = Cursor::new(&self.read_in[..]);
+        let Some((header, len)) =
+            FrameHeader::parse(&mut cursor).map_err(tungstenite_error_to_io_error)?
+        else {
+            return Ok(false);
+        };
+        let header_len = cursor.position() as usize;
+        let len = usize::try_from(len).map_err(|_| invalid_data("frame too long"))?;
+        self.check_message_size(len)?;
+        if self.read_in.len() < header_len + len {
+            self.read_in.reserve(header_len + len - self.read_in.len());
+            return Ok(false);
+        }
+        let frame = self.read_in.split_to(header_len + len);
+
+        let compressed = match header.opcode {
+            OpCode::Data(Data::Text | Data::Binary) if self.message.is_some() => {
+                return Err(invalid_data("expected a continuation frame"));
+            }
+            OpCode::Data(Data::Text | Data::Binary) => header.rsv1,
+            OpCode::Data(Data::Continue) if header.rsv1 && self.message.is_some() => {
+                return Err(invalid_data("reserved bit set on a continuation frame"));
+            }
+            OpCode::Data(Data::Continue) => self.message.is_some(),
+            _ => false,
+        };
+        if !compressed {
+            self.read_out.extend_from_slice(&frame);
+            return Ok(true);
+        }
+
+        let mut payload = frame[header_len..].to_vec();
+        apply_mask(&mut payload, header.mask);
+        let is_final = header.is_final;
+        if let Some(message) = &self.message {
+            self.check_message_size(message.payload.len() + payload.len())?;
+        }
+        match &mut self.message {
+            Some(message) => message.payload.extend_from_slice(&payload),
+            None => {
+                self.message = Some(PartialMessage {
+                    header: FrameHeader {
+                        rsv1: false,
+                        ..header
+                    },
+                    payload,
+                })
+            }
+        }
+
+        if is_final {
+            let PartialMessage {
+                mut header,
+                payload,
+            } = self.message.take().expect("compressed message");
+            let mut payload = self.inflate(payload)?;
+            apply_mask(&mut payload, header.mask);
+            header.is_final = true;
+            encode_frame(&header, &payload, &mut self.read_out)?;
+        }
+        Ok(true)
+    }
+
+    /// Encodes a frame of `write_in` into `write_out`, returns `false` if the
+    /// frame is incomplete.
+    fn encode(&mut self) -> IoResult<bool> {
+        let mut cursor = Cursor::new(&self.write_in[..]);
+        let Some((mut header, len)) =
+            FrameHeader::parse(&mut cursor).map_err(tungstenite_error_to_io_error)?
+        else {
+            return Ok(false);
+        };
+        let header_len = cursor.position() as usize;
+        let len = usize::try_from(len).map_err(|_| invalid_data("frame too long"))?;
+        if self.write_in.len() < header_len + len {
+            return Ok(false);
+        }
+        let frame = self.write_in.split_to(header_len + len);
+
+        // fragmented messages and empty messages are sent uncompressed
+        if !matches!(header.opcode, OpCode::Data(Data::Text | Data::Binary))
+            || !header.is_final
+            || header.rsv1
+            || len == 0
+        {
+            self.write_out.extend_from_slice(&frame);
+            return Ok(true);
+        }
+
+        let mut payload = frame[header_len..].to_vec();
+        apply_mask(&mut payload, header.mask);
+        let mut payload = self.deflate(&payload)?;
+        apply_mask(&mut payload, header.mask);
+        header.rsv1 = true;
+        encode_frame(&header, &payload, &mut self.write_out)?;
+        Ok(true)
+    }
+}
+
+/// A stream which compresses and decompresses the messages with the
+/// `permessage-deflate` extension, below the WebSocket protocol.
+pub(crate) struct DeflateStream<S> {
+    inner: S,
+    codec: Option<Box<Codec>>,
+}
+
+impl<S> DeflateStream<S> {
+    pub(crate) fn new(
+        inner: S,
+        role: Role,
+        params: Option<DeflateParams>,
+        max_message_size: Option<usize>,
+    ) -> Self {
+        Self {
+            inner,
+            codec: params.map(|params| {
+                Box::new(Codec {
+                    role,
+                    params,
+                    max_message_size,
+                    compress: Compress::new(Compression::new(params.level), false),
+                    decompress: Decompress::new(false),
+                    read_in: BytesMut::new(),
+                    read_out: BytesMut::new(),
+                    message: None,
+                    write_in: BytesMut::new(),
+                    write_out: BytesMut::new(),
+                })
+            }),
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> DeflateStream<S> {
+    fn poll_write_out(&mut self, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        if let Some(codec) = &mut self.codec {
+            while !codec.write_out.is_empty() {
+                let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &codec.write_out))?;
+                if n == 0 {
+                    return Poll::Ready(Err(ErrorKind::WriteZero.into()));
+                }
+                codec.write_out.advance(n);
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for DeflateStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<IoResult<()>> {
+        let this = &mut *self;
+        let Some(codec) = &mut this.codec else {
+            return Pin::new(&mut this.inner).poll_read(cx, buf);
+        };
+
+        loop {
+            if !codec.read_out.is_empty() {
+                let n = codec.read_out.len().min(buf.remaining());
+                buf.put_slice(&codec.read_out[..n]);
+                codec.read_out.advance(n);
+                return Poll::Ready(Ok(()));
+            }
+
+            if codec.decode()? {
+                continue;
+            }
+
+            let mut chunk = [0; 8192];
+            let mut chunk_buf = ReadBuf::new(&mut chunk);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut chunk_buf))?;
+            if chunk_buf.filled().is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+            codec.read_in.extend_from_slice(chunk_buf.filled());
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for DeflateStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<IoResult<usize>> {
+        let this = &mut *self;
+        if this.codec.is_none() {
+            return Pin::new(&mut this.inner).poll_write(cx, buf);
+        }
+
+        if let Poll::Ready(Err(err)) = this.poll_write_out(cx) {
+            return Poll::Ready(Err(err));
+        }
+        let codec = this.codec.as_mut().expect("codec");
+        if codec.write_out.len() >= MAX_WRITE_BUFFER_SIZE {
+            return Poll::Pending;
+        }
+
+        codec.write_in.extend_from_slice(buf);
+        while codec.encode()? {}
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        ready!(self.poll_write_out(cx))?;
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        ready!(self.poll_write_out(cx))?;
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+    use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
+
+    use super::*;
+    use crate::{
+        IntoResponse, Server, handler,
+        listener::{Acceptor, Listener, TcpListener},
+        web::websocket::{Message, WebSocket},
+    };
+
+    #[test]
+    fn negotiation() {
+        let config = DeflateConfig::new();
+        let params = |offers| negotiate(offers, &config).map(|params| params.to_string());
+
+        assert_eq!(params("foo"), None);
+        assert_eq!(
+            params("permessage-deflate; client_max_window_bits").as_deref(),
+            Some("permessage-deflate")
+        );
+        assert_eq!(
+            params("permessage-deflate; server_no_context_takeover").as_deref(),
+            Some("permessage-deflate; server_no_context_takeover")
+        );
+        assert_eq!(
+            params("permessage-deflate; server_max_window_bits=10, permessage-deflate").as_deref(),
+            Some("permessage-deflate")
+        );
+        assert_eq!(
+            params("permessage-deflate; server_max_window_bits=\"15\"").as_deref(),
+            Some("permessage-deflate; server_max_window_bits=15")
+        );
+        assert_eq!(
+            params("permessage-deflate; client_max_window_bits=16"),
+            None
+        );
+        assert_eq!(
+            params("permessage-deflate; server_no_context_takeover; server_no_context_takeover"),
+            None
+        );
+        assert_eq!(params("permessage-deflate; foo"), None);
+        assert_eq!(
+            params("permessage-deflate; client_no_context_takeover").as_deref(),
+            Some("permessage-deflate; client_no_context_takeover")
+        );
+
+        let config = DeflateConfig::new().client_no_context_takeover(true);
+        assert_eq!(
+            negotiate("permessage-deflate", &config)
+                .unwrap()
+                .to_string(),
+            "permessage-deflate; client_no_context_takeover"
+        );
+    }
+
+    fn text_frame(payload: &[u8], output: &mut BytesMut) {
+        let header = FrameHeader {
+            opcode: OpCode::Data(Data::Text),
+            ..FrameHeader::default()
+        };
+        encode_frame(&header, payload, output).unwrap();
+    }
+
+    #[test]
+    fn decode() {
+        let params = negotiate("permessage-deflate", &DeflateConfig::new());
+        let mut client = DeflateStream::new((), Role::Client, params, Some(1024))
+            .codec
+            .unwrap();
+
+        // the second message uses the compression context of the first one
+        // (RFC 7692, section 7.2.3.2)
+        client.read_in.extend_from_slice(&[
+            0xc1, 0x07, 0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00, //
+            0xc1, 0x05, 0xf2, 0x00, 0x11, 0x00, 0x00,
+        ]);
+        assert!(client.decode().unwrap());
+        assert!(client.decode().unwrap());
+        assert!(!client.decode().unwrap());
+        assert_eq!(&client.read_out[..], b"\x81\x05Hello\x81\x05Hello");
+
+        // a compressed message split into fragments (RFC 7692, section 7.2.3.3)
+        client.read_out.clear();
+        client.read_in.extend_from_slice(&[
+            0x41, 0x03, 0xf2, 0x48, 0xcd, //
+            0x80, 0x04, 0xc9, 0xc9, 0x07, 0x00,
+        ]);
+        assert!(client.decode().unwrap());
+        assert!(client.read_out.is_empty());
+        assert!(client.decode().unwrap());
+        assert_eq!(&client.read_out[..], b"\x81\x05Hello");
+
+        // an uncompressed message
+        client.read_out.clear();
+        text_frame(b"Hello", &mut client.read_in);
+        assert!(client.decode().unwrap());
+        assert_eq!(&client.read_out[..], b"\x81\x05Hello");
+    }
+
+    #[test]
+    fn round_trip() {
+        let params = negotiate("permessage-deflate", &DeflateConfig::new());
+        let mut server = DeflateStream::new((), Role::Server, params, None)
+            .codec
+            .unwrap();
+        let mut client = DeflateStream::new((), Role::Client, params, Some(1024))
+            .codec
+            .unwrap();
+
+        let data = b"Hello, World! Hello, World!";
+        for _ in 0..2 {
+            text_frame(data, &mut server.write_in);
+        }
+        text_frame(b"", &mut server.write_in);
+        while server.encode().unwrap() {}
+        assert!(server.write_in.is_empty());
+
+        let first_len = server.write_out[1] as usize;
+        let second = &server.write_out[2 + first_len..];
+        assert_eq!(server.write_out[0], 0xc1);
+        assert_eq!(second[0], 0xc1);
+        assert!(first_len < data.len());
+        // the second message refers to the first one
+        assert!((second[1] as usize) < first_len);
+
+        client.read_in = server.write_out.split();
+        while client.decode().unwrap() {}
+        let mut expected = BytesMut::new();
+        for _ in 0..2 {
+            text_frame(data, &mut expected);
+        }
+        text_frame(b"", &mut expected);
+        assert_eq!(client.read_out, expected);
+
+        // the message is too long
+        let data = vec![b'a'; 2048];
+        text_frame(&data, &mut server.write_in);
+        assert!(server.encode().unwrap());
+        client.read_in = server.write_out.split();
+        assert_eq!(client.decode().unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn server() {
+        #[handler(internal)]
+        async fn index(ws: WebSocket) -> impl IntoResponse {
+            ws.deflate(DeflateConfig::new())
+                .on_upgrade(|mut stream| async move {
+                    while let Some(Ok(Message::Text(text))) = stream.next().await {
+                        if stream
+                            .send(Message::Text(text.to_uppercase()))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                })
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = acceptor
+            .local_addr()
+            .remove(0)
+            .as_socket_addr()
+            .cloned()
+            .unwrap();
+        let handle = tokio::spawn(async move {
+            let _ = Server::new_with_acceptor(acceptor).run(index).await;
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                b"GET / HTTP/1.1\r\n\
+                host: localhost\r\n\
+                connection: upgrade\r\n\
+                upgrade: websocket\r\n\
+                sec-websocket-version: 13\r\n\
+                sec-websocket-key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                sec-websocket-extensions: permessage-deflate; client_max_window_bits\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap().to_ascii_lowercase();
+        assert!(head.starts_with("http/1.1 101"));
+        assert!(head.contains("\r\nsec-websocket-extensions: permessage-deflate\r\n"));
+
+        // a compressed text frame (RFC 7692, section 7.2.3.1) with a zero mask
+        stream
+            .write_all(&[
+                0xc1, 0x87, 0x00, 0x00, 0x00, 0x00, 0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00,
+            ])
+            .await
+            .unwrap();
+        let mut header = [0; 2];
+        stream.read_exact(&mut header).await.unwrap();
+        assert_eq!(header[0], 0xc1);
+        let mut payload = vec![0; header[1] as usize];
+        stream.read_exact(&mut payload).await.unwrap();
+
+        let params = negotiate("permessage-deflate", &DeflateConfig::new());
+        let mut client = DeflateStream::new((), Role::Client, params, None)
+            .codec
+            .unwrap();
+        client.read_in.extend_from_slice(&header);
+        client.read_in.extend_from_slice(&payload);
+        assert!(client.decode().unwrap());
+        assert_eq!(&client.read_out[..], b"\x81\x05HELLO");
+        client.read_out.clear();
+
+        // continue with the same decompression context
+        let stream = DeflateStream {
+            inner: stream,
+            codec: Some(client),
+        };
+        let mut client_stream =
+            tokio_tungstenite::WebSocketStream::from_raw_socket(stream, Role::Client, None).await;
+        for text in ["hello world", "hello again"] {
+            client_stream
+                .send(TungsteniteMessage::text(text))
+                .await
+                .unwrap();
+            assert_eq!(
+                client_stream.next().await.unwrap().unwrap(),
+                TungsteniteMessage::text(text.to_uppercase())
+            );
+        }
+
+        handle.abort();
+    }
+}
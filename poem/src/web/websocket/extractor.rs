@@ -4,7 +4,11 @@ use futures_util::{FutureExt, future::BoxFuture};
 use headers::HeaderMapExt;
 use tokio_tungstenite::tungstenite::protocol::{Role, WebSocketConfig};
 
-use super::{WebSocketStream, utils::sign};
+use super::{
+    DeflateConfig, WebSocketStream,
+    deflate::{DeflateStream, negotiate},
+    utils::sign,
+};
 use crate::{
     Body, FromRequest, IntoResponse, OnUpgrade, Request, RequestBody, Response, Result,
     error::WebSocketError,
@@ -24,7 +28,9 @@ pub struct WebSocket {
     on_upgrade: OnUpgrade,
     protocols: Option<Box<[Cow<'static, str>]>>,
     sec_websocket_protocol: Option<HeaderValue>,
+    sec_websocket_extensions: Option<String>,
     config: Option<WebSocketConfig>,
+    deflate: Option<DeflateConfig>,
 }
 
 impl WebSocket {
@@ -57,13 +63,23 @@ impl WebSocket {
             .ok_or(WebSocketError::InvalidProtocol)?;
 
         let sec_websocket_protocol = req.headers().get(header::SEC_WEBSOCKET_PROTOCOL).cloned();
+        let sec_websocket_extensions = req
+            .headers()
+            .get_all(header::SEC_WEBSOCKET_EXTENSIONS)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>();
+        let sec_websocket_extensions =
+            (!sec_websocket_extensions.is_empty()).then(|| sec_websocket_extensions.join(","));
 
         Ok(Self {
             key,
             on_upgrade: req.take_upgrade()?,
             protocols: None,
             sec_websocket_protocol,
+            sec_websocket_extensions,
             config: None,
+            deflate: None,
         })
     }
 }
@@ -119,6 +135,33 @@ impl WebSocket {
         }
     }
 
+    /// Enable the `permessage-deflate` extension, which compresses the
+    /// messages if the client supports it.
+    ///
+    /// ```
+    /// use poem::{
+    ///     IntoResponse, Route, get, handler,
+    ///     web::websocket::{DeflateConfig, WebSocket},
+    /// };
+    ///
+    /// #[handler]
+    /// async fn index(ws: WebSocket) -> impl IntoResponse {
+    ///     ws.deflate(DeflateConfig::new().server_no_context_takeover(true))
+    ///         .on_upgrade(|socket| async move {
+    ///             // ...
+    ///         })
+    /// }
+    ///
+    /// let app = Route::new().at("/", get(index));
+    /// ```
+    #[must_use]
+    pub fn deflate(self, config: DeflateConfig) -> Self {
+        Self {
+            deflate: Some(config),
+            ..self
+        }
+    }
+
     /// Finalize upgrading the connection and call the provided `callback` with
     /// the stream.
     ///
@@ -200,6 +243,14 @@ where
             );
         }
 
+        // check requested extensions
+        let deflate = self.websocket.deflate.as_ref().and_then(|config| {
+            negotiate(self.websocket.sec_websocket_extensions.as_ref()?, config)
+        });
+        if let Some(deflate) = &deflate {
+            builder = builder.header(header::SEC_WEBSOCKET_EXTENSIONS, deflate.to_string());
+        }
+
         let resp = builder.body(Body::empty());
 
         tokio::spawn(async move {
@@ -208,8 +259,12 @@ where
                 Err(_) => return,
             };
 
+            let max_message_size = match &self.websocket.config {
+                Some(config) => config.max_message_size,
+                None => WebSocketConfig::default().max_message_size,
+            };
             let stream = tokio_tungstenite::WebSocketStream::from_raw_socket(
-                upgraded,
+                DeflateStream::new(upgraded, Role::Server, deflate, max_message_size),
                 Role::Server,
                 self.websocket.config,
             )
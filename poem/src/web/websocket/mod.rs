@@ -21,11 +21,15 @@
 //! let app = Route::new().at("/", get(index));
 //! ```
 
+mod deflate;
 mod extractor;
 mod message;
 mod stream;
 mod utils;
 
+pub use deflate::DeflateConfig;
+#[cfg(feature = "test")]
+pub(crate) use deflate::{DeflateStream, negotiate};
 pub use extractor::{BoxWebSocketUpgraded, WebSocket, WebSocketUpgraded};
 pub use message::{CloseCode, Message};
 pub use stream::WebSocketStream;
@@ -33,7 +37,7 @@ pub use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 
 #[cfg(test)]
 mod tests {
-    use std::net::SocketAddr;
+    use std::{net::SocketAddr, time::Duration};
 
     use futures_util::{SinkExt, StreamExt};
     use http::{HeaderValue, header};
@@ -154,4 +158,75 @@ mod tests {
 
         handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_heartbeat() {
+        #[handler(internal)]
+        async fn index(ws: WebSocket) -> impl IntoResponse {
+            ws.on_upgrade(|stream| async move {
+                let mut stream =
+                    stream.heartbeat(Duration::from_millis(100), Duration::from_millis(300));
+                let mut pongs = 0;
+                while let Some(Ok(msg)) = stream.next().await {
+                    if msg.is_pong() {
+                        pongs += 1;
+                        if pongs == 3 {
+                            let _ = stream.send(Message::text("alive")).await;
+                        }
+                    }
+                }
+                // keep the connection open until the client reads the close frame
+                tokio::time::sleep(Duration::from_secs(2)).await;
+            })
+        }
+
+        let acceptor = TcpListener::bind("127.0.0.1:0")
+            .into_acceptor()
+            .await
+            .unwrap();
+        let addr = acceptor
+            .local_addr()
+            .remove(0)
+            .as_socket_addr()
+            .cloned()
+            .unwrap();
+        let handle = tokio::spawn(async move {
+            let _ = Server::new_with_acceptor(acceptor).run(index).await;
+        });
+
+        let (mut client_stream, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+            .await
+            .unwrap();
+
+        // the client replies to the pings while reading
+        let msg = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match client_stream.next().await.unwrap().unwrap() {
+                    tokio_tungstenite::tungstenite::Message::Ping(_) => {}
+                    msg => break msg,
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            msg,
+            tokio_tungstenite::tungstenite::Message::Text("alive".into())
+        );
+
+        // the connection is closed if the client stops replying
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        loop {
+            match client_stream.next().await.unwrap().unwrap() {
+                tokio_tungstenite::tungstenite::Message::Ping(_) => {}
+                tokio_tungstenite::tungstenite::Message::Close(Some(frame)) => {
+                    assert_eq!(CloseCode::from(frame.code), CloseCode::Away);
+                    break;
+                }
+                msg => panic!("unexpected message: {msg:?}"),
+            }
+        }
+
+        handle.abort();
+    }
 }
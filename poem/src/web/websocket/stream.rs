@@ -1,37 +1,174 @@
 use std::{
-    io::{Error as IoError, Result as IoResult},
+    io::{Error as IoError, ErrorKind, Result as IoResult},
     pin::Pin,
-    task::{Context, Poll},
+    task::{Context, Poll, ready},
+    time::Duration,
 };
 
 use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::{self, protocol::CloseFrame};
 
-use super::{Message, WebSocketConfig, utils::tungstenite_error_to_io_error};
+use super::{
+    CloseCode, Message, WebSocketConfig, deflate::DeflateStream,
+    utils::tungstenite_error_to_io_error,
+};
 use crate::Upgraded;
 
+type InnerStream = tokio_tungstenite::WebSocketStream<DeflateStream<Upgraded>>;
+
+enum HeartbeatState {
+    Waiting,
+    SendPing,
+    FlushPing,
+    SendClose,
+    FlushClose,
+    TimedOut,
+}
+
+struct Heartbeat {
+    interval: Interval,
+    timeout: Duration,
+    last_seen: Instant,
+    state: HeartbeatState,
+}
+
+impl Heartbeat {
+    /// Sends the pings, returns an error if the peer has timed out.
+    fn poll(
+        &mut self,
+        inner: &mut InnerStream,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<IoResult<Message>>> {
+        loop {
+            match self.state {
+                HeartbeatState::Waiting => {
+                    ready!(self.interval.poll_tick(cx));
+                    self.state = if self.last_seen.elapsed() >= self.timeout {
+                        HeartbeatState::SendClose
+                    } else {
+                        HeartbeatState::SendPing
+                    };
+                }
+                HeartbeatState::SendPing | HeartbeatState::SendClose => {
+                    let close = matches!(self.state, HeartbeatState::SendClose);
+                    let msg = if close {
+                        tungstenite::Message::Close(Some(CloseFrame {
+                            code: CloseCode::Away.into(),
+                            reason: "heartbeat timeout".into(),
+                        }))
+                    } else {
+                        tungstenite::Message::Ping(Default::default())
+                    };
+                    let res = match ready!(inner.poll_ready_unpin(cx)) {
+                        Ok(()) => inner.start_send_unpin(msg),
+                        Err(err) => Err(err),
+                    };
+                    self.state = match (res, close) {
+                        (Ok(()), false) => HeartbeatState::FlushPing,
+                        (Ok(()), true) => HeartbeatState::FlushClose,
+                        // the error is returned by the inner stream
+                        (Err(_), false) => HeartbeatState::Waiting,
+                        (Err(_), true) => HeartbeatState::TimedOut,
+                    };
+                }
+                HeartbeatState::FlushPing => {
+                    let _ = ready!(inner.poll_flush_unpin(cx));
+                    self.state = HeartbeatState::Waiting;
+                }
+                HeartbeatState::FlushClose => {
+                    let _ = ready!(inner.poll_flush_unpin(cx));
+                    self.state = HeartbeatState::TimedOut;
+                    return Poll::Ready(Some(Err(IoError::new(
+                        ErrorKind::TimedOut,
+                        "heartbeat timeout",
+                    ))));
+                }
+                HeartbeatState::TimedOut => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
 /// A `WebSocket` stream, which implements [`Stream<Message>`] and
 /// [`Sink<Message>`].
 pub struct WebSocketStream {
-    inner: tokio_tungstenite::WebSocketStream<Upgraded>,
+    inner: InnerStream,
+    heartbeat: Option<Heartbeat>,
 }
 
 impl WebSocketStream {
-    pub(crate) fn new(inner: tokio_tungstenite::WebSocketStream<Upgraded>) -> Self {
-        Self { inner }
+    pub(crate) fn new(inner: InnerStream) -> Self {
+        Self {
+            inner,
+            heartbeat: None,
+        }
     }
 
     /// Returns a reference to the configuration of the stream.
     pub fn get_config(&self) -> &WebSocketConfig {
         self.inner.get_config()
     }
+
+    /// Sends a ping every `interval`, and closes the connection with
+    /// [`CloseCode::Away`] if no pong or other message has been received from
+    /// the peer for `timeout`.
+    ///
+    /// The pings are sent while the stream is polled for the next message.
+    /// When the peer times out, the stream returns an error of kind
+    /// [`ErrorKind::TimedOut`] and then ends.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use futures_util::StreamExt;
+    /// use poem::{IntoResponse, handler, web::websocket::WebSocket};
+    ///
+    /// #[handler]
+    /// async fn index(ws: WebSocket) -> impl IntoResponse {
+    ///     ws.on_upgrade(|socket| async move {
+    ///         let mut socket =
+    ///             socket.heartbeat(Duration::from_secs(15), Duration::from_secs(45));
+    ///         while let Some(Ok(msg)) = socket.next().await {
+    ///             // ...
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    #[must_use]
+    pub fn heartbeat(self, interval: Duration, timeout: Duration) -> Self {
+        let mut ticker = tokio::time::interval_at(Instant::now() + interval, interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            heartbeat: Some(Heartbeat {
+                interval: ticker,
+                timeout,
+                last_seen: Instant::now(),
+                state: HeartbeatState::Waiting,
+            }),
+            ..self
+        }
+    }
 }
 
 impl Stream for WebSocketStream {
     type Item = IoResult<Message>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        match self.inner.poll_next_unpin(cx) {
-            Poll::Ready(Some(Ok(msg))) => Poll::Ready(Some(Ok(msg.into()))),
+        let this = &mut *self;
+        if let Some(heartbeat) = &mut this.heartbeat {
+            if let Poll::Ready(res) = heartbeat.poll(&mut this.inner, cx) {
+                return Poll::Ready(res);
+            }
+        }
+
+        match this.inner.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(msg))) => {
+                if let Some(heartbeat) = &mut this.heartbeat {
+                    heartbeat.last_seen = Instant::now();
+                }
+                Poll::Ready(Some(Ok(msg.into())))
+            }
             Poll::Ready(Some(Err(err))) => {
                 Poll::Ready(Some(Err(tungstenite_error_to_io_error(err))))
             }